@@ -1,5 +1,8 @@
 use crate::action_generated;
+use crate::algorithms::known::{Algorithm, AlgorithmStatus, AlgorithmType};
+use crate::algorithms::moves::{parse_extended_move_string, ExtendedMove, ExtendedMoveSequence};
 use crate::common::{Move, Penalty, Solve, SolveType, TimedMove};
+use crate::F2LSlot;
 use anyhow::{anyhow, Result};
 use chrono::{Local, TimeZone};
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
@@ -24,6 +27,10 @@ pub enum Action {
     MergeSessions(String, String),
     RenameSession(String, Option<String>),
     DeleteSolve(String),
+    AlgorithmStatus(Algorithm, AlgorithmStatus),
+    AddAlgorithm(Algorithm, Vec<ExtendedMove>),
+    RemoveAlgorithm(Algorithm, Vec<ExtendedMove>),
+    SetPreferredAlgorithm(Algorithm, Option<Vec<ExtendedMove>>),
 }
 
 #[derive(Clone, Debug)]
@@ -154,6 +161,11 @@ impl StoredAction {
                 if let Some(moves) = moves {
                     solve_builder.add_moves(moves);
                 }
+                if let Some(slot) = solve.target_slot {
+                    // Zero is used when there is no target slot
+                    let idx = F2LSlot::all().iter().position(|s| *s == slot).unwrap();
+                    solve_builder.add_target_slot(idx as u8 + 1);
+                }
 
                 (
                     solve_builder.finish().as_union_value(),
@@ -269,6 +281,77 @@ impl StoredAction {
 
                 (action, action_generated::ActionContents::DeleteSolveAction)
             }
+            Action::AlgorithmStatus(algorithm, status) => {
+                let (algorithm_type, algorithm) = algorithm.to_type_and_number();
+                let action = action_generated::AlgorithmStatusAction::create(
+                    builder,
+                    &action_generated::AlgorithmStatusActionArgs {
+                        algorithm_type: algorithm_type as u8,
+                        algorithm,
+                        status: *status as u8,
+                    },
+                )
+                .as_union_value();
+
+                (
+                    action,
+                    action_generated::ActionContents::AlgorithmStatusAction,
+                )
+            }
+            Action::AddAlgorithm(algorithm, moves) => {
+                let (algorithm_type, algorithm) = algorithm.to_type_and_number();
+                let moves = Some(builder.create_string(&moves.to_string()));
+                let action = action_generated::AddAlgorithmAction::create(
+                    builder,
+                    &action_generated::AddAlgorithmActionArgs {
+                        algorithm_type: algorithm_type as u8,
+                        algorithm,
+                        moves,
+                    },
+                )
+                .as_union_value();
+
+                (action, action_generated::ActionContents::AddAlgorithmAction)
+            }
+            Action::RemoveAlgorithm(algorithm, moves) => {
+                let (algorithm_type, algorithm) = algorithm.to_type_and_number();
+                let moves = Some(builder.create_string(&moves.to_string()));
+                let action = action_generated::RemoveAlgorithmAction::create(
+                    builder,
+                    &action_generated::RemoveAlgorithmActionArgs {
+                        algorithm_type: algorithm_type as u8,
+                        algorithm,
+                        moves,
+                    },
+                )
+                .as_union_value();
+
+                (
+                    action,
+                    action_generated::ActionContents::RemoveAlgorithmAction,
+                )
+            }
+            Action::SetPreferredAlgorithm(algorithm, moves) => {
+                // Moves are not present when resetting to the default algorithm
+                let (algorithm_type, algorithm) = algorithm.to_type_and_number();
+                let moves = moves
+                    .as_ref()
+                    .map(|moves| builder.create_string(&moves.to_string()));
+                let action = action_generated::SetPreferredAlgorithmAction::create(
+                    builder,
+                    &action_generated::SetPreferredAlgorithmActionArgs {
+                        algorithm_type: algorithm_type as u8,
+                        algorithm,
+                        moves,
+                    },
+                )
+                .as_union_value();
+
+                (
+                    action,
+                    action_generated::ActionContents::SetPreferredAlgorithmAction,
+                )
+            }
         };
 
         let id = builder.create_string(&self.id);
@@ -373,6 +456,13 @@ impl StoredAction {
                     }
                     None => None,
                 };
+                let target_slot = match action.target_slot() {
+                    0 => None,
+                    idx => match F2LSlot::all().get(idx as usize - 1) {
+                        Some(slot) => Some(*slot),
+                        None => return None,
+                    },
+                };
                 Some(Self {
                     id,
                     action: Action::NewSolve(Solve {
@@ -385,6 +475,7 @@ impl StoredAction {
                         penalty,
                         device,
                         moves,
+                        target_slot,
                     }),
                 })
             }
@@ -481,10 +572,100 @@ impl StoredAction {
                     action: Action::DeleteSolve(solve),
                 })
             }
+            action_generated::ActionContents::AlgorithmStatusAction => {
+                let action = match action.contents_as_algorithm_status_action() {
+                    Some(action) => action,
+                    None => return None,
+                };
+                let algorithm = match Self::deserialize_algorithm(
+                    action.algorithm_type(),
+                    action.algorithm(),
+                ) {
+                    Some(algorithm) => algorithm,
+                    None => return None,
+                };
+                let status = match AlgorithmStatus::try_from(action.status()) {
+                    Ok(status) => status,
+                    _ => return None,
+                };
+                Some(Self {
+                    id,
+                    action: Action::AlgorithmStatus(algorithm, status),
+                })
+            }
+            action_generated::ActionContents::AddAlgorithmAction => {
+                let action = match action.contents_as_add_algorithm_action() {
+                    Some(action) => action,
+                    None => return None,
+                };
+                let algorithm = match Self::deserialize_algorithm(
+                    action.algorithm_type(),
+                    action.algorithm(),
+                ) {
+                    Some(algorithm) => algorithm,
+                    None => return None,
+                };
+                let moves = match action.moves().map(parse_extended_move_string) {
+                    Some(Ok(moves)) => moves,
+                    _ => return None,
+                };
+                Some(Self {
+                    id,
+                    action: Action::AddAlgorithm(algorithm, moves),
+                })
+            }
+            action_generated::ActionContents::RemoveAlgorithmAction => {
+                let action = match action.contents_as_remove_algorithm_action() {
+                    Some(action) => action,
+                    None => return None,
+                };
+                let algorithm = match Self::deserialize_algorithm(
+                    action.algorithm_type(),
+                    action.algorithm(),
+                ) {
+                    Some(algorithm) => algorithm,
+                    None => return None,
+                };
+                let moves = match action.moves().map(parse_extended_move_string) {
+                    Some(Ok(moves)) => moves,
+                    _ => return None,
+                };
+                Some(Self {
+                    id,
+                    action: Action::RemoveAlgorithm(algorithm, moves),
+                })
+            }
+            action_generated::ActionContents::SetPreferredAlgorithmAction => {
+                let action = match action.contents_as_set_preferred_algorithm_action() {
+                    Some(action) => action,
+                    None => return None,
+                };
+                let algorithm = match Self::deserialize_algorithm(
+                    action.algorithm_type(),
+                    action.algorithm(),
+                ) {
+                    Some(algorithm) => algorithm,
+                    None => return None,
+                };
+                let moves = match action.moves().map(parse_extended_move_string) {
+                    Some(Ok(moves)) => Some(moves),
+                    Some(Err(_)) => return None,
+                    None => None,
+                };
+                Some(Self {
+                    id,
+                    action: Action::SetPreferredAlgorithm(algorithm, moves),
+                })
+            }
             _ => None,
         }
     }
 
+    fn deserialize_algorithm(algorithm_type: u8, algorithm: u16) -> Option<Algorithm> {
+        let algorithm_type = AlgorithmType::try_from(algorithm_type).ok()?;
+        Algorithm::from_type_and_number(algorithm_type, algorithm)
+    }
+
     pub fn deserialize_list(data: &[u8]) -> Result<Vec<Self>> {
         let action_list = action_generated::root_as_action_list(data)?;
         if let Some(action_list) = action_list.actions() {
@@ -113,10 +113,10 @@ pub struct PenaltyUnionTableOffset {}
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_ACTION_CONTENTS: u8 = 0;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
-pub const ENUM_MAX_ACTION_CONTENTS: u8 = 6;
+pub const ENUM_MAX_ACTION_CONTENTS: u8 = 10;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 #[allow(non_camel_case_types)]
-pub const ENUM_VALUES_ACTION_CONTENTS: [ActionContents; 7] = [
+pub const ENUM_VALUES_ACTION_CONTENTS: [ActionContents; 11] = [
   ActionContents::NONE,
   ActionContents::NewSolveAction,
   ActionContents::PenaltyAction,
@@ -124,6 +124,10 @@ pub const ENUM_VALUES_ACTION_CONTENTS: [ActionContents; 7] = [
   ActionContents::MergeSessionsAction,
   ActionContents::RenameSessionAction,
   ActionContents::DeleteSolveAction,
+  ActionContents::AlgorithmStatusAction,
+  ActionContents::AddAlgorithmAction,
+  ActionContents::RemoveAlgorithmAction,
+  ActionContents::SetPreferredAlgorithmAction,
 ];
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -138,9 +142,13 @@ impl ActionContents {
   pub const MergeSessionsAction: Self = Self(4);
   pub const RenameSessionAction: Self = Self(5);
   pub const DeleteSolveAction: Self = Self(6);
+  pub const AlgorithmStatusAction: Self = Self(7);
+  pub const AddAlgorithmAction: Self = Self(8);
+  pub const RemoveAlgorithmAction: Self = Self(9);
+  pub const SetPreferredAlgorithmAction: Self = Self(10);
 
   pub const ENUM_MIN: u8 = 0;
-  pub const ENUM_MAX: u8 = 6;
+  pub const ENUM_MAX: u8 = 10;
   pub const ENUM_VALUES: &'static [Self] = &[
     Self::NONE,
     Self::NewSolveAction,
@@ -149,6 +157,10 @@ impl ActionContents {
     Self::MergeSessionsAction,
     Self::RenameSessionAction,
     Self::DeleteSolveAction,
+    Self::AlgorithmStatusAction,
+    Self::AddAlgorithmAction,
+    Self::RemoveAlgorithmAction,
+    Self::SetPreferredAlgorithmAction,
   ];
   /// Returns the variant's name or "" if unknown.
   pub fn variant_name(self) -> Option<&'static str> {
@@ -160,6 +172,10 @@ impl ActionContents {
       Self::MergeSessionsAction => Some("MergeSessionsAction"),
       Self::RenameSessionAction => Some("RenameSessionAction"),
       Self::DeleteSolveAction => Some("DeleteSolveAction"),
+      Self::AlgorithmStatusAction => Some("AlgorithmStatusAction"),
+      Self::AddAlgorithmAction => Some("AddAlgorithmAction"),
+      Self::RemoveAlgorithmAction => Some("RemoveAlgorithmAction"),
+      Self::SetPreferredAlgorithmAction => Some("SetPreferredAlgorithmAction"),
       _ => None,
     }
   }
@@ -702,6 +718,7 @@ impl<'a> NewSolveAction<'a> {
   pub const VT_PENALTY: flatbuffers::VOffsetT = 18;
   pub const VT_DEVICE: flatbuffers::VOffsetT = 20;
   pub const VT_MOVES: flatbuffers::VOffsetT = 22;
+  pub const VT_TARGET_SLOT: flatbuffers::VOffsetT = 24;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -721,6 +738,7 @@ impl<'a> NewSolveAction<'a> {
     if let Some(x) = args.scramble { builder.add_scramble(x); }
     if let Some(x) = args.session { builder.add_session(x); }
     if let Some(x) = args.id { builder.add_id(x); }
+    builder.add_target_slot(args.target_slot);
     builder.add_penalty_type(args.penalty_type);
     builder.add_solve_type(args.solve_type);
     builder.finish()
@@ -768,6 +786,10 @@ impl<'a> NewSolveAction<'a> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, TimedMove>>>(NewSolveAction::VT_MOVES, None).map(|v| v.safe_slice())
   }
   #[inline]
+  pub fn target_slot(&self) -> u8 {
+    self._tab.get::<u8>(NewSolveAction::VT_TARGET_SLOT, Some(0)).unwrap()
+  }
+  #[inline]
   #[allow(non_snake_case)]
   pub fn penalty_as_time_penalty(&self) -> Option<TimePenalty<'a>> {
     if self.penalty_type() == Penalty::TimePenalty {
@@ -833,6 +855,7 @@ impl flatbuffers::Verifiable for NewSolveAction<'_> {
      })?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("device", Self::VT_DEVICE, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, TimedMove>>>("moves", Self::VT_MOVES, false)?
+     .visit_field::<u8>("target_slot", Self::VT_TARGET_SLOT, false)?
      .finish();
     Ok(())
   }
@@ -848,6 +871,7 @@ pub struct NewSolveActionArgs<'a> {
     pub penalty: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
     pub device: Option<flatbuffers::WIPOffset<&'a str>>,
     pub moves: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, TimedMove>>>,
+    pub target_slot: u8,
 }
 impl<'a> Default for NewSolveActionArgs<'a> {
   #[inline]
@@ -863,6 +887,7 @@ impl<'a> Default for NewSolveActionArgs<'a> {
       penalty: None,
       device: None,
       moves: None,
+      target_slot: 0,
     }
   }
 }
@@ -913,6 +938,10 @@ impl<'a: 'b, 'b> NewSolveActionBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewSolveAction::VT_MOVES, moves);
   }
   #[inline]
+  pub fn add_target_slot(&mut self, target_slot: u8) {
+    self.fbb_.push_slot::<u8>(NewSolveAction::VT_TARGET_SLOT, target_slot, 0);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> NewSolveActionBuilder<'a, 'b> {
     let start = _fbb.start_table();
     NewSolveActionBuilder {
@@ -973,6 +1002,7 @@ impl core::fmt::Debug for NewSolveAction<'_> {
       };
       ds.field("device", &self.device());
       ds.field("moves", &self.moves());
+      ds.field("target_slot", &self.target_slot());
       ds.finish()
   }
 }
@@ -1596,6 +1626,494 @@ impl core::fmt::Debug for DeleteSolveAction<'_> {
       ds.finish()
   }
 }
+pub enum AlgorithmStatusActionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct AlgorithmStatusAction<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for AlgorithmStatusAction<'a> {
+  type Inner = AlgorithmStatusAction<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> AlgorithmStatusAction<'a> {
+  pub const VT_ALGORITHM_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_ALGORITHM: flatbuffers::VOffsetT = 6;
+  pub const VT_STATUS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    AlgorithmStatusAction { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args AlgorithmStatusActionArgs
+  ) -> flatbuffers::WIPOffset<AlgorithmStatusAction<'bldr>> {
+    let mut builder = AlgorithmStatusActionBuilder::new(_fbb);
+    builder.add_algorithm(args.algorithm);
+    builder.add_status(args.status);
+    builder.add_algorithm_type(args.algorithm_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn algorithm_type(&self) -> u8 {
+    self._tab.get::<u8>(AlgorithmStatusAction::VT_ALGORITHM_TYPE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn algorithm(&self) -> u16 {
+    self._tab.get::<u16>(AlgorithmStatusAction::VT_ALGORITHM, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn status(&self) -> u8 {
+    self._tab.get::<u8>(AlgorithmStatusAction::VT_STATUS, Some(0)).unwrap()
+  }
+}
+
+impl flatbuffers::Verifiable for AlgorithmStatusAction<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u8>("algorithm_type", Self::VT_ALGORITHM_TYPE, false)?
+     .visit_field::<u16>("algorithm", Self::VT_ALGORITHM, false)?
+     .visit_field::<u8>("status", Self::VT_STATUS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AlgorithmStatusActionArgs {
+    pub algorithm_type: u8,
+    pub algorithm: u16,
+    pub status: u8,
+}
+impl<'a> Default for AlgorithmStatusActionArgs {
+  #[inline]
+  fn default() -> Self {
+    AlgorithmStatusActionArgs {
+      algorithm_type: 0,
+      algorithm: 0,
+      status: 0,
+    }
+  }
+}
+
+pub struct AlgorithmStatusActionBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> AlgorithmStatusActionBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_algorithm_type(&mut self, algorithm_type: u8) {
+    self.fbb_.push_slot::<u8>(AlgorithmStatusAction::VT_ALGORITHM_TYPE, algorithm_type, 0);
+  }
+  #[inline]
+  pub fn add_algorithm(&mut self, algorithm: u16) {
+    self.fbb_.push_slot::<u16>(AlgorithmStatusAction::VT_ALGORITHM, algorithm, 0);
+  }
+  #[inline]
+  pub fn add_status(&mut self, status: u8) {
+    self.fbb_.push_slot::<u8>(AlgorithmStatusAction::VT_STATUS, status, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> AlgorithmStatusActionBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    AlgorithmStatusActionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<AlgorithmStatusAction<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for AlgorithmStatusAction<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("AlgorithmStatusAction");
+      ds.field("algorithm_type", &self.algorithm_type());
+      ds.field("algorithm", &self.algorithm());
+      ds.field("status", &self.status());
+      ds.finish()
+  }
+}
+pub enum AddAlgorithmActionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct AddAlgorithmAction<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for AddAlgorithmAction<'a> {
+  type Inner = AddAlgorithmAction<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> AddAlgorithmAction<'a> {
+  pub const VT_ALGORITHM_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_ALGORITHM: flatbuffers::VOffsetT = 6;
+  pub const VT_MOVES: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    AddAlgorithmAction { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args AddAlgorithmActionArgs<'args>
+  ) -> flatbuffers::WIPOffset<AddAlgorithmAction<'bldr>> {
+    let mut builder = AddAlgorithmActionBuilder::new(_fbb);
+    if let Some(x) = args.moves { builder.add_moves(x); }
+    builder.add_algorithm(args.algorithm);
+    builder.add_algorithm_type(args.algorithm_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn algorithm_type(&self) -> u8 {
+    self._tab.get::<u8>(AddAlgorithmAction::VT_ALGORITHM_TYPE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn algorithm(&self) -> u16 {
+    self._tab.get::<u16>(AddAlgorithmAction::VT_ALGORITHM, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn moves(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(AddAlgorithmAction::VT_MOVES, None)
+  }
+}
+
+impl flatbuffers::Verifiable for AddAlgorithmAction<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u8>("algorithm_type", Self::VT_ALGORITHM_TYPE, false)?
+     .visit_field::<u16>("algorithm", Self::VT_ALGORITHM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("moves", Self::VT_MOVES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AddAlgorithmActionArgs<'a> {
+    pub algorithm_type: u8,
+    pub algorithm: u16,
+    pub moves: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for AddAlgorithmActionArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    AddAlgorithmActionArgs {
+      algorithm_type: 0,
+      algorithm: 0,
+      moves: None,
+    }
+  }
+}
+
+pub struct AddAlgorithmActionBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> AddAlgorithmActionBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_algorithm_type(&mut self, algorithm_type: u8) {
+    self.fbb_.push_slot::<u8>(AddAlgorithmAction::VT_ALGORITHM_TYPE, algorithm_type, 0);
+  }
+  #[inline]
+  pub fn add_algorithm(&mut self, algorithm: u16) {
+    self.fbb_.push_slot::<u16>(AddAlgorithmAction::VT_ALGORITHM, algorithm, 0);
+  }
+  #[inline]
+  pub fn add_moves(&mut self, moves: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(AddAlgorithmAction::VT_MOVES, moves);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> AddAlgorithmActionBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    AddAlgorithmActionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<AddAlgorithmAction<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for AddAlgorithmAction<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("AddAlgorithmAction");
+      ds.field("algorithm_type", &self.algorithm_type());
+      ds.field("algorithm", &self.algorithm());
+      ds.field("moves", &self.moves());
+      ds.finish()
+  }
+}
+pub enum RemoveAlgorithmActionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RemoveAlgorithmAction<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RemoveAlgorithmAction<'a> {
+  type Inner = RemoveAlgorithmAction<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> RemoveAlgorithmAction<'a> {
+  pub const VT_ALGORITHM_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_ALGORITHM: flatbuffers::VOffsetT = 6;
+  pub const VT_MOVES: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RemoveAlgorithmAction { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args RemoveAlgorithmActionArgs<'args>
+  ) -> flatbuffers::WIPOffset<RemoveAlgorithmAction<'bldr>> {
+    let mut builder = RemoveAlgorithmActionBuilder::new(_fbb);
+    if let Some(x) = args.moves { builder.add_moves(x); }
+    builder.add_algorithm(args.algorithm);
+    builder.add_algorithm_type(args.algorithm_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn algorithm_type(&self) -> u8 {
+    self._tab.get::<u8>(RemoveAlgorithmAction::VT_ALGORITHM_TYPE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn algorithm(&self) -> u16 {
+    self._tab.get::<u16>(RemoveAlgorithmAction::VT_ALGORITHM, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn moves(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(RemoveAlgorithmAction::VT_MOVES, None)
+  }
+}
+
+impl flatbuffers::Verifiable for RemoveAlgorithmAction<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u8>("algorithm_type", Self::VT_ALGORITHM_TYPE, false)?
+     .visit_field::<u16>("algorithm", Self::VT_ALGORITHM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("moves", Self::VT_MOVES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RemoveAlgorithmActionArgs<'a> {
+    pub algorithm_type: u8,
+    pub algorithm: u16,
+    pub moves: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for RemoveAlgorithmActionArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RemoveAlgorithmActionArgs {
+      algorithm_type: 0,
+      algorithm: 0,
+      moves: None,
+    }
+  }
+}
+
+pub struct RemoveAlgorithmActionBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> RemoveAlgorithmActionBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_algorithm_type(&mut self, algorithm_type: u8) {
+    self.fbb_.push_slot::<u8>(RemoveAlgorithmAction::VT_ALGORITHM_TYPE, algorithm_type, 0);
+  }
+  #[inline]
+  pub fn add_algorithm(&mut self, algorithm: u16) {
+    self.fbb_.push_slot::<u16>(RemoveAlgorithmAction::VT_ALGORITHM, algorithm, 0);
+  }
+  #[inline]
+  pub fn add_moves(&mut self, moves: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(RemoveAlgorithmAction::VT_MOVES, moves);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> RemoveAlgorithmActionBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    RemoveAlgorithmActionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RemoveAlgorithmAction<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RemoveAlgorithmAction<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RemoveAlgorithmAction");
+      ds.field("algorithm_type", &self.algorithm_type());
+      ds.field("algorithm", &self.algorithm());
+      ds.field("moves", &self.moves());
+      ds.finish()
+  }
+}
+pub enum SetPreferredAlgorithmActionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct SetPreferredAlgorithmAction<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for SetPreferredAlgorithmAction<'a> {
+  type Inner = SetPreferredAlgorithmAction<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> SetPreferredAlgorithmAction<'a> {
+  pub const VT_ALGORITHM_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_ALGORITHM: flatbuffers::VOffsetT = 6;
+  pub const VT_MOVES: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    SetPreferredAlgorithmAction { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args SetPreferredAlgorithmActionArgs<'args>
+  ) -> flatbuffers::WIPOffset<SetPreferredAlgorithmAction<'bldr>> {
+    let mut builder = SetPreferredAlgorithmActionBuilder::new(_fbb);
+    if let Some(x) = args.moves { builder.add_moves(x); }
+    builder.add_algorithm(args.algorithm);
+    builder.add_algorithm_type(args.algorithm_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn algorithm_type(&self) -> u8 {
+    self._tab.get::<u8>(SetPreferredAlgorithmAction::VT_ALGORITHM_TYPE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn algorithm(&self) -> u16 {
+    self._tab.get::<u16>(SetPreferredAlgorithmAction::VT_ALGORITHM, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn moves(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SetPreferredAlgorithmAction::VT_MOVES, None)
+  }
+}
+
+impl flatbuffers::Verifiable for SetPreferredAlgorithmAction<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u8>("algorithm_type", Self::VT_ALGORITHM_TYPE, false)?
+     .visit_field::<u16>("algorithm", Self::VT_ALGORITHM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("moves", Self::VT_MOVES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SetPreferredAlgorithmActionArgs<'a> {
+    pub algorithm_type: u8,
+    pub algorithm: u16,
+    pub moves: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for SetPreferredAlgorithmActionArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SetPreferredAlgorithmActionArgs {
+      algorithm_type: 0,
+      algorithm: 0,
+      moves: None,
+    }
+  }
+}
+
+pub struct SetPreferredAlgorithmActionBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> SetPreferredAlgorithmActionBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_algorithm_type(&mut self, algorithm_type: u8) {
+    self.fbb_.push_slot::<u8>(SetPreferredAlgorithmAction::VT_ALGORITHM_TYPE, algorithm_type, 0);
+  }
+  #[inline]
+  pub fn add_algorithm(&mut self, algorithm: u16) {
+    self.fbb_.push_slot::<u16>(SetPreferredAlgorithmAction::VT_ALGORITHM, algorithm, 0);
+  }
+  #[inline]
+  pub fn add_moves(&mut self, moves: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SetPreferredAlgorithmAction::VT_MOVES, moves);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> SetPreferredAlgorithmActionBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    SetPreferredAlgorithmActionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<SetPreferredAlgorithmAction<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for SetPreferredAlgorithmAction<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("SetPreferredAlgorithmAction");
+      ds.field("algorithm_type", &self.algorithm_type());
+      ds.field("algorithm", &self.algorithm());
+      ds.field("moves", &self.moves());
+      ds.finish()
+  }
+}
 pub enum ActionOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
@@ -1705,6 +2223,46 @@ impl<'a> Action<'a> {
     }
   }
 
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn contents_as_algorithm_status_action(&self) -> Option<AlgorithmStatusAction<'a>> {
+    if self.contents_type() == ActionContents::AlgorithmStatusAction {
+      self.contents().map(AlgorithmStatusAction::init_from_table)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn contents_as_add_algorithm_action(&self) -> Option<AddAlgorithmAction<'a>> {
+    if self.contents_type() == ActionContents::AddAlgorithmAction {
+      self.contents().map(AddAlgorithmAction::init_from_table)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn contents_as_remove_algorithm_action(&self) -> Option<RemoveAlgorithmAction<'a>> {
+    if self.contents_type() == ActionContents::RemoveAlgorithmAction {
+      self.contents().map(RemoveAlgorithmAction::init_from_table)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn contents_as_set_preferred_algorithm_action(&self) -> Option<SetPreferredAlgorithmAction<'a>> {
+    if self.contents_type() == ActionContents::SetPreferredAlgorithmAction {
+      self.contents().map(SetPreferredAlgorithmAction::init_from_table)
+    } else {
+      None
+    }
+  }
+
 }
 
 impl flatbuffers::Verifiable for Action<'_> {
@@ -1723,6 +2281,10 @@ impl flatbuffers::Verifiable for Action<'_> {
           ActionContents::MergeSessionsAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<MergeSessionsAction>>("ActionContents::MergeSessionsAction", pos),
           ActionContents::RenameSessionAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<RenameSessionAction>>("ActionContents::RenameSessionAction", pos),
           ActionContents::DeleteSolveAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DeleteSolveAction>>("ActionContents::DeleteSolveAction", pos),
+          ActionContents::AlgorithmStatusAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<AlgorithmStatusAction>>("ActionContents::AlgorithmStatusAction", pos),
+          ActionContents::AddAlgorithmAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<AddAlgorithmAction>>("ActionContents::AddAlgorithmAction", pos),
+          ActionContents::RemoveAlgorithmAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<RemoveAlgorithmAction>>("ActionContents::RemoveAlgorithmAction", pos),
+          ActionContents::SetPreferredAlgorithmAction => v.verify_union_variant::<flatbuffers::ForwardsUOffset<SetPreferredAlgorithmAction>>("ActionContents::SetPreferredAlgorithmAction", pos),
           _ => Ok(()),
         }
      })?
@@ -1826,6 +2388,34 @@ impl core::fmt::Debug for Action<'_> {
             ds.field("contents", &"InvalidFlatbuffer: Union discriminant does not match value.")
           }
         },
+        ActionContents::AlgorithmStatusAction => {
+          if let Some(x) = self.contents_as_algorithm_status_action() {
+            ds.field("contents", &x)
+          } else {
+            ds.field("contents", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ActionContents::AddAlgorithmAction => {
+          if let Some(x) = self.contents_as_add_algorithm_action() {
+            ds.field("contents", &x)
+          } else {
+            ds.field("contents", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ActionContents::RemoveAlgorithmAction => {
+          if let Some(x) = self.contents_as_remove_algorithm_action() {
+            ds.field("contents", &x)
+          } else {
+            ds.field("contents", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ActionContents::SetPreferredAlgorithmAction => {
+          if let Some(x) = self.contents_as_set_preferred_algorithm_action() {
+            ds.field("contents", &x)
+          } else {
+            ds.field("contents", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
         _ => {
           let x: Option<()> = None;
           ds.field("contents", &x)
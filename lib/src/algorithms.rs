@@ -1,2 +1,6 @@
+pub mod ergonomics;
 pub mod known;
 pub mod moves;
+pub mod review;
+pub mod set;
+pub mod variant;
@@ -0,0 +1,184 @@
+use crate::{CubeFace, ExtendedMove, SliceMoveAxis};
+
+/// Extra cost of a regrip, in moves
+const REGRIP_COST: f32 = 1.5;
+
+/// Extra cost of a cube rotation on top of the move itself
+const ROTATION_COST: f32 = 1.0;
+
+/// Extra cost of turning a wrist instead of using a fingertrick
+const WRIST_TURN_COST: f32 = 0.1;
+
+/// Range of wrist positions, in quarter turns away from the home grip, that can be
+/// reached without a regrip. A quarter turn back and a half turn forward is possible.
+const WRIST_RANGE: std::ops::RangeInclusive<i32> = -1..=2;
+
+/// Estimated ergonomics of an algorithm, used to rank the algorithms for a case. The
+/// estimate assumes a standard grip with the thumbs on the front face, where turns of
+/// the right and top faces are the fastest to perform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlgorithmErgonomics {
+    /// Number of moves in the execution turn metric, which counts every move performed
+    /// including rotations
+    pub moves: usize,
+    /// Number of cube rotations
+    pub rotations: usize,
+    /// Estimated number of times the grip must be changed because a wrist can't turn
+    /// any further
+    pub regrips: usize,
+    /// Number of moves performed by turning a wrist
+    pub wrist_turns: usize,
+    /// Number of moves that are not turns of the right or top faces
+    pub non_ru_moves: usize,
+    /// Overall difficulty score, in moves. Lower scores are easier to perform.
+    pub score: f32,
+}
+
+/// Position of the wrists while performing an algorithm
+struct Grip {
+    right: i32,
+    left: i32,
+    regrips: usize,
+}
+
+impl Grip {
+    /// Turns a wrist by `count` clockwise quarter turns of its face. Half turns are
+    /// performed in whichever direction keeps the wrist in range.
+    fn turn(wrist: &mut i32, count: i32, regrips: &mut usize) {
+        let count = match count.rem_euclid(4) {
+            1 => 1,
+            2 if WRIST_RANGE.contains(&(*wrist + 2)) => 2,
+            2 => -2,
+            3 => -1,
+            _ => 0,
+        };
+        if !WRIST_RANGE.contains(&(*wrist + count)) {
+            *regrips += 1;
+            *wrist = 0;
+        }
+        *wrist += count;
+    }
+
+    fn turn_right(&mut self, count: i32) {
+        Self::turn(&mut self.right, count, &mut self.regrips);
+    }
+
+    fn turn_left(&mut self, count: i32) {
+        Self::turn(&mut self.left, count, &mut self.regrips);
+    }
+
+    /// Cube rotations are performed with a new grip
+    fn reset(&mut self) {
+        self.right = 0;
+        self.left = 0;
+    }
+}
+
+impl AlgorithmErgonomics {
+    pub fn new(moves: &[ExtendedMove]) -> Self {
+        let mut grip = Grip {
+            right: 0,
+            left: 0,
+            regrips: 0,
+        };
+        let mut rotations = 0;
+        let mut wrist_turns = 0;
+        let mut non_ru_moves = 0;
+        let mut extra_cost = 0.0;
+
+        for mv in moves {
+            // Cost of the move on top of the move itself, based on how fast the move
+            // can be performed from the standard grip
+            extra_cost += match mv {
+                ExtendedMove::Outer(mv) => match mv.face() {
+                    CubeFace::Top | CubeFace::Right => 0.0,
+                    CubeFace::Front | CubeFace::Left | CubeFace::Bottom => 0.3,
+                    CubeFace::Back => 1.2,
+                },
+                ExtendedMove::Wide(mv) => match mv.face() {
+                    CubeFace::Right => 0.2,
+                    _ => 0.5,
+                },
+                ExtendedMove::Slice(mv) => match mv.axis() {
+                    SliceMoveAxis::M => 0.3,
+                    SliceMoveAxis::S | SliceMoveAxis::E => 0.8,
+                },
+                ExtendedMove::Rotation(_) => ROTATION_COST,
+            };
+
+            match mv {
+                ExtendedMove::Outer(mv)
+                    if mv.face() == CubeFace::Top || mv.face() == CubeFace::Right => {}
+                ExtendedMove::Rotation(_) => {
+                    rotations += 1;
+                    grip.reset();
+                }
+                _ => non_ru_moves += 1,
+            }
+
+            // Turns of the left and right faces are performed with the wrists
+            let face_turn = match mv {
+                ExtendedMove::Outer(mv) => Some((mv.face(), mv.rotation())),
+                ExtendedMove::Wide(mv) => Some((mv.face(), mv.rotation())),
+                _ => None,
+            };
+            match face_turn {
+                Some((CubeFace::Right, count)) => {
+                    grip.turn_right(count);
+                    wrist_turns += 1;
+                }
+                Some((CubeFace::Left, count)) => {
+                    grip.turn_left(count);
+                    wrist_turns += 1;
+                }
+                _ => (),
+            }
+        }
+
+        let score = moves.len() as f32
+            + extra_cost
+            + grip.regrips as f32 * REGRIP_COST
+            + wrist_turns as f32 * WRIST_TURN_COST;
+        Self {
+            moves: moves.len(),
+            rotations,
+            regrips: grip.regrips,
+            wrist_turns,
+            non_ru_moves,
+            score,
+        }
+    }
+
+    /// Ranks algorithms by how easy they are to perform, returning the indices of the
+    /// algorithms with the easiest first. `execution_times` holds the measured execution
+    /// time in milliseconds for the algorithms the user has performed. These are used in
+    /// place of the estimate, with the estimates of the other algorithms scaled to the
+    /// speed the user has shown on the measured ones.
+    pub fn rank(algorithms: &[Vec<ExtendedMove>], execution_times: &[Option<u32>]) -> Vec<usize> {
+        let scores: Vec<f32> = algorithms
+            .iter()
+            .map(|moves| Self::new(moves).score)
+            .collect();
+
+        // Find the speed of the user in score per second, which converts the measured
+        // times into the same units as the estimates
+        let mut measured_score = 0.0;
+        let mut measured_time = 0.0;
+        for (score, time) in scores.iter().zip(execution_times.iter()) {
+            if let Some(time) = time {
+                measured_score += score;
+                measured_time += *time as f32 / 1000.0;
+            }
+        }
+
+        let cost = |idx: usize| match execution_times.get(idx) {
+            Some(Some(time)) if measured_time > 0.0 => {
+                *time as f32 / 1000.0 * measured_score / measured_time
+            }
+            _ => scores[idx],
+        };
+        let mut result: Vec<usize> = (0..algorithms.len()).collect();
+        result.sort_by(|a, b| cost(*a).partial_cmp(&cost(*b)).unwrap());
+        result
+    }
+}
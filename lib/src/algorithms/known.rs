@@ -1,5 +1,12 @@
-use crate::{CubeRotation, ExtendedMove, Move, OLLAlgorithm, PLLAlgorithm, SliceMove, WideMove};
+use crate::{
+    CLLAlgorithm, COLLAlgorithm, Cube2x2x2, Cube3x3x3, Cube3x3x3Faces, CubeFace, CubeRotation,
+    EG1Algorithm, EG2Algorithm, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence,
+    F2LAlgorithm, InitialCubeState, Move, OLLAlgorithm, OLLCPAlgorithm, PLLAlgorithm, SliceMove,
+    WideMove, ZBLLAlgorithm,
+};
+use anyhow::{anyhow, Result};
 use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
 
 macro_rules! mv {
     (U) => {
@@ -832,6 +839,246 @@ impl KnownAlgorithms {
             ],
         }
     }
+
+    /// Returns the list of known algorithms for a given 2x2x2 CLL case. These are the
+    /// shortest solutions using only R, U, and F moves.
+    pub fn cll(cll: CLLAlgorithm) -> Vec<Vec<ExtendedMove>> {
+        match cll.to_index() {
+            0 => vec![algorithm!(R2 U2 R U2 R2)],
+            1 => vec![algorithm!(R U2 Fp R2 Fp R2 F U2 Rp)],
+            2 => vec![algorithm!(R Up Rp F U2 R2 F R Up R)],
+            3 => vec![algorithm!(R2 F R2 F2 U2 F R2)],
+            4 => vec![algorithm!(R Up R2 U R2 U R2 Up R)],
+            5 => vec![algorithm!(Rp U F R2 Fp U R2 Fp U2 F)],
+            6 => vec![algorithm!(R U Rp F2 U2 F U F2 U F2)],
+            7 => vec![algorithm!(R U2 Fp R2 F R2 F U2 Rp)],
+            8 => vec![algorithm!(R Up Fp U2 F Up R2 F R2 Fp)],
+            9 => vec![algorithm!(R2 U R2 Fp U2 F R2 Up R2)],
+            10 => vec![algorithm!(R Up F Up F R F2 U2 Rp)],
+            11 => vec![algorithm!(R F Rp U R2 F Up Fp Up R2)],
+            12 => vec![algorithm!(R2 U Rp Up R2 Up Fp U2 F)],
+            13 => vec![algorithm!(F2 Up R U2 Rp U F U2 F)],
+            14 => vec![algorithm!(F Up R Fp Rp U Fp Up R)],
+            15 => vec![algorithm!(R F R Fp Up Rp)],
+            16 => vec![algorithm!(R Up R Fp R Fp Up F2 R2)],
+            17 => vec![algorithm!(R F Up F U2 Fp R U F2)],
+            18 => vec![algorithm!(R U Rp Up Fp Up F)],
+            19 => vec![algorithm!(R Up Rp U2 Fp U2 F)],
+            20 => vec![algorithm!(F Rp Up R Fp Rp U Fp R)],
+            21 => vec![algorithm!(R Up F R2 Fp Up R2 F R Fp)],
+            22 => vec![algorithm!(R2 Up R U2 Rp U2 R Up R2)],
+            23 => vec![algorithm!(R U2 R U Fp U2 F Up R2)],
+            24 => vec![algorithm!(R F2 Rp F2 Up Rp F)],
+            25 => vec![algorithm!(Rp U2 Rp Up F R2 Fp U R2)],
+            26 => vec![algorithm!(R Fp Up Rp Up R F)],
+            27 => vec![algorithm!(R F2 R2 U R Up R F2 Rp)],
+            28 => vec![algorithm!(R U2 Rp Up R Up Rp)],
+            29 => vec![algorithm!(F U2 Fp U2 Fp R U Rp)],
+            30 => vec![algorithm!(Fp U F Rp U2 Rp U2 R)],
+            31 => vec![algorithm!(Rp F R Fp R U Rp)],
+            32 => vec![algorithm!(R2 F R U2 Fp U2 Rp Fp R2)],
+            33 => vec![algorithm!(R Fp U2 F Rp U2 R U R2)],
+            34 => vec![algorithm!(R U Rp U R U2 Rp)],
+            35 => vec![algorithm!(F Rp Fp R U2 R U2 Rp)],
+            36 => vec![algorithm!(Fp R2 F R2 F Rp Up R)],
+            37 => vec![algorithm!(R2 F R U2 F U2 Rp Fp R2)],
+            38 => vec![algorithm!(R Fp Up F Rp Up R)],
+            39 => vec![algorithm!(Rp F R2 Fp R U2 Rp Up R2)],
+            40 => vec![algorithm!(R2 F2 R F Rp F2 R Up R)],
+            41 => vec![algorithm!(R Up Rp Up F2 Up R U Rp U F2)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the list of known algorithms for a given 2x2x2 EG-1 case. These are the
+    /// shortest solutions using only R, U, and F moves.
+    pub fn eg1(eg1: EG1Algorithm) -> Vec<Vec<ExtendedMove>> {
+        match eg1.to_index() {
+            0 => vec![algorithm!(F R2 F Up Fp R Fp R)],
+            1 => vec![algorithm!(F Rp U Rp Up F Up R2)],
+            2 => vec![algorithm!(Rp Fp Rp F2 U Rp U Rp)],
+            3 => vec![algorithm!(R U F2 Up R U2 Fp U R)],
+            4 => vec![algorithm!(F U R2 F2 Up R2 Up Rp)],
+            5 => vec![algorithm!(R U Fp R F R Fp U2 R)],
+            6 => vec![algorithm!(R Up R Up F2 R F R)],
+            7 => vec![algorithm!(Rp F Rp Fp U Rp U2 Rp)],
+            8 => vec![algorithm!(R U R F2 U Fp R2 U2 Fp)],
+            9 => vec![algorithm!(R U R2 Fp R2 U Rp)],
+            10 => vec![algorithm!(Rp U2 Rp U2 Rp Up F2 R2)],
+            11 => vec![algorithm!(R U2 Fp U2 F U2 Rp)],
+            12 => vec![algorithm!(R2 Up F U2 F U F Rp)],
+            13 => vec![algorithm!(R Up Fp U2 F Rp U2 Rp)],
+            14 => vec![algorithm!(R Fp U Fp Up F2 R F2 R)],
+            15 => vec![algorithm!(R2 U R U2 R U2 R)],
+            16 => vec![algorithm!(R2 F2 U R U2 R U2 R)],
+            17 => vec![algorithm!(R Up R Up Fp R Up F R)],
+            18 => vec![algorithm!(R2 Fp U F U2 F Up Rp)],
+            19 => vec![algorithm!(R Up Fp U2 Fp Rp U2 Rp)],
+            20 => vec![algorithm!(R Up F Up F2 Up Fp U Rp)],
+            21 => vec![algorithm!(Fp R U2 Fp U R2 Up Rp)],
+            22 => vec![algorithm!(R Up R2 Fp R F Rp F2 R)],
+            23 => vec![algorithm!(R U2 R2 U Fp U2 F R2)],
+            24 => vec![algorithm!(R F Rp U2 Rp Fp R F2)],
+            25 => vec![algorithm!(R U2 R Fp U2 F U Rp)],
+            26 => vec![algorithm!(R U2 R F U2 F U Rp)],
+            27 => vec![algorithm!(R Fp U Rp U2 R Fp R2 F)],
+            28 => vec![algorithm!(F Up R2 F2 Up R)],
+            29 => vec![algorithm!(F2 U F Rp U2 F2 Up Rp)],
+            30 => vec![algorithm!(R2 F R Fp U Rp U R2)],
+            31 => vec![algorithm!(R2 F2 U F Up F2 R2 Fp Rp)],
+            32 => vec![algorithm!(R2 Fp R Fp U Rp U R2)],
+            33 => vec![algorithm!(F Rp U2 F R2 F Up R)],
+            34 => vec![algorithm!(Fp U R2 F2 U Rp)],
+            35 => vec![algorithm!(R2 Fp Up F Up R Up R2)],
+            36 => vec![algorithm!(F U2 F Up F2 R U2 Rp)],
+            37 => vec![algorithm!(R2 F Up F Up R Up R2)],
+            38 => vec![algorithm!(R U2 R2 Fp Up F2 Up F Rp)],
+            39 => vec![algorithm!(F Rp F2 U2 F R2 U R)],
+            40 => vec![algorithm!(R2 F2 R F Up R2 U Fp R)],
+            41 => vec![algorithm!(F2 U R2 U2 F2 U R2)],
+            42 => vec![algorithm!(R Up F U2 Fp U Rp)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the list of known algorithms for a given 2x2x2 EG-2 case. These are the
+    /// shortest solutions using only R, U, and F moves.
+    pub fn eg2(eg2: EG2Algorithm) -> Vec<Vec<ExtendedMove>> {
+        match eg2.to_index() {
+            0 => vec![algorithm!(R2 F R2 F2 U2 Fp R2)],
+            1 => vec![algorithm!(R U2 F2 Rp F U Fp U Rp)],
+            2 => vec![algorithm!(F R2 F Rp F2 Rp U2 Fp R)],
+            3 => vec![algorithm!(R2 U2 R U2 F2 R2)],
+            4 => vec![algorithm!(R2 U F2 Rp U2 Rp F2 U F2)],
+            5 => vec![algorithm!(R U2 R F2 R2 Up R Up Rp)],
+            6 => vec![algorithm!(F Up R2 Fp R2 Fp R U2 R)],
+            7 => vec![algorithm!(R Up F Up Fp R F2 U2 Rp)],
+            8 => vec![algorithm!(R U R F2 R2 Up R U2 Rp)],
+            9 => vec![algorithm!(F R2 F2 R U R2 U2 R)],
+            10 => vec![algorithm!(R U F Rp Fp R F2 R2)],
+            11 => vec![algorithm!(R U R F2 R2 U2 R Up Rp)],
+            12 => vec![algorithm!(R2 U Fp U R U F2 Rp F)],
+            13 => vec![algorithm!(R U2 Fp Up Fp R Fp R F)],
+            14 => vec![algorithm!(R U F Rp F2 U F2 Up F Rp)],
+            15 => vec![algorithm!(R Up R Fp R Fp U R U2 R2)],
+            16 => vec![algorithm!(R2 F2 R Up Fp U F R)],
+            17 => vec![algorithm!(R U2 R F2 Rp U2 Rp)],
+            18 => vec![algorithm!(R Up F2 Up Rp F U2 Rp)],
+            19 => vec![algorithm!(Rp F U2 F U Fp U2 R)],
+            20 => vec![algorithm!(R Fp U F2 Up F2 R Fp Up Rp)],
+            21 => vec![algorithm!(R Fp U Fp U Rp Up R2)],
+            22 => vec![algorithm!(R Up R Fp Rp Fp U R2 Up R)],
+            23 => vec![algorithm!(R U2 Rp U2 Fp Up Fp R2 F2)],
+            24 => vec![algorithm!(R U2 Fp R U F2 U Rp)],
+            25 => vec![algorithm!(Rp F2 R U2 F U F R2 F2)],
+            26 => vec![algorithm!(Rp F2 U Fp Up F2 Up R)],
+            27 => vec![algorithm!(R U F2 Rp F2 R F2 Up Rp)],
+            28 => vec![algorithm!(R U2 F Rp F2 R U F2 U Rp)],
+            29 => vec![algorithm!(F2 R U2 Fp U Fp Up Rp)],
+            30 => vec![algorithm!(Fp Up Rp U R U2 Fp R2)],
+            31 => vec![algorithm!(Rp F Rp F R2 F U2 Fp)],
+            32 => vec![algorithm!(R U Rp U F2 Rp U Rp F)],
+            33 => vec![algorithm!(R F2 Rp Fp U Fp R F2 R2)],
+            34 => vec![algorithm!(R U R U Fp R U F2 Rp F)],
+            35 => vec![algorithm!(F U R Fp U R2 Fp R2)],
+            36 => vec![algorithm!(F2 R F2 Up Rp F U R)],
+            37 => vec![algorithm!(Rp Fp R Up F2 R Fp U Fp)],
+            38 => vec![algorithm!(R Up R Fp U2 Fp R2 F)],
+            39 => vec![algorithm!(R F Up F R F2 R F2 R2)],
+            40 => vec![algorithm!(R Up R U Rp F2 Rp Up Rp U F2)],
+            41 => vec![algorithm!(R Up R F2 Rp U Rp)],
+            42 => vec![algorithm!(R2 F2 R2)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the list of known algorithms for a given F2L case. Algorithms insert the
+    /// pair into the front right slot with the cross on the bottom.
+    pub fn f2l(f2l: F2LAlgorithm) -> Vec<Vec<ExtendedMove>> {
+        match f2l.as_number() {
+            1 => vec![algorithm!(U R Up Rp), algorithm!(Rp F R Fp)],
+            2 => vec![algorithm!(Up Fp U F), algorithm!(yp Up Rp U R)],
+            3 => vec![algorithm!(Fp Up F), algorithm!(yp Rp Up R)],
+            4 => vec![algorithm!(R U Rp)],
+            5 => vec![algorithm!(Up R U Rp U2 R Up Rp)],
+            6 => vec![
+                algorithm!(U Fp Up F U2 Fp U F),
+                algorithm!(yp U Rp Up R U2 Rp U R),
+            ],
+            7 => vec![algorithm!(Up R U2 Rp U2 R Up Rp)],
+            8 => vec![
+                algorithm!(U Fp U2 F U2 Fp U F),
+                algorithm!(yp U Rp U2 R U2 Rp U R),
+            ],
+            9 => vec![algorithm!(Up R Up Rp U Fp Up F)],
+            10 => vec![algorithm!(Up R U Rp U R U Rp)],
+            11 => vec![algorithm!(Up R U2 Rp U Fp Up F)],
+            12 => vec![algorithm!(R Up Rp U R Up Rp U2 R Up Rp)],
+            13 => vec![
+                algorithm!(U Fp U F Up Fp Up F),
+                algorithm!(yp U Rp U R Up Rp Up R),
+            ],
+            14 => vec![algorithm!(Up R Up Rp U R U Rp)],
+            15 => vec![
+                algorithm!(Rp Dp R Up Rp D R U R Up Rp),
+                algorithm!(yp Rp U R U2 y R U Rp),
+            ],
+            16 => vec![
+                algorithm!(R Up Rp U2 Fp Up F),
+                algorithm!(R Up Rp U2 yp Rp Up R),
+            ],
+            17 => vec![algorithm!(R U2 Rp Up R U Rp)],
+            18 => vec![
+                algorithm!(Fp U2 F U Fp Up F),
+                algorithm!(yp Rp U2 R U Rp Up R),
+            ],
+            19 => vec![algorithm!(U R U2 Rp U R Up Rp)],
+            20 => vec![
+                algorithm!(Up Fp U2 F Up Fp U F),
+                algorithm!(yp Up Rp U2 R Up Rp U R),
+            ],
+            21 => vec![algorithm!(U2 R U Rp U R Up Rp)],
+            22 => vec![
+                algorithm!(U2 Fp Up F Up Fp U F),
+                algorithm!(yp U2 Rp Up R Up Rp U R),
+            ],
+            23 => vec![algorithm!(U R Up Rp Up R Up Rp U R Up Rp)],
+            24 => vec![
+                algorithm!(Up Fp U F U Fp U F Up Fp U F),
+                algorithm!(yp Up Rp U R U Rp U R Up Rp U R),
+            ],
+            25 => vec![algorithm!(Up Rp F R Fp R U Rp)],
+            26 => vec![algorithm!(U R Up Rp F Rp Fp R)],
+            27 => vec![algorithm!(R Up Rp U R Up Rp)],
+            28 => vec![
+                algorithm!(Fp U F Up Fp U F),
+                algorithm!(yp Rp U R Up Rp U R),
+            ],
+            29 => vec![algorithm!(R U Rp Up R U Rp)],
+            30 => vec![
+                algorithm!(Fp Up F U Fp Up F),
+                algorithm!(yp Rp Up R U Rp Up R),
+            ],
+            31 => vec![algorithm!(Up Rp F R Fp R Up Rp)],
+            32 => vec![algorithm!(U R Up Rp U R Up Rp U R Up Rp)],
+            33 => vec![algorithm!(Up R Up Rp U2 R Up Rp)],
+            34 => vec![algorithm!(U R U Rp U2 R U Rp)],
+            35 => vec![algorithm!(Up R U Rp U Fp Up F)],
+            36 => vec![
+                algorithm!(U Fp Up F Up R U Rp),
+                algorithm!(yp U Rp Up R dp R U Rp),
+            ],
+            37 => vec![algorithm!(R2 U2 F R2 Fp U2 Rp U Rp)],
+            38 => vec![algorithm!(R Up Rp Up R U Rp U2 R Up Rp)],
+            39 => vec![algorithm!(R Up Rp U R U2 Rp U R Up Rp)],
+            40 => vec![
+                algorithm!(Fp U F U2 R U Rp U R Up Rp),
+                algorithm!(R Up Rp F R U Rp Up Fp R Up Rp),
+            ],
+            41 => vec![algorithm!(R U Rp Up R Up Rp U2 Fp Up F)],
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[repr(u8)]
@@ -839,30 +1086,303 @@ impl KnownAlgorithms {
 pub enum AlgorithmType {
     OLL = 0,
     PLL = 1,
+    COLL = 2,
+    ZBLL = 3,
+    OLLCP = 4,
+    CLL = 5,
+    EG1 = 6,
+    EG2 = 7,
+    F2L = 8,
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+impl AlgorithmType {
+    pub fn all() -> &'static [Self] {
+        &[
+            AlgorithmType::OLL,
+            AlgorithmType::PLL,
+            AlgorithmType::COLL,
+            AlgorithmType::ZBLL,
+            AlgorithmType::OLLCP,
+            AlgorithmType::CLL,
+            AlgorithmType::EG1,
+            AlgorithmType::EG2,
+            AlgorithmType::F2L,
+        ]
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            AlgorithmType::OLL => "OLL",
+            AlgorithmType::PLL => "PLL",
+            AlgorithmType::COLL => "COLL",
+            AlgorithmType::ZBLL => "ZBLL",
+            AlgorithmType::OLLCP => "OLLCP",
+            AlgorithmType::CLL => "CLL",
+            AlgorithmType::EG1 => "EG-1",
+            AlgorithmType::EG2 => "EG-2",
+            AlgorithmType::F2L => "F2L",
+        }
+    }
+
+    /// Parses the name of an algorithm set, ignoring case and dashes
+    pub fn from_str(name: &str) -> Option<Self> {
+        let name = name.replace('-', "").to_uppercase();
+        Self::all()
+            .iter()
+            .find(|alg_type| alg_type.to_str().replace('-', "") == name)
+            .cloned()
+    }
+
+    /// Gets all cases in this algorithm set
+    pub fn cases(&self) -> Vec<Algorithm> {
+        match self {
+            AlgorithmType::OLL => OLLAlgorithm::all()
+                .iter()
+                .map(|oll| Algorithm::OLL(*oll))
+                .collect(),
+            AlgorithmType::PLL => PLLAlgorithm::all()
+                .iter()
+                .map(|pll| Algorithm::PLL(*pll))
+                .collect(),
+            AlgorithmType::COLL => COLLAlgorithm::all()
+                .into_iter()
+                .map(Algorithm::COLL)
+                .collect(),
+            AlgorithmType::ZBLL => ZBLLAlgorithm::all()
+                .into_iter()
+                .map(Algorithm::ZBLL)
+                .collect(),
+            AlgorithmType::OLLCP => OLLCPAlgorithm::all()
+                .into_iter()
+                .map(Algorithm::OLLCP)
+                .collect(),
+            AlgorithmType::CLL => CLLAlgorithm::all()
+                .into_iter()
+                .map(Algorithm::CLL)
+                .collect(),
+            AlgorithmType::EG1 => EG1Algorithm::all()
+                .into_iter()
+                .map(Algorithm::EG1)
+                .collect(),
+            AlgorithmType::EG2 => EG2Algorithm::all()
+                .into_iter()
+                .map(Algorithm::EG2)
+                .collect(),
+            AlgorithmType::F2L => F2LAlgorithm::all()
+                .into_iter()
+                .map(Algorithm::F2L)
+                .collect(),
+        }
+    }
+}
+
+/// Learning progress of an algorithm, set by the user and used to select cases for
+/// training.
+#[repr(u8)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, TryFromPrimitive)]
+pub enum AlgorithmStatus {
+    Unknown = 0,
+    Learning = 1,
+    Known = 2,
+}
+
+impl AlgorithmStatus {
+    pub fn all() -> &'static [Self] {
+        &[
+            AlgorithmStatus::Unknown,
+            AlgorithmStatus::Learning,
+            AlgorithmStatus::Known,
+        ]
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            AlgorithmStatus::Unknown => "Unknown",
+            AlgorithmStatus::Learning => "Learning",
+            AlgorithmStatus::Known => "Known",
+        }
+    }
+
+    /// Parses the name of a learning status, ignoring case
+    pub fn from_str(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .find(|status| status.to_str().eq_ignore_ascii_case(name))
+            .cloned()
+    }
+}
+
+impl Default for AlgorithmStatus {
+    fn default() -> Self {
+        AlgorithmStatus::Unknown
+    }
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Algorithm {
     OLL(OLLAlgorithm),
     PLL(PLLAlgorithm),
+    COLL(COLLAlgorithm),
+    ZBLL(ZBLLAlgorithm),
+    OLLCP(OLLCPAlgorithm),
+    CLL(CLLAlgorithm),
+    EG1(EG1Algorithm),
+    EG2(EG2Algorithm),
+    F2L(F2LAlgorithm),
 }
 
 impl Algorithm {
-    pub(crate) fn to_type_and_number(&self) -> (AlgorithmType, u8) {
+    /// Gets the algorithm set this case is in
+    pub fn alg_type(&self) -> AlgorithmType {
+        self.to_type_and_number().0
+    }
+
+    pub(crate) fn to_type_and_number(&self) -> (AlgorithmType, u16) {
         match self {
-            Algorithm::OLL(oll) => (AlgorithmType::OLL, oll.as_number()),
-            Algorithm::PLL(pll) => (AlgorithmType::PLL, pll.to_index() as u8),
+            Algorithm::OLL(oll) => (AlgorithmType::OLL, oll.as_number() as u16),
+            Algorithm::PLL(pll) => (AlgorithmType::PLL, pll.to_index() as u16),
+            Algorithm::COLL(coll) => (AlgorithmType::COLL, coll.to_index()),
+            Algorithm::ZBLL(zbll) => (AlgorithmType::ZBLL, zbll.to_index()),
+            Algorithm::OLLCP(ollcp) => (AlgorithmType::OLLCP, ollcp.to_index()),
+            Algorithm::CLL(cll) => (AlgorithmType::CLL, cll.to_index()),
+            Algorithm::EG1(eg1) => (AlgorithmType::EG1, eg1.to_index()),
+            Algorithm::EG2(eg2) => (AlgorithmType::EG2, eg2.to_index()),
+            Algorithm::F2L(f2l) => (AlgorithmType::F2L, f2l.to_index()),
         }
     }
 
-    pub(crate) fn from_type_and_number(alg_type: AlgorithmType, num: u8) -> Option<Self> {
+    pub(crate) fn from_type_and_number(alg_type: AlgorithmType, num: u16) -> Option<Self> {
         match alg_type {
-            AlgorithmType::OLL => Some(Algorithm::OLL(OLLAlgorithm::from_number(num))),
+            AlgorithmType::OLL => match num {
+                1..=57 => Some(Algorithm::OLL(OLLAlgorithm::from_number(num as u8))),
+                _ => None,
+            },
             AlgorithmType::PLL => match PLLAlgorithm::from_index(num as usize) {
                 Some(pll) => Some(Algorithm::PLL(pll)),
                 None => None,
             },
+            AlgorithmType::COLL => COLLAlgorithm::from_index(num).map(Algorithm::COLL),
+            AlgorithmType::ZBLL => ZBLLAlgorithm::from_index(num).map(Algorithm::ZBLL),
+            AlgorithmType::OLLCP => OLLCPAlgorithm::from_index(num).map(Algorithm::OLLCP),
+            AlgorithmType::CLL => CLLAlgorithm::from_index(num).map(Algorithm::CLL),
+            AlgorithmType::EG1 => EG1Algorithm::from_index(num).map(Algorithm::EG1),
+            AlgorithmType::EG2 => EG2Algorithm::from_index(num).map(Algorithm::EG2),
+            AlgorithmType::F2L => F2LAlgorithm::from_index(num).map(Algorithm::F2L),
+        }
+    }
+
+    /// Returns the known algorithms for this case. Cases in the larger last layer sets
+    /// do not have any known algorithms.
+    pub fn known_algorithms(&self) -> Vec<Vec<ExtendedMove>> {
+        match self {
+            Algorithm::OLL(oll) => KnownAlgorithms::oll(*oll),
+            Algorithm::PLL(pll) => KnownAlgorithms::pll(*pll),
+            Algorithm::CLL(cll) => KnownAlgorithms::cll(*cll),
+            Algorithm::EG1(eg1) => KnownAlgorithms::eg1(*eg1),
+            Algorithm::EG2(eg2) => KnownAlgorithms::eg2(*eg2),
+            Algorithm::F2L(f2l) => KnownAlgorithms::f2l(*f2l),
+            Algorithm::COLL(_) | Algorithm::ZBLL(_) | Algorithm::OLLCP(_) => Vec::new(),
+        }
+    }
+
+    /// Checks that the moves are an algorithm for this case. Any adjustment of the last
+    /// layer may be needed before or after the algorithm, but the rest of the cube must
+    /// be left solved with the last layer on top. F2L algorithms may insert the pair into
+    /// any slot.
+    pub fn validate(&self, moves: &[ExtendedMove]) -> Result<()> {
+        if moves.is_empty() {
+            return Err(anyhow!("Algorithm has no moves"));
+        }
+
+        // Undo the algorithm from a solved cube. If the algorithm solves the case, the
+        // result is a cube in the state of the case.
+        let inverse = moves.inverse();
+        let valid = match self {
+            Algorithm::CLL(_) | Algorithm::EG1(_) | Algorithm::EG2(_) => {
+                let mut cube = Cube2x2x2::new();
+                let mut context = ExtendedMoveContext::new(&mut cube);
+                context.do_moves(&inverse);
+                let faces = cube.as_faces();
+
+                // Case detection checks the first layer for these cases
+                match self {
+                    Algorithm::CLL(cll) => {
+                        CLLAlgorithm::from_cube(&faces, CubeFace::Bottom) == Some(*cll)
+                    }
+                    Algorithm::EG1(eg1) => {
+                        EG1Algorithm::from_cube(&faces, CubeFace::Bottom) == Some(*eg1)
+                    }
+                    Algorithm::EG2(eg2) => {
+                        EG2Algorithm::from_cube(&faces, CubeFace::Bottom) == Some(*eg2)
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Algorithm::F2L(f2l) => {
+                let mut cube = Cube3x3x3::new();
+                let mut context = ExtendedMoveContext::new(&mut cube);
+                context.do_moves(&inverse);
+                F2LAlgorithm::from_cube(&cube.as_faces(), CubeFace::Bottom) == Some(*f2l)
+            }
+            _ => {
+                let mut cube = Cube3x3x3::new();
+                let mut context = ExtendedMoveContext::new(&mut cube);
+                context.do_moves(&inverse);
+                let faces = cube.as_faces();
+
+                Self::first_two_layers_solved(&faces)
+                    && match self {
+                        Algorithm::OLL(oll) => {
+                            OLLAlgorithm::from_cube(&faces, CubeFace::Top) == Some(*oll)
+                        }
+                        Algorithm::PLL(pll) => {
+                            PLLAlgorithm::from_cube(&faces, CubeFace::Top) == Some(*pll)
+                        }
+                        Algorithm::COLL(coll) => {
+                            COLLAlgorithm::from_cube(&faces, CubeFace::Top) == Some(*coll)
+                        }
+                        Algorithm::ZBLL(zbll) => {
+                            ZBLLAlgorithm::from_cube(&faces, CubeFace::Top) == Some(*zbll)
+                        }
+                        Algorithm::OLLCP(ollcp) => {
+                            OLLCPAlgorithm::from_cube(&faces, CubeFace::Top) == Some(*ollcp)
+                        }
+                        _ => unreachable!(),
+                    }
+            }
+        };
+        if !valid {
+            return Err(anyhow!("Algorithm does not solve {}", self.to_string()));
         }
+
+        // Rotations do not change the cube state, so check that the algorithm does not
+        // leave the cube held with another face on top
+        let mut cube = Cube3x3x3::new();
+        let mut context = ExtendedMoveContext::new(&mut cube);
+        context.do_moves(moves);
+        if !context.inverse_rotation_top_only().is_empty() {
+            return Err(anyhow!("Algorithm does not end with the last layer on top"));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all pieces outside of the top layer are solved
+    fn first_two_layers_solved(cube: &Cube3x3x3Faces) -> bool {
+        let bottom_solved = (0..3).all(|row| {
+            (0..3).all(|col| cube.color(CubeFace::Bottom, row, col) == CubeFace::Bottom.color())
+        });
+        let sides_solved = [
+            CubeFace::Front,
+            CubeFace::Right,
+            CubeFace::Back,
+            CubeFace::Left,
+        ]
+        .iter()
+        .all(|face| {
+            (1..3).all(|row| (0..3).all(|col| cube.color(*face, row, col) == face.color()))
+        });
+        bottom_solved && sides_solved
     }
 }
 
@@ -871,6 +1391,13 @@ impl ToString for Algorithm {
         match self {
             Algorithm::OLL(oll) => oll.to_string(),
             Algorithm::PLL(pll) => pll.to_str().into(),
+            Algorithm::COLL(coll) => format!("COLL {}", coll),
+            Algorithm::ZBLL(zbll) => format!("ZBLL {}", zbll),
+            Algorithm::OLLCP(ollcp) => format!("OLLCP {}", ollcp),
+            Algorithm::CLL(cll) => format!("CLL {}", cll),
+            Algorithm::EG1(eg1) => format!("EG-1 {}", eg1),
+            Algorithm::EG2(eg2) => format!("EG-2 {}", eg2),
+            Algorithm::F2L(f2l) => format!("F2L {}", f2l),
         }
     }
 }
@@ -1,4 +1,5 @@
-use crate::{CubeFace, FaceRotation, Move, RotationDirection};
+use crate::{Cube, Cube3x3x3, CubeFace, FaceRotation, InitialCubeState, Move, RotationDirection};
+use anyhow::{anyhow, Result};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 /// Move to perform on a cube (including slices and rotations)
@@ -77,11 +78,42 @@ pub enum CubeRotationAxis {
     Z,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// Plane to mirror a move sequence across. `LeftRight` swaps the left and right faces,
+/// which turns a right handed algorithm into the left handed version.
+pub enum MirrorAxis {
+    LeftRight,
+    FrontBack,
+}
+
 /// Operations on sequences of cube moves (including slices and rotations)
 pub trait ExtendedMoveSequence: Sized {
     /// Returns the inverse of this move sequence (undoing all moves)
     fn inverse(&self) -> Vec<ExtendedMove>;
 
+    /// Returns the mirror image of this move sequence. Faces are swapped across the
+    /// given axis and all turns are performed in the opposite direction.
+    fn mirror(&self, axis: MirrorAxis) -> Vec<ExtendedMove>;
+
+    /// Returns this move sequence as performed after the given cube rotation, with the
+    /// rotation undone at the end. For example, `R U R'` rotated by `y` is `B U B'`.
+    fn rotate(&self, rotation: CubeRotation) -> Vec<ExtendedMove>;
+
+    /// Returns this move sequence without the turns of the top face at the start and
+    /// end, which only align the last layer (AUF)
+    fn normalize_auf(&self) -> Vec<ExtendedMove>;
+
+    /// Checks if this move sequence solves the same case as `other`, allowing for
+    /// different alignment of the last layer before and after and for the algorithm
+    /// being performed from a different side of the cube
+    fn same_case(&self, other: &[ExtendedMove]) -> bool;
+
+    /// Checks if this move sequence is the same algorithm as `other`, apart from
+    /// turns of the top face at the start and end and being performed from a different
+    /// side of the cube. Unlike `same_case`, different algorithms for the same case do
+    /// not match.
+    fn same_algorithm(&self, other: &[ExtendedMove]) -> bool;
+
     /// Returns the human-readable string for this move sequence
     fn to_string(&self) -> String;
 }
@@ -96,6 +128,13 @@ struct CubeFaceMap {
     mapping: [CubeFace; 6],
 }
 
+/// Relabeling of the faces of the cube, optionally reflected, used to transform
+/// move sequences
+struct MoveTransform {
+    faces: [CubeFace; 6],
+    mirror: bool,
+}
+
 impl ExtendedMove {
     /// Gets the inverse of the move
     pub fn inverse(&self) -> Self {
@@ -106,6 +145,21 @@ impl ExtendedMove {
             ExtendedMove::Rotation(mv) => ExtendedMove::Rotation(mv.inverse()),
         }
     }
+
+    /// Parses a move in standard notation. Lowercase or `w` suffixed moves are wide moves.
+    pub fn from_str(string: &str) -> Option<Self> {
+        if let Some(mv) = SliceMove::from_str(string) {
+            Some(ExtendedMove::Slice(mv))
+        } else if let Some(mv) = WideMove::from_str(string) {
+            Some(ExtendedMove::Wide(mv))
+        } else if let Some(mv) = CubeRotation::from_str(string) {
+            Some(ExtendedMove::Rotation(mv))
+        } else {
+            Move::from_str(string)
+                .filter(|mv| mv.width() == 1)
+                .map(ExtendedMove::Outer)
+        }
+    }
 }
 
 impl ToString for ExtendedMove {
@@ -173,6 +227,21 @@ impl SliceMove {
     pub fn inverse(&self) -> Self {
         Self::from_axis_and_count(self.axis(), -self.count()).unwrap()
     }
+
+    pub fn from_str(string: &str) -> Option<Self> {
+        match string {
+            "M" => Some(SliceMove::M),
+            "M'" => Some(SliceMove::Mp),
+            "M2" => Some(SliceMove::M2),
+            "S" => Some(SliceMove::S),
+            "S'" => Some(SliceMove::Sp),
+            "S2" => Some(SliceMove::S2),
+            "E" => Some(SliceMove::E),
+            "E'" => Some(SliceMove::Ep),
+            "E2" => Some(SliceMove::E2),
+            _ => None,
+        }
+    }
 }
 
 impl ToString for SliceMove {
@@ -286,6 +355,30 @@ impl WideMove {
     pub fn inverse(&self) -> Self {
         Self::from_face_and_rotation(self.face(), -self.rotation()).unwrap()
     }
+
+    pub fn from_str(string: &str) -> Option<Self> {
+        match string {
+            "Uw" | "u" => Some(WideMove::U),
+            "Uw'" | "u'" => Some(WideMove::Up),
+            "Uw2" | "u2" => Some(WideMove::U2),
+            "Fw" | "f" => Some(WideMove::F),
+            "Fw'" | "f'" => Some(WideMove::Fp),
+            "Fw2" | "f2" => Some(WideMove::F2),
+            "Rw" | "r" => Some(WideMove::R),
+            "Rw'" | "r'" => Some(WideMove::Rp),
+            "Rw2" | "r2" => Some(WideMove::R2),
+            "Bw" | "b" => Some(WideMove::B),
+            "Bw'" | "b'" => Some(WideMove::Bp),
+            "Bw2" | "b2" => Some(WideMove::B2),
+            "Lw" | "l" => Some(WideMove::L),
+            "Lw'" | "l'" => Some(WideMove::Lp),
+            "Lw2" | "l2" => Some(WideMove::L2),
+            "Dw" | "d" => Some(WideMove::D),
+            "Dw'" | "d'" => Some(WideMove::Dp),
+            "Dw2" | "d2" => Some(WideMove::D2),
+            _ => None,
+        }
+    }
 }
 
 impl ToString for WideMove {
@@ -367,6 +460,21 @@ impl CubeRotation {
     pub fn inverse(&self) -> Self {
         Self::from_axis_and_count(self.axis(), -self.count()).unwrap()
     }
+
+    pub fn from_str(string: &str) -> Option<Self> {
+        match string {
+            "x" => Some(CubeRotation::X),
+            "x'" => Some(CubeRotation::Xp),
+            "x2" => Some(CubeRotation::X2),
+            "y" => Some(CubeRotation::Y),
+            "y'" => Some(CubeRotation::Yp),
+            "y2" => Some(CubeRotation::Y2),
+            "z" => Some(CubeRotation::Z),
+            "z'" => Some(CubeRotation::Zp),
+            "z2" => Some(CubeRotation::Z2),
+            _ => None,
+        }
+    }
 }
 
 impl ToString for CubeRotation {
@@ -390,11 +498,43 @@ impl ExtendedMoveSequence for Vec<ExtendedMove> {
         self.as_slice().inverse()
     }
 
+    fn mirror(&self, axis: MirrorAxis) -> Vec<ExtendedMove> {
+        self.as_slice().mirror(axis)
+    }
+
+    fn rotate(&self, rotation: CubeRotation) -> Vec<ExtendedMove> {
+        self.as_slice().rotate(rotation)
+    }
+
+    fn normalize_auf(&self) -> Vec<ExtendedMove> {
+        self.as_slice().normalize_auf()
+    }
+
+    fn same_case(&self, other: &[ExtendedMove]) -> bool {
+        self.as_slice().same_case(other)
+    }
+
+    fn same_algorithm(&self, other: &[ExtendedMove]) -> bool {
+        self.as_slice().same_algorithm(other)
+    }
+
     fn to_string(&self) -> String {
         self.as_slice().to_string()
     }
 }
 
+/// Parses a sequence of moves in standard notation, including slices, wide moves and
+/// rotations
+pub fn parse_extended_move_string(string: &str) -> Result<Vec<ExtendedMove>> {
+    let mut moves = Vec::new();
+    for move_str in string.split_whitespace() {
+        let mv = ExtendedMove::from_str(move_str)
+            .ok_or_else(|| anyhow!("Invalid move '{}'", move_str))?;
+        moves.push(mv);
+    }
+    Ok(moves)
+}
+
 struct NullCube;
 
 impl FaceRotation for NullCube {
@@ -421,6 +561,92 @@ impl ExtendedMoveSequence for &[ExtendedMove] {
         result
     }
 
+    fn mirror(&self, axis: MirrorAxis) -> Vec<ExtendedMove> {
+        let mut faces = CubeFaceMap::new().mapping;
+        let (a, b) = match axis {
+            MirrorAxis::LeftRight => (CubeFace::Left, CubeFace::Right),
+            MirrorAxis::FrontBack => (CubeFace::Front, CubeFace::Back),
+        };
+        faces.swap(a as u8 as usize, b as u8 as usize);
+        let transform = MoveTransform {
+            faces,
+            mirror: true,
+        };
+        self.iter().map(|mv| transform.apply(*mv)).collect()
+    }
+
+    fn rotate(&self, rotation: CubeRotation) -> Vec<ExtendedMove> {
+        let mut face_map = CubeFaceMap::new();
+        face_map.rotate(rotation.axis(), rotation.count());
+        let transform = MoveTransform {
+            faces: face_map.mapping,
+            mirror: false,
+        };
+        self.iter().map(|mv| transform.apply(*mv)).collect()
+    }
+
+    fn normalize_auf(&self) -> Vec<ExtendedMove> {
+        let auf = |mv: &&ExtendedMove| match mv {
+            ExtendedMove::Outer(mv) => mv.face() == CubeFace::Top,
+            _ => false,
+        };
+        let start = self.iter().take_while(auf).count();
+        let end = self.len() - self[start..].iter().rev().take_while(auf).count();
+        self[start..end].to_vec()
+    }
+
+    fn same_case(&self, other: &[ExtendedMove]) -> bool {
+        // Compare the states that each algorithm solves, which are the states reached
+        // by performing the inverse of the algorithm on a solved cube
+        let case = |moves: &[ExtendedMove], before: i32, after: i32| {
+            let mut cube = Cube3x3x3::new();
+            if let Some(mv) = Move::from_face_and_rotation(CubeFace::Top, before) {
+                cube.do_move(mv);
+            }
+            ExtendedMoveContext::new(&mut cube).do_moves(&moves.inverse());
+            if let Some(mv) = Move::from_face_and_rotation(CubeFace::Top, after) {
+                cube.do_move(mv);
+            }
+            cube
+        };
+        let target = case(other, 0, 0);
+        (0..4).any(|rotation| {
+            let moves = match CubeRotation::from_axis_and_count(CubeRotationAxis::Y, rotation) {
+                Some(rotation) => self.rotate(rotation),
+                None => self.to_vec(),
+            };
+            (0..4).any(|before| (0..4).any(|after| case(&moves, before, after) == target))
+        })
+    }
+
+    fn same_algorithm(&self, other: &[ExtendedMove]) -> bool {
+        // Rotations before the first turn only change the side the algorithm is
+        // performed from, and rotations after the last turn don't change the result
+        let normalize = |moves: &[ExtendedMove]| {
+            let mut moves = moves.normalize_auf();
+            loop {
+                match (moves.first(), moves.last()) {
+                    (Some(ExtendedMove::Rotation(rotation)), _) => {
+                        moves = (&moves[1..]).rotate(*rotation).normalize_auf()
+                    }
+                    (_, Some(ExtendedMove::Rotation(_))) => {
+                        moves.pop();
+                        moves = moves.normalize_auf();
+                    }
+                    _ => break moves,
+                }
+            }
+        };
+        let moves = normalize(self);
+        let other = normalize(other);
+        (0..4).any(
+            |count| match CubeRotation::from_axis_and_count(CubeRotationAxis::Y, count) {
+                Some(rotation) => other.rotate(rotation) == moves,
+                None => other == moves,
+            },
+        )
+    }
+
     fn to_string(&self) -> String {
         let moves: Vec<String> = self.iter().map(|mv| mv.to_string()).collect();
         moves.join(" ")
@@ -497,6 +723,72 @@ impl CubeFaceMap {
     }
 }
 
+impl MoveTransform {
+    /// Translates the direction of a turn, which is reversed when mirroring
+    fn count(&self, count: i32) -> i32 {
+        if self.mirror {
+            -count
+        } else {
+            count
+        }
+    }
+
+    /// Transforms a turn of the given face. Returns the transformed face and turn count.
+    fn face_turn(&self, face: CubeFace, count: i32) -> (CubeFace, i32) {
+        (self.faces[face as u8 as usize], self.count(count))
+    }
+
+    /// Transforms a single move. Slices and rotations are transformed as a turn of the
+    /// face that turns in the same direction (`L` for `M`, `R` for `x`), then
+    /// translated back into a move along the axis of the new face.
+    fn apply(&self, mv: ExtendedMove) -> ExtendedMove {
+        match mv {
+            ExtendedMove::Outer(mv) => {
+                let (face, count) = self.face_turn(mv.face(), mv.rotation());
+                ExtendedMove::Outer(Move::from_face_and_rotation(face, count).unwrap())
+            }
+            ExtendedMove::Wide(mv) => {
+                let (face, count) = self.face_turn(mv.face(), mv.rotation());
+                ExtendedMove::Wide(WideMove::from_face_and_rotation(face, count).unwrap())
+            }
+            ExtendedMove::Slice(mv) => {
+                let face = match mv.axis() {
+                    SliceMoveAxis::M => CubeFace::Left,
+                    SliceMoveAxis::S => CubeFace::Front,
+                    SliceMoveAxis::E => CubeFace::Bottom,
+                };
+                let (face, count) = self.face_turn(face, mv.count());
+                let (axis, count) = match face {
+                    CubeFace::Left => (SliceMoveAxis::M, count),
+                    CubeFace::Right => (SliceMoveAxis::M, -count),
+                    CubeFace::Front => (SliceMoveAxis::S, count),
+                    CubeFace::Back => (SliceMoveAxis::S, -count),
+                    CubeFace::Bottom => (SliceMoveAxis::E, count),
+                    CubeFace::Top => (SliceMoveAxis::E, -count),
+                };
+                ExtendedMove::Slice(SliceMove::from_axis_and_count(axis, count).unwrap())
+            }
+            ExtendedMove::Rotation(mv) => {
+                let face = match mv.axis() {
+                    CubeRotationAxis::X => CubeFace::Right,
+                    CubeRotationAxis::Y => CubeFace::Top,
+                    CubeRotationAxis::Z => CubeFace::Front,
+                };
+                let (face, count) = self.face_turn(face, mv.count());
+                let (axis, count) = match face {
+                    CubeFace::Right => (CubeRotationAxis::X, count),
+                    CubeFace::Left => (CubeRotationAxis::X, -count),
+                    CubeFace::Top => (CubeRotationAxis::Y, count),
+                    CubeFace::Bottom => (CubeRotationAxis::Y, -count),
+                    CubeFace::Front => (CubeRotationAxis::Z, count),
+                    CubeFace::Back => (CubeRotationAxis::Z, -count),
+                };
+                ExtendedMove::Rotation(CubeRotation::from_axis_and_count(axis, count).unwrap())
+            }
+        }
+    }
+}
+
 impl<'a, C: FaceRotation> ExtendedMoveContext<'a, C> {
     /// Creates an extended move context for applying extended moves. `cube` is the cube state
     /// to apply the moves to.
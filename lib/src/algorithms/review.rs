@@ -0,0 +1,341 @@
+use crate::{
+    Algorithm, Analysis, Cube, Cube3x3x3, CubeFace, InitialCubeState, Move, OLLAlgorithm,
+    PLLAlgorithm, Penalty, RandomSource, Solve, SolveType, StandardRandomSource,
+};
+use chrono::{DateTime, Duration, Local};
+use std::collections::HashMap;
+
+const INITIAL_EASE_FACTOR: f32 = 2.5;
+const MIN_EASE_FACTOR: f32 = 1.3;
+
+/// Time before a case that was failed is shown again
+const RELEARN_INTERVAL_MINUTES: i64 = 5;
+
+/// Cases with a review interval of at least this many days are considered mastered
+const MASTERED_INTERVAL_DAYS: f32 = 21.0;
+
+const EASY_RECOGNITION_TIME: u32 = 500;
+const GOOD_RECOGNITION_TIME: u32 = 1200;
+const EASY_EXECUTION_TIME: u32 = 1000;
+const GOOD_EXECUTION_TIME: u32 = 2000;
+
+/// Result of a single review of a case, from worst to best
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReviewGrade {
+    Failed,
+    Misrecognized,
+    Misexecuted,
+    Hard,
+    Good,
+    Easy,
+}
+
+/// Spaced repetition state of a single case. Scheduling follows the SM-2 model, where
+/// each successful review multiplies the interval by the ease factor of the case.
+#[derive(Clone, Debug)]
+pub struct ReviewState {
+    pub reviews: usize,
+    pub failures: usize,
+    /// Number of successful reviews since the last failure
+    pub repetitions: usize,
+    pub ease_factor: f32,
+    pub interval: Duration,
+    pub last_review: DateTime<Local>,
+    pub due: DateTime<Local>,
+    pub last_grade: ReviewGrade,
+}
+
+/// Spaced repetition schedule for last layer training cases. The schedule is built
+/// by replaying training solves from history, so it is kept in sync along with the
+/// solves themselves.
+#[derive(Clone, Default)]
+pub struct ReviewSchedule {
+    cases: HashMap<Algorithm, ReviewState>,
+}
+
+impl ReviewGrade {
+    /// Grades a review using the penalty of the solve and the time taken. If the
+    /// solve has move data, the recognition and execution times are graded
+    /// separately and the worst grade is used.
+    pub fn from_solve(penalty: &Penalty, time: u32, steps: Option<(u32, u32)>) -> Self {
+        match penalty {
+            Penalty::DNF => return ReviewGrade::Failed,
+            Penalty::RecognitionDNF => return ReviewGrade::Misrecognized,
+            Penalty::ExecutionDNF => return ReviewGrade::Misexecuted,
+            Penalty::None | Penalty::Time(_) => (),
+        }
+
+        match steps {
+            Some((recognition_time, execution_time)) => Self::from_time(
+                recognition_time,
+                EASY_RECOGNITION_TIME,
+                GOOD_RECOGNITION_TIME,
+            )
+            .min(Self::from_time(
+                execution_time,
+                EASY_EXECUTION_TIME,
+                GOOD_EXECUTION_TIME,
+            )),
+            None => Self::from_time(
+                time,
+                EASY_RECOGNITION_TIME + EASY_EXECUTION_TIME,
+                GOOD_RECOGNITION_TIME + GOOD_EXECUTION_TIME,
+            ),
+        }
+    }
+
+    fn from_time(time: u32, easy: u32, good: u32) -> Self {
+        if time <= easy {
+            ReviewGrade::Easy
+        } else if time <= good {
+            ReviewGrade::Good
+        } else {
+            ReviewGrade::Hard
+        }
+    }
+
+    /// Quality of the response on the 0 to 5 scale used by SM-2
+    fn quality(&self) -> u32 {
+        match self {
+            ReviewGrade::Failed => 0,
+            ReviewGrade::Misrecognized => 1,
+            ReviewGrade::Misexecuted => 2,
+            ReviewGrade::Hard => 3,
+            ReviewGrade::Good => 4,
+            ReviewGrade::Easy => 5,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.quality() >= 3
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ReviewGrade::Failed => "Failed",
+            ReviewGrade::Misrecognized => "Misrecognized",
+            ReviewGrade::Misexecuted => "Misexecuted",
+            ReviewGrade::Hard => "Hard",
+            ReviewGrade::Good => "Good",
+            ReviewGrade::Easy => "Easy",
+        }
+    }
+}
+
+impl ReviewState {
+    fn new(time: DateTime<Local>) -> Self {
+        Self {
+            reviews: 0,
+            failures: 0,
+            repetitions: 0,
+            ease_factor: INITIAL_EASE_FACTOR,
+            interval: Duration::zero(),
+            last_review: time,
+            due: time,
+            last_grade: ReviewGrade::Failed,
+        }
+    }
+
+    fn review(&mut self, grade: ReviewGrade, time: DateTime<Local>) {
+        self.reviews += 1;
+        self.last_review = time;
+        self.last_grade = grade;
+
+        let quality = grade.quality() as f32;
+        self.ease_factor = (self.ease_factor
+            + (0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02)))
+            .max(MIN_EASE_FACTOR);
+
+        if grade.is_success() {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => Duration::days(1),
+                2 => Duration::days(6),
+                _ => Duration::seconds(
+                    (self.interval.num_seconds() as f32 * self.ease_factor) as i64,
+                ),
+            };
+        } else {
+            self.failures += 1;
+            self.repetitions = 0;
+            self.interval = Duration::minutes(RELEARN_INTERVAL_MINUTES);
+        }
+        self.due = time + self.interval;
+    }
+
+    pub fn is_due(&self, now: DateTime<Local>) -> bool {
+        self.due <= now
+    }
+
+    /// Fraction of reviews that were successful
+    pub fn success_rate(&self) -> f32 {
+        if self.reviews == 0 {
+            0.0
+        } else {
+            (self.reviews - self.failures) as f32 / self.reviews as f32
+        }
+    }
+
+    /// How well the case is known, from 0 to 1. A case is fully mastered once its
+    /// review interval reaches three weeks.
+    pub fn mastery(&self) -> f32 {
+        if self.repetitions == 0 {
+            0.0
+        } else {
+            (self.interval.num_seconds() as f32 / (MASTERED_INTERVAL_DAYS * 86400.0)).min(1.0)
+        }
+    }
+}
+
+impl ReviewSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the schedule from training solves. Solves must be given in the order
+    /// they were performed. The `analysis` function is used to get recognition and
+    /// execution times for solves with move data.
+    pub fn from_solves<'a>(
+        solves: impl Iterator<Item = &'a Solve>,
+        analysis: impl Fn(&Solve) -> Option<Analysis>,
+    ) -> Self {
+        let mut schedule = Self::new();
+        for solve in solves {
+            if solve.moves.is_some() {
+                schedule.add_solve(solve, analysis(solve).as_ref());
+            } else {
+                schedule.add_solve(solve, None);
+            }
+        }
+        schedule
+    }
+
+    /// Gets the case being trained by a last layer training scramble
+    pub fn training_case(solve_type: SolveType, scramble: &[Move]) -> Option<Algorithm> {
+        if !solve_type.is_last_layer_training() {
+            return None;
+        }
+
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(scramble);
+        match solve_type {
+            SolveType::OLLTraining => {
+                OLLAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top)
+                    .map(Algorithm::OLL)
+            }
+            SolveType::PLLTraining => {
+                PLLAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top)
+                    .map(Algorithm::PLL)
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds a review for a training solve. Solves that are not last layer training
+    /// solves are ignored.
+    pub fn add_solve(&mut self, solve: &Solve, analysis: Option<&Analysis>) {
+        let algorithm = match Self::training_case(solve.solve_type, &solve.scramble) {
+            Some(algorithm) => algorithm,
+            None => return,
+        };
+
+        // Split the time into recognition and execution of the trained step when
+        // move data is available. Any further looks count as execution time.
+        let steps = match analysis {
+            Some(Analysis::CFOP(cfop)) => match algorithm {
+                Algorithm::OLL(_) => cfop.oll.first().map(|first| {
+                    let total: u32 = cfop
+                        .oll
+                        .iter()
+                        .map(|oll| oll.recognition_time + oll.execution_time)
+                        .sum();
+                    (first.recognition_time, total - first.recognition_time)
+                }),
+                Algorithm::PLL(_) => cfop.pll.first().map(|first| {
+                    let total: u32 = cfop
+                        .pll
+                        .iter()
+                        .map(|pll| pll.recognition_time + pll.execution_time)
+                        .sum();
+                    (first.recognition_time, total - first.recognition_time)
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let grade = ReviewGrade::from_solve(&solve.penalty, solve.time, steps);
+        self.review(algorithm, grade, solve.created);
+    }
+
+    pub fn review(&mut self, algorithm: Algorithm, grade: ReviewGrade, time: DateTime<Local>) {
+        self.cases
+            .entry(algorithm)
+            .or_insert_with(|| ReviewState::new(time))
+            .review(grade, time);
+    }
+
+    pub fn state(&self, algorithm: Algorithm) -> Option<&ReviewState> {
+        self.cases.get(&algorithm)
+    }
+
+    /// Chooses the next case to train from the candidates. Cases that are due are
+    /// chosen first, starting with the one that has been due the longest. If no
+    /// cases are due, a random case that has not been reviewed yet is chosen. If
+    /// every case has been reviewed, the case that will be due next is chosen.
+    /// The `exclude` case is never chosen unless it is the only candidate, so that
+    /// the same case is not repeated immediately.
+    pub fn next_case(
+        &self,
+        candidates: &[Algorithm],
+        exclude: Option<Algorithm>,
+        now: DateTime<Local>,
+    ) -> Option<Algorithm> {
+        self.sourced_next_case(&mut StandardRandomSource, candidates, exclude, now)
+    }
+
+    pub fn sourced_next_case<T: RandomSource>(
+        &self,
+        rng: &mut T,
+        candidates: &[Algorithm],
+        exclude: Option<Algorithm>,
+        now: DateTime<Local>,
+    ) -> Option<Algorithm> {
+        let candidates: Vec<Algorithm> = candidates
+            .iter()
+            .filter(|algorithm| Some(**algorithm) != exclude)
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
+            return exclude;
+        }
+
+        let earliest_due = |due_now: bool| {
+            candidates
+                .iter()
+                .filter_map(|algorithm| {
+                    self.cases
+                        .get(algorithm)
+                        .filter(|state| state.is_due(now) == due_now)
+                        .map(|state| (state.due, *algorithm))
+                })
+                .min_by_key(|(due, _)| *due)
+                .map(|(_, algorithm)| algorithm)
+        };
+
+        if let Some(algorithm) = earliest_due(true) {
+            return Some(algorithm);
+        }
+
+        let new_cases: Vec<Algorithm> = candidates
+            .iter()
+            .filter(|algorithm| !self.cases.contains_key(algorithm))
+            .cloned()
+            .collect();
+        if !new_cases.is_empty() {
+            return Some(new_cases[rng.next(new_cases.len() as u32) as usize]);
+        }
+
+        earliest_due(false)
+    }
+}
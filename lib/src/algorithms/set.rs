@@ -0,0 +1,287 @@
+use crate::algorithms::known::{Algorithm, AlgorithmStatus, AlgorithmType};
+use crate::algorithms::moves::{parse_extended_move_string, ExtendedMove, ExtendedMoveSequence};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// File format for sharing algorithm sets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlgorithmSetFormat {
+    /// Listing with one case per line, in the form `case: alg; alg`. A line with only
+    /// the name of an algorithm set selects the set for the case names that follow.
+    Text,
+    JSON,
+}
+
+/// Algorithms and learning status for a single case in an algorithm set
+pub(crate) struct AlgorithmSetEntry {
+    pub algorithm: Algorithm,
+    pub status: Option<AlgorithmStatus>,
+    pub moves: Vec<Vec<ExtendedMove>>,
+}
+
+/// Algorithm set as read from or written to a file. Every algorithm has been verified
+/// to solve its case.
+pub(crate) struct AlgorithmSet {
+    pub entries: Vec<AlgorithmSetEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AlgorithmSetJson {
+    algorithms: Vec<AlgorithmSetJsonEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AlgorithmSetJsonEntry {
+    set: String,
+    case: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(default)]
+    algorithms: Vec<String>,
+}
+
+/// Normalizes a case name for comparison. Case, spaces and punctuation used in case
+/// numbers are ignored, so that `OLL #27`, `oll 27` and `OLL27` are the same.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '#' | '-' | '_'))
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+impl AlgorithmSetEntry {
+    /// Gets the normalized names a case can be referred to by, without the name of the
+    /// algorithm set
+    fn names(algorithm: Algorithm) -> Vec<String> {
+        let prefix = normalize_name(algorithm.alg_type().to_str());
+        let name = normalize_name(&algorithm.to_string());
+        let mut result = vec![match name.strip_prefix(&prefix) {
+            Some(name) => name.to_string(),
+            None => name,
+        }];
+        if let Algorithm::OLL(oll) = algorithm {
+            // Named OLL cases can also be referred to by number
+            result.push(oll.as_number().to_string());
+        }
+        result
+    }
+
+    /// Finds the case with the given name in an algorithm set
+    fn find_in_set(name: &str, alg_type: AlgorithmType) -> Option<Algorithm> {
+        alg_type
+            .cases()
+            .into_iter()
+            .find(|case| Self::names(*case).iter().any(|case_name| case_name == name))
+    }
+
+    /// Finds a case by name. The name may start with the name of the algorithm set,
+    /// otherwise the case is looked up in `current_set`. If there is no current set,
+    /// the name must only match a case in one set.
+    fn find_case(original_name: &str, current_set: Option<AlgorithmType>) -> Result<Algorithm> {
+        let name = normalize_name(original_name);
+
+        // Check longer set names first, as `OLLCP` starts with `OLL`
+        let mut sets = AlgorithmType::all().to_vec();
+        sets.sort_by_key(|alg_type| std::cmp::Reverse(alg_type.to_str().len()));
+        for alg_type in sets {
+            if let Some(case_name) = name.strip_prefix(&normalize_name(alg_type.to_str())) {
+                if let Some(algorithm) = Self::find_in_set(case_name, alg_type) {
+                    return Ok(algorithm);
+                }
+            }
+        }
+
+        match current_set {
+            Some(alg_type) => Self::find_in_set(&name, alg_type),
+            None => {
+                let mut matches = AlgorithmType::all()
+                    .iter()
+                    .filter_map(|alg_type| Self::find_in_set(&name, *alg_type));
+                match (matches.next(), matches.next()) {
+                    (Some(algorithm), None) => Some(algorithm),
+                    (Some(_), Some(_)) => {
+                        return Err(anyhow!(
+                            "Case '{}' is in more than one algorithm set",
+                            original_name
+                        ))
+                    }
+                    _ => None,
+                }
+            }
+        }
+        .ok_or_else(|| anyhow!("Unknown case '{}'", original_name))
+    }
+
+    /// Parses and verifies an algorithm for this case
+    fn add_moves(&mut self, string: &str) -> Result<()> {
+        let moves = parse_extended_move_string(string)?;
+        self.algorithm.validate(&moves)?;
+        if !self
+            .moves
+            .iter()
+            .any(|existing| existing.same_algorithm(&moves))
+        {
+            self.moves.push(moves);
+        }
+        Ok(())
+    }
+}
+
+impl AlgorithmSet {
+    pub fn import(contents: &str) -> Result<Self> {
+        if contents.trim_start().starts_with('{') {
+            Self::import_json(contents)
+        } else {
+            Self::import_text(contents)
+        }
+    }
+
+    /// Gets the entry for a case, adding it if it is not present. Cases can be listed
+    /// more than once, such as when each algorithm is on its own line.
+    fn entry(&mut self, algorithm: Algorithm) -> &mut AlgorithmSetEntry {
+        let idx = match self
+            .entries
+            .iter()
+            .position(|entry| entry.algorithm == algorithm)
+        {
+            Some(idx) => idx,
+            None => {
+                self.entries.push(AlgorithmSetEntry {
+                    algorithm,
+                    status: None,
+                    moves: Vec::new(),
+                });
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[idx]
+    }
+
+    fn import_json(contents: &str) -> Result<Self> {
+        let file: AlgorithmSetJson = serde_json::from_str(contents)?;
+        let mut result = Self {
+            entries: Vec::new(),
+        };
+        for case in file.algorithms {
+            let alg_type = AlgorithmType::from_str(&case.set)
+                .ok_or_else(|| anyhow!("Unknown algorithm set '{}'", case.set))?;
+            let algorithm = AlgorithmSetEntry::find_case(&case.case, Some(alg_type))?;
+            let entry = result.entry(algorithm);
+            if let Some(status) = &case.status {
+                entry.status = Some(
+                    AlgorithmStatus::from_str(status)
+                        .ok_or_else(|| anyhow!("Unknown learning status '{}'", status))?,
+                );
+            }
+            for moves in &case.algorithms {
+                entry
+                    .add_moves(moves)
+                    .map_err(|error| anyhow!("{}: {}", algorithm.to_string(), error))?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn import_text(contents: &str) -> Result<Self> {
+        let mut result = Self {
+            entries: Vec::new(),
+        };
+        let mut current_set = None;
+        for (idx, line) in contents.lines().enumerate() {
+            let line_error = |error: anyhow::Error| anyhow!("Line {}: {}", idx + 1, error);
+
+            // Anything after `//` is a comment
+            let line = match line.find("//") {
+                Some(comment) => &line[..comment],
+                None => line,
+            }
+            .trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // A line with only a set name starts the cases for that set
+            if let Some(alg_type) = AlgorithmType::from_str(line.trim_end_matches(':').trim()) {
+                current_set = Some(alg_type);
+                continue;
+            }
+
+            // The case name is separated from the algorithms by a colon or a tab. Any
+            // number of algorithms can follow, separated by semicolons.
+            let split = line
+                .find(&[':', '\t'][..])
+                .ok_or_else(|| line_error(anyhow!("Expected case name and algorithms")))?;
+            let (mut name, algorithms) = (line[..split].trim(), &line[split + 1..]);
+
+            // Learning status is optional, in brackets after the case name
+            let mut status = None;
+            if let (Some(start), true) = (name.find('['), name.ends_with(']')) {
+                let status_name = name[start + 1..name.len() - 1].trim();
+                status = Some(AlgorithmStatus::from_str(status_name).ok_or_else(|| {
+                    line_error(anyhow!("Unknown learning status '{}'", status_name))
+                })?);
+                name = name[..start].trim();
+            }
+
+            let algorithm = AlgorithmSetEntry::find_case(name, current_set).map_err(line_error)?;
+            let entry = result.entry(algorithm);
+            if status.is_some() {
+                entry.status = status;
+            }
+            for moves in algorithms
+                .split(';')
+                .filter(|moves| !moves.trim().is_empty())
+            {
+                entry.add_moves(moves).map_err(line_error)?;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn export(&self, format: AlgorithmSetFormat) -> Result<String> {
+        match format {
+            AlgorithmSetFormat::Text => Ok(self.export_text()),
+            AlgorithmSetFormat::JSON => self.export_json(),
+        }
+    }
+
+    fn export_json(&self) -> Result<String> {
+        let file = AlgorithmSetJson {
+            algorithms: self
+                .entries
+                .iter()
+                .map(|entry| AlgorithmSetJsonEntry {
+                    set: entry.algorithm.alg_type().to_str().into(),
+                    case: entry.algorithm.to_string(),
+                    status: entry.status.map(|status| status.to_str().into()),
+                    algorithms: entry.moves.iter().map(|moves| moves.to_string()).collect(),
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&file)?)
+    }
+
+    fn export_text(&self) -> String {
+        let mut result = String::new();
+        let mut current_set = None;
+        for entry in &self.entries {
+            let alg_type = entry.algorithm.alg_type();
+            if current_set != Some(alg_type) {
+                if current_set.is_some() {
+                    result.push('\n');
+                }
+                result += alg_type.to_str();
+                result.push('\n');
+                current_set = Some(alg_type);
+            }
+
+            result += &entry.algorithm.to_string();
+            if let Some(status) = entry.status {
+                result += &format!(" [{}]", status.to_str());
+            }
+            let moves: Vec<String> = entry.moves.iter().map(|moves| moves.to_string()).collect();
+            result += &format!(": {}\n", moves.join("; "));
+        }
+        result
+    }
+}
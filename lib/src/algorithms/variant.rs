@@ -0,0 +1,184 @@
+use crate::{
+    CubeFace, CubeRotation, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence, FaceRotation,
+    Move, RotationDirection,
+};
+
+/// Which algorithm was used to solve a case, as identified from the moves performed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AlgorithmVariant {
+    /// One of the known algorithms for the case, by index into the list of algorithms
+    Known(usize),
+    /// An algorithm defined by the user
+    Custom(Vec<ExtendedMove>),
+    /// The moves did not match any algorithm, such as for an intuitive solution
+    Unknown,
+}
+
+/// Records the outer face turns of a sequence of extended moves as a smart cube would
+/// report them
+struct MoveRecorder {
+    moves: Vec<Move>,
+}
+
+impl FaceRotation for MoveRecorder {
+    fn rotate_wide(&mut self, face: CubeFace, dir: RotationDirection, _width: usize) {
+        self.moves.push(
+            Move::from_face_and_rotation(
+                face,
+                match dir {
+                    RotationDirection::CW => 1,
+                    RotationDirection::CCW => -1,
+                },
+            )
+            .unwrap(),
+        );
+    }
+}
+
+/// Move sequence in a normalized form for comparison. Consecutive turns of faces on the
+/// same axis are merged into a single entry holding the number of clockwise quarter
+/// turns of each of the two faces, as these can be performed in any order.
+#[derive(PartialEq, Eq)]
+struct NormalizedMoves {
+    axes: Vec<(CubeFace, [i32; 2])>,
+}
+
+/// Gets the axis of a face (the lower numbered face of the opposing pair) and which
+/// side of the axis the face is on
+fn face_axis(face: CubeFace) -> (CubeFace, usize) {
+    if (face as u8) < (face.opposite() as u8) {
+        (face, 0)
+    } else {
+        (face.opposite(), 1)
+    }
+}
+
+impl NormalizedMoves {
+    /// Normalizes a move sequence, ignoring turns of `top` at the start and end of the
+    /// sequence as these only align the last layer.
+    fn new(moves: &[Move], top: CubeFace) -> Self {
+        let mut axes: Vec<(CubeFace, [i32; 2])> = Vec::new();
+        for mv in moves {
+            let (axis, side) = face_axis(mv.face());
+            if let Some((last_axis, counts)) = axes.last_mut() {
+                if *last_axis == axis {
+                    counts[side] = (counts[side] + mv.rotation() + 4) % 4;
+                    if counts[0] == 0 && counts[1] == 0 {
+                        axes.pop();
+                    }
+                    continue;
+                }
+            }
+            let mut counts = [0; 2];
+            counts[side] = (mv.rotation() + 4) % 4;
+            axes.push((axis, counts));
+        }
+
+        // Remove last layer alignment from both ends
+        let (top_axis, top_side) = face_axis(top);
+        for idx in [0, 1].iter() {
+            if axes.is_empty() {
+                break;
+            }
+            let entry = if *idx == 0 { 0 } else { axes.len() - 1 };
+            if axes[entry].0 == top_axis {
+                axes[entry].1[top_side] = 0;
+                if axes[entry].1 == [0, 0] {
+                    axes.remove(entry);
+                }
+            }
+        }
+
+        Self { axes }
+    }
+}
+
+/// Gets the cube rotations for all 24 ways to hold the cube, along with the face that
+/// is on top when holding the cube that way
+fn orientations() -> Vec<(Vec<ExtendedMove>, CubeFace)> {
+    let mut result = Vec::new();
+    for top_rotation in [
+        None,
+        Some(CubeRotation::X),
+        Some(CubeRotation::Xp),
+        Some(CubeRotation::X2),
+        Some(CubeRotation::Z),
+        Some(CubeRotation::Zp),
+    ]
+    .iter()
+    {
+        for y_rotation in [
+            None,
+            Some(CubeRotation::Y),
+            Some(CubeRotation::Yp),
+            Some(CubeRotation::Y2),
+        ]
+        .iter()
+        {
+            let rotation: Vec<ExtendedMove> = top_rotation
+                .iter()
+                .chain(y_rotation.iter())
+                .map(|rotation| ExtendedMove::Rotation(*rotation))
+                .collect();
+            let mut cube = MoveRecorder { moves: Vec::new() };
+            let mut context = ExtendedMoveContext::new(&mut cube);
+            context.do_moves(&rotation);
+            let top = context.face(CubeFace::Top);
+            result.push((rotation, top));
+        }
+    }
+    result
+}
+
+/// Gets the moves a smart cube reports when performing an algorithm while holding the
+/// cube with the given rotation
+fn reported_moves(rotation: &[ExtendedMove], algorithm: &[ExtendedMove]) -> Vec<Move> {
+    let mut cube = MoveRecorder { moves: Vec::new() };
+    let mut context = ExtendedMoveContext::new(&mut cube);
+    context.do_moves(rotation);
+    context.do_moves(algorithm);
+    cube.moves
+}
+
+fn matches_algorithm(moves: &[Move], algorithm: &[ExtendedMove]) -> bool {
+    orientations().iter().any(|(rotation, top)| {
+        NormalizedMoves::new(moves, *top)
+            == NormalizedMoves::new(&reported_moves(rotation, algorithm), *top)
+    })
+}
+
+impl AlgorithmVariant {
+    /// Identifies which algorithm was performed from the moves reported by the cube.
+    /// The algorithm can be performed while holding the cube in any orientation, and
+    /// turns used to align the last layer before or after the algorithm are ignored.
+    /// User defined algorithms take priority over known algorithms.
+    pub fn identify(
+        moves: &[Move],
+        known: &[Vec<ExtendedMove>],
+        custom: &[Vec<ExtendedMove>],
+    ) -> Self {
+        for algorithm in custom {
+            if matches_algorithm(moves, algorithm) {
+                return AlgorithmVariant::Custom(algorithm.clone());
+            }
+        }
+        for (idx, algorithm) in known.iter().enumerate() {
+            if matches_algorithm(moves, algorithm) {
+                return AlgorithmVariant::Known(idx);
+            }
+        }
+        AlgorithmVariant::Unknown
+    }
+
+    /// Gets a display name for the variant, which is the algorithm itself if it is known
+    pub fn name(&self, known: &[Vec<ExtendedMove>]) -> String {
+        match self {
+            AlgorithmVariant::Known(idx) => match known.get(*idx) {
+                Some(algorithm) => algorithm.to_string(),
+                None => "Unknown".into(),
+            },
+            AlgorithmVariant::Custom(algorithm) => algorithm.to_string(),
+            AlgorithmVariant::Unknown => "Unknown / intuitive".into(),
+        }
+    }
+}
@@ -1,22 +1,59 @@
+#[cfg(feature = "storage")]
+mod cache;
 mod cfop;
+mod cross_planning;
+mod cube2x2x2;
+mod f2l;
+mod last_layer;
+mod lbl;
+mod lookahead;
+mod mistakes;
+mod optimal;
+mod recognition;
 
-use crate::{Cube, Cube3x3x3, InitialCubeState, Solve, TimedMove};
+use crate::{Cube, Cube2x2x2, Cube3x3x3, InitialCubeState, Solve, SolveType, TimedMove};
+use serde::{Deserialize, Serialize};
 
 pub use cfop::{
-    CFOPAnalysis, CFOPPartialAnalysis, CFOPProgress, CrossAnalysis, F2LPairAnalysis,
-    FinalAlignmentAnalysis, OLLAlgorithm, OLLAnalysis, PLLAlgorithm, PLLAnalysis,
+    AUFAnalysis, CFOPAnalysis, CFOPPartialAnalysis, CFOPProgress, CFOPStep, CrossAnalysis,
+    F2LPairAnalysis, F2LSlot, FinalAlignmentAnalysis, OLLAlgorithm, OLLAnalysis, PLLAlgorithm,
+    PLLAnalysis,
 };
+pub use cross_planning::CrossPlanningAnalysis;
+pub use cube2x2x2::{
+    CLLAlgorithm, CLLAnalysis, Cube2x2x2Analysis, Cube2x2x2Method, Cube2x2x2PartialAnalysis,
+    Cube2x2x2Progress, Cube2x2x2WithSolution, EG1Algorithm, EG2Algorithm, FirstFaceAnalysis,
+    LayerPermutation, PBLAlgorithm, PBLAnalysis,
+};
+pub use f2l::F2LAlgorithm;
+pub use last_layer::{COLLAlgorithm, OLLCPAlgorithm, ZBLLAlgorithm};
+pub use lbl::{LBLAnalysis, LBLAnalysisStages, LBLPartialAnalysis, LBLProgress, LBLStepAnalysis};
+pub use lookahead::{median_move_gap, LookaheadAnalysis, Pause, DEFAULT_PAUSE_THRESHOLD};
+pub use mistakes::{Mistake, MistakeKind};
+pub use optimal::{CFOPEfficiency, CFOPStepSolver, CrossMoveTable, StepEfficiency};
+pub use recognition::RecognitionCase;
 
-#[derive(Clone)]
+#[cfg(feature = "storage")]
+pub(crate) use cache::AnalysisCache;
+
+/// Version of the analyzer. This must be incremented whenever a change is made that
+/// alters analysis results, so that cached results are discarded.
+pub const ANALYSIS_VERSION: u32 = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub enum Analysis {
     Unsuccessful,
     CFOP(CFOPAnalysis),
+    LBL(LBLAnalysis),
+    Cube2x2x2(Cube2x2x2Analysis),
 }
 
 #[derive(Clone)]
 pub enum PartialAnalysis {
     Unsuccessful,
     CFOP(CFOPPartialAnalysis),
+    LBL(LBLPartialAnalysis),
+    Cube2x2x2(Cube2x2x2PartialAnalysis),
 }
 
 #[derive(Clone)]
@@ -61,8 +98,24 @@ pub trait SolveAnalysis {
 
 impl Analysis {
     pub fn analyze(solve: &CubeWithSolution) -> Self {
-        if let Some(cfop) = CFOPAnalysis::analyze(solve) {
-            Analysis::CFOP(cfop)
+        // Use the most likely solving method, and only report a full analysis
+        // if the solve was completed using that method
+        match PartialAnalysis::analyze(solve) {
+            PartialAnalysis::CFOP(cfop) => match cfop.into() {
+                Some(cfop) => Analysis::CFOP(cfop),
+                None => Analysis::Unsuccessful,
+            },
+            PartialAnalysis::LBL(lbl) => match lbl.into() {
+                Some(lbl) => Analysis::LBL(lbl),
+                None => Analysis::Unsuccessful,
+            },
+            _ => Analysis::Unsuccessful,
+        }
+    }
+
+    pub fn analyze_2x2x2(solve: &Cube2x2x2WithSolution) -> Self {
+        if let Some(analysis) = Cube2x2x2Analysis::analyze(solve) {
+            Analysis::Cube2x2x2(analysis)
         } else {
             Analysis::Unsuccessful
         }
@@ -90,7 +143,16 @@ impl PartialAnalysis {
         }
 
         // Analyze with all available solving methods
-        let methods = &[&CFOPPartialAnalysis::analyze(solve)];
+        let cfop = CFOPPartialAnalysis::analyze(solve);
+        let lbl = LBLPartialAnalysis::analyze(solve);
+        let methods: &[&dyn PartialAnalysisMethod] =
+            if lbl.first_layer_corners.iter().any(|corner| corner.paired) {
+                // Corners inserted with their edge are F2L pairs, so the solve is not a
+                // layer-by-layer solve even if corners were also solved on their own
+                &[&cfop]
+            } else {
+                &[&cfop, &lbl]
+            };
 
         // Find the most likely solving method based on transition counts and timing
         let mut best: Option<&dyn PartialAnalysisMethod> = None;
@@ -108,6 +170,14 @@ impl PartialAnalysis {
         }
         best.unwrap().to_partial_analysis()
     }
+
+    pub fn analyze_2x2x2(solve: &Cube2x2x2WithSolution) -> Self {
+        if solve.solution.is_empty() {
+            // No moves, nothing can be analyzed
+            return PartialAnalysis::Unsuccessful;
+        }
+        PartialAnalysis::Cube2x2x2(Cube2x2x2PartialAnalysis::analyze(solve))
+    }
 }
 
 impl AnalysisSummary for Analysis {
@@ -115,6 +185,8 @@ impl AnalysisSummary for Analysis {
         match self {
             Analysis::Unsuccessful => Vec::new(),
             Analysis::CFOP(analysis) => analysis.step_summary(),
+            Analysis::LBL(analysis) => analysis.step_summary(),
+            Analysis::Cube2x2x2(analysis) => analysis.step_summary(),
         }
     }
 
@@ -122,6 +194,8 @@ impl AnalysisSummary for Analysis {
         match self {
             Analysis::Unsuccessful => Vec::new(),
             Analysis::CFOP(analysis) => analysis.detailed_step_summary(),
+            Analysis::LBL(analysis) => analysis.detailed_step_summary(),
+            Analysis::Cube2x2x2(analysis) => analysis.detailed_step_summary(),
         }
     }
 }
@@ -131,6 +205,8 @@ impl AnalysisSummary for PartialAnalysis {
         match self {
             PartialAnalysis::Unsuccessful => Vec::new(),
             PartialAnalysis::CFOP(analysis) => analysis.step_summary(),
+            PartialAnalysis::LBL(analysis) => analysis.step_summary(),
+            PartialAnalysis::Cube2x2x2(analysis) => analysis.step_summary(),
         }
     }
 
@@ -138,6 +214,8 @@ impl AnalysisSummary for PartialAnalysis {
         match self {
             PartialAnalysis::Unsuccessful => Vec::new(),
             PartialAnalysis::CFOP(analysis) => analysis.detailed_step_summary(),
+            PartialAnalysis::LBL(analysis) => analysis.detailed_step_summary(),
+            PartialAnalysis::Cube2x2x2(analysis) => analysis.detailed_step_summary(),
         }
     }
 }
@@ -148,8 +226,22 @@ impl SolveAnalysis for CubeWithSolution {
     }
 }
 
+impl SolveAnalysis for Cube2x2x2WithSolution {
+    fn analyze(&self) -> Analysis {
+        Analysis::analyze_2x2x2(self)
+    }
+}
+
 impl SolveAnalysis for Solve {
     fn analyze(&self) -> Analysis {
+        if self.solve_type == SolveType::Standard2x2x2 {
+            let solve: Option<Cube2x2x2WithSolution> = self.into();
+            return match solve {
+                Some(solve) => Analysis::analyze_2x2x2(&solve),
+                None => Analysis::Unsuccessful,
+            };
+        }
+
         let solve: Option<CubeWithSolution> = self.into();
         if let Some(solve) = solve {
             Analysis::analyze(&solve)
@@ -173,3 +265,18 @@ impl From<&Solve> for Option<CubeWithSolution> {
         }
     }
 }
+
+impl From<&Solve> for Option<Cube2x2x2WithSolution> {
+    fn from(solve: &Solve) -> Option<Cube2x2x2WithSolution> {
+        if let Some(moves) = &solve.moves {
+            let mut cube = Cube2x2x2::new();
+            cube.do_moves(&solve.scramble);
+            Some(Cube2x2x2WithSolution {
+                initial_state: cube,
+                solution: moves.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use super::cross_planning::CROSS_FACE;
+use crate::storage::{DeferredStorage, Storage};
+use crate::{
+    Analysis, CFOPEfficiency, CFOPStepSolver, Color, CrossMoveTable, CrossPlanningAnalysis, Cube,
+    Cube3x3x3, CubeFace, CubeWithSolution, InitialCubeState, Solve, SolveAnalysis, StepEfficiency,
+    ANALYSIS_VERSION,
+};
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Cache of analysis results for solves with move data. Results are persisted in
+/// storage keyed by solve ID and analysis version, so that a solve only needs to be
+/// analyzed once for each version of the analyzer. Stored results are only read
+/// when a solve's analysis is first requested.
+pub(crate) struct AnalysisCache {
+    analyses: CachedResults<Analysis>,
+    cross_efficiencies: CachedResults<StepEfficiency>,
+    cross_planning_efficiencies: CachedResults<StepEfficiency>,
+    efficiencies: CachedResults<CFOPEfficiency>,
+    optimal_crosses: CachedResults<Vec<(Color, usize)>>,
+    /// Solvers for finding optimal steps, one for each cross face. Creating a solver
+    /// takes a moment, so they are shared between solves.
+    solvers: Arc<Mutex<Vec<Arc<CFOPStepSolver>>>>,
+    /// Optimal cross move tables, one for each cross face
+    cross_tables: Arc<Mutex<Vec<(CubeFace, Arc<CrossMoveTable>)>>>,
+    /// Number of results that have become available since the cache was created. Views
+    /// built while results were still being read use this to know to refresh.
+    ready_count: Arc<AtomicU64>,
+}
+
+/// Results of one kind of analysis, stored under a common key prefix
+struct CachedResults<T> {
+    name: &'static str,
+    results: Arc<Mutex<HashMap<String, CachedResult<T>>>>,
+}
+
+enum CachedResult<T> {
+    Pending,
+    Ready(T),
+}
+
+impl AnalysisCache {
+    /// Prepares the cache for reading results from storage. If the results in storage
+    /// were created by a different version of the analyzer, they are removed and the
+    /// solves will be analyzed again when requested.
+    pub async fn load(storage: &mut Storage) -> Result<Self> {
+        let stored_version = match storage.get("analysis_version").await? {
+            Some(raw_version) => Some(u32::from_le_bytes(
+                raw_version
+                    .try_into()
+                    .map_err(|_| anyhow!("Invalid analysis version"))?,
+            )),
+            None => None,
+        };
+
+        let cache = Self {
+            analyses: CachedResults::new("analysis"),
+            cross_efficiencies: CachedResults::new("cross_efficiency"),
+            cross_planning_efficiencies: CachedResults::new("cross_planning_efficiency"),
+            efficiencies: CachedResults::new("efficiency"),
+            optimal_crosses: CachedResults::new("optimal_crosses"),
+            solvers: Arc::new(Mutex::new(Vec::new())),
+            cross_tables: Arc::new(Mutex::new(Vec::new())),
+            ready_count: Arc::new(AtomicU64::new(0)),
+        };
+
+        if stored_version != Some(ANALYSIS_VERSION) {
+            // The analyzer has changed since the results were stored, remove the
+            // outdated results so that they do not take up space. This includes the
+            // results of solves that have since been deleted.
+            if let Some(stored_version) = stored_version {
+                for prefix in &[
+                    cache.analyses.prefix(stored_version),
+                    cache.cross_efficiencies.prefix(stored_version),
+                    cache.cross_planning_efficiencies.prefix(stored_version),
+                    cache.efficiencies.prefix(stored_version),
+                    cache.optimal_crosses.prefix(stored_version),
+                ] {
+                    storage.delete_prefix(prefix).await?;
+                }
+            }
+            storage
+                .put("analysis_version", &ANALYSIS_VERSION.to_le_bytes())
+                .await?;
+        }
+
+        Ok(cache)
+    }
+
+    /// Gets the analysis of a solve. The first request for a solve reads the stored
+    /// result in the background, analyzing the solve and storing the result if there
+    /// isn't one. Returns `None` until the result is available, or if the solve has
+    /// no move data.
+    pub fn analysis(&self, solve: &Solve, storage: &DeferredStorage) -> Option<Analysis> {
+        solve.moves.as_ref()?;
+        let id = solve.id.clone();
+        let solve = solve.clone();
+        self.analyses
+            .get(id, storage, &self.ready_count, move || solve.analyze())
+    }
+
+    /// Gets a comparison of the cross step of a CFOP solve against an optimal
+    /// solution. Results are found in the background like `analysis`, and are `None`
+    /// until available or if the solve was not analyzed as CFOP.
+    pub fn cross_efficiency(
+        &self,
+        solve: &Solve,
+        storage: &DeferredStorage,
+    ) -> Option<StepEfficiency> {
+        let (cfop, cube_solve) = match self.analysis(solve, storage)? {
+            Analysis::CFOP(cfop) => (cfop, Option::<CubeWithSolution>::from(solve)?),
+            _ => return None,
+        };
+        let cross_tables = self.cross_tables.clone();
+        self.cross_efficiencies
+            .get(solve.id.clone(), storage, &self.ready_count, move || {
+                Self::cross_table(&cross_tables, cfop.cross.color.face())
+                    .cross_efficiency(&cfop, &cube_solve)
+            })
+    }
+
+    /// Gets a comparison of the moves of a cross training solve against an optimal
+    /// cross, or an optimal XCross with the solve's target slot. Results are found in
+    /// the background like `analysis`, and are `None` until available or if the target
+    /// was never solved.
+    pub fn cross_planning_efficiency(
+        &self,
+        solve: &Solve,
+        storage: &DeferredStorage,
+    ) -> Option<StepEfficiency> {
+        if !solve.solve_type.is_cross_training() {
+            return None;
+        }
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&solve.scramble);
+        let slot = solve.target_slot;
+        let moves =
+            CrossPlanningAnalysis::target_moves(&initial_state, solve.moves.as_ref()?, slot)?;
+        let solvers = self.solvers.clone();
+        let cross_tables = self.cross_tables.clone();
+        self.cross_planning_efficiencies.get(
+            solve.id.clone(),
+            storage,
+            &self.ready_count,
+            move || {
+                let optimal = match slot {
+                    Some(slot) => {
+                        Self::solver(&solvers, CROSS_FACE).optimal_xcross(&initial_state, slot)
+                    }
+                    None => Some(
+                        Self::cross_table(&cross_tables, CROSS_FACE).optimal_cross(&initial_state),
+                    ),
+                };
+                StepEfficiency { moves, optimal }
+            },
+        )
+    }
+
+    /// Gets a comparison of the cross and F2L steps of a CFOP solve against optimal
+    /// solutions. Results are found in the background like `analysis`, and are `None`
+    /// until available or if the solve was not analyzed as CFOP.
+    pub fn efficiency(&self, solve: &Solve, storage: &DeferredStorage) -> Option<CFOPEfficiency> {
+        let (cfop, cube_solve) = match self.analysis(solve, storage)? {
+            Analysis::CFOP(cfop) => (cfop, Option::<CubeWithSolution>::from(solve)?),
+            _ => return None,
+        };
+        let solvers = self.solvers.clone();
+        self.efficiencies
+            .get(solve.id.clone(), storage, &self.ready_count, move || {
+                Self::solver(&solvers, cfop.cross.color.face()).efficiency(&cfop, &cube_solve)
+            })
+    }
+
+    /// Gets the number of moves in an optimal cross for each color, starting from the
+    /// scrambled state of a 3x3x3 solve. Results are found in the background like
+    /// `analysis`, and are `None` until available.
+    pub fn optimal_crosses(
+        &self,
+        solve: &Solve,
+        storage: &DeferredStorage,
+    ) -> Option<Vec<(Color, usize)>> {
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&solve.scramble);
+        let cross_tables = self.cross_tables.clone();
+        self.optimal_crosses
+            .get(solve.id.clone(), storage, &self.ready_count, move || {
+                (0..6)
+                    .map(|face| {
+                        let face = CubeFace::try_from(face).unwrap();
+                        (
+                            face.color(),
+                            Self::cross_table(&cross_tables, face).moves(&initial_state),
+                        )
+                    })
+                    .collect()
+            })
+    }
+
+    fn solver(solvers: &Mutex<Vec<Arc<CFOPStepSolver>>>, face: CubeFace) -> Arc<CFOPStepSolver> {
+        let mut solvers = solvers.lock().unwrap();
+        match solvers.iter().find(|solver| solver.face() == face) {
+            Some(solver) => solver.clone(),
+            None => {
+                let solver = Arc::new(CFOPStepSolver::new(face));
+                solvers.push(solver.clone());
+                solver
+            }
+        }
+    }
+
+    fn cross_table(
+        cross_tables: &Mutex<Vec<(CubeFace, Arc<CrossMoveTable>)>>,
+        face: CubeFace,
+    ) -> Arc<CrossMoveTable> {
+        let mut cross_tables = cross_tables.lock().unwrap();
+        match cross_tables
+            .iter()
+            .find(|(table_face, _)| *table_face == face)
+        {
+            Some((_, table)) => table.clone(),
+            None => {
+                let table = Arc::new(CrossMoveTable::new(face));
+                cross_tables.push((face, table.clone()));
+                table
+            }
+        }
+    }
+
+    /// Gets the number of results that have become available so far
+    pub fn ready_count(&self) -> u64 {
+        self.ready_count.load(Ordering::SeqCst)
+    }
+
+    /// Removes the cached results for a solve that no longer exists
+    pub fn remove(&self, id: &str, storage: &DeferredStorage) {
+        self.analyses.remove(id, storage);
+        self.cross_efficiencies.remove(id, storage);
+        self.cross_planning_efficiencies.remove(id, storage);
+        self.efficiencies.remove(id, storage);
+        self.optimal_crosses.remove(id, storage);
+    }
+}
+
+impl<T: Clone + Serialize + DeserializeOwned + Send + 'static> CachedResults<T> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            results: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn prefix(&self, version: u32) -> String {
+        format!("{}:{}:", self.name, version)
+    }
+
+    fn key(&self, version: u32, id: &str) -> String {
+        format!("{}{}", self.prefix(version), id)
+    }
+
+    /// Gets the result for a solve. The first request for a solve reads the stored
+    /// result in the background, calling `compute` and storing its result if there
+    /// isn't one. Returns `None` until the result is available.
+    fn get(
+        &self,
+        id: String,
+        storage: &DeferredStorage,
+        ready_count: &Arc<AtomicU64>,
+        compute: impl FnOnce() -> T + Send + 'static,
+    ) -> Option<T> {
+        {
+            let mut results = self.results.lock().unwrap();
+            match results.get(&id) {
+                Some(CachedResult::Ready(result)) => return Some(result.clone()),
+                Some(CachedResult::Pending) => return None,
+                None => {
+                    results.insert(id.clone(), CachedResult::Pending);
+                }
+            }
+        }
+
+        let results = self.results.clone();
+        let ready_count = ready_count.clone();
+        storage.update(&self.key(ANALYSIS_VERSION, &id), move |data| {
+            // Results that can't be read are treated as missing and are replaced
+            let stored: Option<T> = data.and_then(|data| serde_json::from_slice(&data).ok());
+            let (result, new_data) = match stored {
+                Some(result) => (result, None),
+                None => {
+                    let result = compute();
+                    let data = serde_json::to_vec(&result).ok();
+                    (result, data)
+                }
+            };
+
+            // If the solve was deleted while the result was being read, drop the
+            // result instead of storing it again
+            let mut results = results.lock().unwrap();
+            match results.get_mut(&id) {
+                Some(entry) => {
+                    *entry = CachedResult::Ready(result);
+                    ready_count.fetch_add(1, Ordering::SeqCst);
+                    new_data
+                }
+                None => None,
+            }
+        });
+        None
+    }
+
+    fn remove(&self, id: &str, storage: &DeferredStorage) {
+        self.results.lock().unwrap().remove(id);
+        storage.delete(&self.key(ANALYSIS_VERSION, id));
+    }
+}
@@ -1,22 +1,27 @@
+use super::mistakes::{find_mistakes, MAX_TEMPORARY_BREAK_MOVES};
 use crate::tables::analysis::{
     CUBE3_F2L_PAIRS, CUBE3_LAST_LAYER_EDGE, CUBE3_OLL_CASES, CUBE3_PLL_CASES,
 };
 use crate::tables::table3x3x3::CUBE3_EDGE_ADJACENCY;
 use crate::{
-    cube3x3x3::FaceRowOrColumn, AnalysisStepSummary, AnalysisSubstepTime, AnalysisSummary, Color,
-    Cube, Cube3x3x3Faces, CubeFace, CubeWithSolution, InitialCubeState, Move, MoveSequence,
-    PartialAnalysis, PartialAnalysisMethod, TimedMove,
+    cube3x3x3::FaceRowOrColumn, Algorithm, AlgorithmVariant, AnalysisStepSummary,
+    AnalysisSubstepTime, AnalysisSummary, COLLAlgorithm, Color, Cube, Cube3x3x3Faces, CubeFace,
+    CubeWithSolution, ExtendedMove, InitialCubeState, KnownAlgorithms, Mistake, MistakeKind, Move,
+    MoveSequence, OLLCPAlgorithm, PartialAnalysis, PartialAnalysisMethod, TimedMove, ZBLLAlgorithm,
 };
+use serde::{Deserialize, Serialize};
 
 /// Analysis of a full solve using CFOP method. Both one-look and two-look
 /// are fully supported automatically.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CFOPAnalysis {
     pub cross: CrossAnalysis,
     pub f2l_pairs: Vec<F2LPairAnalysis>,
     pub oll: Vec<OLLAnalysis>,
     pub pll: Vec<PLLAnalysis>,
     pub alignment: FinalAlignmentAnalysis,
+    /// Mistakes made during the solve, in the order they were made
+    pub mistakes: Vec<Mistake>,
 }
 
 /// Partial analysis of a cube solution. This analysis can be performed on
@@ -29,6 +34,8 @@ pub struct CFOPPartialAnalysis {
     pub oll: Vec<OLLAnalysis>,
     pub pll: Vec<PLLAnalysis>,
     pub alignment: Option<FinalAlignmentAnalysis>,
+    /// Mistakes made so far, in the order they were made
+    pub mistakes: Vec<Mistake>,
 }
 
 pub trait CFOPAnalysisStages {
@@ -37,10 +44,53 @@ pub trait CFOPAnalysisStages {
     fn oll(&self) -> &[OLLAnalysis];
     fn pll(&self) -> &[PLLAnalysis];
     fn alignment(&self) -> Option<&FinalAlignmentAnalysis>;
+
+    /// Gets the step of the solve that the move at the given index is a part of
+    fn step_at_move(&self, move_index: usize) -> CFOPStep {
+        if let Some(alignment) = self.alignment() {
+            if !alignment.moves.is_empty() && alignment.start_move_index <= move_index {
+                return CFOPStep::Alignment;
+            }
+        }
+        if self
+            .pll()
+            .iter()
+            .any(|pll| pll.start_move_index <= move_index)
+        {
+            return CFOPStep::PLL;
+        }
+        if self
+            .oll()
+            .iter()
+            .any(|oll| oll.start_move_index <= move_index)
+        {
+            return CFOPStep::OLL;
+        }
+        match self
+            .f2l_pairs()
+            .iter()
+            .rposition(|pair| pair.start_move_index <= move_index)
+        {
+            Some(idx) => CFOPStep::F2LPair(idx),
+            None => CFOPStep::Cross,
+        }
+    }
+
+    /// Gets the one-look last layer case that was used to skip PLL, if the solve is
+    /// complete and the last layer was solved by the last OLL algorithm
+    fn pll_skip_case(&self) -> Option<Algorithm> {
+        if !self.pll().is_empty() || self.alignment().is_none() {
+            return None;
+        }
+        match self.oll().last() {
+            Some(oll) if oll.new_state.is_none() => oll.subset_algorithm,
+            _ => None,
+        }
+    }
 }
 
 /// Analysis of the cross phase of a CFOP solution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CrossAnalysis {
     /// Color of the first layer cross
     pub color: Color,
@@ -51,7 +101,7 @@ pub struct CrossAnalysis {
 }
 
 /// Analysis of a single F2L pair insertion in a CFOP solution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct F2LPairAnalysis {
     /// Time spent recognizing the state
     pub recognition_time: u32,
@@ -61,13 +111,30 @@ pub struct F2LPairAnalysis {
     pub start_move_index: usize,
     /// Moves performed
     pub moves: Vec<Move>,
+    /// Slot that the pair was inserted into
+    pub slot: F2LSlot,
+    /// Whether more than one slot was filled by this insertion, such as when the
+    /// next pair was set up and solved along with this one
+    pub multi_slotted: bool,
+    /// Whether one of the pieces of the pair was already in place before the
+    /// insertion, such that only the other piece was inserted through the slot
+    pub keyhole: bool,
+}
+
+/// F2L slot position, relative to the cube held with the cross on the bottom
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum F2LSlot {
+    FrontRight,
+    FrontLeft,
+    BackRight,
+    BackLeft,
 }
 
 /// Analysis of an OLL algorithm performance. There may be more than
 /// one of these in a solve in the case of a two-look solution, a
 /// choice of algorithm that goes through multiple states, or a
 /// mistake during solution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OLLAnalysis {
     /// The algorithm that needs to be performed to solve OLL in a single step
     pub one_look_algorithm: OLLAlgorithm,
@@ -84,11 +151,18 @@ pub struct OLLAnalysis {
     pub start_move_index: usize,
     /// Moves performed
     pub moves: Vec<Move>,
+    /// Adjustment of the last layer before the algorithm, which is included in the
+    /// moves and execution time above. This is not available for 2x2x2 solves.
+    pub pre_auf: Option<AUFAnalysis>,
+    /// Case from a one-look last layer subset (COLL, ZBLL, or OLLCP) if the algorithm
+    /// also permuted the corners while orienting the last layer. Known OLL algorithms
+    /// that happen to leave the corners permuted are not counted.
+    pub subset_algorithm: Option<Algorithm>,
 }
 
 /// OLL algorithm used during solve. Two-look algorithms are named and
 /// others use their standard number.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub enum OLLAlgorithm {
     H,
     Pi,
@@ -104,7 +178,7 @@ pub enum OLLAlgorithm {
 /// one of these in a solve in the case of a two-look solution, a
 /// choice of algorithm that goes through multiple states, or a
 /// mistake during solution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PLLAnalysis {
     /// The algorithm that needs to be performed to solve the cube in a single step
     pub one_look_algorithm: PLLAlgorithm,
@@ -121,10 +195,27 @@ pub struct PLLAnalysis {
     pub start_move_index: usize,
     /// Moves performed
     pub moves: Vec<Move>,
+    /// Adjustment of the last layer before the algorithm, which is included in the
+    /// moves and execution time above
+    pub pre_auf: AUFAnalysis,
+}
+
+/// Adjustment of the last layer, by turning only the last layer face, before a last
+/// layer algorithm is performed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AUFAnalysis {
+    /// Moves performed to adjust the last layer. This is empty if the algorithm was
+    /// started without an adjustment.
+    pub moves: Vec<Move>,
+    /// Time from the first adjustment move until the start of the algorithm
+    pub time: u32,
+    /// Whether the adjustment used the fewest moves possible for the algorithm that
+    /// was performed after it
+    pub optimal: bool,
 }
 
 /// PLL algorithm used during solve.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub enum PLLAlgorithm {
     Aa,
     Ab,
@@ -152,7 +243,7 @@ pub enum PLLAlgorithm {
 /// Analysis of the final alignment of the last layer. This is after the PLL algorithm
 /// is completed and one or more face rotations are needed to finish the solve. These
 /// fields may be zero if the cube was solved directly after the PLL algorithm.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FinalAlignmentAnalysis {
     /// Time spent aligning the last layer
     pub time: u32,
@@ -162,6 +253,17 @@ pub struct FinalAlignmentAnalysis {
     pub moves: Vec<Move>,
 }
 
+/// Step of a CFOP solve
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CFOPStep {
+    Cross,
+    /// F2L pair by the order they were inserted
+    F2LPair(usize),
+    OLL,
+    PLL,
+    Alignment,
+}
+
 /// State of the cube as it's being solved with CFOP method
 #[derive(Clone, PartialEq, Eq)]
 pub enum CFOPProgress {
@@ -188,6 +290,7 @@ struct AnalysisData {
     state_start_index: usize,
     state_recognition_time: Option<u32>,
     state_moves: Vec<Move>,
+    state_move_times: Vec<u32>,
     total_moves: usize,
     cube: Cube3x3x3Faces,
     cross_color: Color,
@@ -197,6 +300,9 @@ struct AnalysisData {
     oll_analysis: Vec<OLLAnalysis>,
     pll_analysis: Vec<PLLAnalysis>,
     alignment: Option<FinalAlignmentAnalysis>,
+    /// Move index and previous move time when a solved F2L pair was taken out
+    pair_break: Option<(usize, u32)>,
+    broken_pairs: Vec<Mistake>,
     time: u32,
 }
 
@@ -204,6 +310,175 @@ impl CFOPAnalysis {
     pub fn analyze(solve: &CubeWithSolution) -> Option<Self> {
         CFOPPartialAnalysis::analyze(solve).into()
     }
+
+    /// Gets the one-look last layer case that was used to skip PLL, if any
+    pub fn pll_skip_case(&self) -> Option<Algorithm> {
+        CFOPAnalysisStages::pll_skip_case(self)
+    }
+
+    /// Total time spent adjusting the last layer, before each last layer algorithm
+    /// and in the final alignment
+    pub fn auf_time(&self) -> u32 {
+        self.oll
+            .iter()
+            .filter_map(|oll| oll.pre_auf.as_ref())
+            .chain(self.pll.iter().map(|pll| &pll.pre_auf))
+            .map(|auf| auf.time)
+            .sum::<u32>()
+            + self.alignment.time
+    }
+
+    /// Number of adjustments before last layer algorithms that used more moves
+    /// than needed
+    pub fn bad_auf_count(&self) -> usize {
+        self.oll
+            .iter()
+            .filter_map(|oll| oll.pre_auf.as_ref())
+            .chain(self.pll.iter().map(|pll| &pll.pre_auf))
+            .filter(|auf| !auf.optimal)
+            .count()
+    }
+}
+
+impl F2LSlot {
+    pub fn all() -> [F2LSlot; 4] {
+        [
+            F2LSlot::FrontRight,
+            F2LSlot::FrontLeft,
+            F2LSlot::BackRight,
+            F2LSlot::BackLeft,
+        ]
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            F2LSlot::FrontRight => "FR",
+            F2LSlot::FrontLeft => "FL",
+            F2LSlot::BackRight => "BR",
+            F2LSlot::BackLeft => "BL",
+        }
+    }
+
+    pub fn from_str(name: &str) -> Option<Self> {
+        match name {
+            "FR" => Some(F2LSlot::FrontRight),
+            "FL" => Some(F2LSlot::FrontLeft),
+            "BR" => Some(F2LSlot::BackRight),
+            "BL" => Some(F2LSlot::BackLeft),
+            _ => None,
+        }
+    }
+
+    pub fn is_back(&self) -> bool {
+        matches!(self, F2LSlot::BackRight | F2LSlot::BackLeft)
+    }
+
+    /// Gets the slot between two side faces when the cross is on `cross_face`. Whole cube
+    /// rotations are not known, so the cube is assumed to be held with the cross on the
+    /// bottom using the smallest rotation from the scrambling orientation (white on top
+    /// and green in front).
+    fn from_side_faces(cross_face: CubeFace, faces: [CubeFace; 2]) -> Self {
+        let front = match cross_face {
+            CubeFace::Front => CubeFace::Top,
+            CubeFace::Back => CubeFace::Bottom,
+            _ => CubeFace::Front,
+        };
+
+        // Right face is the cross product of the up and front directions
+        let up = Self::face_direction(cross_face.opposite());
+        let forward = Self::face_direction(front);
+        let right_direction = [
+            up[1] * forward[2] - up[2] * forward[1],
+            up[2] * forward[0] - up[0] * forward[2],
+            up[0] * forward[1] - up[1] * forward[0],
+        ];
+        let right = faces
+            .iter()
+            .any(|face| Self::face_direction(*face) == right_direction);
+
+        match (faces.contains(&front), right) {
+            (true, true) => F2LSlot::FrontRight,
+            (true, false) => F2LSlot::FrontLeft,
+            (false, true) => F2LSlot::BackRight,
+            (false, false) => F2LSlot::BackLeft,
+        }
+    }
+
+    /// Gets the two side faces around the slot when the cross is on `cross_face`, using
+    /// the same cube orientation as `from_side_faces`
+    pub fn side_faces(&self, cross_face: CubeFace) -> [CubeFace; 2] {
+        let sides: Vec<CubeFace> = [
+            CubeFace::Top,
+            CubeFace::Front,
+            CubeFace::Right,
+            CubeFace::Back,
+            CubeFace::Left,
+            CubeFace::Bottom,
+        ]
+        .iter()
+        .copied()
+        .filter(|face| *face != cross_face && *face != cross_face.opposite())
+        .collect();
+        for first in &sides {
+            for second in &sides {
+                let faces = [*first, *second];
+                if first < second
+                    && *second != first.opposite()
+                    && Self::from_side_faces(cross_face, faces) == *self
+                {
+                    return faces;
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    fn face_direction(face: CubeFace) -> [i8; 3] {
+        match face {
+            CubeFace::Top => [0, 1, 0],
+            CubeFace::Bottom => [0, -1, 0],
+            CubeFace::Front => [0, 0, 1],
+            CubeFace::Back => [0, 0, -1],
+            CubeFace::Right => [1, 0, 0],
+            CubeFace::Left => [-1, 0, 0],
+        }
+    }
+}
+
+impl std::fmt::Display for CFOPStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CFOPStep::Cross => write!(f, "Cross"),
+            CFOPStep::F2LPair(idx) => write!(f, "Pair {}", idx + 1),
+            CFOPStep::OLL => write!(f, "OLL"),
+            CFOPStep::PLL => write!(f, "PLL"),
+            CFOPStep::Alignment => write!(f, "Alignment"),
+        }
+    }
+}
+
+impl OLLAnalysis {
+    /// Identifies which algorithm was used for the performed case, checking the given
+    /// user defined algorithms before the known algorithms
+    pub fn variant(&self, custom: &[Vec<ExtendedMove>]) -> AlgorithmVariant {
+        AlgorithmVariant::identify(
+            &self.moves,
+            &KnownAlgorithms::oll(self.performed_algorithm),
+            custom,
+        )
+    }
+}
+
+impl PLLAnalysis {
+    /// Identifies which algorithm was used for the performed case, checking the given
+    /// user defined algorithms before the known algorithms
+    pub fn variant(&self, custom: &[Vec<ExtendedMove>]) -> AlgorithmVariant {
+        AlgorithmVariant::identify(
+            &self.moves,
+            &KnownAlgorithms::pll(self.performed_algorithm),
+            custom,
+        )
+    }
 }
 
 impl OLLAlgorithm {
@@ -527,6 +802,14 @@ impl PLLAlgorithm {
         ]
     }
 
+    /// Checks if this case only needs the edges to be permuted
+    pub const fn is_edges_only(&self) -> bool {
+        matches!(
+            self,
+            PLLAlgorithm::H | PLLAlgorithm::Ua | PLLAlgorithm::Ub | PLLAlgorithm::Z
+        )
+    }
+
     pub(crate) const fn from_index(idx: usize) -> Option<Self> {
         match idx {
             0 => Some(PLLAlgorithm::Aa),
@@ -712,6 +995,7 @@ impl AnalysisData {
             state_start_index: 0,
             state_recognition_time: None,
             state_moves: Vec::new(),
+            state_move_times: Vec::new(),
             total_moves: 0,
             cube: solve.initial_state.as_faces(),
             cross_color,
@@ -721,6 +1005,8 @@ impl AnalysisData {
             oll_analysis: Vec::new(),
             pll_analysis: Vec::new(),
             alignment: None,
+            pair_break: None,
+            broken_pairs: Vec::new(),
             time: 0,
         };
         result.check_for_state_transitions();
@@ -733,30 +1019,39 @@ impl AnalysisData {
         self.state_start_index = self.total_moves;
         self.state_recognition_time = None;
         self.state_moves.clear();
+        self.state_move_times.clear();
     }
 
     fn cross_solved(&self) -> bool {
+        self.cross_solved_on(&self.cube)
+    }
+
+    fn cross_solved_on(&self, cube: &Cube3x3x3Faces) -> bool {
         let cross_edges = &CUBE3_EDGE_ADJACENCY[self.cross_face as u8 as usize];
-        self.cube.color(self.cross_face, 0, 1) == self.cross_face.color()
-            && self.cube.color(self.cross_face, 1, 0) == self.cross_face.color()
-            && self.cube.color(self.cross_face, 1, 2) == self.cross_face.color()
-            && self.cube.color(self.cross_face, 2, 1) == self.cross_face.color()
-            && self.cube.color_by_idx(cross_edges[0])
+        cube.color(self.cross_face, 0, 1) == self.cross_face.color()
+            && cube.color(self.cross_face, 1, 0) == self.cross_face.color()
+            && cube.color(self.cross_face, 1, 2) == self.cross_face.color()
+            && cube.color(self.cross_face, 2, 1) == self.cross_face.color()
+            && cube.color_by_idx(cross_edges[0])
                 == Cube3x3x3Faces::face_for_idx(cross_edges[0]).color()
-            && self.cube.color_by_idx(cross_edges[1])
+            && cube.color_by_idx(cross_edges[1])
                 == Cube3x3x3Faces::face_for_idx(cross_edges[1]).color()
-            && self.cube.color_by_idx(cross_edges[2])
+            && cube.color_by_idx(cross_edges[2])
                 == Cube3x3x3Faces::face_for_idx(cross_edges[2]).color()
-            && self.cube.color_by_idx(cross_edges[3])
+            && cube.color_by_idx(cross_edges[3])
                 == Cube3x3x3Faces::face_for_idx(cross_edges[3]).color()
     }
 
     fn f2l_pair_count(&self) -> usize {
+        self.f2l_pair_count_on(&self.cube)
+    }
+
+    fn f2l_pair_count_on(&self, cube: &Cube3x3x3Faces) -> usize {
         let mut pair_count = 0;
         for pairs in &CUBE3_F2L_PAIRS[self.cross_face as u8 as usize] {
             let mut ok = true;
             for piece in pairs {
-                if self.cube.color_by_idx(*piece) != Cube3x3x3Faces::face_for_idx(*piece).color() {
+                if cube.color_by_idx(*piece) != Cube3x3x3Faces::face_for_idx(*piece).color() {
                     ok = false;
                 }
             }
@@ -767,8 +1062,119 @@ impl AnalysisData {
         pair_count
     }
 
+    fn pair_solved(cube: &Cube3x3x3Faces, pair: &[usize; 5]) -> bool {
+        pair.iter()
+            .all(|idx| cube.color_by_idx(*idx) == Cube3x3x3Faces::face_for_idx(*idx).color())
+    }
+
+    /// Finds the slot that was filled by the moves performed since the start of the
+    /// current state, along with whether other slots were filled as well and whether
+    /// the pair was inserted with a keyhole.
+    fn inserted_slot(&self) -> (F2LSlot, bool, bool) {
+        let mut start = self.cube.clone();
+        start.do_moves(&self.state_moves.inverse());
+
+        // The pair count has increased since the start of the state, so there is
+        // always at least one newly filled slot
+        let mut inserted = CUBE3_F2L_PAIRS[self.cross_face as u8 as usize]
+            .iter()
+            .filter(|pair| Self::pair_solved(&self.cube, pair) && !Self::pair_solved(&start, pair));
+        let pair = inserted.next().unwrap();
+        let multi_slotted = inserted.next().is_some();
+
+        let piece_solved =
+            |idx: usize| start.color_by_idx(idx) == Cube3x3x3Faces::face_for_idx(idx).color();
+        let corner_solved = piece_solved(pair[0]) && piece_solved(pair[1]) && piece_solved(pair[3]);
+        let edge_solved = piece_solved(pair[2]) && piece_solved(pair[4]);
+
+        let slot = F2LSlot::from_side_faces(
+            self.cross_face,
+            [
+                Cube3x3x3Faces::face_for_idx(pair[1]),
+                Cube3x3x3Faces::face_for_idx(pair[3]),
+            ],
+        );
+        (slot, multi_slotted, corner_solved || edge_solved)
+    }
+
     fn f2l_solved(&self) -> bool {
-        self.cross_solved() && self.f2l_pair_count() == 4
+        self.f2l_solved_on(&self.cube)
+    }
+
+    fn f2l_solved_on(&self, cube: &Cube3x3x3Faces) -> bool {
+        self.cross_solved_on(cube) && self.f2l_pair_count_on(cube) == 4
+    }
+
+    /// Splits the adjustment of the last layer off the start of the moves performed
+    /// since the start of the current state. The adjustment is optimal if no adjustment
+    /// with fewer moves, followed by the rest of the moves, gives the same result. The
+    /// result of a cube state is given by `result`.
+    fn pre_auf<T: PartialEq>(&self, result: impl Fn(&Cube3x3x3Faces) -> T) -> AUFAnalysis {
+        let last_layer = self.cross_face.opposite();
+        let count = self
+            .state_moves
+            .iter()
+            .take_while(|mv| mv.face() == last_layer)
+            .count();
+        let time = match self.state_move_times.get(count) {
+            Some(algorithm_start) if count > 0 => algorithm_start - self.state_move_times[0],
+            _ => 0,
+        };
+
+        // Smart cubes report half turns as two quarter turns, so a pair of matching
+        // quarter turns is counted as a single move
+        let mut performed = 0;
+        let mut idx = 0;
+        while idx < count {
+            let mv = self.state_moves[idx];
+            if mv.rotation().abs() == 1 && self.state_moves[..count].get(idx + 1) == Some(&mv) {
+                idx += 2;
+            } else {
+                idx += 1;
+            }
+            performed += 1;
+        }
+
+        let mut start = self.cube.clone();
+        start.do_moves(&self.state_moves.inverse());
+        let expected = result(&self.cube);
+        let optimal = count == 0
+            || [0, 1, -1, 2].iter().all(|rotation| {
+                let adjustment = Move::from_face_and_rotation(last_layer, *rotation);
+                if adjustment.is_some() as usize >= performed {
+                    return true;
+                }
+                let mut cube = start.clone();
+                if let Some(adjustment) = adjustment {
+                    cube.do_move(adjustment);
+                }
+                cube.do_moves(&self.state_moves[count..]);
+                !self.f2l_solved_on(&cube) || result(&cube) != expected
+            });
+
+        AUFAnalysis {
+            moves: self.state_moves[..count].to_vec(),
+            time,
+            optimal,
+        }
+    }
+
+    /// Splits the adjustment off the start of an OLL algorithm
+    fn oll_pre_auf(&self) -> AUFAnalysis {
+        let last_layer = self.cross_face.opposite();
+        self.pre_auf(|cube| OLLAlgorithm::from_cube(cube, last_layer))
+    }
+
+    /// Splits the adjustment off the start of a PLL algorithm. The last layer must
+    /// stay oriented for an adjustment to give the same result.
+    fn pll_pre_auf(&self) -> AUFAnalysis {
+        let last_layer = self.cross_face.opposite();
+        self.pre_auf(|cube| {
+            (
+                OLLAlgorithm::from_cube(cube, last_layer),
+                PLLAlgorithm::from_cube(cube, last_layer),
+            )
+        })
     }
 
     fn last_layer_oriented(&self) -> bool {
@@ -811,6 +1217,37 @@ impl AnalysisData {
         true
     }
 
+    /// Finds the one-look last layer subset case that was solved by the moves performed
+    /// since the start of the current state. The last layer must be oriented.
+    fn solved_subset_case(&self, oll: &OLLAnalysis) -> Option<Algorithm> {
+        let last_layer = self.cross_face.opposite();
+        let ll_solved = self.last_layer_solved();
+        if !ll_solved {
+            match PLLAlgorithm::from_cube(&self.cube, last_layer) {
+                Some(pll) if pll.is_edges_only() => (),
+                _ => return None,
+            }
+        }
+
+        // A known OLL algorithm that left the corners permuted was a lucky case,
+        // not a subset algorithm
+        if let AlgorithmVariant::Known(_) = oll.variant(&[]) {
+            return None;
+        }
+
+        let mut start = self.cube.clone();
+        start.do_moves(&self.state_moves.inverse());
+        if ll_solved {
+            if let Some(zbll) = ZBLLAlgorithm::from_cube(&start, last_layer) {
+                return Some(Algorithm::ZBLL(zbll));
+            }
+        }
+        match COLLAlgorithm::from_cube(&start, last_layer) {
+            Some(coll) => Some(Algorithm::COLL(coll)),
+            None => OLLCPAlgorithm::from_cube(&start, last_layer).map(Algorithm::OLLCP),
+        }
+    }
+
     fn check_for_single_state_transition(&mut self) {
         if self.total_moves > self.state_start_index && self.state_recognition_time.is_none() {
             self.state_recognition_time = Some(self.time - self.state_start_time);
@@ -831,11 +1268,15 @@ impl AnalysisData {
                 if self.cross_solved() && new_pair_count > count {
                     if self.state_moves.len() != 0 {
                         let recognition_time = self.state_recognition_time.unwrap_or(0);
+                        let (slot, multi_slotted, keyhole) = self.inserted_slot();
                         self.f2l_pairs.push(F2LPairAnalysis {
                             recognition_time,
                             execution_time: self.time - self.state_start_time - recognition_time,
                             start_move_index: self.state_start_index,
                             moves: self.state_moves.clone(),
+                            slot,
+                            multi_slotted,
+                            keyhole,
                         });
                     }
                     if new_pair_count == 4 {
@@ -865,7 +1306,7 @@ impl AnalysisData {
                             // To arrive at OLL solved state, we must have performed the OLL
                             // algorithm found at the start of the state.
                             let recognition_time = self.state_recognition_time.unwrap_or(0);
-                            self.oll_analysis.push(OLLAnalysis {
+                            let mut analysis = OLLAnalysis {
                                 one_look_algorithm,
                                 performed_algorithm: one_look_algorithm,
                                 new_state: None,
@@ -875,7 +1316,11 @@ impl AnalysisData {
                                     - recognition_time,
                                 start_move_index: self.state_start_index,
                                 moves: self.state_moves.clone(),
-                            });
+                                pre_auf: Some(self.oll_pre_auf()),
+                                subset_algorithm: None,
+                            };
+                            analysis.subset_algorithm = self.solved_subset_case(&analysis);
+                            self.oll_analysis.push(analysis);
                         }
 
                         // Check PLL and transition state
@@ -919,6 +1364,8 @@ impl AnalysisData {
                                     - recognition_time,
                                 start_move_index: self.state_start_index,
                                 moves: self.state_moves.clone(),
+                                pre_auf: Some(self.oll_pre_auf()),
+                                subset_algorithm: None,
                             });
                         }
                         self.new_state(CFOPProgress::OLL(new_one_look_algorithm));
@@ -943,6 +1390,7 @@ impl AnalysisData {
                                     - recognition_time,
                                 start_move_index: self.state_start_index,
                                 moves: self.state_moves.clone(),
+                                pre_auf: self.pll_pre_auf(),
                             });
                         }
                         self.new_state(CFOPProgress::FinalAlignment);
@@ -977,6 +1425,7 @@ impl AnalysisData {
                                     - recognition_time,
                                 start_move_index: self.state_start_index,
                                 moves: self.state_moves.clone(),
+                                pre_auf: self.pll_pre_auf(),
                             });
                         }
                         self.new_state(CFOPProgress::PLL(new_one_look_algorithm));
@@ -1009,11 +1458,38 @@ impl AnalysisData {
         }
     }
 
+    fn check_for_broken_pair(&mut self, prev_time: u32) {
+        if let CFOPProgress::F2LPair(count) = self.progress {
+            let move_index = self.total_moves - 1;
+            if self.f2l_pair_count() < count {
+                if self.pair_break.is_none() {
+                    self.pair_break = Some((move_index, prev_time));
+                }
+            } else if let Some((start_index, start_time)) = self.pair_break.take() {
+                // Pair was restored, only count it as a mistake if it wasn't taken
+                // out on purpose for a few moves.
+                let move_count = move_index + 1 - start_index;
+                if move_count > MAX_TEMPORARY_BREAK_MOVES {
+                    self.broken_pairs.push(Mistake {
+                        kind: MistakeKind::BrokenPair,
+                        step: CFOPStep::F2LPair(self.f2l_pairs.len()),
+                        move_index: start_index,
+                        move_count,
+                        time_lost: self.time - start_time,
+                    });
+                }
+            }
+        }
+    }
+
     fn do_move(&mut self, timed_move: &TimedMove) {
+        let prev_time = self.time;
         self.cube.do_move(timed_move.move_());
         self.time = timed_move.time();
         self.total_moves += 1;
         self.state_moves.push(timed_move.move_());
+        self.state_move_times.push(timed_move.time());
+        self.check_for_broken_pair(prev_time);
         self.check_for_state_transitions();
     }
 }
@@ -1050,14 +1526,19 @@ impl CFOPPartialAnalysis {
             data.do_move(mv);
         }
 
-        Self {
+        let mut result = Self {
             progress: data.progress,
             cross: data.cross_analysis,
             f2l_pairs: data.f2l_pairs,
             oll: data.oll_analysis,
             pll: data.pll_analysis,
             alignment: data.alignment,
-        }
+            mistakes: data.broken_pairs,
+        };
+        let mistakes = find_mistakes(&result, &solve.solution);
+        result.mistakes.extend(mistakes);
+        result.mistakes.sort_by_key(|mistake| mistake.move_index);
+        result
     }
 }
 
@@ -1153,7 +1634,10 @@ impl<T: CFOPAnalysisStages> AnalysisSummary for T {
 
             // Only show last performed OLL algorithm. First part of two-look OLL
             // is not very interesting.
-            oll_algorithm = Some(oll.performed_algorithm.to_string());
+            oll_algorithm = Some(match oll.subset_algorithm {
+                Some(subset) => subset.to_string(),
+                None => oll.performed_algorithm.to_string(),
+            });
         }
 
         if oll_move_count > 0 {
@@ -1223,7 +1707,7 @@ impl<T: CFOPAnalysisStages> AnalysisSummary for T {
                 name: "Alignment".into(),
                 short_name: "Align".into(),
                 major_step_index: 3,
-                algorithm: None,
+                algorithm: self.pll_skip_case().map(|_| "Skip".into()),
                 recognition_time: pll_recognition_time,
                 execution_time: pll_execution_time,
                 substeps,
@@ -1250,7 +1734,7 @@ impl<T: CFOPAnalysisStages> AnalysisSummary for T {
         }
         for pair in self.f2l_pairs() {
             result.push(AnalysisStepSummary {
-                name: "F2L Pair".into(),
+                name: format!("F2L Pair ({})", pair.slot.to_str()),
                 short_name: "Pair".into(),
                 major_step_index: 1,
                 algorithm: None,
@@ -1268,7 +1752,10 @@ impl<T: CFOPAnalysisStages> AnalysisSummary for T {
                 name: "OLL".into(),
                 short_name: "OLL".into(),
                 major_step_index: 2,
-                algorithm: Some(oll.performed_algorithm.to_string()),
+                algorithm: Some(match oll.subset_algorithm {
+                    Some(subset) => subset.to_string(),
+                    None => oll.performed_algorithm.to_string(),
+                }),
                 recognition_time: oll.recognition_time,
                 execution_time: oll.execution_time,
                 substeps: vec![
@@ -1367,6 +1854,7 @@ impl From<CFOPPartialAnalysis> for Option<CFOPAnalysis> {
                     oll: analysis.oll,
                     pll: analysis.pll,
                     alignment,
+                    mistakes: analysis.mistakes,
                 });
             }
         }
@@ -1374,6 +1862,18 @@ impl From<CFOPPartialAnalysis> for Option<CFOPAnalysis> {
     }
 }
 
+impl std::fmt::Display for AUFAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "AUF: {} moves in {}ms{}",
+            self.moves.len(),
+            self.time,
+            if self.optimal { "" } else { " (suboptimal)" }
+        )
+    }
+}
+
 impl std::fmt::Display for CFOPPartialAnalysis {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if let Some(cross) = &self.cross {
@@ -1388,7 +1888,14 @@ impl std::fmt::Display for CFOPPartialAnalysis {
         for pair in &self.f2l_pairs {
             write!(
                 f,
-                "F2L pair: Recognition {}ms, {} moves in {}ms\n",
+                "F2L pair ({}{}{}): Recognition {}ms, {} moves in {}ms\n",
+                pair.slot.to_str(),
+                if pair.multi_slotted {
+                    ", multi-slot"
+                } else {
+                    ""
+                },
+                if pair.keyhole { ", keyhole" } else { "" },
                 pair.recognition_time,
                 pair.moves.len(),
                 pair.execution_time
@@ -1405,6 +1912,16 @@ impl std::fmt::Display for CFOPPartialAnalysis {
                     oll.execution_time,
                     oll.one_look_algorithm.to_string(),
                 )?;
+            } else if let Some(subset) = oll.subset_algorithm {
+                writeln!(
+                    f,
+                    "OLL ({}, {}): Recognition {}ms, {} moves in {}ms",
+                    oll.performed_algorithm.to_string(),
+                    subset.to_string(),
+                    oll.recognition_time,
+                    oll.moves.len(),
+                    oll.execution_time
+                )?;
             } else {
                 write!(
                     f,
@@ -1415,6 +1932,11 @@ impl std::fmt::Display for CFOPPartialAnalysis {
                     oll.execution_time
                 )?;
             }
+            if let Some(auf) = &oll.pre_auf {
+                if !auf.moves.is_empty() {
+                    writeln!(f, "    {}", auf)?;
+                }
+            }
         }
         for pll in &self.pll {
             if pll.new_state.is_some() {
@@ -1437,6 +1959,12 @@ impl std::fmt::Display for CFOPPartialAnalysis {
                     pll.execution_time
                 )?;
             }
+            if !pll.pre_auf.moves.is_empty() {
+                writeln!(f, "    {}", pll.pre_auf)?;
+            }
+        }
+        if let Some(case) = self.pll_skip_case() {
+            writeln!(f, "PLL skipped with {}", case.to_string())?;
         }
         if let Some(alignment) = &self.alignment {
             if alignment.moves.len() != 0 {
@@ -1448,6 +1976,15 @@ impl std::fmt::Display for CFOPPartialAnalysis {
                 )?;
             }
         }
+        for mistake in &self.mistakes {
+            writeln!(
+                f,
+                "Mistake ({}): {} moves, {}ms lost",
+                mistake.kind.to_str(),
+                mistake.move_count,
+                mistake.time_lost
+            )?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,118 @@
+use super::lookahead::{median, move_gaps};
+use super::optimal::half_turn_moves;
+use crate::cube3x3x3::{cross_edges, f2l_slot_pieces};
+use crate::{Cube, Cube3x3x3, CubeFace, F2LSlot, InitialCubeState, StepEfficiency, TimedMove};
+
+/// Face the cross is solved on in cross training, which is white on top as scrambled
+pub(crate) const CROSS_FACE: CubeFace = CubeFace::Top;
+
+/// Analysis of a cross planning attempt. The cross, or an XCross with a target F2L
+/// pair, is planned during inspection and then executed. The moves executed are
+/// compared against an optimal solution, and the gaps between moves show whether the
+/// plan was executed without stopping to look for pieces.
+#[derive(Clone, Debug)]
+pub struct CrossPlanningAnalysis {
+    /// Target slot when planning an XCross, or `None` when planning the cross alone
+    pub slot: Option<F2LSlot>,
+    /// Moves performed until the cross was solved, counted with consecutive turns of
+    /// the same face as a single move
+    pub efficiency: StepEfficiency,
+    /// Time from the start of inspection until the first move
+    pub planning_time: u32,
+    /// Time from the first move until the cross was solved
+    pub execution_time: u32,
+    /// Longest gap between moves while executing the cross
+    pub longest_gap: u32,
+    /// Shortest gap between moves that is counted as a pause
+    pub pause_gap: u32,
+}
+
+impl CrossPlanningAnalysis {
+    /// Analyzes a cross planning solve with the cross on top after the scramble. The
+    /// comparison against an optimal solution is given as `efficiency`, as finding an
+    /// optimal XCross takes a moment. Move times are relative to the start of
+    /// inspection. Gaps between moves that are at least `threshold` times `median_gap`
+    /// are counted as pauses, as in `LookaheadAnalysis::analyze`. Returns `None` if the
+    /// cross, or the target slot for an XCross, was never solved.
+    pub fn analyze(
+        initial_state: &Cube3x3x3,
+        solution: &[TimedMove],
+        slot: Option<F2LSlot>,
+        efficiency: StepEfficiency,
+        median_gap: Option<u32>,
+        threshold: f32,
+    ) -> Option<Self> {
+        let solution = Self::target_solution(initial_state, solution, slot)?;
+
+        let median_gap = median_gap
+            .or_else(|| median(&mut move_gaps(solution).collect::<Vec<u32>>()))
+            .unwrap_or(0);
+        let pause_gap = ((median_gap as f32 * threshold) as u32).max(1);
+
+        let first = solution.first().unwrap().time();
+        let last = solution.last().unwrap().time();
+        Some(Self {
+            slot,
+            efficiency,
+            planning_time: first,
+            execution_time: last - first,
+            longest_gap: move_gaps(solution).max().unwrap_or(0),
+            pause_gap,
+        })
+    }
+
+    /// Checks if the cross, and the target slot for an XCross, are solved
+    pub fn target_solved(cube: &Cube3x3x3, slot: Option<F2LSlot>) -> bool {
+        let solved = Cube3x3x3::new();
+        let mut edges = cross_edges(CROSS_FACE);
+        let mut corners = Vec::new();
+        if let Some(slot) = slot {
+            let (corner, edge) = f2l_slot_pieces(CROSS_FACE, slot);
+            corners.push(corner);
+            edges.push(edge);
+        }
+        corners
+            .iter()
+            .all(|corner| cube.corner_piece(*corner) == solved.corner_piece(*corner))
+            && edges
+                .iter()
+                .all(|edge| cube.edge_piece(*edge) == solved.edge_piece(*edge))
+    }
+
+    /// Counts the moves of a solution until the target was solved, in the half turn
+    /// metric. Returns `None` if the target was never solved.
+    pub fn target_moves(
+        initial_state: &Cube3x3x3,
+        solution: &[TimedMove],
+        slot: Option<F2LSlot>,
+    ) -> Option<usize> {
+        let solution = Self::target_solution(initial_state, solution, slot)?;
+        Some(half_turn_moves(solution.iter().map(|mv| mv.move_())))
+    }
+
+    /// Gets the moves of a solution up to the point where the target was solved
+    fn target_solution<'a>(
+        initial_state: &Cube3x3x3,
+        solution: &'a [TimedMove],
+        slot: Option<F2LSlot>,
+    ) -> Option<&'a [TimedMove]> {
+        let mut cube = initial_state.clone();
+        for (idx, mv) in solution.iter().enumerate() {
+            cube.do_move(mv.move_());
+            if Self::target_solved(&cube, slot) {
+                return Some(&solution[..idx + 1]);
+            }
+        }
+        None
+    }
+
+    /// Whether the cross was executed without pausing
+    pub fn without_pauses(&self) -> bool {
+        self.longest_gap < self.pause_gap
+    }
+
+    /// Whether the cross was executed in an optimal number of moves
+    pub fn is_optimal(&self) -> bool {
+        self.efficiency.extra_moves() == Some(0)
+    }
+}
@@ -0,0 +1,1222 @@
+use crate::tables::analysis::{CUBE2_CLL_CASES, CUBE2_EG1_CASES, CUBE2_EG2_CASES};
+use crate::tables::corner::CUBE2_CORNER_INDICIES;
+use crate::{
+    Algorithm, AlgorithmVariant, AnalysisStepSummary, AnalysisSubstepTime, AnalysisSummary, Color,
+    Corner, Cube, Cube2x2x2, Cube2x2x2Faces, CubeFace, ExtendedMove, FinalAlignmentAnalysis,
+    InitialCubeState, KnownAlgorithms, Move, OLLAlgorithm, OLLAnalysis, PartialAnalysis,
+    PartialAnalysisMethod, TimedMove,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// A 2x2x2 cube state along with the moves performed to solve it
+#[derive(Clone)]
+pub struct Cube2x2x2WithSolution {
+    pub initial_state: Cube2x2x2,
+    pub solution: Vec<TimedMove>,
+}
+
+/// Method used to solve a 2x2x2 cube after the first face
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cube2x2x2Method {
+    /// Last layer orientation followed by permutation of both layers
+    Ortega,
+    /// Last layer corners solved in one algorithm after the first layer
+    CLL,
+    /// Last layer corners solved in one algorithm after a first face with two adjacent
+    /// corners swapped
+    EG1,
+    /// Last layer corners solved in one algorithm after a first face with two diagonal
+    /// corners swapped
+    EG2,
+}
+
+/// Permutation of the corners of a layer relative to each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LayerPermutation {
+    Solved,
+    AdjacentSwap,
+    DiagonalSwap,
+}
+
+/// Analysis of the first face of a 2x2x2 solution
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FirstFaceAnalysis {
+    /// Color of the first face
+    pub color: Color,
+    /// Permutation of the first layer when the first face was completed
+    pub layer: LayerPermutation,
+    /// Time spent solving the first face
+    pub time: u32,
+    /// Moves performed
+    pub moves: Vec<Move>,
+}
+
+/// Analysis of the algorithm of a CLL or EG solution. This algorithm solves the last
+/// layer and fixes the permutation of the first layer at the same time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CLLAnalysis {
+    /// Case that was solved. This is a CLL, EG-1, or EG-2 case depending on the
+    /// permutation of the first layer.
+    pub algorithm: Algorithm,
+    /// Time spent recognizing the state
+    pub recognition_time: u32,
+    /// Time spent executing the algorithm
+    pub execution_time: u32,
+    /// Move index of the start of the algorithm
+    pub start_move_index: usize,
+    /// Moves performed
+    pub moves: Vec<Move>,
+}
+
+/// Analysis of a PBL (permutation of both layers) algorithm performance in an Ortega
+/// solution. There may be more than one of these if the layers were permuted one at
+/// a time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PBLAnalysis {
+    /// The case at the start of the algorithm
+    pub algorithm: PBLAlgorithm,
+    /// The state after the algorithm. If `None`, both layers are solved.
+    pub new_state: Option<PBLAlgorithm>,
+    /// Time spent recognizing the state
+    pub recognition_time: u32,
+    /// Time spent executing the algorithm
+    pub execution_time: u32,
+    /// Move index of the start of the algorithm
+    pub start_move_index: usize,
+    /// Moves performed
+    pub moves: Vec<Move>,
+}
+
+/// PBL case, named by the permutation of the last layer and the first layer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PBLAlgorithm {
+    pub top: LayerPermutation,
+    pub bottom: LayerPermutation,
+}
+
+/// CLL case. These cases solve the last layer corners when the first layer is solved.
+/// Cases are named after the OLL case they belong to, and cases where the last layer
+/// is already oriented are named after the PBL case.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct CLLAlgorithm(u16);
+
+/// EG-1 case. These cases solve the last layer corners and swap two adjacent corners of
+/// the first layer. Cases are named the same way as CLL cases.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct EG1Algorithm(u16);
+
+/// EG-2 case. These cases solve the last layer corners and swap two diagonal corners of
+/// the first layer. Cases are named the same way as CLL cases.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct EG2Algorithm(u16);
+
+/// Analysis of a full 2x2x2 solve. The Ortega, CLL, EG-1 and EG-2 methods are detected
+/// automatically. Solves without timing information, such as manually entered
+/// solutions, can be analyzed by giving every move a time of zero.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Cube2x2x2Analysis {
+    pub method: Cube2x2x2Method,
+    pub first_face: FirstFaceAnalysis,
+    /// Orientation of the last layer, only present in Ortega solves
+    pub oll: Vec<OLLAnalysis>,
+    /// Permutation of both layers, only present in Ortega solves
+    pub pbl: Vec<PBLAnalysis>,
+    /// Last layer algorithm, only present in CLL and EG solves
+    pub last_layer: Option<CLLAnalysis>,
+    pub alignment: FinalAlignmentAnalysis,
+}
+
+/// Partial analysis of a 2x2x2 solution. This analysis can be performed on an
+/// incomplete solve to get the current progress.
+#[derive(Clone)]
+pub struct Cube2x2x2PartialAnalysis {
+    pub progress: Cube2x2x2Progress,
+    pub first_face: Option<FirstFaceAnalysis>,
+    pub oll: Vec<OLLAnalysis>,
+    pub pbl: Vec<PBLAnalysis>,
+    pub last_layer: Option<CLLAnalysis>,
+    pub alignment: Option<FinalAlignmentAnalysis>,
+}
+
+/// State of a 2x2x2 cube as it's being solved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cube2x2x2Progress {
+    /// No progress on solve
+    Initial,
+    /// First face is solved. The case to solve the rest of the cube in one algorithm
+    /// is given.
+    LastLayer(Algorithm),
+    /// Both faces are solved. The current PBL case is given.
+    PBL(PBLAlgorithm),
+    /// All layers are solved but they are not yet aligned
+    FinalAlignment,
+    /// Cube is solved
+    Solved,
+}
+
+/// State of a 2x2x2 cube with the first face solved. Positions are numbered clockwise
+/// looking down on the last layer, with the first layer positions directly below.
+/// Pieces are numbered by the side colors they belong between, so the state depends
+/// only on the pieces relative to each other and not on how the cube is held. Last
+/// layer corner orientation is the index of the last layer colored sticker on the
+/// corner, counting clockwise from the sticker on the last layer face.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct FirstFaceState {
+    top_permutation: [u8; 4],
+    top_orientation: [u8; 4],
+    bottom_permutation: [u8; 4],
+}
+
+/// Gets the face that a sticker of a corner position is on
+fn corner_face(corner: usize, sticker: usize) -> CubeFace {
+    Cube2x2x2Faces::face_for_idx(CUBE2_CORNER_INDICIES[corner][sticker])
+}
+
+/// Gets the index of the sticker of a corner position that is on a face, if any
+fn corner_sticker_on_face(corner: usize, face: CubeFace) -> Option<usize> {
+    (0..3).find(|sticker| corner_face(corner, *sticker) == face)
+}
+
+/// Finds the corner position that is on all of the given faces
+fn corner_on_faces(faces: &[CubeFace]) -> Option<usize> {
+    (0..8).find(|corner| {
+        faces
+            .iter()
+            .all(|face| corner_sticker_on_face(*corner, *face).is_some())
+    })
+}
+
+/// Gets the corner positions around a face in clockwise order, along with the index
+/// of the sticker on the face. The stickers of a corner are in clockwise order, so the
+/// next two stickers are on the sides of the face.
+fn corners_around(face: CubeFace) -> [(usize, usize); 4] {
+    let mut result = [(0, 0); 4];
+    let mut corner = corner_on_faces(&[face]).unwrap();
+    for entry in result.iter_mut() {
+        let sticker = corner_sticker_on_face(corner, face).unwrap();
+        *entry = (corner, sticker);
+
+        // The next corner clockwise shares the side face of the last sticker
+        let side = corner_face(corner, (sticker + 2) % 3);
+        corner = (0..8)
+            .find(|other| {
+                *other != corner
+                    && corner_sticker_on_face(*other, face).is_some()
+                    && corner_sticker_on_face(*other, side).is_some()
+            })
+            .unwrap();
+    }
+    result
+}
+
+/// Gets the side colors around the last layer in clockwise order, as seen on a solved
+/// cube
+fn side_colors(top_color: Color) -> [Color; 4] {
+    let solved = Cube2x2x2Faces::new();
+    let mut result = [top_color; 4];
+    for (side, (corner, sticker)) in corners_around(top_color.face()).iter().enumerate() {
+        result[side] =
+            solved.corner_color(Corner::try_from(*corner as u8).unwrap(), (sticker + 1) % 3);
+    }
+    result
+}
+
+fn face_solved(cube: &Cube2x2x2Faces, face: CubeFace) -> bool {
+    let color = cube.color(face, 0, 0);
+    (0..4).all(|idx| cube.color(face, idx / 2, idx % 2) == color)
+}
+
+/// Checks if the cube is solved in any orientation
+fn solved_in_any_orientation(cube: &Cube2x2x2Faces) -> bool {
+    (0..6).all(|face| face_solved(cube, CubeFace::try_from(face).unwrap()))
+}
+
+impl LayerPermutation {
+    /// Finds the permutation of a layer from the pieces in each position. Each pair of
+    /// neighboring pieces that belong next to each other forms a bar. A solved layer has
+    /// four bars, a layer with an adjacent swap has one, and a layer with a diagonal swap
+    /// has none.
+    fn from_pieces(pieces: &[u8; 4]) -> Self {
+        let bars = (0..4)
+            .filter(|pos| pieces[(pos + 1) % 4] == (pieces[*pos] + 1) % 4)
+            .count();
+        match bars {
+            4 => LayerPermutation::Solved,
+            0 => LayerPermutation::DiagonalSwap,
+            _ => LayerPermutation::AdjacentSwap,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            LayerPermutation::Solved => "-",
+            LayerPermutation::AdjacentSwap => "Adj",
+            LayerPermutation::DiagonalSwap => "Diag",
+        }
+    }
+
+    /// Cases for the last step of a solve with this first layer permutation. Each case
+    /// is the smallest key of all the states in the case, the probability weight of the
+    /// case, and the OLL number of the case (zero if the last layer is oriented).
+    fn cases(&self) -> &'static [(u32, u8, u8)] {
+        match self {
+            LayerPermutation::Solved => &CUBE2_CLL_CASES,
+            LayerPermutation::AdjacentSwap => &CUBE2_EG1_CASES,
+            LayerPermutation::DiagonalSwap => &CUBE2_EG2_CASES,
+        }
+    }
+
+    fn find_case(&self, cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<u16> {
+        let state = FirstFaceState::from_cube(cube, first_face)?;
+        if state.bottom_layer() != *self {
+            return None;
+        }
+        let key = state.canonical_key();
+        self.cases()
+            .iter()
+            .position(|(case_key, _, _)| *case_key == key)
+            .map(|idx| idx as u16)
+    }
+
+    fn oll(&self, idx: u16) -> Option<OLLAlgorithm> {
+        match self.cases()[idx as usize].2 {
+            0 => None,
+            oll => Some(OLLAlgorithm::from_number(oll)),
+        }
+    }
+
+    /// Gets the number of the case within the cases for the same OLL case
+    fn number_in_group(&self, idx: u16) -> usize {
+        let cases = self.cases();
+        let group = cases[idx as usize].2;
+        cases[..idx as usize]
+            .iter()
+            .rev()
+            .take_while(|(_, _, case_group)| *case_group == group)
+            .count()
+    }
+
+    fn case_name(&self, idx: u16) -> String {
+        match self.oll(idx) {
+            Some(oll) => format!("{} {}", oll.to_string(), self.number_in_group(idx) + 1),
+            None => {
+                // Cases with an oriented last layer are a PBL case
+                let state = FirstFaceState::from_key(self.cases()[idx as usize].0);
+                state.pbl().to_string()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for LayerPermutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl FirstFaceState {
+    /// Reads the state of the cube. Returns `None` if the first face is not solved.
+    fn from_cube(cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<Self> {
+        if !face_solved(cube, first_face) {
+            return None;
+        }
+        let top_color = cube.color(first_face, 0, 0).face().opposite().color();
+        let sides = side_colors(top_color);
+        let side_index = |color: Color| sides.iter().position(|side| *side == color);
+
+        let mut result = Self {
+            top_permutation: [0; 4],
+            top_orientation: [0; 4],
+            bottom_permutation: [0; 4],
+        };
+        for (pos, (corner, sticker)) in corners_around(first_face.opposite()).iter().enumerate() {
+            let position = Corner::try_from(*corner as u8).unwrap();
+            let colors = [
+                cube.corner_color(position, *sticker),
+                cube.corner_color(position, (sticker + 1) % 3),
+                cube.corner_color(position, (sticker + 2) % 3),
+            ];
+            let orientation = colors.iter().position(|color| *color == top_color)?;
+            result.top_orientation[pos] = orientation as u8;
+            result.top_permutation[pos] = side_index(colors[(orientation + 1) % 3])? as u8;
+
+            // The first layer corner below this one shares both side faces. It is
+            // identified by the color on the same side face as the piece above.
+            let side = corner_face(*corner, (sticker + 1) % 3);
+            let other_side = corner_face(*corner, (sticker + 2) % 3);
+            let below = corner_on_faces(&[first_face, side, other_side])?;
+            let below_sticker = corner_sticker_on_face(below, side)?;
+            let color = cube.corner_color(Corner::try_from(below as u8).unwrap(), below_sticker);
+            result.bottom_permutation[pos] = side_index(color)? as u8;
+        }
+        Some(result)
+    }
+
+    /// Packs the state into a key, using 6 bits per position: last layer permutation,
+    /// last layer orientation, and first layer permutation
+    fn key(&self) -> u32 {
+        let mut key = 0;
+        for pos in 0..4 {
+            key <<= 6;
+            key |= (self.top_permutation[pos] as u32) << 4;
+            key |= (self.top_orientation[pos] as u32) << 2;
+            key |= self.bottom_permutation[pos] as u32;
+        }
+        key
+    }
+
+    fn from_key(key: u32) -> Self {
+        let mut result = Self {
+            top_permutation: [0; 4],
+            top_orientation: [0; 4],
+            bottom_permutation: [0; 4],
+        };
+        for pos in 0..4 {
+            let bits = key >> ((3 - pos) * 6);
+            result.top_permutation[pos] = ((bits >> 4) & 3) as u8;
+            result.top_orientation[pos] = ((bits >> 2) & 3) as u8;
+            result.bottom_permutation[pos] = (bits & 3) as u8;
+        }
+        result
+    }
+
+    /// Gets the state after turning the last layer `top_turns` times and the first
+    /// layer `bottom_turns` times before the case, and aligning the layers by
+    /// `top_align` and `bottom_align` after solving it
+    fn transformed(
+        &self,
+        top_turns: usize,
+        bottom_turns: usize,
+        top_align: usize,
+        bottom_align: usize,
+    ) -> Self {
+        let mut result = *self;
+        for pos in 0..4 {
+            let top_pos = (pos + top_turns) % 4;
+            let bottom_pos = (pos + bottom_turns) % 4;
+            result.top_permutation[top_pos] =
+                ((self.top_permutation[pos] as usize + top_align) % 4) as u8;
+            result.top_orientation[top_pos] = self.top_orientation[pos];
+            result.bottom_permutation[bottom_pos] =
+                ((self.bottom_permutation[pos] as usize + bottom_align) % 4) as u8;
+        }
+        result
+    }
+
+    /// Gets the key for the case this state is in. States that only differ by turns of
+    /// either layer before or after the case are the same case, so the smallest key of
+    /// all of these states is used.
+    fn canonical_key(&self) -> u32 {
+        let mut result = u32::MAX;
+        for transform in 0..256 {
+            let state = self.transformed(
+                transform & 3,
+                (transform >> 2) & 3,
+                (transform >> 4) & 3,
+                transform >> 6,
+            );
+            result = result.min(state.key());
+        }
+        result
+    }
+
+    fn top_oriented(&self) -> bool {
+        self.top_orientation == [0; 4]
+    }
+
+    fn top_layer(&self) -> LayerPermutation {
+        LayerPermutation::from_pieces(&self.top_permutation)
+    }
+
+    fn bottom_layer(&self) -> LayerPermutation {
+        LayerPermutation::from_pieces(&self.bottom_permutation)
+    }
+
+    /// Checks if both layers are solved, but possibly not aligned with each other
+    fn layers_solved(&self) -> bool {
+        self.top_oriented()
+            && self.top_layer() == LayerPermutation::Solved
+            && self.bottom_layer() == LayerPermutation::Solved
+    }
+
+    fn pbl(&self) -> PBLAlgorithm {
+        PBLAlgorithm {
+            top: self.top_layer(),
+            bottom: self.bottom_layer(),
+        }
+    }
+
+    /// Gets the CLL or EG case for this state. Returns `None` if both layers are solved.
+    fn last_layer_case(&self) -> Option<Algorithm> {
+        let layer = self.bottom_layer();
+        let key = self.canonical_key();
+        let idx = layer
+            .cases()
+            .iter()
+            .position(|(case_key, _, _)| *case_key == key)? as u16;
+        Some(match layer {
+            LayerPermutation::Solved => Algorithm::CLL(CLLAlgorithm(idx)),
+            LayerPermutation::AdjacentSwap => Algorithm::EG1(EG1Algorithm(idx)),
+            LayerPermutation::DiagonalSwap => Algorithm::EG2(EG2Algorithm(idx)),
+        })
+    }
+}
+
+impl PBLAlgorithm {
+    /// Recognizes the PBL case of a cube with the first face and last layer oriented.
+    /// Returns `None` if the last layer is not oriented or both layers are solved.
+    pub fn from_cube(cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<Self> {
+        let state = FirstFaceState::from_cube(cube, first_face)?;
+        if !state.top_oriented() || state.layers_solved() {
+            return None;
+        }
+        Some(state.pbl())
+    }
+}
+
+impl std::fmt::Display for PBLAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.top, self.bottom)
+    }
+}
+
+impl CLLAlgorithm {
+    /// Total weight of all last layer states with the first layer solved, including the
+    /// solved state
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 162;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE2_CLL_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the CLL case of the cube. Returns `None` if the first layer is not
+    /// solved or the cube is already solved.
+    pub fn from_cube(cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<Self> {
+        LayerPermutation::Solved
+            .find_case(cube, first_face)
+            .map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE2_CLL_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of, or `None` if the last layer is oriented
+    pub fn oll(&self) -> Option<OLLAlgorithm> {
+        LayerPermutation::Solved.oll(self.0)
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE2_CLL_CASES[self.0 as usize].1 as usize
+    }
+}
+
+impl EG1Algorithm {
+    /// Total weight of all last layer states with two adjacent first layer corners
+    /// swapped
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 162;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE2_EG1_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the EG-1 case of the cube. Returns `None` if the first face is not
+    /// solved or does not have two adjacent corners swapped.
+    pub fn from_cube(cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<Self> {
+        LayerPermutation::AdjacentSwap
+            .find_case(cube, first_face)
+            .map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE2_EG1_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of, or `None` if the last layer is oriented
+    pub fn oll(&self) -> Option<OLLAlgorithm> {
+        LayerPermutation::AdjacentSwap.oll(self.0)
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE2_EG1_CASES[self.0 as usize].1 as usize
+    }
+}
+
+impl EG2Algorithm {
+    /// Total weight of all last layer states with two diagonal first layer corners
+    /// swapped
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 162;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE2_EG2_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the EG-2 case of the cube. Returns `None` if the first face is not
+    /// solved or does not have two diagonal corners swapped.
+    pub fn from_cube(cube: &Cube2x2x2Faces, first_face: CubeFace) -> Option<Self> {
+        LayerPermutation::DiagonalSwap
+            .find_case(cube, first_face)
+            .map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE2_EG2_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of, or `None` if the last layer is oriented
+    pub fn oll(&self) -> Option<OLLAlgorithm> {
+        LayerPermutation::DiagonalSwap.oll(self.0)
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE2_EG2_CASES[self.0 as usize].1 as usize
+    }
+}
+
+impl std::fmt::Display for CLLAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", LayerPermutation::Solved.case_name(self.0))
+    }
+}
+
+impl std::fmt::Display for EG1Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", LayerPermutation::AdjacentSwap.case_name(self.0))
+    }
+}
+
+impl std::fmt::Display for EG2Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", LayerPermutation::DiagonalSwap.case_name(self.0))
+    }
+}
+
+impl CLLAnalysis {
+    /// Identifies which algorithm was used for the performed case, checking the given
+    /// user defined algorithms before the known algorithms
+    pub fn variant(&self, custom: &[Vec<ExtendedMove>]) -> AlgorithmVariant {
+        let known = match self.algorithm {
+            Algorithm::CLL(cll) => KnownAlgorithms::cll(cll),
+            Algorithm::EG1(eg1) => KnownAlgorithms::eg1(eg1),
+            Algorithm::EG2(eg2) => KnownAlgorithms::eg2(eg2),
+            _ => Vec::new(),
+        };
+        AlgorithmVariant::identify(&self.moves, &known, custom)
+    }
+}
+
+struct AnalysisData {
+    progress: Cube2x2x2Progress,
+    state_start_time: u32,
+    state_start_index: usize,
+    state_recognition_time: Option<u32>,
+    state_moves: Vec<Move>,
+    total_moves: usize,
+    cube: Cube2x2x2Faces,
+    color: Color,
+    first_face: Option<FirstFaceAnalysis>,
+    oll: Vec<OLLAnalysis>,
+    pbl: Vec<PBLAnalysis>,
+    last_layer: Option<CLLAnalysis>,
+    alignment: Option<FinalAlignmentAnalysis>,
+    time: u32,
+}
+
+impl AnalysisData {
+    fn new(solve: &Cube2x2x2WithSolution, color: Color) -> Self {
+        let mut result = Self {
+            progress: Cube2x2x2Progress::Initial,
+            state_start_time: 0,
+            state_start_index: 0,
+            state_recognition_time: None,
+            state_moves: Vec::new(),
+            total_moves: 0,
+            cube: solve.initial_state.as_faces(),
+            color,
+            first_face: None,
+            oll: Vec::new(),
+            pbl: Vec::new(),
+            last_layer: None,
+            alignment: None,
+            time: 0,
+        };
+        result.check_for_state_transitions();
+        result
+    }
+
+    fn new_state(&mut self, state: Cube2x2x2Progress) {
+        self.progress = state;
+        self.state_start_time = self.time;
+        self.state_start_index = self.total_moves;
+        self.state_recognition_time = None;
+        self.state_moves.clear();
+    }
+
+    /// Gets the state of the cube relative to the first face. There are no centers on
+    /// a 2x2x2 cube, so the first face can be on any side of the cube.
+    fn state(&self) -> Option<FirstFaceState> {
+        (0..6)
+            .map(|face| CubeFace::try_from(face).unwrap())
+            .find(|face| {
+                face_solved(&self.cube, *face) && self.cube.color(*face, 0, 0) == self.color
+            })
+            .and_then(|face| FirstFaceState::from_cube(&self.cube, face))
+    }
+
+    /// Moves to the state for the rest of the solve after the first face
+    fn start_last_layer(&mut self, state: &FirstFaceState) {
+        if state.layers_solved() {
+            self.new_state(Cube2x2x2Progress::FinalAlignment);
+        } else {
+            self.new_state(Cube2x2x2Progress::LastLayer(
+                state.last_layer_case().unwrap(),
+            ));
+        }
+    }
+
+    fn recognition_and_execution_time(&self) -> (u32, u32) {
+        let recognition_time = self.state_recognition_time.unwrap_or(0);
+        (
+            recognition_time,
+            self.time - self.state_start_time - recognition_time,
+        )
+    }
+
+    fn check_for_single_state_transition(&mut self) {
+        if self.total_moves > self.state_start_index && self.state_recognition_time.is_none() {
+            self.state_recognition_time = Some(self.time - self.state_start_time);
+        }
+        let state = match self.state() {
+            Some(state) => state,
+            None => return,
+        };
+        match self.progress.clone() {
+            Cube2x2x2Progress::Initial => {
+                self.first_face = Some(FirstFaceAnalysis {
+                    color: self.color,
+                    layer: state.bottom_layer(),
+                    time: self.time - self.state_start_time,
+                    moves: self.state_moves.clone(),
+                });
+                self.start_last_layer(&state);
+            }
+            Cube2x2x2Progress::LastLayer(algorithm) => {
+                if state.layers_solved() {
+                    // Whole cube was solved with a single algorithm, record CLL or EG
+                    // algorithm performance
+                    if !self.state_moves.is_empty() {
+                        let (recognition_time, execution_time) =
+                            self.recognition_and_execution_time();
+                        self.last_layer = Some(CLLAnalysis {
+                            algorithm,
+                            recognition_time,
+                            execution_time,
+                            start_move_index: self.state_start_index,
+                            moves: self.state_moves.clone(),
+                        });
+                    }
+                    self.new_state(Cube2x2x2Progress::FinalAlignment);
+                } else if state.top_oriented() && !self.state_moves.is_empty() {
+                    // Last layer was oriented without solving the cube, this is an
+                    // Ortega solve. If the last layer was already oriented after the
+                    // first face, the rest of the solve is a single algorithm.
+                    let oll = match last_layer_oll(algorithm) {
+                        Some(oll) => oll,
+                        None => return,
+                    };
+                    let (recognition_time, execution_time) = self.recognition_and_execution_time();
+                    self.oll.push(OLLAnalysis {
+                        one_look_algorithm: oll,
+                        performed_algorithm: oll,
+                        new_state: None,
+                        recognition_time,
+                        execution_time,
+                        start_move_index: self.state_start_index,
+                        moves: self.state_moves.clone(),
+                        pre_auf: None,
+                        subset_algorithm: None,
+                    });
+                    self.new_state(Cube2x2x2Progress::PBL(state.pbl()));
+                }
+            }
+            Cube2x2x2Progress::PBL(algorithm) => {
+                if !state.top_oriented() {
+                    return;
+                }
+                let new_state = if state.layers_solved() {
+                    None
+                } else if state.pbl() != algorithm {
+                    Some(state.pbl())
+                } else {
+                    return;
+                };
+                if !self.state_moves.is_empty() {
+                    let (recognition_time, execution_time) = self.recognition_and_execution_time();
+                    self.pbl.push(PBLAnalysis {
+                        algorithm,
+                        new_state,
+                        recognition_time,
+                        execution_time,
+                        start_move_index: self.state_start_index,
+                        moves: self.state_moves.clone(),
+                    });
+                }
+                match new_state {
+                    Some(pbl) => self.new_state(Cube2x2x2Progress::PBL(pbl)),
+                    None => self.new_state(Cube2x2x2Progress::FinalAlignment),
+                }
+            }
+            Cube2x2x2Progress::FinalAlignment => {
+                if solved_in_any_orientation(&self.cube) {
+                    self.alignment = Some(FinalAlignmentAnalysis {
+                        time: self.time - self.state_start_time,
+                        start_move_index: self.state_start_index,
+                        moves: self.state_moves.clone(),
+                    });
+                    self.new_state(Cube2x2x2Progress::Solved);
+                }
+            }
+            Cube2x2x2Progress::Solved => (),
+        }
+    }
+
+    fn check_for_state_transitions(&mut self) {
+        // Perform state transitions until no change
+        loop {
+            let before = self.progress.clone();
+            self.check_for_single_state_transition();
+            if self.progress == before {
+                return;
+            }
+        }
+    }
+
+    fn do_move(&mut self, timed_move: &TimedMove) {
+        self.cube.do_move(timed_move.move_());
+        self.time = timed_move.time();
+        self.total_moves += 1;
+        self.state_moves.push(timed_move.move_());
+        self.check_for_state_transitions();
+    }
+}
+
+/// Gets the OLL case of a CLL or EG case
+fn last_layer_oll(algorithm: Algorithm) -> Option<OLLAlgorithm> {
+    match algorithm {
+        Algorithm::CLL(cll) => cll.oll(),
+        Algorithm::EG1(eg1) => eg1.oll(),
+        Algorithm::EG2(eg2) => eg2.oll(),
+        _ => None,
+    }
+}
+
+impl Cube2x2x2Analysis {
+    pub fn analyze(solve: &Cube2x2x2WithSolution) -> Option<Self> {
+        Cube2x2x2PartialAnalysis::analyze(solve).into()
+    }
+}
+
+impl Cube2x2x2PartialAnalysis {
+    pub fn analyze(solve: &Cube2x2x2WithSolution) -> Self {
+        let cases = [
+            Self::analyze_for_color(solve, Color::White),
+            Self::analyze_for_color(solve, Color::Green),
+            Self::analyze_for_color(solve, Color::Red),
+            Self::analyze_for_color(solve, Color::Blue),
+            Self::analyze_for_color(solve, Color::Orange),
+            Self::analyze_for_color(solve, Color::Yellow),
+        ];
+        let mut best: Option<Self> = None;
+        for case in cases {
+            if let Some(prev_best) = &best {
+                if case.transition_count() > prev_best.transition_count()
+                    || (case.transition_count() == prev_best.transition_count()
+                        && case.sum_of_transition_times() < prev_best.sum_of_transition_times())
+                {
+                    best = Some(case);
+                }
+            } else {
+                best = Some(case);
+            }
+        }
+        best.unwrap()
+    }
+
+    fn analyze_for_color(solve: &Cube2x2x2WithSolution, color: Color) -> Self {
+        let mut data = AnalysisData::new(solve, color);
+        for mv in &solve.solution {
+            data.do_move(mv);
+        }
+
+        Self {
+            progress: data.progress,
+            first_face: data.first_face,
+            oll: data.oll,
+            pbl: data.pbl,
+            last_layer: data.last_layer,
+            alignment: data.alignment,
+        }
+    }
+
+    /// Gets the method used for the solve so far. Returns `None` if the first face is
+    /// not yet solved. When the last layer is oriented after the first face, the solve
+    /// is counted as CLL or EG, as the rest of the solve is a single algorithm.
+    pub fn method(&self) -> Option<Cube2x2x2Method> {
+        let first_face = self.first_face.as_ref()?;
+        if !self.oll.is_empty() || !self.pbl.is_empty() {
+            return Some(Cube2x2x2Method::Ortega);
+        }
+        Some(match first_face.layer {
+            LayerPermutation::Solved => Cube2x2x2Method::CLL,
+            LayerPermutation::AdjacentSwap => Cube2x2x2Method::EG1,
+            LayerPermutation::DiagonalSwap => Cube2x2x2Method::EG2,
+        })
+    }
+}
+
+impl Cube2x2x2Method {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Cube2x2x2Method::Ortega => "Ortega",
+            Cube2x2x2Method::CLL => "CLL",
+            Cube2x2x2Method::EG1 => "EG-1",
+            Cube2x2x2Method::EG2 => "EG-2",
+        }
+    }
+}
+
+/// Steps of a full or partial 2x2x2 analysis, used to share the summary code
+struct Stages<'a> {
+    method: Option<Cube2x2x2Method>,
+    first_face: Option<&'a FirstFaceAnalysis>,
+    oll: &'a [OLLAnalysis],
+    pbl: &'a [PBLAnalysis],
+    last_layer: Option<&'a CLLAnalysis>,
+    alignment: Option<&'a FinalAlignmentAnalysis>,
+}
+
+impl<'a> Stages<'a> {
+    fn first_face_summary(&self, name: String) -> Option<AnalysisStepSummary> {
+        let first_face = self.first_face?;
+        Some(AnalysisStepSummary {
+            name,
+            short_name: "Face".into(),
+            major_step_index: 0,
+            algorithm: None,
+            recognition_time: 0,
+            execution_time: first_face.time,
+            substeps: vec![AnalysisSubstepTime::Execution(first_face.time)],
+            move_count: first_face.moves.len(),
+        })
+    }
+
+    fn oll_summary(oll: &OLLAnalysis) -> AnalysisStepSummary {
+        AnalysisStepSummary {
+            name: "OLL".into(),
+            short_name: "OLL".into(),
+            major_step_index: 1,
+            algorithm: Some(oll.performed_algorithm.to_string()),
+            recognition_time: oll.recognition_time,
+            execution_time: oll.execution_time,
+            substeps: vec![
+                AnalysisSubstepTime::Recognition(oll.recognition_time),
+                AnalysisSubstepTime::Execution(oll.execution_time),
+            ],
+            move_count: oll.moves.len(),
+        }
+    }
+
+    fn last_layer_summary(&self, last_layer: &CLLAnalysis) -> AnalysisStepSummary {
+        let name = match self.method {
+            Some(method) => method.to_str(),
+            None => "CLL",
+        };
+        AnalysisStepSummary {
+            name: name.into(),
+            short_name: name.into(),
+            major_step_index: 1,
+            algorithm: Some(match last_layer.algorithm {
+                Algorithm::CLL(cll) => cll.to_string(),
+                Algorithm::EG1(eg1) => eg1.to_string(),
+                Algorithm::EG2(eg2) => eg2.to_string(),
+                algorithm => algorithm.to_string(),
+            }),
+            recognition_time: last_layer.recognition_time,
+            execution_time: last_layer.execution_time,
+            substeps: vec![
+                AnalysisSubstepTime::Recognition(last_layer.recognition_time),
+                AnalysisSubstepTime::Execution(last_layer.execution_time),
+            ],
+            move_count: last_layer.moves.len(),
+        }
+    }
+
+    fn alignment_summary(&self, major_step_index: usize) -> Option<AnalysisStepSummary> {
+        let alignment = self.alignment?;
+        if alignment.time == 0 {
+            return None;
+        }
+        Some(AnalysisStepSummary {
+            name: "Alignment".into(),
+            short_name: "Align".into(),
+            major_step_index,
+            algorithm: None,
+            recognition_time: 0,
+            execution_time: alignment.time,
+            substeps: vec![AnalysisSubstepTime::Execution(alignment.time)],
+            move_count: alignment.moves.len(),
+        })
+    }
+
+    /// Adds the final alignment into the timing of the last step
+    fn add_alignment(&self, step: &mut AnalysisStepSummary) {
+        if let Some(alignment) = self.alignment {
+            if alignment.time > 0 {
+                step.execution_time += alignment.time;
+                step.move_count += alignment.moves.len();
+                step.substeps
+                    .push(AnalysisSubstepTime::Execution(alignment.time));
+            }
+        }
+    }
+
+    fn step_summary(&self) -> Vec<AnalysisStepSummary> {
+        let mut result = Vec::new();
+        result.extend(self.first_face_summary("First Face".into()));
+
+        // Only show the last OLL algorithm performed, as in CFOP
+        let mut oll_steps = self.oll.iter().map(Self::oll_summary);
+        if let Some(mut oll) = oll_steps.next() {
+            for step in oll_steps {
+                oll.recognition_time += step.recognition_time;
+                oll.execution_time += step.execution_time;
+                oll.move_count += step.move_count;
+                oll.substeps.extend(step.substeps);
+                oll.algorithm = step.algorithm;
+            }
+            result.push(oll);
+        }
+
+        if let Some(last_layer) = self.last_layer {
+            let mut step = self.last_layer_summary(last_layer);
+            self.add_alignment(&mut step);
+            result.push(step);
+        } else if !self.pbl.is_empty() {
+            let mut substeps = Vec::new();
+            for pbl in self.pbl {
+                substeps.push(AnalysisSubstepTime::Recognition(pbl.recognition_time));
+                substeps.push(AnalysisSubstepTime::Execution(pbl.execution_time));
+            }
+            let mut step = AnalysisStepSummary {
+                name: "PBL".into(),
+                short_name: "PBL".into(),
+                major_step_index: 2,
+                algorithm: Some(self.pbl[0].algorithm.to_string()),
+                recognition_time: self.pbl.iter().map(|pbl| pbl.recognition_time).sum(),
+                execution_time: self.pbl.iter().map(|pbl| pbl.execution_time).sum(),
+                substeps,
+                move_count: self.pbl.iter().map(|pbl| pbl.moves.len()).sum(),
+            };
+            self.add_alignment(&mut step);
+            result.push(step);
+        } else {
+            // Last step skipped, show the alignment as a separate step so that it
+            // doesn't affect algorithm statistics
+            result.extend(self.alignment_summary(2));
+        }
+
+        result
+    }
+
+    fn detailed_step_summary(&self) -> Vec<AnalysisStepSummary> {
+        let mut result = Vec::new();
+        if let Some(first_face) = self.first_face {
+            result.extend(self.first_face_summary(format!("{} Face", first_face.color.to_str())));
+        }
+        result.extend(self.oll.iter().map(Self::oll_summary));
+        if let Some(last_layer) = self.last_layer {
+            result.push(self.last_layer_summary(last_layer));
+        }
+        for pbl in self.pbl {
+            result.push(AnalysisStepSummary {
+                name: "PBL".into(),
+                short_name: "PBL".into(),
+                major_step_index: 2,
+                algorithm: Some(pbl.algorithm.to_string()),
+                recognition_time: pbl.recognition_time,
+                execution_time: pbl.execution_time,
+                substeps: vec![
+                    AnalysisSubstepTime::Recognition(pbl.recognition_time),
+                    AnalysisSubstepTime::Execution(pbl.execution_time),
+                ],
+                move_count: pbl.moves.len(),
+            });
+        }
+        result.extend(self.alignment_summary(2));
+        result
+    }
+}
+
+impl Cube2x2x2Analysis {
+    fn stages(&self) -> Stages<'_> {
+        Stages {
+            method: Some(self.method),
+            first_face: Some(&self.first_face),
+            oll: &self.oll,
+            pbl: &self.pbl,
+            last_layer: self.last_layer.as_ref(),
+            alignment: Some(&self.alignment),
+        }
+    }
+}
+
+impl Cube2x2x2PartialAnalysis {
+    fn stages(&self) -> Stages<'_> {
+        Stages {
+            method: self.method(),
+            first_face: self.first_face.as_ref(),
+            oll: &self.oll,
+            pbl: &self.pbl,
+            last_layer: self.last_layer.as_ref(),
+            alignment: self.alignment.as_ref(),
+        }
+    }
+}
+
+impl AnalysisSummary for Cube2x2x2Analysis {
+    fn step_summary(&self) -> Vec<AnalysisStepSummary> {
+        self.stages().step_summary()
+    }
+
+    fn detailed_step_summary(&self) -> Vec<AnalysisStepSummary> {
+        self.stages().detailed_step_summary()
+    }
+}
+
+impl AnalysisSummary for Cube2x2x2PartialAnalysis {
+    fn step_summary(&self) -> Vec<AnalysisStepSummary> {
+        self.stages().step_summary()
+    }
+
+    fn detailed_step_summary(&self) -> Vec<AnalysisStepSummary> {
+        self.stages().detailed_step_summary()
+    }
+}
+
+impl PartialAnalysisMethod for Cube2x2x2PartialAnalysis {
+    fn transition_count(&self) -> usize {
+        let mut count = 0;
+        if self.first_face.is_some() {
+            count += 1;
+        }
+        count += self.oll.len();
+        count += self.pbl.len();
+        if self.last_layer.is_some() {
+            count += 1;
+        }
+        if self.alignment.is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    fn sum_of_transition_times(&self) -> u32 {
+        let mut sum = 0;
+        let mut time = 0;
+        if let Some(first_face) = &self.first_face {
+            time += first_face.time;
+            sum += time;
+        }
+        for oll in &self.oll {
+            time += oll.recognition_time + oll.execution_time;
+            sum += time;
+        }
+        for pbl in &self.pbl {
+            time += pbl.recognition_time + pbl.execution_time;
+            sum += time;
+        }
+        if let Some(last_layer) = &self.last_layer {
+            time += last_layer.recognition_time + last_layer.execution_time;
+            sum += time;
+        }
+        if let Some(alignment) = &self.alignment {
+            time += alignment.time;
+            sum += time;
+        }
+        sum
+    }
+
+    fn is_complete(&self) -> bool {
+        self.progress == Cube2x2x2Progress::Solved
+    }
+
+    fn to_partial_analysis(&self) -> PartialAnalysis {
+        PartialAnalysis::Cube2x2x2(self.clone())
+    }
+}
+
+impl From<Cube2x2x2PartialAnalysis> for Option<Cube2x2x2Analysis> {
+    fn from(analysis: Cube2x2x2PartialAnalysis) -> Option<Cube2x2x2Analysis> {
+        let method = analysis.method()?;
+        Some(Cube2x2x2Analysis {
+            method,
+            first_face: analysis.first_face?,
+            oll: analysis.oll,
+            pbl: analysis.pbl,
+            last_layer: analysis.last_layer,
+            alignment: analysis.alignment?,
+        })
+    }
+}
+
+impl std::fmt::Display for Cube2x2x2PartialAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(method) = self.method() {
+            writeln!(f, "Method: {}", method.to_str())?;
+        }
+        for step in self.detailed_step_summary() {
+            match step.algorithm {
+                Some(algorithm) => writeln!(
+                    f,
+                    "{} ({}): Recognition {}ms, {} moves in {}ms",
+                    step.name,
+                    algorithm,
+                    step.recognition_time,
+                    step.move_count,
+                    step.execution_time
+                )?,
+                None => writeln!(
+                    f,
+                    "{}: {} moves in {}ms",
+                    step.name, step.move_count, step.execution_time
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
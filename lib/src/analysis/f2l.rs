@@ -0,0 +1,247 @@
+use super::last_layer::LastLayerState;
+use crate::tables::analysis::{CUBE3_F2L_CASES, CUBE3_F2L_PAIRS};
+use crate::tables::table3x3x3::CUBE3_EDGE_ADJACENCY;
+use crate::{Color, Cube3x3x3Faces, CubeFace};
+use serde::{Deserialize, Serialize};
+
+/// Position of a piece of the last F2L pair when it is in its slot instead of in the
+/// last layer
+const SLOT_POSITION: u8 = 4;
+
+/// F2L case for the last unsolved pair, numbered in the standard order. Cases 1-24
+/// have both pieces in the last layer, 25-30 have the corner in the slot, 31-36 have
+/// the edge in the slot, and 37-41 have both pieces in the slot.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct F2LAlgorithm(u8);
+
+/// State of the pieces of the unsolved F2L pair. Positions are the last layer
+/// positions of `LastLayerState`, counted clockwise from the position above the slot,
+/// or `SLOT_POSITION` if the piece is in the slot. Corner orientation is the index of
+/// the cross colored sticker on the corner, counting from the sticker on the last
+/// layer or cross face. Edge orientation is one if the sticker with the color of the
+/// first side of the slot is not where it would be when solved (on the side face in
+/// the last layer, or on the first side in the slot).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct F2LState {
+    corner: u8,
+    corner_orientation: u8,
+    edge: u8,
+    edge_orientation: u8,
+}
+
+/// Sticker indices of an F2L slot below a last layer corner position
+struct SlotStickers {
+    /// Sticker on the cross face, then the first and second sides
+    corner: [usize; 3],
+    /// Sticker on the first and second sides
+    edge: [usize; 2],
+}
+
+impl SlotStickers {
+    /// Gets the stickers of the slot below last layer corner position `pos`. The first
+    /// side of the slot is the side at `pos` and the second is the one before it.
+    fn new(cross_face: CubeFace, pos: usize) -> Self {
+        let sides = LastLayerState::side_colors(cross_face.opposite());
+        let first = sides[pos];
+        let second = sides[(pos + 3) % 4];
+        let color = |idx: usize| Cube3x3x3Faces::face_for_idx(idx).color();
+        let pair = CUBE3_F2L_PAIRS[cross_face as u8 as usize]
+            .iter()
+            .find(|pair| {
+                (color(pair[1]) == first && color(pair[3]) == second)
+                    || (color(pair[1]) == second && color(pair[3]) == first)
+            })
+            .unwrap();
+        if color(pair[1]) == first {
+            Self {
+                corner: [pair[0], pair[1], pair[3]],
+                edge: [pair[2], pair[4]],
+            }
+        } else {
+            Self {
+                corner: [pair[0], pair[3], pair[1]],
+                edge: [pair[4], pair[2]],
+            }
+        }
+    }
+}
+
+impl F2LState {
+    /// Reads the state of the unsolved F2L pair. Returns `None` if the cross is not
+    /// solved, or if there isn't exactly one unsolved pair.
+    fn from_cube(cube: &Cube3x3x3Faces, cross_face: CubeFace) -> Option<Self> {
+        let solved =
+            |idx: &usize| cube.color_by_idx(*idx) == Cube3x3x3Faces::face_for_idx(*idx).color();
+        let cross_solved = [(0, 1), (1, 0), (1, 2), (2, 1)]
+            .iter()
+            .all(|(row, col)| solved(&Cube3x3x3Faces::idx(cross_face, *row, *col)))
+            && CUBE3_EDGE_ADJACENCY[cross_face as u8 as usize]
+                .iter()
+                .all(solved);
+        if !cross_solved {
+            return None;
+        }
+
+        let mut unsolved = CUBE3_F2L_PAIRS[cross_face as u8 as usize]
+            .iter()
+            .filter(|pair| !pair.iter().all(solved));
+        let pair = unsolved.next()?;
+        if unsolved.next().is_some() {
+            return None;
+        }
+
+        // Find the last layer corner position above the unsolved slot
+        let last_layer = cross_face.opposite();
+        let sides = LastLayerState::side_colors(last_layer);
+        let pair_colors = [
+            Cube3x3x3Faces::face_for_idx(pair[1]).color(),
+            Cube3x3x3Faces::face_for_idx(pair[3]).color(),
+        ];
+        let slot = (0..4).find(|pos| {
+            pair_colors.contains(&sides[*pos]) && pair_colors.contains(&sides[(pos + 3) % 4])
+        })?;
+        let corner_colors = [cross_face.color(), sides[slot], sides[(slot + 3) % 4]];
+        let edge_colors = [sides[slot], sides[(slot + 3) % 4]];
+        let slot_stickers = SlotStickers::new(cross_face, slot);
+
+        // Corner orientation is found from the cross color, and the piece is checked
+        // using the colors of the other stickers
+        let corner = |stickers: [usize; 3]| -> Option<u8> {
+            let colors = [
+                cube.color_by_idx(stickers[0]),
+                cube.color_by_idx(stickers[1]),
+                cube.color_by_idx(stickers[2]),
+            ];
+            let orientation = colors.iter().position(|color| *color == corner_colors[0])?;
+            let mut sorted: Vec<Color> = colors.to_vec();
+            sorted.sort_by_key(|color| *color as u8);
+            let mut expected: Vec<Color> = corner_colors.to_vec();
+            expected.sort_by_key(|color| *color as u8);
+            if sorted == expected {
+                Some(orientation as u8)
+            } else {
+                None
+            }
+        };
+        let edge = |stickers: [usize; 2]| -> Option<u8> {
+            let colors = [
+                cube.color_by_idx(stickers[0]),
+                cube.color_by_idx(stickers[1]),
+            ];
+            let orientation = colors.iter().position(|color| *color == edge_colors[0])?;
+            if colors[1 - orientation] == edge_colors[1] {
+                Some(orientation as u8)
+            } else {
+                None
+            }
+        };
+
+        let mut result = None;
+        if let Some(orientation) = corner(slot_stickers.corner) {
+            result = Some((SLOT_POSITION, orientation));
+        }
+        for pos in 0..4 {
+            if let Some(orientation) = corner(LastLayerState::corner_stickers(last_layer, pos)) {
+                result = Some((((pos + 4 - slot) % 4) as u8, orientation));
+            }
+        }
+        let (corner, corner_orientation) = result?;
+
+        // Edges in the last layer are solved with the side color on the side face, so
+        // the orientation is flipped to match the slot
+        let mut result = None;
+        if let Some(orientation) = edge(slot_stickers.edge) {
+            result = Some((SLOT_POSITION, orientation));
+        }
+        for pos in 0..4 {
+            let stickers = LastLayerState::edge_stickers(last_layer, pos);
+            if let Some(orientation) = edge(stickers) {
+                result = Some((((pos + 4 - slot) % 4) as u8, 1 - orientation));
+            }
+        }
+        let (edge, edge_orientation) = result?;
+
+        Some(Self {
+            corner,
+            corner_orientation,
+            edge,
+            edge_orientation,
+        })
+    }
+
+    /// Packs the state into a key
+    fn key(&self) -> u8 {
+        (self.corner * 3 + self.corner_orientation) * 10 + self.edge * 2 + self.edge_orientation
+    }
+
+    /// Gets the state after turning the last layer `turns` times
+    fn transformed(&self, turns: u8) -> Self {
+        let turn = |pos: u8| {
+            if pos == SLOT_POSITION {
+                pos
+            } else {
+                (pos + turns) % 4
+            }
+        };
+        Self {
+            corner: turn(self.corner),
+            edge: turn(self.edge),
+            ..*self
+        }
+    }
+
+    /// Gets the key for the case this state is in. States that only differ by turns
+    /// of the last layer are the same case, so the smallest key of these states is used.
+    fn canonical_key(&self) -> u8 {
+        (0..4)
+            .map(|turns| self.transformed(turns).key())
+            .min()
+            .unwrap()
+    }
+}
+
+impl F2LAlgorithm {
+    pub fn all() -> Vec<Self> {
+        (0..CUBE3_F2L_CASES.len() as u8).map(Self).collect()
+    }
+
+    /// Recognizes the F2L case of the last unsolved pair when the cross is on
+    /// `cross_face`. Returns `None` if the cross and exactly three pairs are not solved.
+    pub fn from_cube(cube: &Cube3x3x3Faces, cross_face: CubeFace) -> Option<Self> {
+        let key = F2LState::from_cube(cube, cross_face)?.canonical_key();
+        CUBE3_F2L_CASES
+            .iter()
+            .position(|case| *case == key)
+            .map(|idx| Self(idx as u8))
+    }
+
+    pub fn from_number(n: u8) -> Option<Self> {
+        if n >= 1 && (n as usize) <= CUBE3_F2L_CASES.len() {
+            Some(Self(n - 1))
+        } else {
+            None
+        }
+    }
+
+    pub fn as_number(&self) -> u8 {
+        self.0 + 1
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE3_F2L_CASES.len() {
+            Some(Self(idx as u8))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0 as u16
+    }
+}
+
+impl std::fmt::Display for F2LAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_number())
+    }
+}
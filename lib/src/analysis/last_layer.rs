@@ -0,0 +1,546 @@
+use crate::tables::analysis::{
+    CUBE3_COLL_CASES, CUBE3_LAST_LAYER_EDGE, CUBE3_OLLCP_CASES, CUBE3_ZBLL_CASES,
+};
+use crate::{Color, Cube3x3x3, Cube3x3x3Faces, CubeFace, OLLAlgorithm, PLLAlgorithm, RandomSource};
+use serde::{Deserialize, Serialize};
+
+/// Positions of the last layer corners on the last layer face, clockwise starting
+/// from the corner between the last and first side in `CUBE3_LAST_LAYER_EDGE`
+const CORNER_FACE_POSITIONS: [(usize, usize); 4] = [(0, 0), (0, 2), (2, 2), (2, 0)];
+
+/// Positions of the last layer edges on the last layer face, in the same order as the
+/// sides in `CUBE3_LAST_LAYER_EDGE`
+const EDGE_FACE_POSITIONS: [(usize, usize); 4] = [(0, 1), (1, 2), (2, 1), (1, 0)];
+
+/// One-look last layer subset (COLL, ZBLL or OLLCP). Each subset is recognized from a different part of
+/// the last layer state.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Subset {
+    /// Corners only, edges must be oriented
+    Corners,
+    /// Full last layer, edges must be oriented
+    FullLastLayer,
+    /// Corners and edge orientation, edges must not be oriented
+    CornersAndOrientation,
+}
+
+/// State of the last layer pieces. Positions and pieces are numbered clockwise looking
+/// down on the last layer. Corner orientation is the index of the last layer colored
+/// sticker on the corner, counting from the sticker on the last layer face. Edge
+/// orientation is one if the last layer colored sticker is on the side.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) struct LastLayerState {
+    corner_permutation: [u8; 4],
+    corner_orientation: [u8; 4],
+    edge_permutation: [u8; 4],
+    edge_orientation: [u8; 4],
+}
+
+/// COLL case. These cases orient and permute the last layer corners when the edges are
+/// already oriented, leaving an edge-only PLL. Cases are named after the OLL case they
+/// belong to.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct COLLAlgorithm(u16);
+
+/// ZBLL case. These cases solve the entire last layer when the edges are already
+/// oriented. Cases are named after the OLL case they belong to, and cases where the
+/// corners are already oriented are the PLL cases.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct ZBLLAlgorithm(u16);
+
+/// OLLCP case. These cases orient the last layer and permute the corners at the same
+/// time when the edges are not oriented, leaving an edge-only PLL. Cases are named
+/// after the OLL case they belong to.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct OLLCPAlgorithm(u16);
+
+impl Subset {
+    /// Cases of the subset. Each case is the smallest key of all the states in the case,
+    /// the probability weight of the case, and the OLL number of the case (zero if the
+    /// last layer is oriented).
+    fn cases(&self) -> &'static [(u32, u8, u8)] {
+        match self {
+            Subset::Corners => &CUBE3_COLL_CASES,
+            Subset::FullLastLayer => &CUBE3_ZBLL_CASES,
+            Subset::CornersAndOrientation => &CUBE3_OLLCP_CASES,
+        }
+    }
+
+    fn find_case(&self, cube: &Cube3x3x3Faces, last_layer: CubeFace) -> Option<u16> {
+        let state = LastLayerState::from_cube(cube, last_layer)?;
+        let edges_oriented = state.edge_orientation == [0; 4];
+        if edges_oriented != (*self != Subset::CornersAndOrientation) {
+            return None;
+        }
+        let key = state.canonical_key(*self);
+        self.cases()
+            .iter()
+            .position(|(case_key, _, _)| *case_key == key)
+            .map(|idx| idx as u16)
+    }
+
+    /// Gets the number of the case within the cases for the same OLL case
+    fn number_in_group(&self, idx: u16) -> usize {
+        let cases = self.cases();
+        let group = cases[idx as usize].2;
+        cases[..idx as usize]
+            .iter()
+            .rev()
+            .take_while(|(_, _, case_group)| *case_group == group)
+            .count()
+    }
+
+    fn state(&self, idx: u16) -> LastLayerState {
+        let mut state = LastLayerState::from_key(self.cases()[idx as usize].0);
+        if *self != Subset::FullLastLayer {
+            state.edge_permutation = state.edge_permutation_for_corners(0);
+        }
+        state
+    }
+
+    fn random_state<T: RandomSource>(
+        &self,
+        rng: &mut T,
+        idx: u16,
+        last_layer: CubeFace,
+    ) -> Cube3x3x3 {
+        let mut state = LastLayerState::from_key(self.cases()[idx as usize].0);
+        if *self != Subset::FullLastLayer {
+            // Edge permutation is not part of the case, pick one at random
+            state.edge_permutation = state.edge_permutation_for_corners(rng.next(12) as usize);
+        }
+        let state = state.transformed(rng.next(4) as usize, rng.next(4) as usize);
+        state.as_faces(last_layer).as_pieces()
+    }
+}
+
+impl LastLayerState {
+    fn solved() -> Self {
+        Self {
+            corner_permutation: [0, 1, 2, 3],
+            corner_orientation: [0; 4],
+            edge_permutation: [0, 1, 2, 3],
+            edge_orientation: [0; 4],
+        }
+    }
+
+    /// Gets the sticker indices of a corner, starting with the sticker on the last layer
+    /// face and continuing around the corner
+    pub(super) fn corner_stickers(last_layer: CubeFace, pos: usize) -> [usize; 3] {
+        let edges = &CUBE3_LAST_LAYER_EDGE[last_layer as u8 as usize];
+        let (row, col) = CORNER_FACE_POSITIONS[pos];
+        [
+            Cube3x3x3Faces::idx(last_layer, row, col),
+            edges[pos].idx(0),
+            edges[(pos + 3) % 4].idx(2),
+        ]
+    }
+
+    /// Gets the sticker indices of an edge, starting with the sticker on the last layer
+    /// face
+    pub(super) fn edge_stickers(last_layer: CubeFace, pos: usize) -> [usize; 2] {
+        let edges = &CUBE3_LAST_LAYER_EDGE[last_layer as u8 as usize];
+        let (row, col) = EDGE_FACE_POSITIONS[pos];
+        [Cube3x3x3Faces::idx(last_layer, row, col), edges[pos].idx(1)]
+    }
+
+    pub(super) fn side_colors(last_layer: CubeFace) -> [Color; 4] {
+        let edges = &CUBE3_LAST_LAYER_EDGE[last_layer as u8 as usize];
+        let mut result = [last_layer.color(); 4];
+        for (side, edge) in edges.iter().enumerate() {
+            result[side] = Cube3x3x3Faces::face_for_idx(edge.idx(1)).color();
+        }
+        result
+    }
+
+    /// Reads the state of the last layer. Returns `None` if the pieces in the last layer
+    /// don't belong there.
+    pub(super) fn from_cube(cube: &Cube3x3x3Faces, last_layer: CubeFace) -> Option<Self> {
+        let top = last_layer.color();
+        let sides = Self::side_colors(last_layer);
+        let side_index = |color: Color| sides.iter().position(|side| *side == color);
+
+        let mut result = Self::solved();
+        for pos in 0..4 {
+            let stickers = Self::corner_stickers(last_layer, pos);
+            let colors = [
+                cube.color_by_idx(stickers[0]),
+                cube.color_by_idx(stickers[1]),
+                cube.color_by_idx(stickers[2]),
+            ];
+            let orientation = colors.iter().position(|color| *color == top)?;
+            let piece = side_index(colors[(orientation + 1) % 3])?;
+            if colors[(orientation + 2) % 3] != sides[(piece + 3) % 4] {
+                return None;
+            }
+            result.corner_permutation[pos] = piece as u8;
+            result.corner_orientation[pos] = orientation as u8;
+
+            let stickers = Self::edge_stickers(last_layer, pos);
+            let colors = [
+                cube.color_by_idx(stickers[0]),
+                cube.color_by_idx(stickers[1]),
+            ];
+            let orientation = colors.iter().position(|color| *color == top)?;
+            result.edge_permutation[pos] = side_index(colors[1 - orientation])? as u8;
+            result.edge_orientation[pos] = orientation as u8;
+        }
+        Some(result)
+    }
+
+    /// Gets a cube with the last layer in this state and all other pieces solved
+    fn as_faces(&self, last_layer: CubeFace) -> Cube3x3x3Faces {
+        let mut colors = [last_layer.color(); 6 * 9];
+        for (idx, color) in colors.iter_mut().enumerate() {
+            *color = Cube3x3x3Faces::face_for_idx(idx).color();
+        }
+
+        let top = last_layer.color();
+        let sides = Self::side_colors(last_layer);
+        for pos in 0..4 {
+            let piece = self.corner_permutation[pos] as usize;
+            let piece_colors = [top, sides[piece], sides[(piece + 3) % 4]];
+            let stickers = Self::corner_stickers(last_layer, pos);
+            for (idx, color) in piece_colors.iter().enumerate() {
+                colors[stickers[(idx + self.corner_orientation[pos] as usize) % 3]] = *color;
+            }
+
+            let piece = self.edge_permutation[pos] as usize;
+            let piece_colors = [top, sides[piece]];
+            let stickers = Self::edge_stickers(last_layer, pos);
+            for (idx, color) in piece_colors.iter().enumerate() {
+                colors[stickers[(idx + self.edge_orientation[pos] as usize) % 2]] = *color;
+            }
+        }
+        Cube3x3x3Faces::from_colors(colors)
+    }
+
+    /// Packs the parts of the state that the subset is recognized from into a key. Each
+    /// position uses 7 bits: corner permutation, corner orientation, edge permutation,
+    /// and edge orientation.
+    fn key(&self, subset: Subset) -> u32 {
+        let mut key = 0;
+        for pos in 0..4 {
+            key <<= 7;
+            key |= (self.corner_permutation[pos] as u32) << 5;
+            key |= (self.corner_orientation[pos] as u32) << 3;
+            if subset == Subset::FullLastLayer {
+                key |= (self.edge_permutation[pos] as u32) << 1;
+            }
+            if subset == Subset::CornersAndOrientation {
+                key |= self.edge_orientation[pos] as u32;
+            }
+        }
+        key
+    }
+
+    /// Unpacks a key. Parts of the state that are not in the key are left as zero.
+    fn from_key(key: u32) -> Self {
+        let mut result = Self::solved();
+        for pos in 0..4 {
+            let bits = key >> ((3 - pos) * 7);
+            result.corner_permutation[pos] = ((bits >> 5) & 3) as u8;
+            result.corner_orientation[pos] = ((bits >> 3) & 3) as u8;
+            result.edge_permutation[pos] = ((bits >> 1) & 3) as u8;
+            result.edge_orientation[pos] = (bits & 1) as u8;
+        }
+        result
+    }
+
+    /// Gets the state after turning the last layer `pre` times before the case, and
+    /// `post` times after solving it
+    fn transformed(&self, pre: usize, post: usize) -> Self {
+        let mut result = *self;
+        for pos in 0..4 {
+            let new_pos = (pos + pre) % 4;
+            result.corner_permutation[new_pos] =
+                ((self.corner_permutation[pos] as usize + post) % 4) as u8;
+            result.corner_orientation[new_pos] = self.corner_orientation[pos];
+            result.edge_permutation[new_pos] =
+                ((self.edge_permutation[pos] as usize + post) % 4) as u8;
+            result.edge_orientation[new_pos] = self.edge_orientation[pos];
+        }
+        result
+    }
+
+    /// Gets the key for the case this state is in. States that only differ by turns of
+    /// the last layer before or after the case are the same case, so the smallest key
+    /// of all of these states is used.
+    fn canonical_key(&self, subset: Subset) -> u32 {
+        let mut result = u32::MAX;
+        for pre in 0..4 {
+            for post in 0..4 {
+                result = result.min(self.transformed(pre, post).key(subset));
+            }
+        }
+        result
+    }
+
+    /// Gets the number of quarter turns of the last layer between the angle this state
+    /// is seen from and the reference angle of its case, which is the angle with the
+    /// smallest key. Only the orientation of the pieces is compared if
+    /// `orientation_only` is set, as OLL cases are recognized without the permutation.
+    /// Angles that look the same because the case is symmetric are counted as the same
+    /// angle.
+    pub(super) fn viewing_angle(&self, orientation_only: bool) -> u8 {
+        let view_key = |state: &Self| {
+            let mut key = 0;
+            for pos in 0..4 {
+                key <<= 7;
+                if !orientation_only {
+                    key |= (state.corner_permutation[pos] as u32) << 5;
+                    key |= (state.edge_permutation[pos] as u32) << 1;
+                }
+                key |= (state.corner_orientation[pos] as u32) << 3;
+                key |= state.edge_orientation[pos] as u32;
+            }
+            key
+        };
+
+        // Turns of the last layer after the case only change the colors seen, which
+        // don't matter for recognition
+        let angle_key = |pre: usize| {
+            (0..4)
+                .map(|post| view_key(&self.transformed(pre, post)))
+                .min()
+                .unwrap()
+        };
+        let reference = (0..4).map(angle_key).min().unwrap();
+        (0..4).find(|pre| angle_key(*pre) == reference).unwrap() as u8
+    }
+
+    fn is_odd_permutation(permutation: &[u8; 4]) -> bool {
+        let mut inversions = 0;
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                if permutation[i] > permutation[j] {
+                    inversions += 1;
+                }
+            }
+        }
+        inversions % 2 == 1
+    }
+
+    /// Gets the `n`th (modulo 12) edge permutation that is possible with the current
+    /// corner permutation
+    fn edge_permutation_for_corners(&self, n: usize) -> [u8; 4] {
+        let odd = Self::is_odd_permutation(&self.corner_permutation);
+        let mut permutations = Vec::new();
+        for idx in 0..24 {
+            let mut remaining = vec![0, 1, 2, 3];
+            let mut permutation = [0; 4];
+            let mut rest = idx;
+            for (pos, piece) in permutation.iter_mut().enumerate() {
+                let choice = rest % (4 - pos);
+                rest /= 4 - pos;
+                *piece = remaining.remove(choice);
+            }
+            if Self::is_odd_permutation(&permutation) == odd {
+                permutations.push(permutation);
+            }
+        }
+        permutations[n % permutations.len()]
+    }
+}
+
+impl COLLAlgorithm {
+    /// Total weight of all last layer states with oriented edges, including the states
+    /// where the corners are already oriented that are not part of COLL
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 162;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE3_COLL_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the COLL case of the last layer. Returns `None` if the edges are not
+    /// oriented or the corners are already oriented.
+    pub fn from_cube(cube: &Cube3x3x3Faces, last_layer: CubeFace) -> Option<Self> {
+        Subset::Corners.find_case(cube, last_layer).map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE3_COLL_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of
+    pub fn oll(&self) -> OLLAlgorithm {
+        OLLAlgorithm::from_number(CUBE3_COLL_CASES[self.0 as usize].2)
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE3_COLL_CASES[self.0 as usize].1 as usize
+    }
+
+    /// Gets a cube in this case with the given last layer. The rest of the cube is solved.
+    pub fn as_faces(&self, last_layer: CubeFace) -> Cube3x3x3Faces {
+        Subset::Corners.state(self.0).as_faces(last_layer)
+    }
+
+    pub(crate) fn random_state<T: RandomSource>(
+        &self,
+        rng: &mut T,
+        last_layer: CubeFace,
+    ) -> Cube3x3x3 {
+        Subset::Corners.random_state(rng, self.0, last_layer)
+    }
+}
+
+impl ZBLLAlgorithm {
+    /// Total weight of all last layer states with oriented edges, including the solved
+    /// state
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 1944;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE3_ZBLL_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the ZBLL case of the last layer. Returns `None` if the edges are not
+    /// oriented or the last layer is solved.
+    pub fn from_cube(cube: &Cube3x3x3Faces, last_layer: CubeFace) -> Option<Self> {
+        Subset::FullLastLayer.find_case(cube, last_layer).map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE3_ZBLL_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of, or `None` if this is a PLL case
+    pub fn oll(&self) -> Option<OLLAlgorithm> {
+        match CUBE3_ZBLL_CASES[self.0 as usize].2 {
+            0 => None,
+            oll => Some(OLLAlgorithm::from_number(oll)),
+        }
+    }
+
+    /// PLL case that this case is, if the corners are already oriented
+    pub fn pll(&self) -> Option<PLLAlgorithm> {
+        match self.oll() {
+            Some(_) => None,
+            None => PLLAlgorithm::from_cube(&self.as_faces(CubeFace::Top), CubeFace::Top),
+        }
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE3_ZBLL_CASES[self.0 as usize].1 as usize
+    }
+
+    /// Gets a cube in this case with the given last layer. The rest of the cube is solved.
+    pub fn as_faces(&self, last_layer: CubeFace) -> Cube3x3x3Faces {
+        Subset::FullLastLayer.state(self.0).as_faces(last_layer)
+    }
+
+    pub(crate) fn random_state<T: RandomSource>(
+        &self,
+        rng: &mut T,
+        last_layer: CubeFace,
+    ) -> Cube3x3x3 {
+        Subset::FullLastLayer.random_state(rng, self.0, last_layer)
+    }
+}
+
+impl OLLCPAlgorithm {
+    /// Total weight of all last layer states where the edges are not oriented
+    pub const TOTAL_PROBABILITY_WEIGHT: usize = 1134;
+
+    pub fn all() -> Vec<Self> {
+        (0..CUBE3_OLLCP_CASES.len() as u16).map(Self).collect()
+    }
+
+    /// Recognizes the OLLCP case of the last layer. Returns `None` if the edges are
+    /// oriented.
+    pub fn from_cube(cube: &Cube3x3x3Faces, last_layer: CubeFace) -> Option<Self> {
+        Subset::CornersAndOrientation
+            .find_case(cube, last_layer)
+            .map(Self)
+    }
+
+    pub(crate) fn from_index(idx: u16) -> Option<Self> {
+        if (idx as usize) < CUBE3_OLLCP_CASES.len() {
+            Some(Self(idx))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn to_index(self) -> u16 {
+        self.0
+    }
+
+    /// OLL case that this case is a part of
+    pub fn oll(&self) -> OLLAlgorithm {
+        OLLAlgorithm::from_number(CUBE3_OLLCP_CASES[self.0 as usize].2)
+    }
+
+    pub fn probability_weight(&self) -> usize {
+        CUBE3_OLLCP_CASES[self.0 as usize].1 as usize
+    }
+
+    /// Gets a cube in this case with the given last layer. The rest of the cube is solved.
+    pub fn as_faces(&self, last_layer: CubeFace) -> Cube3x3x3Faces {
+        Subset::CornersAndOrientation
+            .state(self.0)
+            .as_faces(last_layer)
+    }
+
+    pub(crate) fn random_state<T: RandomSource>(
+        &self,
+        rng: &mut T,
+        last_layer: CubeFace,
+    ) -> Cube3x3x3 {
+        Subset::CornersAndOrientation.random_state(rng, self.0, last_layer)
+    }
+}
+
+impl std::fmt::Display for COLLAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.oll().to_string(),
+            Subset::Corners.number_in_group(self.0) + 1
+        )
+    }
+}
+
+impl std::fmt::Display for ZBLLAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.oll(), self.pll()) {
+            (Some(oll), _) => write!(
+                f,
+                "{} {}",
+                oll.to_string(),
+                Subset::FullLastLayer.number_in_group(self.0) + 1
+            ),
+            (None, Some(pll)) => write!(f, "{}", pll.to_str()),
+            (None, None) => write!(f, "?"),
+        }
+    }
+}
+
+impl std::fmt::Display for OLLCPAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Cases within an OLL case are lettered, as OLL cases are already numbered
+        write!(
+            f,
+            "{}{}",
+            self.oll().to_string(),
+            (b'a' + Subset::CornersAndOrientation.number_in_group(self.0) as u8) as char
+        )
+    }
+}
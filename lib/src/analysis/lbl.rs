@@ -0,0 +1,843 @@
+use crate::tables::analysis::{CUBE3_F2L_PAIRS, CUBE3_LAST_LAYER_EDGE};
+use crate::tables::table3x3x3::CUBE3_EDGE_ADJACENCY;
+use crate::{
+    cube3x3x3::FaceRowOrColumn, AnalysisStepSummary, AnalysisSubstepTime, AnalysisSummary, Color,
+    CrossAnalysis, Cube, Cube3x3x3Faces, CubeFace, CubeWithSolution, FinalAlignmentAnalysis, Move,
+    OLLAlgorithm, PLLAlgorithm, PartialAnalysis, PartialAnalysisMethod, TimedMove,
+};
+use serde::{Deserialize, Serialize};
+
+/// Analysis of a full solve using the beginner layer-by-layer method. The first
+/// layer is solved as a cross followed by the corners, then the second layer edges
+/// are inserted, and the last layer is solved in four steps: orienting the edges
+/// into a cross, orienting the corners, permuting the corners, and permuting the
+/// edges.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LBLAnalysis {
+    pub cross: CrossAnalysis,
+    pub first_layer_corners: Vec<LBLStepAnalysis>,
+    pub second_layer_edges: Vec<LBLStepAnalysis>,
+    pub last_layer_cross: Vec<LBLStepAnalysis>,
+    pub last_layer_face: Vec<LBLStepAnalysis>,
+    pub corner_permutation: Vec<LBLStepAnalysis>,
+    pub edge_permutation: Vec<LBLStepAnalysis>,
+    pub alignment: FinalAlignmentAnalysis,
+}
+
+/// Partial analysis of a cube solution using the layer-by-layer method. This
+/// analysis can be performed on an incomplete solve to get the current progress.
+#[derive(Clone)]
+pub struct LBLPartialAnalysis {
+    pub progress: LBLProgress,
+    pub cross: Option<CrossAnalysis>,
+    pub first_layer_corners: Vec<LBLStepAnalysis>,
+    pub second_layer_edges: Vec<LBLStepAnalysis>,
+    pub last_layer_cross: Vec<LBLStepAnalysis>,
+    pub last_layer_face: Vec<LBLStepAnalysis>,
+    pub corner_permutation: Vec<LBLStepAnalysis>,
+    pub edge_permutation: Vec<LBLStepAnalysis>,
+    pub alignment: Option<FinalAlignmentAnalysis>,
+}
+
+pub trait LBLAnalysisStages {
+    fn cross(&self) -> Option<&CrossAnalysis>;
+    fn first_layer_corners(&self) -> &[LBLStepAnalysis];
+    fn second_layer_edges(&self) -> &[LBLStepAnalysis];
+    fn last_layer_cross(&self) -> &[LBLStepAnalysis];
+    fn last_layer_face(&self) -> &[LBLStepAnalysis];
+    fn corner_permutation(&self) -> &[LBLStepAnalysis];
+    fn edge_permutation(&self) -> &[LBLStepAnalysis];
+    fn alignment(&self) -> Option<&FinalAlignmentAnalysis>;
+}
+
+/// Analysis of a single step of a layer-by-layer solution. The first layer corners
+/// and second layer edges have one of these per piece inserted. The last layer
+/// steps have one of these per algorithm performed, so there may be more than one
+/// if an algorithm must be repeated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LBLStepAnalysis {
+    /// Time spent recognizing the state
+    pub recognition_time: u32,
+    /// Time spent executing the step
+    pub execution_time: u32,
+    /// Move index of the start of this step
+    pub start_move_index: usize,
+    /// Moves performed
+    pub moves: Vec<Move>,
+    /// For first layer corners, whether the corner was inserted along with its
+    /// second layer edge as an F2L pair
+    pub paired: bool,
+}
+
+/// State of the cube as it's being solved with the layer-by-layer method
+#[derive(Clone, PartialEq, Eq)]
+pub enum LBLProgress {
+    /// No progress on solve
+    Initial,
+    /// Cross on first layer complete, solving first layer corners. Current number
+    /// of corners inserted is given.
+    FirstLayerCorners(usize),
+    /// First layer is solved, solving second layer edges. Current number of edges
+    /// inserted is given.
+    SecondLayerEdges(usize),
+    /// First two layers are solved, orienting last layer edges. Current OLL case
+    /// is given.
+    LastLayerCross(OLLAlgorithm),
+    /// Last layer edges are oriented, orienting last layer corners. Current OLL
+    /// case is given.
+    LastLayerFace(OLLAlgorithm),
+    /// Last layer is oriented, permuting last layer corners. Current PLL case
+    /// is given.
+    CornerPermutation(PLLAlgorithm),
+    /// Last layer corners are permuted, permuting last layer edges. Current PLL
+    /// case is given.
+    EdgePermutation(PLLAlgorithm),
+    /// All layers are solved but last layer is not yet aligned
+    FinalAlignment,
+    /// Cube is solved
+    Solved,
+}
+
+struct AnalysisData {
+    progress: LBLProgress,
+    state_start_time: u32,
+    state_start_index: usize,
+    state_recognition_time: Option<u32>,
+    state_moves: Vec<Move>,
+    state_pair_count: usize,
+    total_moves: usize,
+    cube: Cube3x3x3Faces,
+    cross_color: Color,
+    cross_face: CubeFace,
+    cross_analysis: Option<CrossAnalysis>,
+    first_layer_corners: Vec<LBLStepAnalysis>,
+    second_layer_edges: Vec<LBLStepAnalysis>,
+    last_layer_cross: Vec<LBLStepAnalysis>,
+    last_layer_face: Vec<LBLStepAnalysis>,
+    corner_permutation: Vec<LBLStepAnalysis>,
+    edge_permutation: Vec<LBLStepAnalysis>,
+    alignment: Option<FinalAlignmentAnalysis>,
+    time: u32,
+}
+
+impl LBLAnalysis {
+    pub fn analyze(solve: &CubeWithSolution) -> Option<Self> {
+        LBLPartialAnalysis::analyze(solve).into()
+    }
+}
+
+impl AnalysisData {
+    fn new(solve: &CubeWithSolution, cross_color: Color) -> Self {
+        let mut result = Self {
+            progress: LBLProgress::Initial,
+            state_start_time: 0,
+            state_start_index: 0,
+            state_recognition_time: None,
+            state_moves: Vec::new(),
+            state_pair_count: 0,
+            total_moves: 0,
+            cube: solve.initial_state.as_faces(),
+            cross_color,
+            cross_face: cross_color.face(),
+            cross_analysis: None,
+            first_layer_corners: Vec::new(),
+            second_layer_edges: Vec::new(),
+            last_layer_cross: Vec::new(),
+            last_layer_face: Vec::new(),
+            corner_permutation: Vec::new(),
+            edge_permutation: Vec::new(),
+            alignment: None,
+            time: 0,
+        };
+        result.check_for_state_transitions();
+        result
+    }
+
+    fn new_state(&mut self, state: LBLProgress) {
+        self.progress = state;
+        self.state_start_time = self.time;
+        self.state_start_index = self.total_moves;
+        self.state_recognition_time = None;
+        self.state_moves.clear();
+        self.state_pair_count = self.f2l_pair_count();
+    }
+
+    fn piece_solved(&self, idx: usize) -> bool {
+        self.cube.color_by_idx(idx) == Cube3x3x3Faces::face_for_idx(idx).color()
+    }
+
+    fn cross_solved(&self) -> bool {
+        let cross_edges = &CUBE3_EDGE_ADJACENCY[self.cross_face as u8 as usize];
+        self.cube.color(self.cross_face, 0, 1) == self.cross_face.color()
+            && self.cube.color(self.cross_face, 1, 0) == self.cross_face.color()
+            && self.cube.color(self.cross_face, 1, 2) == self.cross_face.color()
+            && self.cube.color(self.cross_face, 2, 1) == self.cross_face.color()
+            && cross_edges.iter().all(|idx| self.piece_solved(*idx))
+    }
+
+    fn first_layer_corner_count(&self) -> usize {
+        CUBE3_F2L_PAIRS[self.cross_face as u8 as usize]
+            .iter()
+            .filter(|pair| {
+                self.piece_solved(pair[0])
+                    && self.piece_solved(pair[1])
+                    && self.piece_solved(pair[3])
+            })
+            .count()
+    }
+
+    fn second_layer_edge_count(&self) -> usize {
+        CUBE3_F2L_PAIRS[self.cross_face as u8 as usize]
+            .iter()
+            .filter(|pair| self.piece_solved(pair[2]) && self.piece_solved(pair[4]))
+            .count()
+    }
+
+    fn f2l_pair_count(&self) -> usize {
+        CUBE3_F2L_PAIRS[self.cross_face as u8 as usize]
+            .iter()
+            .filter(|pair| pair.iter().all(|idx| self.piece_solved(*idx)))
+            .count()
+    }
+
+    fn first_layer_solved(&self) -> bool {
+        self.cross_solved() && self.first_layer_corner_count() == 4
+    }
+
+    fn first_two_layers_solved(&self) -> bool {
+        self.first_layer_solved() && self.second_layer_edge_count() == 4
+    }
+
+    fn last_layer_edges_oriented(&self) -> bool {
+        let face = self.cross_face.opposite();
+        self.cube.color(face, 0, 1) == face.color()
+            && self.cube.color(face, 1, 0) == face.color()
+            && self.cube.color(face, 1, 2) == face.color()
+            && self.cube.color(face, 2, 1) == face.color()
+    }
+
+    fn last_layer_oriented(&self) -> bool {
+        let face = self.cross_face.opposite();
+        for row in 0..3 {
+            for col in 0..3 {
+                if self.cube.color(face, row, col) != face.color() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn last_layer_corners_permuted(&self) -> bool {
+        // The corners are permuted when the two corner stickers on each side of the
+        // last layer match, regardless of the edge between them.
+        let face = self.cross_face.opposite();
+        for edge in &CUBE3_LAST_LAYER_EDGE[face as u8 as usize] {
+            match edge {
+                FaceRowOrColumn::RowLeftToRight(face, row)
+                | FaceRowOrColumn::RowRightToLeft(face, row) => {
+                    if self.cube.color(*face, *row, 0) != self.cube.color(*face, *row, 2) {
+                        return false;
+                    }
+                }
+                FaceRowOrColumn::ColumnTopDown(face, col)
+                | FaceRowOrColumn::ColumnBottomUp(face, col) => {
+                    if self.cube.color(*face, 0, *col) != self.cube.color(*face, 2, *col) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Gets the progress state for the current cube state, starting from the first
+    /// layer corners. The cross must be solved.
+    fn first_layer_progress(&self) -> LBLProgress {
+        let corners = self.first_layer_corner_count();
+        if corners < 4 {
+            LBLProgress::FirstLayerCorners(corners)
+        } else {
+            self.second_layer_progress()
+        }
+    }
+
+    /// Gets the progress state for the current cube state, starting from the second
+    /// layer edges. The first layer must be solved.
+    fn second_layer_progress(&self) -> LBLProgress {
+        let edges = self.second_layer_edge_count();
+        if edges < 4 {
+            LBLProgress::SecondLayerEdges(edges)
+        } else {
+            self.last_layer_progress()
+        }
+    }
+
+    /// Gets the progress state for the current cube state within the last layer. The
+    /// first two layers must be solved.
+    fn last_layer_progress(&self) -> LBLProgress {
+        let last_layer = self.cross_face.opposite();
+        if self.last_layer_oriented() {
+            match PLLAlgorithm::from_cube(&self.cube, last_layer) {
+                None => LBLProgress::FinalAlignment,
+                Some(pll) => {
+                    if self.last_layer_corners_permuted() {
+                        LBLProgress::EdgePermutation(pll)
+                    } else {
+                        LBLProgress::CornerPermutation(pll)
+                    }
+                }
+            }
+        } else {
+            let oll = OLLAlgorithm::from_cube(&self.cube, last_layer).unwrap();
+            if self.last_layer_edges_oriented() {
+                LBLProgress::LastLayerFace(oll)
+            } else {
+                LBLProgress::LastLayerCross(oll)
+            }
+        }
+    }
+
+    fn step_analysis(&self, paired: bool) -> LBLStepAnalysis {
+        let recognition_time = self.state_recognition_time.unwrap_or(0);
+        LBLStepAnalysis {
+            recognition_time,
+            execution_time: self.time - self.state_start_time - recognition_time,
+            start_move_index: self.state_start_index,
+            moves: self.state_moves.clone(),
+            paired,
+        }
+    }
+
+    fn check_for_single_state_transition(&mut self) {
+        if self.total_moves > self.state_start_index && self.state_recognition_time.is_none() {
+            self.state_recognition_time = Some(self.time - self.state_start_time);
+        }
+        match self.progress.clone() {
+            LBLProgress::Initial => {
+                if self.cross_solved() {
+                    self.cross_analysis = Some(CrossAnalysis {
+                        color: self.cross_color,
+                        time: self.time - self.state_start_time,
+                        moves: self.state_moves.clone(),
+                    });
+                    self.new_state(self.first_layer_progress());
+                }
+            }
+            LBLProgress::FirstLayerCorners(count) => {
+                if self.cross_solved() && self.first_layer_corner_count() > count {
+                    if !self.state_moves.is_empty() {
+                        // Corners inserted with their edge are F2L pairs, which are
+                        // a sign that the solve is not really a layer-by-layer solve
+                        let paired = self.f2l_pair_count() > self.state_pair_count;
+                        let step = self.step_analysis(paired);
+                        self.first_layer_corners.push(step);
+                    }
+                    self.new_state(self.first_layer_progress());
+                }
+            }
+            LBLProgress::SecondLayerEdges(count) => {
+                if self.first_layer_solved() && self.second_layer_edge_count() > count {
+                    if !self.state_moves.is_empty() {
+                        let step = self.step_analysis(false);
+                        self.second_layer_edges.push(step);
+                    }
+                    self.new_state(self.second_layer_progress());
+                }
+            }
+            LBLProgress::LastLayerCross(_)
+            | LBLProgress::LastLayerFace(_)
+            | LBLProgress::CornerPermutation(_)
+            | LBLProgress::EdgePermutation(_) => {
+                if !self.first_two_layers_solved() {
+                    return;
+                }
+
+                // Record a step whenever the last layer case changes, either by
+                // completing the step or by performing an algorithm that leaves
+                // the step in a different case.
+                let new_progress = self.last_layer_progress();
+                if new_progress != self.progress {
+                    if !self.state_moves.is_empty() {
+                        let step = self.step_analysis(false);
+                        match self.progress {
+                            LBLProgress::LastLayerCross(_) => self.last_layer_cross.push(step),
+                            LBLProgress::LastLayerFace(_) => self.last_layer_face.push(step),
+                            LBLProgress::CornerPermutation(_) => self.corner_permutation.push(step),
+                            _ => self.edge_permutation.push(step),
+                        }
+                    }
+                    self.new_state(new_progress);
+                }
+            }
+            LBLProgress::FinalAlignment => {
+                if self.cube.is_solved() {
+                    self.alignment = Some(FinalAlignmentAnalysis {
+                        time: self.time - self.state_start_time,
+                        start_move_index: self.state_start_index,
+                        moves: self.state_moves.clone(),
+                    });
+                    self.new_state(LBLProgress::Solved);
+                }
+            }
+            LBLProgress::Solved => (),
+        }
+    }
+
+    fn check_for_state_transitions(&mut self) {
+        // Perform state transitions until no change
+        loop {
+            let before = self.progress.clone();
+            self.check_for_single_state_transition();
+            if self.progress == before {
+                return;
+            }
+        }
+    }
+
+    fn do_move(&mut self, timed_move: &TimedMove) {
+        self.cube.do_move(timed_move.move_());
+        self.time = timed_move.time();
+        self.total_moves += 1;
+        self.state_moves.push(timed_move.move_());
+        self.check_for_state_transitions();
+    }
+}
+
+impl LBLPartialAnalysis {
+    pub fn analyze(solve: &CubeWithSolution) -> Self {
+        let cases = [
+            Self::analyze_for_cross_color(solve, Color::White),
+            Self::analyze_for_cross_color(solve, Color::Green),
+            Self::analyze_for_cross_color(solve, Color::Red),
+            Self::analyze_for_cross_color(solve, Color::Blue),
+            Self::analyze_for_cross_color(solve, Color::Orange),
+            Self::analyze_for_cross_color(solve, Color::Yellow),
+        ];
+        let mut best: Option<Self> = None;
+        for case in cases {
+            if let Some(prev_best) = &best {
+                if case.transition_count() > prev_best.transition_count()
+                    || (case.transition_count() == prev_best.transition_count()
+                        && case.sum_of_transition_times() < prev_best.sum_of_transition_times())
+                {
+                    best = Some(case);
+                }
+            } else {
+                best = Some(case);
+            }
+        }
+        best.unwrap()
+    }
+
+    fn analyze_for_cross_color(solve: &CubeWithSolution, cross_color: Color) -> Self {
+        let mut data = AnalysisData::new(solve, cross_color);
+        for mv in &solve.solution {
+            data.do_move(mv);
+        }
+
+        Self {
+            progress: data.progress,
+            cross: data.cross_analysis,
+            first_layer_corners: data.first_layer_corners,
+            second_layer_edges: data.second_layer_edges,
+            last_layer_cross: data.last_layer_cross,
+            last_layer_face: data.last_layer_face,
+            corner_permutation: data.corner_permutation,
+            edge_permutation: data.edge_permutation,
+            alignment: data.alignment,
+        }
+    }
+
+    /// Gets the color of the last layer, if the cross has been solved
+    fn last_layer_color(&self) -> Option<Color> {
+        self.cross
+            .as_ref()
+            .map(|cross| cross.color.face().opposite().color())
+    }
+
+    /// Steps of the analysis that are counted as transitions, in solve order.
+    /// Corners that were inserted as F2L pairs are not counted, as they are not
+    /// a separate step of a layer-by-layer solve.
+    fn transitions(&self) -> impl Iterator<Item = &LBLStepAnalysis> {
+        self.first_layer_corners
+            .iter()
+            .filter(|corner| !corner.paired)
+            .chain(self.second_layer_edges.iter())
+            .chain(self.last_layer_cross.iter())
+            .chain(self.last_layer_face.iter())
+            .chain(self.corner_permutation.iter())
+            .chain(self.edge_permutation.iter())
+    }
+}
+
+impl LBLAnalysisStages for LBLAnalysis {
+    fn cross(&self) -> Option<&CrossAnalysis> {
+        Some(&self.cross)
+    }
+
+    fn first_layer_corners(&self) -> &[LBLStepAnalysis] {
+        &self.first_layer_corners
+    }
+
+    fn second_layer_edges(&self) -> &[LBLStepAnalysis] {
+        &self.second_layer_edges
+    }
+
+    fn last_layer_cross(&self) -> &[LBLStepAnalysis] {
+        &self.last_layer_cross
+    }
+
+    fn last_layer_face(&self) -> &[LBLStepAnalysis] {
+        &self.last_layer_face
+    }
+
+    fn corner_permutation(&self) -> &[LBLStepAnalysis] {
+        &self.corner_permutation
+    }
+
+    fn edge_permutation(&self) -> &[LBLStepAnalysis] {
+        &self.edge_permutation
+    }
+
+    fn alignment(&self) -> Option<&FinalAlignmentAnalysis> {
+        Some(&self.alignment)
+    }
+}
+
+impl LBLAnalysisStages for LBLPartialAnalysis {
+    fn cross(&self) -> Option<&CrossAnalysis> {
+        self.cross.as_ref()
+    }
+
+    fn first_layer_corners(&self) -> &[LBLStepAnalysis] {
+        &self.first_layer_corners
+    }
+
+    fn second_layer_edges(&self) -> &[LBLStepAnalysis] {
+        &self.second_layer_edges
+    }
+
+    fn last_layer_cross(&self) -> &[LBLStepAnalysis] {
+        &self.last_layer_cross
+    }
+
+    fn last_layer_face(&self) -> &[LBLStepAnalysis] {
+        &self.last_layer_face
+    }
+
+    fn corner_permutation(&self) -> &[LBLStepAnalysis] {
+        &self.corner_permutation
+    }
+
+    fn edge_permutation(&self) -> &[LBLStepAnalysis] {
+        &self.edge_permutation
+    }
+
+    fn alignment(&self) -> Option<&FinalAlignmentAnalysis> {
+        self.alignment.as_ref()
+    }
+}
+
+/// Names of the steps of a layer-by-layer solve after the cross, along with their
+/// short names and major step index
+const LBL_STEP_NAMES: [(&str, &str, usize); 6] = [
+    ("First Layer Corner", "Corner", 1),
+    ("Second Layer Edge", "Edge", 2),
+    ("Last Layer Cross", "LL Cross", 3),
+    ("Last Layer Face", "LL Face", 4),
+    ("Corner Permutation", "CP", 5),
+    ("Edge Permutation", "EP", 6),
+];
+
+fn lbl_steps<T: LBLAnalysisStages>(analysis: &T) -> [&[LBLStepAnalysis]; 6] {
+    [
+        analysis.first_layer_corners(),
+        analysis.second_layer_edges(),
+        analysis.last_layer_cross(),
+        analysis.last_layer_face(),
+        analysis.corner_permutation(),
+        analysis.edge_permutation(),
+    ]
+}
+
+fn lbl_step_summary(
+    name: String,
+    short_name: &str,
+    major_step_index: usize,
+    step: &LBLStepAnalysis,
+) -> AnalysisStepSummary {
+    AnalysisStepSummary {
+        name,
+        short_name: short_name.into(),
+        major_step_index,
+        algorithm: None,
+        recognition_time: step.recognition_time,
+        execution_time: step.execution_time,
+        substeps: vec![
+            AnalysisSubstepTime::Recognition(step.recognition_time),
+            AnalysisSubstepTime::Execution(step.execution_time),
+        ],
+        move_count: step.moves.len(),
+    }
+}
+
+fn cross_step_summary(name: String, cross: &CrossAnalysis) -> AnalysisStepSummary {
+    AnalysisStepSummary {
+        name,
+        short_name: "Cross".into(),
+        major_step_index: 0,
+        algorithm: None,
+        recognition_time: 0,
+        execution_time: cross.time,
+        substeps: vec![AnalysisSubstepTime::Execution(cross.time)],
+        move_count: cross.moves.len(),
+    }
+}
+
+fn alignment_step_summary(alignment: &FinalAlignmentAnalysis) -> AnalysisStepSummary {
+    AnalysisStepSummary {
+        name: "Alignment".into(),
+        short_name: "Align".into(),
+        major_step_index: 6,
+        algorithm: None,
+        recognition_time: 0,
+        execution_time: alignment.time,
+        substeps: vec![AnalysisSubstepTime::Execution(alignment.time)],
+        move_count: alignment.moves.len(),
+    }
+}
+
+impl AnalysisSummary for LBLAnalysis {
+    fn step_summary(&self) -> Vec<AnalysisStepSummary> {
+        lbl_summary(self)
+    }
+
+    fn detailed_step_summary(&self) -> Vec<AnalysisStepSummary> {
+        lbl_detailed_summary(self, self.cross.color)
+    }
+}
+
+impl AnalysisSummary for LBLPartialAnalysis {
+    fn step_summary(&self) -> Vec<AnalysisStepSummary> {
+        lbl_summary(self)
+    }
+
+    fn detailed_step_summary(&self) -> Vec<AnalysisStepSummary> {
+        match &self.cross {
+            Some(cross) => lbl_detailed_summary(self, cross.color),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn lbl_summary<T: LBLAnalysisStages>(analysis: &T) -> Vec<AnalysisStepSummary> {
+    let mut result = Vec::new();
+    if let Some(cross) = analysis.cross() {
+        result.push(cross_step_summary("Cross".into(), cross));
+    }
+
+    // First layer corners and second layer edges are shown individually, like
+    // F2L pairs. Last layer steps are combined into a single step each.
+    for (steps, (name, short_name, major_step_index)) in
+        lbl_steps(analysis).iter().zip(LBL_STEP_NAMES.iter())
+    {
+        if *major_step_index <= 2 {
+            for step in steps.iter() {
+                result.push(lbl_step_summary(
+                    (*name).into(),
+                    short_name,
+                    *major_step_index,
+                    step,
+                ));
+            }
+            continue;
+        }
+
+        let mut total = AnalysisStepSummary {
+            name: (*name).into(),
+            short_name: (*short_name).into(),
+            major_step_index: *major_step_index,
+            algorithm: None,
+            recognition_time: 0,
+            execution_time: 0,
+            substeps: Vec::new(),
+            move_count: 0,
+        };
+        for step in steps.iter() {
+            total.recognition_time += step.recognition_time;
+            total.execution_time += step.execution_time;
+            total.move_count += step.moves.len();
+            total
+                .substeps
+                .push(AnalysisSubstepTime::Recognition(step.recognition_time));
+            total
+                .substeps
+                .push(AnalysisSubstepTime::Execution(step.execution_time));
+        }
+
+        // Add final alignment into the last step
+        if *major_step_index == 6 {
+            if let Some(alignment) = analysis.alignment() {
+                if alignment.time > 0 {
+                    if steps.is_empty() {
+                        result.push(alignment_step_summary(alignment));
+                        continue;
+                    }
+                    total.execution_time += alignment.time;
+                    total.move_count += alignment.moves.len();
+                    total
+                        .substeps
+                        .push(AnalysisSubstepTime::Execution(alignment.time));
+                }
+            }
+        }
+
+        if total.move_count > 0 {
+            result.push(total);
+        }
+    }
+    result
+}
+
+fn lbl_detailed_summary<T: LBLAnalysisStages>(
+    analysis: &T,
+    cross_color: Color,
+) -> Vec<AnalysisStepSummary> {
+    let last_layer_color = cross_color.face().opposite().color();
+    let mut result = Vec::new();
+    if let Some(cross) = analysis.cross() {
+        result.push(cross_step_summary(
+            format!("{} Cross", cross_color.to_str()),
+            cross,
+        ));
+    }
+    for (steps, (name, short_name, major_step_index)) in
+        lbl_steps(analysis).iter().zip(LBL_STEP_NAMES.iter())
+    {
+        let name = match major_step_index {
+            3 => format!("{} Cross", last_layer_color.to_str()),
+            4 => format!("{} Face", last_layer_color.to_str()),
+            _ => (*name).into(),
+        };
+        for step in steps.iter() {
+            result.push(lbl_step_summary(
+                name.clone(),
+                short_name,
+                *major_step_index,
+                step,
+            ));
+        }
+    }
+    if let Some(alignment) = analysis.alignment() {
+        if alignment.time > 0 {
+            result.push(alignment_step_summary(alignment));
+        }
+    }
+    result
+}
+
+impl PartialAnalysisMethod for LBLPartialAnalysis {
+    fn transition_count(&self) -> usize {
+        let mut count = self.transitions().count();
+        if self.cross.is_some() {
+            count += 1;
+        }
+        if self.alignment.is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    fn sum_of_transition_times(&self) -> u32 {
+        let mut sum = 0;
+        let mut time = 0;
+        if let Some(cross) = &self.cross {
+            time += cross.time;
+            sum += time;
+        }
+        for step in self.transitions() {
+            time += step.recognition_time + step.execution_time;
+            sum += time;
+        }
+        if let Some(align) = &self.alignment {
+            time += align.time;
+            sum += time;
+        }
+        sum
+    }
+
+    fn is_complete(&self) -> bool {
+        self.progress == LBLProgress::Solved
+    }
+
+    fn to_partial_analysis(&self) -> PartialAnalysis {
+        PartialAnalysis::LBL(self.clone())
+    }
+}
+
+impl From<LBLPartialAnalysis> for Option<LBLAnalysis> {
+    fn from(analysis: LBLPartialAnalysis) -> Option<LBLAnalysis> {
+        if let Some(cross) = analysis.cross {
+            if let Some(alignment) = analysis.alignment {
+                return Some(LBLAnalysis {
+                    cross,
+                    first_layer_corners: analysis.first_layer_corners,
+                    second_layer_edges: analysis.second_layer_edges,
+                    last_layer_cross: analysis.last_layer_cross,
+                    last_layer_face: analysis.last_layer_face,
+                    corner_permutation: analysis.corner_permutation,
+                    edge_permutation: analysis.edge_permutation,
+                    alignment,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl std::fmt::Display for LBLPartialAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(cross) = &self.cross {
+            writeln!(
+                f,
+                "{} cross: {} moves in {}ms",
+                cross.color.to_str(),
+                cross.moves.len(),
+                cross.time
+            )?;
+        }
+        let last_layer_color = self.last_layer_color().unwrap_or(Color::Yellow);
+        for (steps, (name, _, major_step_index)) in
+            lbl_steps(self).iter().zip(LBL_STEP_NAMES.iter())
+        {
+            let name = match major_step_index {
+                3 => format!("{} cross", last_layer_color.to_str()),
+                4 => format!("{} face", last_layer_color.to_str()),
+                _ => (*name).into(),
+            };
+            for step in steps.iter() {
+                writeln!(
+                    f,
+                    "{}{}: Recognition {}ms, {} moves in {}ms",
+                    name,
+                    if step.paired { " (paired)" } else { "" },
+                    step.recognition_time,
+                    step.moves.len(),
+                    step.execution_time
+                )?;
+            }
+        }
+        if let Some(alignment) = &self.alignment {
+            if !alignment.moves.is_empty() {
+                writeln!(
+                    f,
+                    "Alignment: {} moves in {}ms",
+                    alignment.moves.len(),
+                    alignment.time
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use super::cfop::CFOPAnalysisStages;
+use crate::{CFOPAnalysis, CFOPStep, Solve, TimedMove};
+
+/// Default gap between moves that is considered a pause, as a multiple of the median
+/// gap between moves
+pub const DEFAULT_PAUSE_THRESHOLD: f32 = 3.0;
+
+/// A pause between two moves of a solve
+#[derive(Clone, Debug)]
+pub struct Pause {
+    /// Time of the move before the pause
+    pub start_time: u32,
+    pub duration: u32,
+    /// Index of the move that ended the pause
+    pub move_index: usize,
+    /// Step of the solve that the move ending the pause belongs to
+    pub step: CFOPStep,
+}
+
+/// Analysis of the pauses between moves of a solve. A solve with good lookahead has
+/// few pauses, as the next step is found while executing the current one.
+#[derive(Clone, Debug)]
+pub struct LookaheadAnalysis {
+    pub pauses: Vec<Pause>,
+    /// Shortest gap between moves that was counted as a pause
+    pub pause_gap: u32,
+    /// Percentage of the time between the first and last move that was spent turning
+    /// instead of paused
+    pub score: f32,
+}
+
+/// Gets the median gap between consecutive moves over a set of solves, ignoring solves
+/// without move data. Returns `None` if there are no moves to look at.
+pub fn median_move_gap<'a, T: Iterator<Item = &'a Solve>>(solves: T) -> Option<u32> {
+    let mut gaps = Vec::new();
+    for solve in solves {
+        if let Some(moves) = &solve.moves {
+            gaps.extend(move_gaps(moves));
+        }
+    }
+    median(&mut gaps)
+}
+
+pub(super) fn move_gaps(moves: &[TimedMove]) -> impl Iterator<Item = u32> + '_ {
+    moves
+        .windows(2)
+        .map(|pair| pair[1].time().saturating_sub(pair[0].time()))
+}
+
+pub(super) fn median(values: &mut [u32]) -> Option<u32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
+
+impl LookaheadAnalysis {
+    /// Finds the pauses in a solve. Gaps between moves that are at least `threshold`
+    /// times `median_gap` are counted as pauses. If `median_gap` is not given, the median
+    /// gap of this solve is used.
+    pub fn analyze(
+        analysis: &CFOPAnalysis,
+        solution: &[TimedMove],
+        median_gap: Option<u32>,
+        threshold: f32,
+    ) -> Self {
+        let median_gap = median_gap
+            .or_else(|| median(&mut move_gaps(solution).collect::<Vec<u32>>()))
+            .unwrap_or(0);
+        let pause_gap = ((median_gap as f32 * threshold) as u32).max(1);
+
+        let mut pauses = Vec::new();
+        for (idx, gap) in move_gaps(solution).enumerate() {
+            if gap < pause_gap {
+                continue;
+            }
+            let move_index = idx + 1;
+            pauses.push(Pause {
+                start_time: solution[idx].time(),
+                duration: gap,
+                move_index,
+                step: analysis.step_at_move(move_index),
+            });
+        }
+
+        let total_time = match (solution.first(), solution.last()) {
+            (Some(first), Some(last)) => last.time().saturating_sub(first.time()),
+            _ => 0,
+        };
+        let pause_time: u32 = pauses.iter().map(|pause| pause.duration).sum();
+        let score = if total_time == 0 {
+            100.0
+        } else {
+            total_time.saturating_sub(pause_time) as f32 * 100.0 / total_time as f32
+        };
+
+        Self {
+            pauses,
+            pause_gap,
+            score,
+        }
+    }
+
+    /// Total time spent in pauses
+    pub fn pause_time(&self) -> u32 {
+        self.pauses.iter().map(|pause| pause.duration).sum()
+    }
+
+    /// Total time spent in pauses during steps matching the given filter
+    pub fn step_pause_time<F: Fn(CFOPStep) -> bool>(&self, filter: F) -> u32 {
+        self.pauses
+            .iter()
+            .filter(|pause| filter(pause.step))
+            .map(|pause| pause.duration)
+            .sum()
+    }
+}
+
+impl CFOPAnalysis {
+    /// Finds the pauses in this solve. See `LookaheadAnalysis::analyze`.
+    pub fn lookahead(
+        &self,
+        solution: &[TimedMove],
+        median_gap: Option<u32>,
+        threshold: f32,
+    ) -> LookaheadAnalysis {
+        LookaheadAnalysis::analyze(self, solution, median_gap, threshold)
+    }
+}
@@ -0,0 +1,180 @@
+use super::cfop::CFOPAnalysisStages;
+use crate::{CFOPAnalysis, CFOPStep, OLLAnalysis, PLLAnalysis, TimedMove};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Number of moves a solved F2L pair can be taken out for before it is counted as a
+/// mistake. Some F2L cases use a solved slot for a moment on purpose.
+pub(super) const MAX_TEMPORARY_BREAK_MOVES: usize = 4;
+
+/// Type of mistake made during a solve
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MistakeKind {
+    /// A sequence of moves was performed and then undone
+    UndoneMoves,
+    /// A solved F2L pair was taken out and had to be solved again
+    BrokenPair,
+    /// An OLL or PLL algorithm did not lead to a state expected from a one or two look
+    /// solution, such as when the wrong algorithm was performed or it was executed
+    /// incorrectly
+    FailedAlgorithm,
+    /// More than two looks were needed to solve OLL or PLL
+    RepeatedLook,
+}
+
+/// A mistake made during a solve, along with the time it cost
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Mistake {
+    pub kind: MistakeKind,
+    /// Step of the solve the mistake was made in
+    pub step: CFOPStep,
+    /// Index of the first move involved in the mistake
+    pub move_index: usize,
+    /// Number of moves involved in the mistake
+    pub move_count: usize,
+    /// Time lost to the mistake
+    pub time_lost: u32,
+}
+
+impl MistakeKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            MistakeKind::UndoneMoves => "Undone moves",
+            MistakeKind::BrokenPair => "Broken F2L pair",
+            MistakeKind::FailedAlgorithm => "Failed algorithm",
+            MistakeKind::RepeatedLook => "Extra look",
+        }
+    }
+}
+
+impl OLLAnalysis {
+    /// Checks if this step ended in a state that isn't expected from a one or two look
+    /// OLL. The first look of two-look OLL must orient the edges.
+    fn is_failed_attempt(&self) -> bool {
+        match self.new_state {
+            Some(new_state) => self.one_look_algorithm.is_cross() || !new_state.is_cross(),
+            None => false,
+        }
+    }
+}
+
+impl PLLAnalysis {
+    /// Checks if this step ended in a state that isn't expected from a one or two look
+    /// PLL. The first look of two-look PLL must permute the corners.
+    fn is_failed_attempt(&self) -> bool {
+        match self.new_state {
+            Some(new_state) => {
+                self.one_look_algorithm.is_edges_only() || !new_state.is_edges_only()
+            }
+            None => false,
+        }
+    }
+}
+
+/// Finds sequences of moves that were directly followed by their inverse. Returns
+/// the range of move indices for each sequence, including the moves undoing it.
+fn undone_move_ranges(solution: &[TimedMove]) -> Vec<Range<usize>> {
+    // Moves that are undone are matched with a stack, so that a sequence followed by
+    // its inverse cancels out from the inside out.
+    let mut stack: Vec<usize> = Vec::new();
+    let mut undone = vec![false; solution.len()];
+    for (idx, mv) in solution.iter().enumerate() {
+        if let Some(prev) = stack.last() {
+            if solution[*prev].move_() == mv.move_().inverse() {
+                undone[*prev] = true;
+                undone[idx] = true;
+                stack.pop();
+                continue;
+            }
+        }
+        stack.push(idx);
+    }
+
+    // Each contiguous run of undone moves cancels out completely
+    let mut result = Vec::new();
+    let mut start = None;
+    for (idx, undone) in undone.iter().enumerate() {
+        match (start, *undone) {
+            (None, true) => start = Some(idx),
+            (Some(range_start), false) => {
+                result.push(range_start..idx);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(range_start) = start {
+        result.push(range_start..solution.len());
+    }
+    result
+}
+
+/// Finds mistakes in a solve that can be seen from the moves and the steps of the
+/// analysis. Broken F2L pairs are detected while analyzing the solve and are not
+/// included here.
+pub(super) fn find_mistakes<T: CFOPAnalysisStages>(
+    analysis: &T,
+    solution: &[TimedMove],
+) -> Vec<Mistake> {
+    let mut result = Vec::new();
+
+    for range in undone_move_ranges(solution) {
+        // Time lost is from the move before the undone moves to the end of the undo
+        let start_time = if range.start > 0 {
+            solution[range.start - 1].time()
+        } else {
+            solution[range.start].time()
+        };
+        result.push(Mistake {
+            kind: MistakeKind::UndoneMoves,
+            step: analysis.step_at_move(range.start),
+            move_index: range.start,
+            move_count: range.len(),
+            time_lost: solution[range.end - 1].time().saturating_sub(start_time),
+        });
+    }
+
+    // Look at the last layer steps. Anything past two looks is an extra look, and a
+    // look that didn't make the expected progress is a failed algorithm.
+    for (idx, oll) in analysis.oll().iter().enumerate() {
+        let kind = if idx >= 2 {
+            MistakeKind::RepeatedLook
+        } else if oll.is_failed_attempt() {
+            MistakeKind::FailedAlgorithm
+        } else {
+            continue;
+        };
+        result.push(Mistake {
+            kind,
+            step: CFOPStep::OLL,
+            move_index: oll.start_move_index,
+            move_count: oll.moves.len(),
+            time_lost: oll.recognition_time + oll.execution_time,
+        });
+    }
+    for (idx, pll) in analysis.pll().iter().enumerate() {
+        let kind = if idx >= 2 {
+            MistakeKind::RepeatedLook
+        } else if pll.is_failed_attempt() {
+            MistakeKind::FailedAlgorithm
+        } else {
+            continue;
+        };
+        result.push(Mistake {
+            kind,
+            step: CFOPStep::PLL,
+            move_index: pll.start_move_index,
+            move_count: pll.moves.len(),
+            time_lost: pll.recognition_time + pll.execution_time,
+        });
+    }
+
+    result
+}
+
+impl CFOPAnalysis {
+    /// Total time lost to mistakes during the solve
+    pub fn mistake_time(&self) -> u32 {
+        self.mistakes.iter().map(|mistake| mistake.time_lost).sum()
+    }
+}
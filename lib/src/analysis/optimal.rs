@@ -0,0 +1,608 @@
+use crate::cube3x3x3::{cross_edges, f2l_slot_pieces, f2l_slots};
+use crate::{
+    CFOPAnalysis, Corner, Cube, Cube3x3x3, CubeFace, CubeWithSolution, Edge3x3x3, F2LSlot,
+    InitialCubeState, Move,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+/// Number of states of a single piece. Corners have 8 positions with 3 orientations
+/// each, and edges have 12 positions with 2 orientations each.
+const PIECE_STATE_COUNT: usize = 24;
+
+/// Number of corner positions
+const CORNER_COUNT: usize = 8;
+
+/// Number of edge positions
+const EDGE_COUNT: usize = 12;
+
+/// Number of face turns that are searched
+const FACE_TURN_COUNT: usize = 18;
+
+/// Longest solution searched for when finding optimal F2L pair insertions
+const MAX_F2L_SEARCH_DEPTH: usize = 14;
+
+/// Number of search nodes to visit before giving up on an optimal F2L pair insertion
+const MAX_F2L_SEARCH_NODES: usize = 1_000_000;
+
+/// Largest number of pieces tracked by a search, which is the cross edges along with
+/// the corner and edge of every F2L slot
+const MAX_SEARCH_PIECES: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Piece {
+    Corner(Corner),
+    Edge(Edge3x3x3),
+}
+
+/// New state of each piece after each face turn
+struct PieceTransitions {
+    corners: Vec<[u8; PIECE_STATE_COUNT]>,
+    edges: Vec<[u8; PIECE_STATE_COUNT]>,
+}
+
+/// Table of the number of moves required to solve a small set of pieces. Two pieces
+/// can't be in the same position, so the table is indexed by the rank of each piece's
+/// position among the positions not taken by the pieces before it, along with the
+/// orientation of each piece. The table is built with a breadth first search from the
+/// solved state.
+pub(crate) struct PieceDistanceTable {
+    pieces: Vec<Piece>,
+    /// Number of possible values of each piece's part of the index
+    radices: Vec<usize>,
+    moves: Vec<u8>,
+}
+
+/// Table of the number of moves in an optimal solution of the cross on a face, for
+/// every placement of the four cross edges. Building the table takes a moment, so it
+/// should be kept around when checking many states.
+pub struct CrossMoveTable {
+    transitions: PieceTransitions,
+    table: PieceDistanceTable,
+}
+
+/// Finds optimal solutions for the cross and F2L pairs of a CFOP solve. Creating the
+/// solver builds move tables for the cross face, so it should be reused when
+/// looking at many solves with the same cross color.
+pub struct CFOPStepSolver {
+    face: CubeFace,
+    transitions: PieceTransitions,
+    slots: Vec<(Corner, Edge3x3x3)>,
+    cross: CrossMoveTable,
+    /// Tables for each F2L slot, each containing the corner and edge of the slot along
+    /// with half of the cross edges
+    slot_tables: Vec<[PieceDistanceTable; 2]>,
+}
+
+/// Comparison of the moves performed in the cross and F2L steps of a CFOP solve
+/// against optimal solutions from the same starting states
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CFOPEfficiency {
+    pub cross: StepEfficiency,
+    pub f2l_pairs: Vec<StepEfficiency>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StepEfficiency {
+    /// Number of moves performed, in the half turn metric
+    pub moves: usize,
+    /// An optimal solution for the step from the state it started in. This is `None`
+    /// if the search for an optimal solution took too long and was abandoned.
+    pub optimal: Option<Vec<Move>>,
+}
+
+/// Counts moves in the half turn metric, which is the metric optimal solutions are
+/// found in. Bluetooth cubes report half turns as two quarter turns, so consecutive
+/// turns of the same face are combined into one move.
+pub(crate) fn half_turn_moves(moves: impl IntoIterator<Item = Move>) -> usize {
+    let mut count = 0;
+    let mut current: Option<(CubeFace, i32)> = None;
+    for mv in moves {
+        let face = mv.face();
+        current = match current {
+            Some((current_face, rotation)) if current_face == face => {
+                Some((face, rotation + mv.rotation()))
+            }
+            Some((_, rotation)) => {
+                if rotation % 4 != 0 {
+                    count += 1;
+                }
+                Some((face, mv.rotation()))
+            }
+            None => Some((face, mv.rotation())),
+        };
+    }
+    if let Some((_, rotation)) = current {
+        if rotation % 4 != 0 {
+            count += 1;
+        }
+    }
+    count
+}
+
+impl Piece {
+    /// Gets the state of this piece in a cube. States are the position of the piece
+    /// times the number of orientations plus its orientation.
+    fn state(&self, cube: &Cube3x3x3) -> u8 {
+        match self {
+            Piece::Corner(corner) => {
+                for pos in 0..8 {
+                    let piece = cube.corner_piece(Corner::try_from(pos).unwrap());
+                    if piece.piece == *corner {
+                        return pos * 3 + piece.orientation;
+                    }
+                }
+            }
+            Piece::Edge(edge) => {
+                for pos in 0..12 {
+                    let piece = cube.edge_piece(Edge3x3x3::try_from(pos).unwrap());
+                    if piece.piece == *edge {
+                        return pos * 2 + piece.orientation;
+                    }
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    fn solved_state(&self) -> u8 {
+        match self {
+            Piece::Corner(corner) => *corner as u8 * 3,
+            Piece::Edge(edge) => *edge as u8 * 2,
+        }
+    }
+
+    fn position_count(&self) -> usize {
+        match self {
+            Piece::Corner(_) => CORNER_COUNT,
+            Piece::Edge(_) => EDGE_COUNT,
+        }
+    }
+
+    fn orientation_count(&self) -> usize {
+        match self {
+            Piece::Corner(_) => 3,
+            Piece::Edge(_) => 2,
+        }
+    }
+}
+
+impl PieceTransitions {
+    fn new() -> Self {
+        let mut corners = Vec::new();
+        let mut edges = Vec::new();
+        for mv in 0..FACE_TURN_COUNT {
+            // Pieces moved by a face turn can be found by applying the move to a solved
+            // cube. The piece at each position is the one that moved there.
+            let mut cube = Cube3x3x3::new();
+            cube.do_move(Move::try_from(mv as u8).unwrap());
+
+            let mut corner_result = [0; PIECE_STATE_COUNT];
+            for dest in 0..8 {
+                let piece = cube.corner_piece(Corner::try_from(dest as u8).unwrap());
+                for orientation in 0..3 {
+                    corner_result[piece.piece as u8 as usize * 3 + orientation] =
+                        (dest * 3 + (orientation + piece.orientation as usize) % 3) as u8;
+                }
+            }
+            corners.push(corner_result);
+
+            let mut edge_result = [0; PIECE_STATE_COUNT];
+            for dest in 0..12 {
+                let piece = cube.edge_piece(Edge3x3x3::try_from(dest as u8).unwrap());
+                for orientation in 0..2 {
+                    edge_result[piece.piece as u8 as usize * 2 + orientation] =
+                        (dest * 2 + (orientation ^ piece.orientation as usize)) as u8;
+                }
+            }
+            edges.push(edge_result);
+        }
+        Self { corners, edges }
+    }
+
+    fn apply(&self, piece: &Piece, state: u8, mv: usize) -> u8 {
+        match piece {
+            Piece::Corner(_) => self.corners[mv][state as usize],
+            Piece::Edge(_) => self.edges[mv][state as usize],
+        }
+    }
+}
+
+impl PieceDistanceTable {
+    fn new(pieces: &[Piece], transitions: &PieceTransitions) -> Self {
+        let mut corners = 0;
+        let mut edges = 0;
+        let radices: Vec<usize> = pieces
+            .iter()
+            .map(|piece| {
+                let placed = match piece {
+                    Piece::Corner(_) => &mut corners,
+                    Piece::Edge(_) => &mut edges,
+                };
+                *placed += 1;
+                (piece.position_count() + 1 - *placed) * piece.orientation_count()
+            })
+            .collect();
+        let mut table = Self {
+            pieces: pieces.to_vec(),
+            moves: vec![u8::MAX; radices.iter().product()],
+            radices,
+        };
+
+        let solved: Vec<u8> = pieces.iter().map(|piece| piece.solved_state()).collect();
+        let solved = table.index(&solved);
+        table.moves[solved] = 0;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(solved);
+        let mut states = vec![0; pieces.len()];
+        let mut next_states = vec![0; pieces.len()];
+        while let Some(idx) = queue.pop_front() {
+            let count = table.moves[idx] + 1;
+            table.states(idx, &mut states);
+            for mv in 0..FACE_TURN_COUNT {
+                for ((next, piece), state) in
+                    next_states.iter_mut().zip(pieces.iter()).zip(states.iter())
+                {
+                    *next = transitions.apply(piece, *state, mv);
+                }
+                let next = table.index(&next_states);
+                if table.moves[next] == u8::MAX {
+                    table.moves[next] = count;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        table
+    }
+
+    fn index(&self, states: &[u8]) -> usize {
+        let mut corners: u16 = 0;
+        let mut edges: u16 = 0;
+        let mut idx = 0;
+        for ((piece, state), radix) in self.pieces.iter().zip(states).zip(&self.radices) {
+            let orientations = piece.orientation_count();
+            let position = *state as usize / orientations;
+            let used = match piece {
+                Piece::Corner(_) => &mut corners,
+                Piece::Edge(_) => &mut edges,
+            };
+            let rank = position - (*used & ((1 << position) - 1)).count_ones() as usize;
+            idx = idx * radix + rank * orientations + *state as usize % orientations;
+            *used |= 1 << position;
+        }
+        idx
+    }
+
+    /// Gets the state of each piece from an index into the table
+    fn states(&self, mut idx: usize, states: &mut [u8]) {
+        let mut parts = [0; MAX_SEARCH_PIECES];
+        for (part, radix) in parts[..states.len()].iter_mut().zip(&self.radices).rev() {
+            *part = idx % radix;
+            idx /= radix;
+        }
+
+        let mut corners: u16 = 0;
+        let mut edges: u16 = 0;
+        for ((state, piece), part) in states.iter_mut().zip(&self.pieces).zip(&parts) {
+            let orientations = piece.orientation_count();
+            let used = match piece {
+                Piece::Corner(_) => &mut corners,
+                Piece::Edge(_) => &mut edges,
+            };
+            let position = (0..piece.position_count())
+                .filter(|position| *used & (1 << position) == 0)
+                .nth(part / orientations)
+                .unwrap();
+            *used |= 1 << position;
+            *state = (position * orientations + part % orientations) as u8;
+        }
+    }
+
+    fn moves_for_states(&self, states: &[u8]) -> usize {
+        self.moves[self.index(states)] as usize
+    }
+
+    fn moves(&self, cube: &Cube3x3x3) -> usize {
+        let states: Vec<u8> = self.pieces.iter().map(|piece| piece.state(cube)).collect();
+        self.moves_for_states(&states)
+    }
+
+    /// Finds an optimal solution for the pieces in the table
+    fn solution(&self, cube: &Cube3x3x3, transitions: &PieceTransitions) -> Vec<Move> {
+        // The table contains exact move counts, so always taking a move that brings
+        // the pieces one move closer to solved gives an optimal solution
+        let mut states: Vec<u8> = self.pieces.iter().map(|p| p.state(cube)).collect();
+        let mut remaining = self.moves_for_states(&states);
+        let mut result = Vec::new();
+        while remaining > 0 {
+            for mv in 0..FACE_TURN_COUNT {
+                let next: Vec<u8> = self
+                    .pieces
+                    .iter()
+                    .zip(states.iter())
+                    .map(|(piece, state)| transitions.apply(piece, *state, mv))
+                    .collect();
+                if self.moves_for_states(&next) < remaining {
+                    states = next;
+                    remaining -= 1;
+                    result.push(Move::try_from(mv as u8).unwrap());
+                    break;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl CrossMoveTable {
+    pub fn new(face: CubeFace) -> Self {
+        let transitions = PieceTransitions::new();
+        let pieces: Vec<Piece> = cross_edges(face).into_iter().map(Piece::Edge).collect();
+        Self {
+            table: PieceDistanceTable::new(&pieces, &transitions),
+            transitions,
+        }
+    }
+
+    /// Gets the number of moves in an optimal solution of the cross
+    pub fn moves(&self, cube: &Cube3x3x3) -> usize {
+        self.table.moves(cube)
+    }
+
+    /// Finds an optimal solution for the cross
+    pub fn optimal_cross(&self, cube: &Cube3x3x3) -> Vec<Move> {
+        self.table.solution(cube, &self.transitions)
+    }
+
+    /// Compares the cross step of a solve with an optimal solution from the same
+    /// starting state. The cross table is all that is needed for this, so it is much
+    /// faster than `CFOPStepSolver::efficiency`.
+    pub fn cross_efficiency(
+        &self,
+        analysis: &CFOPAnalysis,
+        solve: &CubeWithSolution,
+    ) -> StepEfficiency {
+        StepEfficiency {
+            moves: half_turn_moves(analysis.cross.moves.iter().copied()),
+            optimal: Some(self.optimal_cross(&solve.initial_state)),
+        }
+    }
+}
+
+impl CFOPStepSolver {
+    pub fn new(face: CubeFace) -> Self {
+        let transitions = PieceTransitions::new();
+        let edges: Vec<Piece> = cross_edges(face).into_iter().map(Piece::Edge).collect();
+        let slots = f2l_slots(face);
+        let cross = CrossMoveTable::new(face);
+        let slot_tables = slots
+            .iter()
+            .map(|(corner, edge)| {
+                let pair = [Piece::Corner(*corner), Piece::Edge(*edge)];
+                [
+                    PieceDistanceTable::new(&[&pair[..], &edges[0..2]].concat(), &transitions),
+                    PieceDistanceTable::new(&[&pair[..], &edges[2..4]].concat(), &transitions),
+                ]
+            })
+            .collect();
+        Self {
+            face,
+            transitions,
+            slots,
+            cross,
+            slot_tables,
+        }
+    }
+
+    pub fn face(&self) -> CubeFace {
+        self.face
+    }
+
+    /// Gets the index of an F2L slot, as used by `solved_slots` and `optimal_f2l_pairs`
+    pub fn slot_index(&self, slot: F2LSlot) -> usize {
+        let pieces = f2l_slot_pieces(self.face, slot);
+        self.slots.iter().position(|slot| *slot == pieces).unwrap()
+    }
+
+    /// Gets the position of the F2L slot with the given index
+    pub fn slot(&self, idx: usize) -> F2LSlot {
+        *F2LSlot::all()
+            .iter()
+            .find(|slot| self.slot_index(**slot) == idx)
+            .unwrap()
+    }
+
+    pub fn cross_solved(&self, cube: &Cube3x3x3) -> bool {
+        self.cross.moves(cube) == 0
+    }
+
+    /// Gets the indicies of the F2L slots that are solved
+    pub fn solved_slots(&self, cube: &Cube3x3x3) -> Vec<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, (corner, edge))| {
+                Piece::Corner(*corner).state(cube) == Piece::Corner(*corner).solved_state()
+                    && Piece::Edge(*edge).state(cube) == Piece::Edge(*edge).solved_state()
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Finds an optimal solution for the cross
+    pub fn optimal_cross(&self, cube: &Cube3x3x3) -> Vec<Move> {
+        self.cross.optimal_cross(cube)
+    }
+
+    /// Finds an optimal solution that solves the given F2L slots without disturbing
+    /// the cross. Returns `None` if the cross is not solved or the search takes too long.
+    pub fn optimal_f2l_pairs(&self, cube: &Cube3x3x3, slots: &[usize]) -> Option<Vec<Move>> {
+        if !self.cross_solved(cube) {
+            return None;
+        }
+        self.search_slots(cube, slots)
+    }
+
+    /// Finds an optimal solution that solves the cross along with the given F2L slot.
+    /// Returns `None` if the search takes too long.
+    pub fn optimal_xcross(&self, cube: &Cube3x3x3, slot: F2LSlot) -> Option<Vec<Move>> {
+        self.search_slots(cube, &[self.slot_index(slot)])
+    }
+
+    /// Searches for an optimal solution that leaves the cross and the given F2L slots
+    /// solved
+    fn search_slots(&self, cube: &Cube3x3x3, slots: &[usize]) -> Option<Vec<Move>> {
+        // Gather the pieces involved and where they are found in each table
+        let mut search = F2LSearch {
+            transitions: &self.transitions,
+            pieces: self.cross.table.pieces.clone(),
+            tables: Vec::new(),
+            path: Vec::new(),
+            nodes: 0,
+        };
+        for slot in slots {
+            let (corner, edge) = self.slots[*slot];
+            search.pieces.push(Piece::Corner(corner));
+            search.pieces.push(Piece::Edge(edge));
+        }
+        search.add_table(&self.cross.table);
+        for slot in slots {
+            for table in &self.slot_tables[*slot] {
+                search.add_table(table);
+            }
+        }
+
+        let mut states: Vec<u8> = search.pieces.iter().map(|p| p.state(cube)).collect();
+        let mut depth = search.lower_bound(&states);
+        while depth <= MAX_F2L_SEARCH_DEPTH {
+            if search.search(&mut states, depth, None) {
+                return Some(search.path);
+            }
+            if search.nodes > MAX_F2L_SEARCH_NODES {
+                return None;
+            }
+            depth += 1;
+        }
+        None
+    }
+
+    /// Compares the cross and F2L steps of a solve with optimal solutions from the
+    /// same starting states
+    pub fn efficiency(&self, analysis: &CFOPAnalysis, solve: &CubeWithSolution) -> CFOPEfficiency {
+        let cross = self.cross.cross_efficiency(analysis, solve);
+
+        let mut f2l_pairs = Vec::new();
+        for pair in &analysis.f2l_pairs {
+            let mut start = solve.initial_state.clone();
+            for mv in &solve.solution[..pair.start_move_index] {
+                start.do_move(mv.move_());
+            }
+            let mut end = start.clone();
+            end.do_moves(&pair.moves);
+
+            // Find an optimal way to end up with the same slots solved
+            f2l_pairs.push(StepEfficiency {
+                moves: half_turn_moves(pair.moves.iter().copied()),
+                optimal: self.optimal_f2l_pairs(&start, &self.solved_slots(&end)),
+            });
+        }
+
+        CFOPEfficiency { cross, f2l_pairs }
+    }
+}
+
+/// Iterative deepening search for F2L pair insertions
+struct F2LSearch<'a> {
+    transitions: &'a PieceTransitions,
+    pieces: Vec<Piece>,
+    /// Distance tables used as the lower bound for the search, along with the index
+    /// into `pieces` of each piece in the table
+    tables: Vec<(&'a PieceDistanceTable, Vec<usize>)>,
+    path: Vec<Move>,
+    nodes: usize,
+}
+
+impl<'a> F2LSearch<'a> {
+    fn add_table(&mut self, table: &'a PieceDistanceTable) {
+        let indicies = table
+            .pieces
+            .iter()
+            .map(|piece| self.pieces.iter().position(|p| p == piece).unwrap())
+            .collect();
+        self.tables.push((table, indicies));
+    }
+
+    fn lower_bound(&self, states: &[u8]) -> usize {
+        let mut table_states = Vec::with_capacity(4);
+        self.tables
+            .iter()
+            .map(|(table, indicies)| {
+                table_states.clear();
+                table_states.extend(indicies.iter().map(|i| states[*i]));
+                table.moves_for_states(&table_states)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn search(&mut self, states: &mut [u8], depth: usize, last_face: Option<CubeFace>) -> bool {
+        self.nodes += 1;
+        let bound = self.lower_bound(states);
+        if bound == 0 {
+            return true;
+        }
+        if bound > depth || self.nodes > MAX_F2L_SEARCH_NODES {
+            return false;
+        }
+
+        for mv in 0..FACE_TURN_COUNT {
+            let face_turn = Move::try_from(mv as u8).unwrap();
+            if let Some(last_face) = last_face {
+                // Turning the same face twice in a row is never optimal, and turns of
+                // opposite faces are only searched in one order
+                if face_turn.face() == last_face
+                    || (face_turn.face() == last_face.opposite()
+                        && (face_turn.face() as u8) < (last_face as u8))
+                {
+                    continue;
+                }
+            }
+
+            let mut old_states = [0; MAX_SEARCH_PIECES];
+            old_states[..states.len()].copy_from_slice(states);
+            for (state, piece) in states.iter_mut().zip(self.pieces.iter()) {
+                *state = self.transitions.apply(piece, *state, mv);
+            }
+            self.path.push(face_turn);
+            if self.search(states, depth - 1, Some(face_turn.face())) {
+                return true;
+            }
+            self.path.pop();
+            states.copy_from_slice(&old_states[..states.len()]);
+        }
+        false
+    }
+}
+
+impl StepEfficiency {
+    /// Number of moves performed beyond an optimal solution
+    pub fn extra_moves(&self) -> Option<usize> {
+        self.optimal
+            .as_ref()
+            .map(|optimal| self.moves.saturating_sub(optimal.len()))
+    }
+}
+
+impl CFOPEfficiency {
+    /// Number of moves performed beyond optimal over all F2L pairs. Returns `None` if
+    /// an optimal solution is not known for one of the pairs.
+    pub fn f2l_extra_moves(&self) -> Option<usize> {
+        self.f2l_pairs
+            .iter()
+            .map(|pair| pair.extra_moves())
+            .sum::<Option<usize>>()
+    }
+}
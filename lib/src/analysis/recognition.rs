@@ -0,0 +1,42 @@
+use super::last_layer::LastLayerState;
+use crate::{
+    Algorithm, Cube, Cube3x3x3, CubeFace, InitialCubeState, Move, OLLAlgorithm, PLLAlgorithm,
+    SolveType,
+};
+
+/// Last layer case shown in a recognition drill, along with the angle it is seen from.
+/// Only two sides of the last layer are visible at once, so some angles of a case can
+/// be harder to recognize than others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecognitionCase {
+    pub algorithm: Algorithm,
+    /// Number of quarter turns of the last layer away from the reference angle of the
+    /// case. Symmetric cases look the same from more than one angle, and these are
+    /// always given the smallest angle.
+    pub angle: u8,
+}
+
+impl RecognitionCase {
+    /// Number of angles a case can be seen from
+    pub const ANGLE_COUNT: usize = 4;
+
+    /// Finds the case shown by a recognition drill scramble. Scrambles are applied with
+    /// the last layer on top.
+    pub fn from_scramble(solve_type: SolveType, scramble: &[Move]) -> Option<Self> {
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(scramble);
+        let faces = cube.as_faces();
+        let state = LastLayerState::from_cube(&faces, CubeFace::Top)?;
+        match solve_type {
+            SolveType::OLLRecognition => Some(Self {
+                algorithm: Algorithm::OLL(OLLAlgorithm::from_cube(&faces, CubeFace::Top)?),
+                angle: state.viewing_angle(true),
+            }),
+            SolveType::PLLRecognition => Some(Self {
+                algorithm: Algorithm::PLL(PLLAlgorithm::from_cube(&faces, CubeFace::Top)?),
+                angle: state.viewing_angle(false),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -102,7 +102,13 @@ impl<P: Peripheral> GANCubeVersion1<P> {
             return Err(anyhow!("Cube state is invalid"));
         }
         let state = cipher.decrypt(&state)?;
-        let state = Self::decode_cube_state(&state)?;
+
+        // If the cube reports a state that isn't valid, its internal state is out of
+        // sync. Connect anyway and let the client know so that the state can be reset.
+        let (state, synced) = match Self::decode_cube_state(&state) {
+            Ok(state) => (state, true),
+            Err(_) => (Cube3x3x3::new(), false),
+        };
         let state = Mutex::new(state);
 
         // Get the initial move count
@@ -127,7 +133,7 @@ impl<P: Peripheral> GANCubeVersion1<P> {
             state,
             battery_percentage: Mutex::new(battery_percentage as u32),
             battery_charging: Mutex::new(battery_charging),
-            synced: Mutex::new(true),
+            synced: Mutex::new(synced),
             last_move_count,
             characteristics,
             cipher,
@@ -182,8 +188,10 @@ impl<P: Peripheral> GANCubeVersion1<P> {
             }
         }
 
-        // Create cube state and convert to normal format
-        Ok(Cube3x3x3Faces::from_colors(state).as_pieces())
+        // Create cube state, make sure it is valid, and convert to normal format
+        let state = Cube3x3x3Faces::from_colors(state);
+        state.validate()?;
+        Ok(state.as_pieces())
     }
 
     fn move_poll(&self) -> Result<()> {
@@ -504,6 +512,15 @@ impl<P: Peripheral> GANCubeVersion2<P> {
                         *last_move_count.lock().unwrap() =
                             Some(Self::extract_bits(&value, 4, 8) as u8);
 
+                        // If the cube reports a state that isn't valid, its internal
+                        // state is out of sync. Let the client know and ignore moves
+                        // until the state is reset.
+                        let desync = || {
+                            *synced_copy.lock().unwrap() = false;
+                            *last_move_count.lock().unwrap() = None;
+                            *state_set_copy.lock().unwrap() = true;
+                        };
+
                         // Set up corner and edge state
                         let mut corners = [0; 8];
                         let mut corner_twist = [0; 8];
@@ -524,6 +541,7 @@ impl<P: Peripheral> GANCubeVersion2<P> {
                             corner_twist[i] = Self::extract_bits(&value, 33 + i * 2, 2);
                             total_corner_twist += corner_twist[i];
                             if !corners_left.remove(&corners[i]) || corner_twist[i] >= 3 {
+                                desync();
                                 return;
                             }
                         }
@@ -535,6 +553,7 @@ impl<P: Peripheral> GANCubeVersion2<P> {
                             edge_parity[i] = Self::extract_bits(&value, 91 + i, 1);
                             total_edge_parity += edge_parity[i];
                             if !edges_left.remove(&edges[i]) || edge_parity[i] >= 2 {
+                                desync();
                                 return;
                             }
                         }
@@ -573,6 +592,10 @@ impl<P: Peripheral> GANCubeVersion2<P> {
                             corner_pieces.try_into().unwrap(),
                             edge_pieces.try_into().unwrap(),
                         );
+                        if cube.validate().is_err() {
+                            desync();
+                            return;
+                        }
 
                         *state_copy.lock().unwrap() = cube;
                         *state_set_copy.lock().unwrap() = true;
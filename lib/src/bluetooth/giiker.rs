@@ -3,6 +3,7 @@ use crate::common::{Cube, InitialCubeState, Move, TimedMove};
 use crate::cube3x3x3::Cube3x3x3;
 use anyhow::{anyhow, Result};
 use btleplug::api::{Characteristic, Peripheral};
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -65,6 +66,13 @@ impl<P: Peripheral + 'static> GiikerCube<P> {
                 }
             }
 
+            // Each packet also contains the cube's state. If it isn't valid, the cube's
+            // internal state is out of sync.
+            if !Self::valid_state(&value) {
+                *synced_copy.lock().unwrap() = false;
+                return;
+            }
+
             let mv = match value[16] {
                 0x11 => Move::B,
                 0x12 => Move::B2,
@@ -113,6 +121,22 @@ impl<P: Peripheral + 'static> GiikerCube<P> {
             synced,
         })
     }
+
+    /// Checks the cube state in a move packet. The state is stored as one nibble per
+    /// value, with the corner positions first, followed by corner orientations and then
+    /// edge positions. Pieces are numbered from one, and each must appear exactly once.
+    fn valid_state(value: &[u8]) -> bool {
+        let nibble = |idx: usize| {
+            if idx % 2 == 0 {
+                value[idx / 2] >> 4
+            } else {
+                value[idx / 2] & 0xf
+            }
+        };
+        let corners: HashSet<u8> = (0..8).map(nibble).collect();
+        let edges: HashSet<u8> = (16..28).map(nibble).collect();
+        corners == (1..=8).collect() && edges == (1..=12).collect()
+    }
 }
 
 impl<P: Peripheral + 'static> BluetoothCubeDevice for GiikerCube<P> {
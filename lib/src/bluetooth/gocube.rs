@@ -114,12 +114,14 @@ impl<P: Peripheral + 'static> GoCube<P> {
                         return;
                     }
 
+                    // A state that isn't valid means the cube is out of sync. The
+                    // state has still been received, so connecting can continue.
                     if let Ok(state) = Self::decode_cube_state(&value.value) {
                         *state_copy.lock().unwrap() = state;
-                        *state_set_copy.lock().unwrap() = true;
                     } else {
                         *synced_copy.lock().unwrap() = false;
                     }
+                    *state_set_copy.lock().unwrap() = true;
                 }
                 Self::BATTERY_MESSAGE => {
                     *battery_percentage_copy.lock().unwrap() = Some(value.value[3] as u32);
@@ -220,8 +222,10 @@ impl<P: Peripheral + 'static> GoCube<P> {
             }
         }
 
-        // Create cube state and convert to normal format
-        Ok(Cube3x3x3Faces::from_colors(state).as_pieces())
+        // Create cube state, make sure it is valid, and convert to normal format
+        let state = Cube3x3x3Faces::from_colors(state);
+        state.validate()?;
+        Ok(state.as_pieces())
     }
 }
 
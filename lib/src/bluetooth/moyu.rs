@@ -63,6 +63,12 @@ impl<P: Peripheral + 'static> MoYuCube<P> {
                         / 65536.0;
                     let face = turn[4];
                     let direction = turn[5] as i8 / 36;
+                    if face as usize >= Self::FACES.len() {
+                        // Turn of a face that doesn't exist, the cube state can no
+                        // longer be tracked
+                        *synced_copy.lock().unwrap() = false;
+                        return;
+                    }
 
                     // Decode face rotation into moves
                     let old_rotation = face_rotations[face as usize];
@@ -1,7 +1,9 @@
 use crate::rand::{RandomSource, StandardRandomSource};
+use crate::{Edge3x3x3, Edge4x4x4, F2LSlot, ScrambleConstraint, HARD_CROSS_MOVES};
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local};
 use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
 use std::cmp::{Ord, Ordering, PartialOrd};
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -12,7 +14,7 @@ use uuid::Uuid;
 use std::convert::TryInto;
 
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy, TryFromPrimitive)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, TryFromPrimitive, Serialize, Deserialize)]
 /// Colors of the cube
 pub enum Color {
     White = 0,
@@ -44,6 +46,129 @@ pub struct CornerPiece {
     pub orientation: u8,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Reason that a cube state is not reachable from the solved state
+pub enum CubeStateError {
+    /// A color does not appear on the expected number of stickers
+    ColorCount {
+        color: Color,
+        count: usize,
+        expected: usize,
+    },
+    /// A center is not in its solved position (white on top, green in front)
+    MisplacedCenter { face: CubeFace, color: Color },
+    /// The stickers at a corner position do not form any corner piece
+    InvalidCorner {
+        position: Corner,
+        colors: [Color; 3],
+    },
+    /// A corner piece appears in more than one position
+    DuplicateCorner { piece: Corner, position: Corner },
+    /// The stickers at an edge position do not form any edge piece
+    InvalidEdge {
+        position: Edge3x3x3,
+        colors: [Color; 2],
+    },
+    /// An edge piece appears in more than one position
+    DuplicateEdge {
+        piece: Edge3x3x3,
+        position: Edge3x3x3,
+    },
+    /// The stickers at an edge wing position do not form any edge wing piece, or form a
+    /// wing that has been flipped in place
+    InvalidWing {
+        position: Edge4x4x4,
+        colors: [Color; 2],
+    },
+    /// An edge wing piece appears in more than one position
+    DuplicateWing {
+        piece: Edge4x4x4,
+        position: Edge4x4x4,
+    },
+    /// Corner orientations do not add up to a multiple of 3. The twist is the number of
+    /// clockwise twists left over.
+    CornerTwist { twist: u8 },
+    /// Edge orientations do not add up to a multiple of 2 (a single edge is flipped)
+    EdgeFlip,
+    /// Corner and edge permutations have different parity (two pieces are swapped)
+    PermutationParity,
+}
+
+impl std::fmt::Display for CubeStateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CubeStateError::ColorCount {
+                color,
+                count,
+                expected,
+            } => write!(
+                f,
+                "{} appears on {} stickers (expected {})",
+                color.to_str(),
+                count,
+                expected
+            ),
+            CubeStateError::MisplacedCenter { face, color } => write!(
+                f,
+                "{:?} center is {} (expected {})",
+                face,
+                color.to_str(),
+                face.color().to_str()
+            ),
+            CubeStateError::InvalidCorner { position, colors } => write!(
+                f,
+                "Corner at {:?} has colors {}/{}/{}, which is not a valid piece",
+                position,
+                colors[0].to_str(),
+                colors[1].to_str(),
+                colors[2].to_str()
+            ),
+            CubeStateError::DuplicateCorner { piece, position } => write!(
+                f,
+                "Corner piece {:?} appears more than once (again at {:?})",
+                piece, position
+            ),
+            CubeStateError::InvalidEdge { position, colors } => write!(
+                f,
+                "Edge at {:?} has colors {}/{}, which is not a valid piece",
+                position,
+                colors[0].to_str(),
+                colors[1].to_str()
+            ),
+            CubeStateError::DuplicateEdge { piece, position } => write!(
+                f,
+                "Edge piece {:?} appears more than once (again at {:?})",
+                piece, position
+            ),
+            CubeStateError::InvalidWing { position, colors } => write!(
+                f,
+                "Edge wing at {:?} has colors {}/{}, which is not a valid piece",
+                position,
+                colors[0].to_str(),
+                colors[1].to_str()
+            ),
+            CubeStateError::DuplicateWing { piece, position } => write!(
+                f,
+                "Edge wing piece {:?} appears more than once (again at {:?})",
+                piece, position
+            ),
+            CubeStateError::CornerTwist { twist } => write!(
+                f,
+                "A corner is twisted {}",
+                if *twist == 1 {
+                    "clockwise"
+                } else {
+                    "counter-clockwise"
+                }
+            ),
+            CubeStateError::EdgeFlip => write!(f, "An edge is flipped"),
+            CubeStateError::PermutationParity => write!(f, "Two pieces are swapped"),
+        }
+    }
+}
+
+impl std::error::Error for CubeStateError {}
+
 #[cfg(not(feature = "no_solver"))]
 pub(crate) struct CornerOrientationMoveTable;
 #[cfg(not(feature = "no_solver"))]
@@ -80,6 +205,87 @@ pub(crate) const fn factorial(mut n: usize) -> usize {
     result
 }
 
+/// Determines if a permutation is odd. Each entry is the index of the item that is placed
+/// at that position.
+pub(crate) fn odd_permutation(permutation: &[usize]) -> bool {
+    let mut visited = vec![false; permutation.len()];
+    let mut swaps = 0;
+    for i in 0..permutation.len() {
+        // Each cycle of length n requires n - 1 swaps
+        let mut j = i;
+        let mut cycle_len = 0;
+        while !visited[j] {
+            visited[j] = true;
+            j = permutation[j];
+            cycle_len += 1;
+        }
+        if cycle_len > 0 {
+            swaps += cycle_len - 1;
+        }
+    }
+    (swaps & 1) != 0
+}
+
+/// Counts the stickers of each color and checks them against the expected count
+pub(crate) fn validate_color_counts(
+    colors: &[Color],
+    expected: usize,
+) -> Result<(), CubeStateError> {
+    let mut counts = [0; 6];
+    for color in colors {
+        counts[*color as u8 as usize] += 1;
+    }
+    for (i, count) in counts.iter().enumerate() {
+        if *count != expected {
+            return Err(CubeStateError::ColorCount {
+                color: Color::try_from(i as u8).unwrap(),
+                count: *count,
+                expected,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every corner piece is present exactly once and that the corner twist is
+/// solvable
+pub(crate) fn validate_corners(corners: &[CornerPiece; 8]) -> Result<(), CubeStateError> {
+    let mut seen = [false; 8];
+    let mut twist = 0;
+    for (i, corner) in corners.iter().enumerate() {
+        if seen[corner.piece as u8 as usize] {
+            return Err(CubeStateError::DuplicateCorner {
+                piece: corner.piece,
+                position: Corner::try_from(i as u8).unwrap(),
+            });
+        }
+        seen[corner.piece as u8 as usize] = true;
+        twist += corner.orientation;
+    }
+    if twist % 3 != 0 {
+        return Err(CubeStateError::CornerTwist { twist: twist % 3 });
+    }
+    Ok(())
+}
+
+/// Finds the corner piece and orientation that has the given colors, in the order of the
+/// faces at the corner
+pub(crate) fn corner_from_colors(colors: [Color; 3]) -> Option<CornerPiece> {
+    for i in 0..8 {
+        for orientation in 0..3 {
+            if (0..3).all(|j| {
+                colors[(j + orientation) % 3] == crate::tables::corner::CUBE_CORNER_COLORS[i][j]
+            }) {
+                return Some(CornerPiece {
+                    piece: Corner::try_from(i as u8).unwrap(),
+                    orientation: orientation as u8,
+                });
+            }
+        }
+    }
+    None
+}
+
 impl Color {
     pub fn face(&self) -> CubeFace {
         match self {
@@ -138,6 +344,79 @@ impl CubeFace {
             CubeFace::Bottom => CubeFace::Top,
         }
     }
+
+    /// Letter used for this face in facelet strings
+    pub fn facelet(&self) -> char {
+        match self {
+            CubeFace::Top => 'U',
+            CubeFace::Front => 'F',
+            CubeFace::Right => 'R',
+            CubeFace::Back => 'B',
+            CubeFace::Left => 'L',
+            CubeFace::Bottom => 'D',
+        }
+    }
+
+    pub fn from_facelet(facelet: char) -> Option<CubeFace> {
+        match facelet.to_ascii_uppercase() {
+            'U' => Some(CubeFace::Top),
+            'F' => Some(CubeFace::Front),
+            'R' => Some(CubeFace::Right),
+            'B' => Some(CubeFace::Back),
+            'L' => Some(CubeFace::Left),
+            'D' => Some(CubeFace::Bottom),
+            _ => None,
+        }
+    }
+}
+
+/// Order of faces in facelet strings (URFDLB). Each face is row major using the same
+/// orientation as the face arrays, so only the face order differs.
+const FACELET_ORDER: [CubeFace; 6] = [
+    CubeFace::Top,
+    CubeFace::Right,
+    CubeFace::Front,
+    CubeFace::Bottom,
+    CubeFace::Left,
+    CubeFace::Back,
+];
+
+/// Converts a face color array into a facelet string. Each sticker is given the letter
+/// of the face whose center has that color in the solved state.
+pub(crate) fn colors_to_facelets(state: &[Color], face_size: usize) -> String {
+    let mut result = String::with_capacity(state.len());
+    for face in &FACELET_ORDER {
+        let start = *face as u8 as usize * face_size;
+        for color in &state[start..start + face_size] {
+            result.push(color.face().facelet());
+        }
+    }
+    result
+}
+
+/// Parses a facelet string into a face color array. Whitespace is ignored so that
+/// strings split by face can be pasted directly.
+pub(crate) fn colors_from_facelets(string: &str, face_size: usize) -> Result<Vec<Color>> {
+    let facelets: Vec<char> = string.chars().filter(|c| !c.is_whitespace()).collect();
+    if facelets.len() != face_size * 6 {
+        return Err(anyhow!(
+            "Facelet string has {} stickers, expected {}",
+            facelets.len(),
+            face_size * 6
+        ));
+    }
+
+    let mut state = vec![Color::White; face_size * 6];
+    for (i, face) in FACELET_ORDER.iter().enumerate() {
+        let start = *face as u8 as usize * face_size;
+        for j in 0..face_size {
+            let facelet = facelets[i * face_size + j];
+            state[start + j] = CubeFace::from_facelet(facelet)
+                .ok_or_else(|| anyhow!("Invalid facelet '{}'", facelet))?
+                .color();
+        }
+    }
+    Ok(state)
 }
 
 #[repr(u8)]
@@ -149,7 +428,7 @@ pub enum RotationDirection {
 }
 
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy, TryFromPrimitive)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, TryFromPrimitive, Serialize, Deserialize)]
 /// Move to perform on a cube
 pub enum Move {
     U = 0,
@@ -226,6 +505,7 @@ pub struct Solve {
     pub penalty: Penalty,
     pub device: Option<String>,
     pub moves: Option<Vec<TimedMove>>,
+    pub target_slot: Option<F2LSlot>,
 }
 
 impl Solve {
@@ -591,6 +871,14 @@ pub enum SolveType {
     Clock = 14,*/
     OLLTraining = 15,
     PLLTraining = 16,
+    DailyChallenge3x3x3 = 17,
+    HardCrossTraining = 18,
+    LastSlotTraining = 19,
+    F2LTraining = 20,
+    CrossTraining = 21,
+    XCrossTraining = 22,
+    OLLRecognition = 23,
+    PLLRecognition = 24,
 }
 
 impl SolveType {
@@ -613,6 +901,14 @@ impl SolveType {
             "Clock" => Some(SolveType::Clock),*/
             "OLL Training" => Some(SolveType::OLLTraining),
             "PLL Training" => Some(SolveType::PLLTraining),
+            "3x3x3 Daily Challenge" => Some(SolveType::DailyChallenge3x3x3),
+            "Hard Cross Training" => Some(SolveType::HardCrossTraining),
+            "Last Slot Training" => Some(SolveType::LastSlotTraining),
+            "F2L Training" => Some(SolveType::F2LTraining),
+            "Cross Training" => Some(SolveType::CrossTraining),
+            "XCross Training" => Some(SolveType::XCrossTraining),
+            "OLL Recognition" => Some(SolveType::OLLRecognition),
+            "PLL Recognition" => Some(SolveType::PLLRecognition),
             _ => None,
         }
     }
@@ -620,7 +916,12 @@ impl SolveType {
     pub fn is_3x3x3(&self) -> bool {
         matches!(
             self,
-            SolveType::Standard3x3x3 | SolveType::OneHanded3x3x3 | SolveType::Blind3x3x3
+            SolveType::Standard3x3x3
+                | SolveType::OneHanded3x3x3
+                | SolveType::Blind3x3x3
+                | SolveType::DailyChallenge3x3x3
+                | SolveType::HardCrossTraining
+                | SolveType::LastSlotTraining
         )
     }
 
@@ -631,6 +932,51 @@ impl SolveType {
     pub fn is_last_layer_training(&self) -> bool {
         matches!(self, SolveType::OLLTraining | SolveType::PLLTraining)
     }
+
+    /// F2L training solves start with the cross and three pairs solved, and are complete
+    /// when the last pair is inserted
+    pub fn is_f2l_training(&self) -> bool {
+        matches!(self, SolveType::F2LTraining)
+    }
+
+    /// Cross planning solves time the inspection, and with a Bluetooth cube are complete
+    /// once the cross (and the target pair for an XCross) is solved
+    pub fn is_cross_training(&self) -> bool {
+        matches!(self, SolveType::CrossTraining | SolveType::XCrossTraining)
+    }
+
+    /// Recognition drills show a last layer case without a cube to solve, and time how
+    /// long it takes to name the case. Wrong answers have a recognition DNF penalty.
+    pub fn is_recognition_training(&self) -> bool {
+        matches!(self, SolveType::OLLRecognition | SolveType::PLLRecognition)
+    }
+
+    /// Training modes that drill a single algorithm case in each solve. Solves in these
+    /// modes can be marked as misrecognized or misexecuted.
+    pub fn is_algorithm_training(&self) -> bool {
+        self.is_last_layer_training() || self.is_f2l_training()
+    }
+
+    /// Daily challenge solves use scrambles derived from the date, so they are kept
+    /// in their own sessions with one session per day
+    pub fn is_daily_challenge(&self) -> bool {
+        matches!(self, SolveType::DailyChallenge3x3x3)
+    }
+
+    /// Constraints on the scrambled state for training modes that drill specific
+    /// situations. The cross is on the white face, which is on top after scrambling.
+    pub fn scramble_constraints(&self) -> Vec<ScrambleConstraint> {
+        match self {
+            SolveType::HardCrossTraining => vec![ScrambleConstraint::MinimumCrossMoves(
+                CubeFace::Top,
+                HARD_CROSS_MOVES,
+            )],
+            SolveType::LastSlotTraining | SolveType::F2LTraining => {
+                vec![ScrambleConstraint::SolvedF2LPairs(CubeFace::Top, 3)]
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl ToString for SolveType {
@@ -653,6 +999,14 @@ impl ToString for SolveType {
             SolveType::Clock => "Clock".into(),*/
             SolveType::OLLTraining => "OLL Training".into(),
             SolveType::PLLTraining => "PLL Training".into(),
+            SolveType::DailyChallenge3x3x3 => "3x3x3 Daily Challenge".into(),
+            SolveType::HardCrossTraining => "Hard Cross Training".into(),
+            SolveType::LastSlotTraining => "Last Slot Training".into(),
+            SolveType::F2LTraining => "F2L Training".into(),
+            SolveType::CrossTraining => "Cross Training".into(),
+            SolveType::XCrossTraining => "XCross Training".into(),
+            SolveType::OLLRecognition => "OLL Recognition".into(),
+            SolveType::PLLRecognition => "PLL Recognition".into(),
         }
     }
 }
@@ -1477,8 +1831,12 @@ pub fn parse_timed_move_string(string: &str) -> Result<Vec<TimedMove>> {
 
 /// Generates a random scramble
 pub fn scramble_megaminx() -> Vec<Move> {
+    sourced_scramble_megaminx(&mut StandardRandomSource)
+}
+
+/// Generates a random scramble with a given random number source
+pub fn sourced_scramble_megaminx<T: RandomSource>(rand: &mut T) -> Vec<Move> {
     let mut result = Vec::new();
-    let mut rand = StandardRandomSource;
     for _ in 0..7 {
         for _ in 0..5 {
             result.push([Move::Rpp, Move::Rmm][rand.next(2) as usize]);
@@ -0,0 +1,317 @@
+use crate::common::odd_permutation;
+use crate::cube3x3x3::{cross_edges, f2l_slots};
+use crate::{
+    Corner, CornerPiece, CrossMoveTable, Cube, Cube3x3x3, CubeFace, Edge3x3x3, EdgePiece3x3x3,
+    F2LAlgorithm, InitialCubeState, LastLayerRandomization, OLLAlgorithm, PLLAlgorithm,
+    RandomSource,
+};
+use std::convert::TryFrom;
+
+#[cfg(not(feature = "no_solver"))]
+use crate::{Move, MoveSequence, StandardRandomSource};
+
+/// Largest number of moves required to optimally solve a cross from any state
+pub const MAX_CROSS_MOVES: usize = 8;
+
+/// Minimum optimal cross length used by hard cross training. Around 18% of random
+/// states require this many moves or more.
+pub const HARD_CROSS_MOVES: usize = 7;
+
+/// Number of candidate states to try before giving up on a set of constraints
+const MAX_CONSTRAINED_ATTEMPTS: usize = 100_000;
+
+/// Constraint on the cube state that results from a generated 3x3x3 scramble
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrambleConstraint {
+    /// An optimal solution of the cross on the given face requires at least this many moves
+    MinimumCrossMoves(CubeFace, usize),
+    /// An optimal solution of the cross on the given face requires at most this many moves
+    MaximumCrossMoves(CubeFace, usize),
+    /// The cross on the given face is solved along with exactly this many F2L pairs
+    SolvedF2LPairs(CubeFace, usize),
+    /// The first two layers are solved and the last layer on top is the given OLL case
+    OLLCase(OLLAlgorithm),
+    /// The first two layers are solved and the last layer on top is oriented, with the
+    /// given PLL case
+    PLLCase(PLLAlgorithm),
+    /// The cross on the given face and three F2L pairs are solved, with the last pair in
+    /// the given F2L case
+    F2LCase(CubeFace, F2LAlgorithm),
+    /// All edges are oriented with respect to the front and back faces, as after the
+    /// first step of the ZZ method
+    EdgesOriented,
+}
+
+/// A set of constraints that a generated 3x3x3 scramble must satisfy
+pub struct ScrambleConstraints {
+    constraints: Vec<ScrambleConstraint>,
+    cross_tables: Vec<(CubeFace, CrossMoveTable)>,
+}
+
+fn corner_solved(cube: &Cube3x3x3, corner: Corner) -> bool {
+    cube.corner_piece(corner)
+        == CornerPiece {
+            piece: corner,
+            orientation: 0,
+        }
+}
+
+fn edge_solved(cube: &Cube3x3x3, edge: Edge3x3x3) -> bool {
+    cube.edge_piece(edge)
+        == EdgePiece3x3x3 {
+            piece: edge,
+            orientation: 0,
+        }
+}
+
+fn cross_solved(cube: &Cube3x3x3, face: CubeFace) -> bool {
+    cross_edges(face)
+        .iter()
+        .all(|edge| edge_solved(cube, *edge))
+}
+
+fn solved_f2l_pair_count(cube: &Cube3x3x3, face: CubeFace) -> usize {
+    f2l_slots(face)
+        .iter()
+        .filter(|(corner, edge)| corner_solved(cube, *corner) && edge_solved(cube, *edge))
+        .count()
+}
+
+/// Adds the cross edges of `face` and `pairs` randomly chosen F2L pairs to the pieces
+/// that are solved in a generated state
+fn add_solved_f2l_pairs<T: RandomSource>(
+    rng: &mut T,
+    face: CubeFace,
+    pairs: usize,
+    solved_corners: &mut Vec<Corner>,
+    solved_edges: &mut Vec<Edge3x3x3>,
+) {
+    solved_edges.append(&mut cross_edges(face));
+    let mut slots = f2l_slots(face);
+    for _ in 0..pairs.min(slots.len()) {
+        let (corner, edge) = slots.remove(rng.next(slots.len() as u32) as usize);
+        solved_corners.push(corner);
+        solved_edges.push(edge);
+    }
+}
+
+/// Randomly permutes the pieces at the given positions
+fn shuffle<T: RandomSource, P>(rng: &mut T, pieces: &mut [P], positions: &[usize]) {
+    for i in (1..positions.len()).rev() {
+        let j = rng.next(i as u32 + 1) as usize;
+        pieces.swap(positions[i], positions[j]);
+    }
+}
+
+/// Generates a random cube state where the given pieces are solved. All other pieces
+/// are placed randomly, and are oriented randomly unless `edges_oriented` is set, in
+/// which case all edges are oriented.
+fn random_state_with_solved_pieces<T: RandomSource>(
+    rng: &mut T,
+    solved_corners: &[Corner],
+    solved_edges: &[Edge3x3x3],
+    edges_oriented: bool,
+) -> Cube3x3x3 {
+    let solved = Cube3x3x3::new();
+    let mut corners = [solved.corner_piece(Corner::URF); 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        *corner = solved.corner_piece(Corner::try_from(i as u8).unwrap());
+    }
+    let mut edges = [solved.edge_piece(Edge3x3x3::UR); 12];
+    for (i, edge) in edges.iter_mut().enumerate() {
+        *edge = solved.edge_piece(Edge3x3x3::try_from(i as u8).unwrap());
+    }
+
+    let free_corners: Vec<usize> = (0..8)
+        .filter(|i| !solved_corners.iter().any(|c| *c as u8 as usize == *i))
+        .collect();
+    let free_edges: Vec<usize> = (0..12)
+        .filter(|i| !solved_edges.iter().any(|e| *e as u8 as usize == *i))
+        .collect();
+
+    // Randomize the free pieces
+    shuffle(rng, &mut corners, &free_corners);
+    shuffle(rng, &mut edges, &free_edges);
+
+    // Corner and edge permutations must have the same parity (otherwise it is not solvable)
+    let corner_permutation: Vec<usize> = corners.iter().map(|c| c.piece as u8 as usize).collect();
+    let edge_permutation: Vec<usize> = edges.iter().map(|e| e.piece as u8 as usize).collect();
+    if odd_permutation(&corner_permutation) != odd_permutation(&edge_permutation) {
+        if free_edges.len() >= 2 {
+            edges.swap(free_edges[0], free_edges[1]);
+        } else if free_corners.len() >= 2 {
+            corners.swap(free_corners[0], free_corners[1]);
+        }
+    }
+
+    // Randomize the orientations of the free pieces, with the last one making the sum
+    // of the orientations valid
+    if let Some((last, rest)) = free_corners.split_last() {
+        let mut corner_orientation_sum = 0;
+        for i in rest {
+            corners[*i].orientation = rng.next(3) as u8;
+            corner_orientation_sum += corners[*i].orientation;
+        }
+        corners[*last].orientation = (3 - (corner_orientation_sum % 3)) % 3;
+    }
+    if !edges_oriented {
+        if let Some((last, rest)) = free_edges.split_last() {
+            let mut edge_orientation_sum = 0;
+            for i in rest {
+                edges[*i].orientation = rng.next(2) as u8;
+                edge_orientation_sum += edges[*i].orientation;
+            }
+            edges[*last].orientation = edge_orientation_sum & 1;
+        }
+    }
+
+    Cube3x3x3::from_corners_and_edges(corners, edges)
+}
+
+impl ScrambleConstraints {
+    pub fn new(constraints: &[ScrambleConstraint]) -> Self {
+        // Build the cross tables up front, as they are needed for every candidate state
+        let mut cross_tables: Vec<(CubeFace, CrossMoveTable)> = Vec::new();
+        for constraint in constraints {
+            match constraint {
+                ScrambleConstraint::MinimumCrossMoves(face, _)
+                | ScrambleConstraint::MaximumCrossMoves(face, _)
+                    if !cross_tables
+                        .iter()
+                        .any(|(table_face, _)| table_face == face) =>
+                {
+                    cross_tables.push((*face, CrossMoveTable::new(*face)));
+                }
+                _ => (),
+            }
+        }
+
+        Self {
+            constraints: constraints.to_vec(),
+            cross_tables,
+        }
+    }
+
+    fn cross_moves(&self, cube: &Cube3x3x3, face: CubeFace) -> usize {
+        self.cross_tables
+            .iter()
+            .find(|(table_face, _)| *table_face == face)
+            .unwrap()
+            .1
+            .moves(cube)
+    }
+
+    /// Checks if a cube state satisfies all of the constraints
+    pub fn is_satisfied(&self, cube: &Cube3x3x3) -> bool {
+        self.constraints.iter().all(|constraint| match constraint {
+            ScrambleConstraint::MinimumCrossMoves(face, moves) => {
+                self.cross_moves(cube, *face) >= *moves
+            }
+            ScrambleConstraint::MaximumCrossMoves(face, moves) => {
+                self.cross_moves(cube, *face) <= *moves
+            }
+            ScrambleConstraint::SolvedF2LPairs(face, pairs) => {
+                cross_solved(cube, *face) && solved_f2l_pair_count(cube, *face) == *pairs
+            }
+            ScrambleConstraint::OLLCase(case) => {
+                cube.first_two_layers_solved(CubeFace::Bottom)
+                    && OLLAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top) == Some(*case)
+            }
+            ScrambleConstraint::PLLCase(case) => {
+                cube.first_two_layers_solved(CubeFace::Bottom)
+                    && (0..12)
+                        .all(|i| cube.edge_piece(Edge3x3x3::try_from(i).unwrap()).orientation == 0)
+                    && (0..8)
+                        .all(|i| cube.corner_piece(Corner::try_from(i).unwrap()).orientation == 0)
+                    && PLLAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top) == Some(*case)
+            }
+            ScrambleConstraint::F2LCase(face, case) => {
+                F2LAlgorithm::from_cube(&cube.as_faces(), *face) == Some(*case)
+            }
+            ScrambleConstraint::EdgesOriented => {
+                (0..12).all(|i| cube.edge_piece(Edge3x3x3::try_from(i).unwrap()).orientation == 0)
+            }
+        })
+    }
+
+    /// Generates a candidate state. Constraints that fix pieces in place are satisfied
+    /// by construction, the rest are left to the caller to check.
+    fn sourced_candidate<T: RandomSource>(&self, rng: &mut T) -> Cube3x3x3 {
+        let mut solved_corners = Vec::new();
+        let mut solved_edges = Vec::new();
+        let mut edges_oriented = false;
+        for constraint in &self.constraints {
+            match constraint {
+                ScrambleConstraint::OLLCase(case) => {
+                    return Cube3x3x3::sourced_random_last_layer(
+                        rng,
+                        CubeFace::Top,
+                        LastLayerRandomization::RandomOLL(vec![*case]),
+                    );
+                }
+                ScrambleConstraint::PLLCase(case) => {
+                    return Cube3x3x3::sourced_random_last_layer(
+                        rng,
+                        CubeFace::Top,
+                        LastLayerRandomization::RandomPLL(vec![*case]),
+                    );
+                }
+                ScrambleConstraint::SolvedF2LPairs(face, pairs) => {
+                    add_solved_f2l_pairs(
+                        rng,
+                        *face,
+                        *pairs,
+                        &mut solved_corners,
+                        &mut solved_edges,
+                    );
+                }
+                ScrambleConstraint::F2LCase(face, _) => {
+                    add_solved_f2l_pairs(rng, *face, 3, &mut solved_corners, &mut solved_edges);
+                }
+                ScrambleConstraint::EdgesOriented => edges_oriented = true,
+                ScrambleConstraint::MinimumCrossMoves(_, _)
+                | ScrambleConstraint::MaximumCrossMoves(_, _) => (),
+            }
+        }
+        random_state_with_solved_pieces(rng, &solved_corners, &solved_edges, edges_oriented)
+    }
+
+    /// Generates a random unsolved cube state that satisfies all of the constraints.
+    /// Returns `None` if no such state was found, which happens when the constraints
+    /// conflict with each other.
+    pub fn sourced_random_state<T: RandomSource>(&self, rng: &mut T) -> Option<Cube3x3x3> {
+        for _ in 0..MAX_CONSTRAINED_ATTEMPTS {
+            let cube = self.sourced_candidate(rng);
+            if !cube.is_solved() && self.is_satisfied(&cube) {
+                return Some(cube);
+            }
+        }
+        None
+    }
+
+    /// Generates a random scramble that satisfies all of the constraints. Returns `None`
+    /// if the constraints could not be satisfied.
+    #[cfg(not(feature = "no_solver"))]
+    pub fn sourced_scramble<T: RandomSource>(&self, rng: &mut T) -> Option<Vec<Move>> {
+        let state = self.sourced_random_state(rng)?;
+        let solution = state.solve().unwrap();
+        Some(solution.inverse())
+    }
+}
+
+/// Generates a random scramble where the resulting state satisfies all of the given
+/// constraints. Returns `None` if the constraints could not be satisfied.
+#[cfg(not(feature = "no_solver"))]
+pub fn constrained_scramble_3x3x3(constraints: &[ScrambleConstraint]) -> Option<Vec<Move>> {
+    sourced_constrained_scramble_3x3x3(&mut StandardRandomSource, constraints)
+}
+
+/// Generates a random scramble where the resulting state satisfies all of the given
+/// constraints, with a given random number source
+#[cfg(not(feature = "no_solver"))]
+pub fn sourced_constrained_scramble_3x3x3<T: RandomSource>(
+    rng: &mut T,
+    constraints: &[ScrambleConstraint],
+) -> Option<Vec<Move>> {
+    ScrambleConstraints::new(constraints).sourced_scramble(rng)
+}
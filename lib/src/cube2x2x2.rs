@@ -1,7 +1,8 @@
 use crate::{
-    Color, Corner, CornerPiece, Cube, CubeFace, FaceRotation, InitialCubeState, Move, RandomSource,
-    RotationDirection,
+    Color, Corner, CornerPiece, Cube, CubeFace, CubeStateError, FaceRotation, InitialCubeState,
+    Move, RandomSource, RotationDirection,
 };
+use anyhow::Result;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
@@ -10,6 +11,8 @@ use crate::common::{
     CornerOrientationMoveTable, CornerOrientationPruneTable, CornerPermutationMoveTable,
     CornerPermutationPruneTable, MoveSequence,
 };
+#[cfg(not(feature = "no_solver"))]
+use crate::StandardRandomSource;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// A 2x2x2 cube represented in piece format (optimal for computational algorithms).
@@ -143,6 +146,13 @@ impl Cube2x2x2 {
         self.corners[corner as u8 as usize]
     }
 
+    /// Checks that this cube state can be reached from the solved state. Every corner
+    /// must be present exactly once and the corner twist must be solvable. There is no
+    /// permutation parity restriction on a 2x2x2.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_corners(&self.corners)
+    }
+
     /// Index for the corner orientations is a simple base 3 integer representation. The
     /// zero index is the solved state. Note that the last corner is not represented in
     /// the index as its value is implicit (all corner orientations must add to a
@@ -310,6 +320,20 @@ impl Cube2x2x2Faces {
         Self { state }
     }
 
+    /// Create a cube state from a facelet string. The string contains 24 face letters
+    /// (U, R, F, D, L, B) in URFDLB face order. The resulting state is not checked, use
+    /// `validate` before converting to piece format.
+    pub fn from_facelet_string(string: &str) -> Result<Self> {
+        let mut state = [Color::White; 6 * 4];
+        state.copy_from_slice(&crate::common::colors_from_facelets(string, 4)?);
+        Ok(Self { state })
+    }
+
+    /// Gets this cube state as a facelet string in URFDLB face order
+    pub fn to_facelet_string(&self) -> String {
+        crate::common::colors_to_facelets(&self.state, 4)
+    }
+
     pub(crate) const fn face_start(face: CubeFace) -> usize {
         face as u8 as usize * 4
     }
@@ -337,6 +361,29 @@ impl Cube2x2x2Faces {
         self.state[crate::tables::corner::CUBE2_CORNER_INDICIES[corner as u8 as usize][idx]]
     }
 
+    /// Checks that this cube state can be reached from the solved state. If this returns
+    /// an error, the result of `as_pieces` is not meaningful.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_color_counts(&self.state, 4)?;
+
+        for corner_idx in 0..8 {
+            let corner = Corner::try_from(corner_idx).unwrap();
+            let colors = [
+                self.corner_color(corner, 0),
+                self.corner_color(corner, 1),
+                self.corner_color(corner, 2),
+            ];
+            if crate::common::corner_from_colors(colors).is_none() {
+                return Err(CubeStateError::InvalidCorner {
+                    position: corner,
+                    colors,
+                });
+            }
+        }
+
+        self.as_pieces().validate()
+    }
+
     /// Gets this cube state in piece format
     pub fn as_pieces(&self) -> Cube2x2x2 {
         let mut pieces = Cube2x2x2::new();
@@ -542,7 +589,13 @@ impl std::fmt::Display for Cube2x2x2Faces {
 /// Generates a random scramble
 #[cfg(not(feature = "no_solver"))]
 pub fn scramble_2x2x2() -> Vec<Move> {
-    let state = Cube2x2x2::random();
+    sourced_scramble_2x2x2(&mut StandardRandomSource)
+}
+
+/// Generates a random scramble with a given random number source
+#[cfg(not(feature = "no_solver"))]
+pub fn sourced_scramble_2x2x2<T: RandomSource>(rng: &mut T) -> Vec<Move> {
+    let state = Cube2x2x2::sourced_random(rng);
     let solution = state.solve().unwrap();
     solution.inverse()
 }
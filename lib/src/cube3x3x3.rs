@@ -1,8 +1,10 @@
 use crate::{
-    Color, Corner, CornerPiece, Cube, CubeFace, CubeRotation, CubeRotationAxis, ExtendedMove,
-    ExtendedMoveContext, ExtendedMoveSequence, FaceRotation, InitialCubeState, KnownAlgorithms,
-    Move, OLLAlgorithm, PLLAlgorithm, RandomSource, RotationDirection, StandardRandomSource,
+    COLLAlgorithm, Color, Corner, CornerPiece, Cube, CubeFace, CubeRotation, CubeRotationAxis,
+    CubeStateError, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence, F2LSlot, FaceRotation,
+    InitialCubeState, KnownAlgorithms, Move, OLLAlgorithm, OLLCPAlgorithm, PLLAlgorithm,
+    RandomSource, RotationDirection, StandardRandomSource, ZBLLAlgorithm,
 };
+use anyhow::Result;
 use num_enum::TryFromPrimitive;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -500,6 +502,75 @@ pub enum LastLayerRandomization {
     WeightedRandomInvertedOLLAlgorithm(BTreeMap<OLLAlgorithm, Vec<ExtendedMove>>),
     /// Random PLL case with realistic probability for each case
     WeightedRandomPLL(Vec<PLLAlgorithm>),
+    /// Random COLL case with equal probability for each case (edge permutation is random)
+    RandomCOLL(Vec<COLLAlgorithm>),
+    /// Random ZBLL case with equal probability for each case
+    RandomZBLL(Vec<ZBLLAlgorithm>),
+    /// Random OLLCP case with equal probability for each case (edge permutation is random)
+    RandomOLLCP(Vec<OLLCPAlgorithm>),
+    /// Random COLL case with realistic probability for each case (edge permutation is random)
+    WeightedRandomCOLL(Vec<COLLAlgorithm>),
+    /// Random ZBLL case with realistic probability for each case
+    WeightedRandomZBLL(Vec<ZBLLAlgorithm>),
+    /// Random OLLCP case with realistic probability for each case (edge permutation is random)
+    WeightedRandomOLLCP(Vec<OLLCPAlgorithm>),
+}
+
+/// Gets the edges that make up the cross on a face
+pub(crate) fn cross_edges(face: CubeFace) -> Vec<Edge3x3x3> {
+    (0..12)
+        .map(|i| Edge3x3x3::try_from(i as u8).unwrap())
+        .filter(|edge| {
+            crate::tables::table3x3x3::CUBE3_EDGE_INDICIES[*edge as u8 as usize]
+                .iter()
+                .any(|idx| Cube3x3x3Faces::face_for_idx(*idx) == face)
+        })
+        .collect()
+}
+
+/// Gets the corner and edge pieces of each F2L slot when the cross is on a face
+pub(crate) fn f2l_slots(face: CubeFace) -> Vec<(Corner, Edge3x3x3)> {
+    let mut slots = Vec::new();
+    for (i, indicies) in crate::tables::corner::CUBE3_CORNER_INDICIES
+        .iter()
+        .enumerate()
+    {
+        let faces: Vec<CubeFace> = indicies
+            .iter()
+            .map(|idx| Cube3x3x3Faces::face_for_idx(*idx))
+            .collect();
+        if !faces.contains(&face) {
+            continue;
+        }
+
+        // The edge of the slot is the one shared by the two side faces of the corner
+        let edge = (0..12)
+            .map(|i| Edge3x3x3::try_from(i as u8).unwrap())
+            .find(|edge| {
+                crate::tables::table3x3x3::CUBE3_EDGE_INDICIES[*edge as u8 as usize]
+                    .iter()
+                    .all(|idx| {
+                        let edge_face = Cube3x3x3Faces::face_for_idx(*idx);
+                        edge_face != face && faces.contains(&edge_face)
+                    })
+            })
+            .unwrap();
+        slots.push((Corner::try_from(i as u8).unwrap(), edge));
+    }
+    slots
+}
+
+/// Gets the corner and edge pieces of an F2L slot when the cross is on a face
+pub(crate) fn f2l_slot_pieces(face: CubeFace, slot: F2LSlot) -> (Corner, Edge3x3x3) {
+    let faces = slot.side_faces(face);
+    f2l_slots(face)
+        .into_iter()
+        .find(|(_, edge)| {
+            crate::tables::table3x3x3::CUBE3_EDGE_INDICIES[*edge as u8 as usize]
+                .iter()
+                .all(|idx| faces.contains(&Cube3x3x3Faces::face_for_idx(*idx)))
+        })
+        .unwrap()
 }
 
 impl Cube3x3x3 {
@@ -641,6 +712,18 @@ impl Cube3x3x3 {
         false
     }
 
+    /// Checks that the cross on `cross_face` and all four F2L pairs are solved
+    pub fn first_two_layers_solved(&self, cross_face: CubeFace) -> bool {
+        let solved = Self::new();
+        cross_edges(cross_face)
+            .iter()
+            .all(|edge| self.edge_piece(*edge) == solved.edge_piece(*edge))
+            && f2l_slots(cross_face).iter().all(|(corner, edge)| {
+                self.corner_piece(*corner) == solved.corner_piece(*corner)
+                    && self.edge_piece(*edge) == solved.edge_piece(*edge)
+            })
+    }
+
     pub fn random_last_layer(last_layer: CubeFace, randomization: LastLayerRandomization) -> Self {
         Self::sourced_random_last_layer(&mut StandardRandomSource, last_layer, randomization)
     }
@@ -837,6 +920,57 @@ impl Cube3x3x3 {
                     LastLayerRandomization::RandomPLL(weighted_cases),
                 )
             }
+            LastLayerRandomization::RandomCOLL(coll_choices) => match coll_choices.len() {
+                0 => Self::new(),
+                len => coll_choices[rng.next(len as u32) as usize].random_state(rng, last_layer),
+            },
+            LastLayerRandomization::RandomZBLL(zbll_choices) => match zbll_choices.len() {
+                0 => Self::new(),
+                len => zbll_choices[rng.next(len as u32) as usize].random_state(rng, last_layer),
+            },
+            LastLayerRandomization::RandomOLLCP(ollcp_choices) => match ollcp_choices.len() {
+                0 => Self::new(),
+                len => ollcp_choices[rng.next(len as u32) as usize].random_state(rng, last_layer),
+            },
+            LastLayerRandomization::WeightedRandomCOLL(coll_choices) => {
+                let mut weighted_cases = Vec::new();
+                for coll in coll_choices {
+                    for _ in 0..coll.probability_weight() {
+                        weighted_cases.push(coll);
+                    }
+                }
+                Self::sourced_random_last_layer(
+                    rng,
+                    last_layer,
+                    LastLayerRandomization::RandomCOLL(weighted_cases),
+                )
+            }
+            LastLayerRandomization::WeightedRandomZBLL(zbll_choices) => {
+                let mut weighted_cases = Vec::new();
+                for zbll in zbll_choices {
+                    for _ in 0..zbll.probability_weight() {
+                        weighted_cases.push(zbll);
+                    }
+                }
+                Self::sourced_random_last_layer(
+                    rng,
+                    last_layer,
+                    LastLayerRandomization::RandomZBLL(weighted_cases),
+                )
+            }
+            LastLayerRandomization::WeightedRandomOLLCP(ollcp_choices) => {
+                let mut weighted_cases = Vec::new();
+                for ollcp in ollcp_choices {
+                    for _ in 0..ollcp.probability_weight() {
+                        weighted_cases.push(ollcp);
+                    }
+                }
+                Self::sourced_random_last_layer(
+                    rng,
+                    last_layer,
+                    LastLayerRandomization::RandomOLLCP(weighted_cases),
+                )
+            }
         }
     }
 
@@ -850,6 +984,49 @@ impl Cube3x3x3 {
         self.edges[edge as u8 as usize]
     }
 
+    /// Checks that this cube state can be reached from the solved state. Every piece
+    /// must be present exactly once, the corner twist and edge flip must be solvable,
+    /// and the corner and edge permutations must have the same parity.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_corners(&self.corners)?;
+
+        let mut seen = [false; 12];
+        let mut flip = 0;
+        for (i, edge) in self.edges.iter().enumerate() {
+            if seen[edge.piece as u8 as usize] {
+                return Err(CubeStateError::DuplicateEdge {
+                    piece: edge.piece,
+                    position: Edge3x3x3::try_from(i as u8).unwrap(),
+                });
+            }
+            seen[edge.piece as u8 as usize] = true;
+            flip += edge.orientation;
+        }
+        if flip % 2 != 0 {
+            return Err(CubeStateError::EdgeFlip);
+        }
+
+        // Every move swaps the same parity of corners and edges, so the permutation
+        // parity of both sets of pieces must always match
+        let corner_permutation: Vec<usize> = self
+            .corners
+            .iter()
+            .map(|corner| corner.piece as u8 as usize)
+            .collect();
+        let edge_permutation: Vec<usize> = self
+            .edges
+            .iter()
+            .map(|edge| edge.piece as u8 as usize)
+            .collect();
+        if crate::common::odd_permutation(&corner_permutation)
+            != crate::common::odd_permutation(&edge_permutation)
+        {
+            return Err(CubeStateError::PermutationParity);
+        }
+
+        Ok(())
+    }
+
     /// Index for the corner orientations is a simple base 3 integer representation. The
     /// zero index is the solved state. Note that the last corner is not represented in
     /// the index as its value is implicit (all corner orientations must add to a
@@ -1165,6 +1342,20 @@ impl Cube3x3x3Faces {
         Self { state }
     }
 
+    /// Create a cube state from a facelet string. The string contains 54 face letters
+    /// (U, R, F, D, L, B) in URFDLB face order. The resulting state is not checked, use
+    /// `validate` before converting to piece format.
+    pub fn from_facelet_string(string: &str) -> Result<Self> {
+        let mut state = [Color::White; 6 * 9];
+        state.copy_from_slice(&crate::common::colors_from_facelets(string, 9)?);
+        Ok(Self { state })
+    }
+
+    /// Gets this cube state as a facelet string in URFDLB face order
+    pub fn to_facelet_string(&self) -> String {
+        crate::common::colors_to_facelets(&self.state, 9)
+    }
+
     pub(crate) const fn face_start(face: CubeFace) -> usize {
         face as u8 as usize * 9
     }
@@ -1203,6 +1394,56 @@ impl Cube3x3x3Faces {
         self.state[crate::tables::table3x3x3::CUBE3_EDGE_INDICIES[edge as u8 as usize][idx]]
     }
 
+    /// Checks that this cube state can be reached from the solved state. Centers must be
+    /// in the solved orientation (white on top, green in front). If this returns an error,
+    /// the result of `as_pieces` is not meaningful.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_color_counts(&self.state, 9)?;
+
+        for face in 0..6 {
+            let face = CubeFace::try_from(face).unwrap();
+            let color = self.color(face, 1, 1);
+            if color != face.color() {
+                return Err(CubeStateError::MisplacedCenter { face, color });
+            }
+        }
+
+        // Make sure every position holds a real piece, otherwise the piece format
+        // conversion will silently produce an incorrect state
+        for corner_idx in 0..8 {
+            let corner = Corner::try_from(corner_idx).unwrap();
+            let colors = [
+                self.corner_color(corner, 0),
+                self.corner_color(corner, 1),
+                self.corner_color(corner, 2),
+            ];
+            if crate::common::corner_from_colors(colors).is_none() {
+                return Err(CubeStateError::InvalidCorner {
+                    position: corner,
+                    colors,
+                });
+            }
+        }
+        for edge_idx in 0..12 {
+            let edge = Edge3x3x3::try_from(edge_idx).unwrap();
+            let colors = [self.edge_color(edge, 0), self.edge_color(edge, 1)];
+            if !crate::tables::table3x3x3::CUBE3_EDGE_COLORS
+                .iter()
+                .any(|piece| {
+                    (colors[0] == piece[0] && colors[1] == piece[1])
+                        || (colors[1] == piece[0] && colors[0] == piece[1])
+                })
+            {
+                return Err(CubeStateError::InvalidEdge {
+                    position: edge,
+                    colors,
+                });
+            }
+        }
+
+        self.as_pieces().validate()
+    }
+
     /// Gets this cube state in piece format
     pub fn as_pieces(&self) -> Cube3x3x3 {
         let mut pieces = Cube3x3x3::new();
@@ -1441,7 +1682,13 @@ impl std::fmt::Display for Cube3x3x3Faces {
 /// Generates a random scramble
 #[cfg(not(feature = "no_solver"))]
 pub fn scramble_3x3x3() -> Vec<Move> {
-    let state = Cube3x3x3::random();
+    sourced_scramble_3x3x3(&mut StandardRandomSource)
+}
+
+/// Generates a random scramble with a given random number source
+#[cfg(not(feature = "no_solver"))]
+pub fn sourced_scramble_3x3x3<T: RandomSource>(rng: &mut T) -> Vec<Move> {
+    let state = Cube3x3x3::sourced_random(rng);
     let solution = state.solve().unwrap();
     solution.inverse()
 }
@@ -1458,7 +1705,17 @@ pub fn scramble_3x3x3_fast() -> Vec<Move> {
 /// desired last layer on top.
 #[cfg(not(feature = "no_solver"))]
 pub fn scramble_last_layer(last_layer: LastLayerRandomization) -> Vec<Move> {
-    let state = Cube3x3x3::random_last_layer(CubeFace::Top, last_layer);
+    sourced_scramble_last_layer(&mut StandardRandomSource, last_layer)
+}
+
+/// Generates a random scramble for the last layer only with a given random number source.
+/// Moves should be applied with the desired last layer on top.
+#[cfg(not(feature = "no_solver"))]
+pub fn sourced_scramble_last_layer<T: RandomSource>(
+    rng: &mut T,
+    last_layer: LastLayerRandomization,
+) -> Vec<Move> {
+    let state = Cube3x3x3::sourced_random_last_layer(rng, CubeFace::Top, last_layer);
     let solution = state.solve().unwrap();
     solution.inverse()
 }
@@ -1,7 +1,8 @@
 use crate::{
-    Color, Corner, CornerPiece, Cube, CubeFace, FaceRotation, InitialCubeState, Move, RandomSource,
-    RotationDirection,
+    Color, Corner, CornerPiece, Cube, CubeFace, CubeStateError, FaceRotation, InitialCubeState,
+    Move, RandomSource, RotationDirection,
 };
+use anyhow::Result;
 use num_enum::TryFromPrimitive;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -11,6 +12,8 @@ use crate::common::MoveSequence;
 #[cfg(not(feature = "no_solver"))]
 use crate::Cube3x3x3Faces;
 #[cfg(not(feature = "no_solver"))]
+use crate::StandardRandomSource;
+#[cfg(not(feature = "no_solver"))]
 use std::convert::TryInto;
 
 #[repr(u8)]
@@ -1041,6 +1044,39 @@ impl Cube4x4x4 {
         self.edges[edge as u8 as usize]
     }
 
+    /// Checks that this cube state can be reached from the solved state. Every corner and
+    /// edge wing must be present exactly once and the corner twist must be solvable. Edge
+    /// wings cannot be flipped in place, so the orientation of a wing is determined by
+    /// its position. The permutation parity of the wings is not restricted on a 4x4x4.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_corners(&self.corners)?;
+
+        let mut seen = [false; 24];
+        for (i, edge) in self.edges.iter().enumerate() {
+            let position = Edge4x4x4::try_from(i as u8).unwrap();
+            if seen[edge.piece as u8 as usize] {
+                return Err(CubeStateError::DuplicateWing {
+                    piece: edge.piece,
+                    position,
+                });
+            }
+            seen[edge.piece as u8 as usize] = true;
+
+            // A wing is flipped when moving between positions of different parity, which
+            // is also what distinguishes the two wings of each edge
+            if edge.orientation != (edge.piece as u8 ^ i as u8) & 1 {
+                let mut colors =
+                    crate::tables::table4x4x4::CUBE4_EDGE_COLORS[edge.piece as u8 as usize / 2];
+                if edge.orientation != 0 {
+                    colors.swap(0, 1);
+                }
+                return Err(CubeStateError::InvalidWing { position, colors });
+            }
+        }
+
+        crate::common::validate_color_counts(&self.centers, 4)
+    }
+
     pub fn center_color(&self, face: CubeFace, row: usize, col: usize) -> Color {
         self.centers[Self::center_idx(face, row, col)]
     }
@@ -1746,6 +1782,20 @@ impl Cube4x4x4Faces {
         Self { state }
     }
 
+    /// Create a cube state from a facelet string. The string contains 96 face letters
+    /// (U, R, F, D, L, B) in URFDLB face order. The resulting state is not checked, use
+    /// `validate` before converting to piece format.
+    pub fn from_facelet_string(string: &str) -> Result<Self> {
+        let mut state = [Color::White; 6 * 16];
+        state.copy_from_slice(&crate::common::colors_from_facelets(string, 16)?);
+        Ok(Self { state })
+    }
+
+    /// Gets this cube state as a facelet string in URFDLB face order
+    pub fn to_facelet_string(&self) -> String {
+        crate::common::colors_to_facelets(&self.state, 16)
+    }
+
     pub(crate) const fn face_start(face: CubeFace) -> usize {
         face as u8 as usize * 16
     }
@@ -1789,6 +1839,58 @@ impl Cube4x4x4Faces {
         self.color_by_idx(Self::idx(center, row + 1, col + 1))
     }
 
+    /// Checks that this cube state can be reached from the solved state. If this returns
+    /// an error, the result of `as_pieces` is not meaningful.
+    pub fn validate(&self) -> Result<(), CubeStateError> {
+        crate::common::validate_color_counts(&self.state, 16)?;
+
+        for corner_idx in 0..8 {
+            let corner = Corner::try_from(corner_idx).unwrap();
+            let colors = [
+                self.corner_color(corner, 0),
+                self.corner_color(corner, 1),
+                self.corner_color(corner, 2),
+            ];
+            if crate::common::corner_from_colors(colors).is_none() {
+                return Err(CubeStateError::InvalidCorner {
+                    position: corner,
+                    colors,
+                });
+            }
+        }
+        // The order of the stickers at a position determines which of the two wings with
+        // those colors is there. A wing that was flipped in place shows the sticker order of
+        // the other wing, so the wing it is read as will also be seen at another position.
+        let mut seen = [false; 24];
+        for edge_idx in 0..24 {
+            let edge = Edge4x4x4::try_from(edge_idx).unwrap();
+            let colors = [self.edge_color(edge, 0), self.edge_color(edge, 1)];
+            let piece = crate::tables::table4x4x4::CUBE4_EDGE_COLORS
+                .iter()
+                .enumerate()
+                .find_map(|(i, piece)| {
+                    if colors[0] == piece[0] && colors[1] == piece[1] {
+                        Some((i * 2) as u8 ^ (edge_idx & 1))
+                    } else if colors[1] == piece[0] && colors[0] == piece[1] {
+                        Some((i * 2) as u8 ^ (edge_idx & 1) ^ 1)
+                    } else {
+                        None
+                    }
+                });
+            match piece {
+                Some(piece) if !seen[piece as usize] => seen[piece as usize] = true,
+                _ => {
+                    return Err(CubeStateError::InvalidWing {
+                        position: edge,
+                        colors,
+                    })
+                }
+            }
+        }
+
+        self.as_pieces().validate()
+    }
+
     /// Gets this cube state in piece format
     pub fn as_pieces(&self) -> Cube4x4x4 {
         let mut pieces = Cube4x4x4::new();
@@ -2091,7 +2193,13 @@ impl std::fmt::Display for Cube4x4x4Faces {
 /// Generates a random scramble
 #[cfg(not(feature = "no_solver"))]
 pub fn scramble_4x4x4() -> Vec<Move> {
-    let state = Cube4x4x4::random();
+    sourced_scramble_4x4x4(&mut StandardRandomSource)
+}
+
+/// Generates a random scramble with a given random number source
+#[cfg(not(feature = "no_solver"))]
+pub fn sourced_scramble_4x4x4<T: RandomSource>(rng: &mut T) -> Vec<Move> {
+    let state = Cube4x4x4::sourced_random(rng);
     let solution = state.solve().unwrap();
     solution.inverse()
 }
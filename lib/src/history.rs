@@ -1,9 +1,14 @@
 use crate::action::{Action, ActionList, StoredAction};
+use crate::algorithms::known::{Algorithm, AlgorithmStatus, AlgorithmType};
+use crate::algorithms::moves::{ExtendedMove, ExtendedMoveSequence};
+use crate::algorithms::set::{AlgorithmSet, AlgorithmSetEntry, AlgorithmSetFormat};
+use crate::analysis::AnalysisCache;
 use crate::common::{MoveSequence, Penalty, Solve, SolveType, TimedMoveSequence};
 use crate::import::ImportedSession;
 use crate::request::{SyncRequest, SyncResponse};
 use crate::storage::{DeferredStorage, Storage};
 use crate::sync::{SyncOperation, SyncStatus};
+use crate::{Analysis, CFOPEfficiency, Color, StepEfficiency};
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
@@ -36,6 +41,7 @@ pub struct History {
     update_id: u64,
     next_update_id: u64,
     settings: Settings,
+    analysis_cache: AnalysisCache,
 }
 
 #[derive(Clone, Copy)]
@@ -50,6 +56,9 @@ pub enum HistoryLoadProgress {
 struct SolveDatabase {
     solve_map: SolveMap,
     sessions: HashMap<String, Session>,
+    algorithm_status: HashMap<Algorithm, AlgorithmStatus>,
+    custom_algorithms: HashMap<Algorithm, Vec<Vec<ExtendedMove>>>,
+    preferred_algorithms: HashMap<Algorithm, Vec<ExtendedMove>>,
     actions: HashSet<String>,
 }
 
@@ -193,49 +202,35 @@ impl History {
             },
         };
 
-        let storage = DeferredStorage::new(storage);
-
+        let mut moved_synced_actions = false;
         if sync_key.is_none() || sync_id.is_none() {
             // No valid sync information in the database, create new sync information
             sync_key = Some(SyncRequest::new_sync_key());
             sync_id = Some(UNSYNCED);
-            storage.put("sync_key", sync_key.as_ref().unwrap().as_bytes());
-            storage.put("sync_id", &sync_id.unwrap().to_le_bytes());
+            storage
+                .put("sync_key", sync_key.as_ref().unwrap().as_bytes())
+                .await?;
+            storage
+                .put("sync_id", &sync_id.unwrap().to_le_bytes())
+                .await?;
 
             // If there was synced information that is now invalid, move it to local so
             // that can be synced under the new key and data loss is avoided.
             if synced_actions.has_actions() {
                 local_actions.prepend(&mut synced_actions);
-                local_actions.save_index(&storage);
-                synced_actions.save_index(&storage);
+                moved_synced_actions = true;
             }
         }
 
-        let mut result = Self {
-            storage,
-            solves: SolveDatabase::new(),
-            synced_solves: SolveDatabase::new(),
-            synced_actions,
-            sync_key: sync_key.unwrap(),
-            sync_id: sync_id.unwrap(),
-            local_actions,
-            current_sync: None,
-            last_sync_result: SyncStatus::NotSynced,
-            current_session,
-            update_id: 0,
-            next_update_id: 1,
-            settings,
-        };
-
         // Resolve actions to create solve and session lists
-        let total = result.synced_actions.len() + result.local_actions.len();
+        let mut synced_solves = SolveDatabase::new();
+        let mut next_update_id = 1;
+        let total = synced_actions.len() + local_actions.len();
         *progress.lock().unwrap() = HistoryLoadProgress::ResolveDeltas(0, total);
         let mut done = 0;
 
-        for action in &result.synced_actions {
-            result
-                .synced_solves
-                .resolve_action(action, &mut result.next_update_id);
+        for action in &synced_actions {
+            synced_solves.resolve_action(action, &mut next_update_id);
 
             done += 1;
             if done % 4096 == 0 {
@@ -243,11 +238,9 @@ impl History {
             }
         }
 
-        result.solves = result.synced_solves.clone();
-        for action in &result.local_actions {
-            result
-                .solves
-                .resolve_action(action, &mut result.next_update_id);
+        let mut solves = synced_solves.clone();
+        for action in &local_actions {
+            solves.resolve_action(action, &mut next_update_id);
 
             done += 1;
             if done % 4096 == 0 {
@@ -255,7 +248,30 @@ impl History {
             }
         }
 
-        Ok(result)
+        let analysis_cache = AnalysisCache::load(&mut storage).await?;
+
+        let storage = DeferredStorage::new(storage);
+        if moved_synced_actions {
+            local_actions.save_index(&storage);
+            synced_actions.save_index(&storage);
+        }
+
+        Ok(Self {
+            storage,
+            solves,
+            synced_solves,
+            synced_actions,
+            sync_key: sync_key.unwrap(),
+            sync_id: sync_id.unwrap(),
+            local_actions,
+            current_sync: None,
+            last_sync_result: SyncStatus::NotSynced,
+            current_session,
+            update_id: 0,
+            next_update_id,
+            settings,
+            analysis_cache,
+        })
     }
 
     pub fn iter(&self) -> SolveIterator {
@@ -268,12 +284,52 @@ impl History {
         self.solves.solve(id)
     }
 
+    /// Gets the analysis of a solve, using the cached result if the solve has been
+    /// analyzed before. Results are read or computed in the background, so this
+    /// returns `None` until the result is available, and `update_id` changes once it
+    /// is. Also returns `None` if the solve has no move data.
+    pub fn analysis(&self, solve: &Solve) -> Option<Analysis> {
+        self.analysis_cache.analysis(solve, &self.storage)
+    }
+
+    /// Gets a comparison of the cross step of a CFOP solve against an optimal
+    /// solution. Like `analysis`, this returns `None` until the result is available,
+    /// and also if the solve was not analyzed as CFOP.
+    pub fn cross_efficiency(&self, solve: &Solve) -> Option<StepEfficiency> {
+        self.analysis_cache.cross_efficiency(solve, &self.storage)
+    }
+
+    /// Gets a comparison of a cross training solve against an optimal cross, or an
+    /// optimal XCross with the target slot saved with the solve. Like `analysis`, this
+    /// returns `None` until the result is available, and also if the target was never
+    /// solved.
+    pub fn cross_planning_efficiency(&self, solve: &Solve) -> Option<StepEfficiency> {
+        self.analysis_cache
+            .cross_planning_efficiency(solve, &self.storage)
+    }
+
+    /// Gets a comparison of the cross and F2L steps of a CFOP solve against optimal
+    /// solutions. Searching for optimal F2L pairs is slow, so use `cross_efficiency`
+    /// when only the cross is needed. Like `analysis`, this returns `None` until the
+    /// result is available, and also if the solve was not analyzed as CFOP.
+    pub fn efficiency(&self, solve: &Solve) -> Option<CFOPEfficiency> {
+        self.analysis_cache.efficiency(solve, &self.storage)
+    }
+
+    /// Gets the number of moves in an optimal cross for each color, starting from the
+    /// scrambled state of a 3x3x3 solve. Like `analysis`, this returns `None` until
+    /// the result is available.
+    pub fn optimal_crosses(&self, solve: &Solve) -> Option<Vec<(Color, usize)>> {
+        self.analysis_cache.optimal_crosses(solve, &self.storage)
+    }
+
     pub fn sessions(&self) -> &HashMap<String, Session> {
         &self.solves.sessions
     }
 
     pub fn update_id(&self) -> u64 {
-        self.update_id
+        // Both counts only increase, so the sum changes whenever either one does
+        self.update_id + self.analysis_cache.ready_count()
     }
 
     pub fn sync_key(&self) -> &str {
@@ -332,6 +388,7 @@ impl History {
     }
 
     pub fn set_current_session(&mut self, session: String) {
+        self.storage.put("session", session.as_bytes());
         self.current_session = session;
         self.update_id = self.next_update_id;
         self.next_update_id += 1;
@@ -365,9 +422,107 @@ impl History {
     }
 
     pub fn delete_solve(&mut self, solve_id: String) {
+        self.analysis_cache.remove(&solve_id, &self.storage);
         self.new_action(StoredAction::new(Action::DeleteSolve(solve_id)));
     }
 
+    pub fn algorithm_status(&self, algorithm: Algorithm) -> AlgorithmStatus {
+        self.solves.algorithm_status(algorithm)
+    }
+
+    pub fn set_algorithm_status(&mut self, algorithm: Algorithm, status: AlgorithmStatus) {
+        if self.algorithm_status(algorithm) != status {
+            self.new_action(StoredAction::new(Action::AlgorithmStatus(
+                algorithm, status,
+            )));
+        }
+    }
+
+    /// Gets the algorithms the user has added for a case
+    pub fn custom_algorithms(&self, algorithm: Algorithm) -> &[Vec<ExtendedMove>] {
+        self.solves
+            .custom_algorithms
+            .get(&algorithm)
+            .map(|algorithms| algorithms.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Gets the algorithm the user has chosen as preferred for a case, if any
+    pub fn preferred_algorithm(&self, algorithm: Algorithm) -> Option<&[ExtendedMove]> {
+        self.solves
+            .preferred_algorithms
+            .get(&algorithm)
+            .map(|moves| moves.as_slice())
+    }
+
+    /// Gets all algorithms for a case. The preferred algorithm is first, followed by the
+    /// algorithms added by the user and then the known algorithms.
+    pub fn algorithms(&self, algorithm: Algorithm) -> Vec<Vec<ExtendedMove>> {
+        let mut result: Vec<Vec<ExtendedMove>> = Vec::new();
+        let preferred = self
+            .preferred_algorithm(algorithm)
+            .map(|moves| moves.to_vec());
+        for moves in preferred
+            .into_iter()
+            .chain(self.custom_algorithms(algorithm).iter().cloned())
+            .chain(algorithm.known_algorithms())
+        {
+            if !result.contains(&moves) {
+                result.push(moves);
+            }
+        }
+        result
+    }
+
+    /// Checks if an algorithm is already available for a case. Algorithms that only
+    /// differ by turns of the top face at the start and end, or by being performed
+    /// from another side of the cube, are considered the same.
+    fn has_algorithm(&self, algorithm: Algorithm, moves: &[ExtendedMove]) -> bool {
+        self.algorithms(algorithm)
+            .iter()
+            .any(|existing| existing.same_algorithm(moves))
+    }
+
+    /// Adds an algorithm for a case. Returns an error if the moves do not solve the case.
+    pub fn add_algorithm(&mut self, algorithm: Algorithm, moves: Vec<ExtendedMove>) -> Result<()> {
+        algorithm.validate(&moves)?;
+        if !self.has_algorithm(algorithm, &moves) {
+            self.new_action(StoredAction::new(Action::AddAlgorithm(algorithm, moves)));
+        }
+        Ok(())
+    }
+
+    pub fn remove_algorithm(&mut self, algorithm: Algorithm, moves: &[ExtendedMove]) {
+        if self
+            .custom_algorithms(algorithm)
+            .iter()
+            .any(|alg| alg.as_slice() == moves)
+        {
+            self.new_action(StoredAction::new(Action::RemoveAlgorithm(
+                algorithm,
+                moves.to_vec(),
+            )));
+        }
+    }
+
+    /// Sets the preferred algorithm for a case, or resets to the default if `moves` is
+    /// `None`. Returns an error if the moves do not solve the case.
+    pub fn set_preferred_algorithm(
+        &mut self,
+        algorithm: Algorithm,
+        moves: Option<Vec<ExtendedMove>>,
+    ) -> Result<()> {
+        if let Some(moves) = &moves {
+            algorithm.validate(moves)?;
+        }
+        if self.preferred_algorithm(algorithm) != moves.as_deref() {
+            self.new_action(StoredAction::new(Action::SetPreferredAlgorithm(
+                algorithm, moves,
+            )));
+        }
+        Ok(())
+    }
+
     pub fn local_commit(&mut self) {
         self.local_actions.commit(&self.storage, false);
     }
@@ -567,6 +722,12 @@ impl History {
                         .unwrap()
                         .insert("solve".into(), json!(moves.to_string()));
                 }
+                if let Some(slot) = solve.target_slot {
+                    value
+                        .as_object_mut()
+                        .unwrap()
+                        .insert("target_slot".into(), json!(slot.to_str()));
+                }
                 solve_list.push(value);
             }
             if solve_list.len() != 0 {
@@ -676,6 +837,85 @@ impl History {
         ))
     }
 
+    /// Exports the algorithms for every case that has algorithms, along with the
+    /// learning status of cases that are being learned or are known. The preferred
+    /// algorithm for each case is listed first.
+    pub fn export_algorithms(&self, format: AlgorithmSetFormat) -> Result<String> {
+        let mut entries = Vec::new();
+        for alg_type in AlgorithmType::all() {
+            for algorithm in alg_type.cases() {
+                let moves = self.algorithms(algorithm);
+                let status = match self.algorithm_status(algorithm) {
+                    AlgorithmStatus::Unknown => None,
+                    status => Some(status),
+                };
+                if !moves.is_empty() || status.is_some() {
+                    entries.push(AlgorithmSetEntry {
+                        algorithm,
+                        status,
+                        moves,
+                    });
+                }
+            }
+        }
+        AlgorithmSet { entries }.export(format)
+    }
+
+    /// Imports an algorithm set. The first algorithm listed for a case becomes the
+    /// preferred algorithm, and the rest are added to the algorithms for the case. All
+    /// algorithms are verified before any changes are made, so nothing is imported if
+    /// any algorithm does not solve its case.
+    pub fn import_algorithms(&mut self, contents: String) -> Result<String> {
+        let set = AlgorithmSet::import(&contents)?;
+
+        let mut file_algorithms = 0;
+        let mut preferred_count = 0;
+        let mut added_count = 0;
+        let mut status_count = 0;
+        for entry in set.entries {
+            let algorithm = entry.algorithm;
+            file_algorithms += entry.moves.len();
+
+            if let Some(status) = entry.status {
+                if self.algorithm_status(algorithm) != status {
+                    self.set_algorithm_status(algorithm, status);
+                    status_count += 1;
+                }
+            }
+
+            // Algorithms that only differ by AUF or rotation from one that is already
+            // available are not imported again
+            let mut moves = entry.moves.into_iter();
+            if let Some(preferred) = moves.next() {
+                if !self
+                    .algorithms(algorithm)
+                    .first()
+                    .map(|current| current.same_algorithm(&preferred))
+                    .unwrap_or(false)
+                {
+                    self.set_preferred_algorithm(algorithm, Some(preferred))?;
+                    preferred_count += 1;
+                }
+            }
+            for moves in moves {
+                if !self.has_algorithm(algorithm, &moves) {
+                    self.add_algorithm(algorithm, moves)?;
+                    added_count += 1;
+                }
+            }
+        }
+
+        self.local_commit();
+
+        Ok(format!(
+            "File contained {} algorithm(s).\n\
+            {} preferred algorithm(s) changed.\n\
+            {} algorithm(s) added.\n\
+            {} learning status(es) changed.",
+            file_algorithms, preferred_count, added_count, status_count
+        ))
+    }
+
     pub fn auto_split_sessions(&mut self, max_gap_time: i64) -> usize {
         // Go through all sessions for organization
         let mut to_move = BTreeMap::new();
@@ -850,6 +1090,9 @@ impl SolveDatabase {
                 solve_times: HashMap::new(),
             },
             sessions: HashMap::new(),
+            algorithm_status: HashMap::new(),
+            custom_algorithms: HashMap::new(),
+            preferred_algorithms: HashMap::new(),
             actions: HashSet::new(),
         }
     }
@@ -858,6 +1101,13 @@ impl SolveDatabase {
         self.solve_map.solve(id)
     }
 
+    fn algorithm_status(&self, algorithm: Algorithm) -> AlgorithmStatus {
+        self.algorithm_status
+            .get(&algorithm)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn add_solve_to_session(
         &mut self,
         solve: SolveTimeAndId,
@@ -987,6 +1237,43 @@ impl SolveDatabase {
                 }
                 None => false,
             },
+            Action::AlgorithmStatus(algorithm, status) => {
+                self.algorithm_status.insert(*algorithm, *status);
+                true
+            }
+            Action::AddAlgorithm(algorithm, moves) => {
+                let algorithms = self.custom_algorithms.entry(*algorithm).or_default();
+                if algorithms.contains(moves) {
+                    false
+                } else {
+                    algorithms.push(moves.clone());
+                    true
+                }
+            }
+            Action::RemoveAlgorithm(algorithm, moves) => {
+                match self.custom_algorithms.get_mut(algorithm) {
+                    Some(algorithms) => match algorithms.iter().position(|alg| alg == moves) {
+                        Some(idx) => {
+                            algorithms.remove(idx);
+
+                            // A removed algorithm can no longer be the preferred one
+                            if self.preferred_algorithms.get(algorithm) == Some(moves) {
+                                self.preferred_algorithms.remove(algorithm);
+                            }
+                            true
+                        }
+                        None => false,
+                    },
+                    None => false,
+                }
+            }
+            Action::SetPreferredAlgorithm(algorithm, moves) => {
+                match moves {
+                    Some(moves) => self.preferred_algorithms.insert(*algorithm, moves.clone()),
+                    None => self.preferred_algorithms.remove(algorithm),
+                };
+                true
+            }
         }
     }
 }
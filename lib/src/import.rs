@@ -1,4 +1,5 @@
 use crate::common::{parse_move_string, parse_timed_move_string, Penalty, Solve, SolveType};
+use crate::F2LSlot;
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local, TimeZone, Utc};
 use serde_json::{Map, Value};
@@ -154,6 +155,12 @@ impl ImportedSession {
                     None
                 };
 
+                // Target slot is optional, and only present for XCross training solves
+                let target_slot = solve
+                    .get("target_slot")
+                    .and_then(|slot| slot.as_str())
+                    .and_then(F2LSlot::from_str);
+
                 // Add solve to list
                 solves.push(Solve {
                     id: id.into(),
@@ -177,6 +184,7 @@ impl ImportedSession {
                     },
                     device: device.map(|string| string.into()),
                     moves,
+                    target_slot,
                 });
             }
 
@@ -337,6 +345,7 @@ impl ImportedSession {
                     penalty,
                     device: None,
                     moves,
+                    target_slot: None,
                 });
             }
 
@@ -473,6 +482,7 @@ impl ImportedSession {
                 penalty,
                 device: Some(device.into()),
                 moves: solution,
+                target_slot: None,
             });
         }
 
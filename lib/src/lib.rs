@@ -2,13 +2,18 @@ mod action;
 mod algorithms;
 mod analysis;
 mod common;
+mod constraint;
 mod cube2x2x2;
 mod cube3x3x3;
 mod cube4x4x4;
 mod rand;
 mod request;
+mod svg;
 mod tables;
 
+#[cfg(not(feature = "no_solver"))]
+mod scramble_set;
+
 #[cfg(feature = "storage")]
 mod future;
 #[cfg(feature = "storage")]
@@ -28,28 +33,44 @@ mod action_generated;
 #[allow(dead_code, unused_imports)]
 mod index_generated;
 
-pub use crate::rand::{RandomSource, SimpleSeededRandomSource, StandardRandomSource};
+pub use crate::rand::{
+    RandomSource, SeededRandomSource, SimpleSeededRandomSource, StandardRandomSource,
+};
 pub use action::{Action, StoredAction};
-pub use algorithms::known::{Algorithm, AlgorithmType, KnownAlgorithms};
+pub use algorithms::ergonomics::AlgorithmErgonomics;
+pub use algorithms::known::{Algorithm, AlgorithmStatus, AlgorithmType, KnownAlgorithms};
 pub use algorithms::moves::{
-    CubeRotation, CubeRotationAxis, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence,
-    SliceMove, SliceMoveAxis, WideMove,
+    parse_extended_move_string, CubeRotation, CubeRotationAxis, ExtendedMove, ExtendedMoveContext,
+    ExtendedMoveSequence, MirrorAxis, SliceMove, SliceMoveAxis, WideMove,
 };
+pub use algorithms::review::{ReviewGrade, ReviewSchedule, ReviewState};
+pub use algorithms::set::AlgorithmSetFormat;
+pub use algorithms::variant::AlgorithmVariant;
 pub use analysis::{
-    Analysis, AnalysisStepSummary, AnalysisSubstepTime, AnalysisSummary, CFOPAnalysis,
-    CFOPPartialAnalysis, CFOPProgress, CrossAnalysis, CubeWithSolution, F2LPairAnalysis,
-    FinalAlignmentAnalysis, OLLAlgorithm, OLLAnalysis, PLLAlgorithm, PLLAnalysis, PartialAnalysis,
-    PartialAnalysisMethod, SolveAnalysis,
+    median_move_gap, AUFAnalysis, Analysis, AnalysisStepSummary, AnalysisSubstepTime,
+    AnalysisSummary, CFOPAnalysis, CFOPEfficiency, CFOPPartialAnalysis, CFOPProgress, CFOPStep,
+    CFOPStepSolver, CLLAlgorithm, CLLAnalysis, COLLAlgorithm, CrossAnalysis, CrossMoveTable,
+    CrossPlanningAnalysis, Cube2x2x2Analysis, Cube2x2x2Method, Cube2x2x2PartialAnalysis,
+    Cube2x2x2Progress, Cube2x2x2WithSolution, CubeWithSolution, EG1Algorithm, EG2Algorithm,
+    F2LAlgorithm, F2LPairAnalysis, F2LSlot, FinalAlignmentAnalysis, FirstFaceAnalysis, LBLAnalysis,
+    LBLAnalysisStages, LBLPartialAnalysis, LBLProgress, LBLStepAnalysis, LayerPermutation,
+    LookaheadAnalysis, Mistake, MistakeKind, OLLAlgorithm, OLLAnalysis, OLLCPAlgorithm,
+    PBLAlgorithm, PBLAnalysis, PLLAlgorithm, PLLAnalysis, PartialAnalysis, PartialAnalysisMethod,
+    Pause, RecognitionCase, SolveAnalysis, StepEfficiency, ZBLLAlgorithm, ANALYSIS_VERSION,
+    DEFAULT_PAUSE_THRESHOLD,
 };
 pub use common::{
-    parse_move_string, parse_timed_move_string, scramble_megaminx, Average, BestSolve, Color,
-    Corner, CornerPiece, Cube, CubeFace, FaceRotation, InitialCubeState, ListAverage, Move,
-    MoveSequence, Penalty, RotationDirection, Solve, SolveList, SolveType, TimedMove,
+    parse_move_string, parse_timed_move_string, scramble_megaminx, sourced_scramble_megaminx,
+    Average, BestSolve, Color, Corner, CornerPiece, Cube, CubeFace, CubeStateError, FaceRotation,
+    InitialCubeState, ListAverage, Move, MoveSequence, Penalty, RotationDirection, Solve,
+    SolveList, SolveType, TimedMove,
 };
+pub use constraint::{ScrambleConstraint, ScrambleConstraints, HARD_CROSS_MOVES, MAX_CROSS_MOVES};
 pub use cube2x2x2::{Cube2x2x2, Cube2x2x2Faces};
 pub use cube3x3x3::{Cube3x3x3, Cube3x3x3Faces, Edge3x3x3, EdgePiece3x3x3, LastLayerRandomization};
 pub use cube4x4x4::{Cube4x4x4, Cube4x4x4Faces, Edge4x4x4, EdgePiece4x4x4};
 pub use request::{SyncRequest, SyncResponse, SYNC_API_VERSION};
+pub use svg::{LastLayerSvgStyle, SvgOptions};
 
 #[cfg(feature = "storage")]
 pub use history::{History, HistoryLoadProgress, Session};
@@ -63,20 +84,40 @@ pub use bluetooth::{
 };
 
 #[cfg(not(feature = "no_solver"))]
-pub use cube2x2x2::scramble_2x2x2;
+pub use constraint::{constrained_scramble_3x3x3, sourced_constrained_scramble_3x3x3};
+#[cfg(not(feature = "no_solver"))]
+pub use cube2x2x2::{scramble_2x2x2, sourced_scramble_2x2x2};
+#[cfg(not(feature = "no_solver"))]
+pub use cube3x3x3::{
+    scramble_3x3x3, scramble_3x3x3_fast, scramble_last_layer, sourced_scramble_3x3x3,
+    sourced_scramble_last_layer,
+};
 #[cfg(not(feature = "no_solver"))]
-pub use cube3x3x3::{scramble_3x3x3, scramble_3x3x3_fast, scramble_last_layer};
+pub use cube4x4x4::{scramble_4x4x4, scramble_4x4x4_fast, sourced_scramble_4x4x4};
 #[cfg(not(feature = "no_solver"))]
-pub use cube4x4x4::{scramble_4x4x4, scramble_4x4x4_fast};
+pub use scramble_set::{
+    sourced_scramble, ScrambleGroup, ScrambleRound, ScrambleSet, DAILY_CHALLENGE_SOLVE_COUNT,
+};
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        Cube, Cube2x2x2, Cube2x2x2Faces, Cube3x3x3, Cube3x3x3Faces, Cube4x4x4, Cube4x4x4Faces,
-        CubeFace, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence, InitialCubeState,
-        KnownAlgorithms, LastLayerRandomization, Move, MoveSequence, OLLAlgorithm, PLLAlgorithm,
-        RandomSource, SimpleSeededRandomSource,
+        parse_extended_move_string, parse_move_string, parse_timed_move_string, Algorithm,
+        AlgorithmErgonomics, AlgorithmSetFormat, AlgorithmStatus, AlgorithmType, AlgorithmVariant,
+        Analysis, AnalysisSummary, CFOPStep, CFOPStepSolver, CLLAlgorithm, COLLAlgorithm, Color,
+        Corner, CrossMoveTable, CrossPlanningAnalysis, Cube, Cube2x2x2, Cube2x2x2Faces,
+        Cube2x2x2Method, Cube2x2x2WithSolution, Cube3x3x3, Cube3x3x3Faces, Cube4x4x4,
+        Cube4x4x4Faces, CubeFace, CubeRotation, CubeStateError, CubeWithSolution, EG1Algorithm,
+        EG2Algorithm, Edge3x3x3, Edge4x4x4, ExtendedMove, ExtendedMoveContext,
+        ExtendedMoveSequence, F2LAlgorithm, F2LSlot, InitialCubeState, KnownAlgorithms,
+        LBLPartialAnalysis, LastLayerRandomization, LastLayerSvgStyle, LayerPermutation,
+        MirrorAxis, MistakeKind, Move, MoveSequence, OLLAlgorithm, OLLCPAlgorithm, PBLAlgorithm,
+        PLLAlgorithm, Penalty, RandomSource, RecognitionCase, ReviewGrade, ReviewSchedule,
+        ScrambleConstraint, ScrambleConstraints, ScrambleSet, SimpleSeededRandomSource, SolveType,
+        StepEfficiency, SvgOptions, TimedMove, ZBLLAlgorithm, DEFAULT_PAUSE_THRESHOLD,
+        HARD_CROSS_MOVES,
     };
+    use chrono::{Duration, Local, TimeZone};
     use std::convert::TryFrom;
 
     fn basic_small_cube_movement<T: Cube + InitialCubeState + std::fmt::Display>() {
@@ -488,6 +529,422 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cube_state_validation() {
+        let mut rng = SimpleSeededRandomSource::new();
+        for _ in 0..100 {
+            let cube = Cube2x2x2::sourced_random(&mut rng);
+            assert_eq!(
+                cube.as_faces().validate(),
+                Ok(()),
+                "invalid 2x2x2\n{}",
+                cube
+            );
+            let cube = Cube3x3x3::sourced_random(&mut rng);
+            assert_eq!(
+                cube.as_faces().validate(),
+                Ok(()),
+                "invalid 3x3x3\n{}",
+                cube
+            );
+            let cube = Cube4x4x4::sourced_random(&mut rng);
+            assert_eq!(
+                cube.as_faces().validate(),
+                Ok(()),
+                "invalid 4x4x4\n{}",
+                cube
+            );
+        }
+
+        let corners = |cube: &Cube3x3x3| {
+            let mut corners = [cube.corner_piece(Corner::URF); 8];
+            for (i, corner) in corners.iter_mut().enumerate() {
+                *corner = cube.corner_piece(Corner::try_from(i as u8).unwrap());
+            }
+            corners
+        };
+        let edges = |cube: &Cube3x3x3| {
+            let mut edges = [cube.edge_piece(Edge3x3x3::UR); 12];
+            for (i, edge) in edges.iter_mut().enumerate() {
+                *edge = cube.edge_piece(Edge3x3x3::try_from(i as u8).unwrap());
+            }
+            edges
+        };
+        let cube = Cube3x3x3::sourced_random(&mut rng);
+
+        let mut twisted = corners(&cube);
+        twisted[0].orientation = (twisted[0].orientation + 1) % 3;
+        let twisted = Cube3x3x3::from_corners_and_edges(twisted, edges(&cube));
+        assert!(matches!(
+            twisted.as_faces().validate(),
+            Err(CubeStateError::CornerTwist { .. })
+        ));
+
+        let mut flipped = edges(&cube);
+        flipped[0].orientation ^= 1;
+        let flipped = Cube3x3x3::from_corners_and_edges(corners(&cube), flipped);
+        assert_eq!(flipped.as_faces().validate(), Err(CubeStateError::EdgeFlip));
+
+        let mut swapped = edges(&cube);
+        swapped.swap(0, 1);
+        let swapped = Cube3x3x3::from_corners_and_edges(corners(&cube), swapped);
+        assert_eq!(
+            swapped.as_faces().validate(),
+            Err(CubeStateError::PermutationParity)
+        );
+
+        let mut duplicate = edges(&cube);
+        duplicate[1] = duplicate[0];
+        let duplicate = Cube3x3x3::from_corners_and_edges(corners(&cube), duplicate);
+        assert!(matches!(
+            duplicate.validate(),
+            Err(CubeStateError::DuplicateEdge { .. })
+        ));
+
+        // Swap two stickers of different colors on the same face to create a piece that
+        // does not exist
+        let faces = Cube3x3x3Faces::new();
+        let mut colors = [Color::White; 6 * 9];
+        for face in 0..6 {
+            for row in 0..3 {
+                for col in 0..3 {
+                    colors[face * 9 + row * 3 + col] =
+                        faces.color(CubeFace::try_from(face as u8).unwrap(), row, col);
+                }
+            }
+        }
+        colors.swap(0, 9);
+        assert_eq!(
+            Cube3x3x3Faces::from_colors(colors).validate(),
+            Err(CubeStateError::InvalidCorner {
+                position: Corner::UFL,
+                colors: [Color::White, Color::White, Color::Orange],
+            })
+        );
+        colors[0] = Color::White;
+        assert_eq!(
+            Cube3x3x3Faces::from_colors(colors).validate(),
+            Err(CubeStateError::ColorCount {
+                color: Color::White,
+                count: 10,
+                expected: 9,
+            })
+        );
+
+        // Edge wings can't be flipped in place
+        let cube = Cube4x4x4::sourced_random(&mut rng);
+        let faces = cube.as_faces();
+        let mut colors = [Color::White; 6 * 16];
+        for face in 0..6 {
+            for row in 0..4 {
+                for col in 0..4 {
+                    colors[face * 16 + row * 4 + col] =
+                        faces.color(CubeFace::try_from(face as u8).unwrap(), row, col);
+                }
+            }
+        }
+        let [first, second] = crate::tables::table4x4x4::CUBE4_EDGE_INDICIES[0];
+        colors.swap(first, second);
+        assert!(matches!(
+            Cube4x4x4Faces::from_colors(colors).validate(),
+            Err(CubeStateError::InvalidWing { .. })
+        ));
+
+        let mut corners = [cube.corner_piece(Corner::URF); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            *corner = cube.corner_piece(Corner::try_from(i as u8).unwrap());
+        }
+        let mut edges = [cube.edge_piece(Edge4x4x4::URB); 24];
+        for (i, edge) in edges.iter_mut().enumerate() {
+            *edge = cube.edge_piece(Edge4x4x4::try_from(i as u8).unwrap());
+        }
+        let mut centers = [Color::White; 24];
+        for face in 0..6 {
+            for row in 0..2 {
+                for col in 0..2 {
+                    centers[face * 4 + row * 2 + col] =
+                        cube.center_color(CubeFace::try_from(face as u8).unwrap(), row, col);
+                }
+            }
+        }
+        assert_eq!(
+            Cube4x4x4::from_corners_edges_and_centers(corners, edges, centers).validate(),
+            Ok(())
+        );
+        edges[0].orientation ^= 1;
+        assert!(matches!(
+            Cube4x4x4::from_corners_edges_and_centers(corners, edges, centers).validate(),
+            Err(CubeStateError::InvalidWing {
+                position: Edge4x4x4::URB,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn facelet_strings() {
+        let mut cube = Cube3x3x3Faces::new();
+        assert_eq!(
+            cube.to_facelet_string(),
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"
+        );
+        cube.do_move(Move::R);
+        assert_eq!(
+            cube.to_facelet_string(),
+            "UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB"
+        );
+        assert_eq!(
+            Cube3x3x3Faces::from_facelet_string(
+                "UUFUUFUUF RRRRRRRRR FFDFFDFFD DDBDDBDDB LLLLLLLLL UBBUBBUBB"
+            )
+            .unwrap(),
+            cube
+        );
+        assert!(Cube3x3x3Faces::from_facelet_string("UUUUUUUUU").is_err());
+        assert!(Cube3x3x3Faces::from_facelet_string(
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBX"
+        )
+        .is_err());
+
+        let mut rng = SimpleSeededRandomSource::new();
+        for _ in 0..10 {
+            let cube = Cube2x2x2::sourced_random(&mut rng).as_faces();
+            let string = cube.to_facelet_string();
+            assert_eq!(string.len(), 24);
+            assert_eq!(Cube2x2x2Faces::from_facelet_string(&string).unwrap(), cube);
+            let cube = Cube3x3x3::sourced_random(&mut rng).as_faces();
+            let string = cube.to_facelet_string();
+            assert_eq!(string.len(), 54);
+            assert_eq!(Cube3x3x3Faces::from_facelet_string(&string).unwrap(), cube);
+            let cube = Cube4x4x4::sourced_random(&mut rng).as_faces();
+            let string = cube.to_facelet_string();
+            assert_eq!(string.len(), 96);
+            assert_eq!(Cube4x4x4Faces::from_facelet_string(&string).unwrap(), cube);
+        }
+    }
+
+    #[test]
+    fn scramble_set() {
+        let set = ScrambleSet::generate(1234, SolveType::Standard2x2x2, 2, 3, 5, 2);
+        assert_eq!(
+            set,
+            ScrambleSet::generate(1234, SolveType::Standard2x2x2, 2, 3, 5, 2)
+        );
+        assert_ne!(
+            set,
+            ScrambleSet::generate(1235, SolveType::Standard2x2x2, 2, 3, 5, 2)
+        );
+        assert_eq!(set.rounds.len(), 2);
+        for round in &set.rounds {
+            assert_eq!(round.groups.len(), 3);
+            for group in &round.groups {
+                assert_eq!(group.scrambles.len(), 5);
+                assert_eq!(group.extras.len(), 2);
+                for scramble in group.scrambles.iter().chain(group.extras.iter()) {
+                    let mut cube = Cube2x2x2::new();
+                    cube.do_moves(scramble);
+                    assert!(!cube.is_solved());
+                }
+            }
+        }
+        assert!(set.to_json().is_ok());
+        assert_eq!(ScrambleSet::group_name(0), "A");
+        assert_eq!(ScrambleSet::group_name(25), "Z");
+        assert_eq!(ScrambleSet::group_name(26), "AA");
+
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 7, 4).unwrap();
+        let daily = ScrambleSet::daily_challenge(date);
+        assert_eq!(daily, ScrambleSet::daily_challenge(date));
+        assert_eq!(daily.rounds[0].groups[0].scrambles.len(), 5);
+        assert_ne!(
+            ScrambleSet::daily_challenge_seed(date),
+            ScrambleSet::daily_challenge_seed(date.succ_opt().unwrap())
+        );
+    }
+
+    #[test]
+    fn constrained_scrambles() {
+        let table = CrossMoveTable::new(CubeFace::Top);
+        let mut cube = Cube3x3x3::new();
+        assert_eq!(table.moves(&cube), 0);
+        cube.do_moves(&[Move::R, Move::F]);
+        assert_eq!(table.moves(&cube), 2);
+
+        let mut rng = SimpleSeededRandomSource::new();
+        for constraints in &[
+            vec![ScrambleConstraint::MinimumCrossMoves(
+                CubeFace::Top,
+                HARD_CROSS_MOVES,
+            )],
+            vec![ScrambleConstraint::SolvedF2LPairs(CubeFace::Bottom, 3)],
+            vec![
+                ScrambleConstraint::SolvedF2LPairs(CubeFace::Top, 1),
+                ScrambleConstraint::EdgesOriented,
+            ],
+            vec![ScrambleConstraint::OLLCase(OLLAlgorithm::from_number(45))],
+            vec![ScrambleConstraint::PLLCase(PLLAlgorithm::T)],
+            vec![ScrambleConstraint::F2LCase(
+                CubeFace::Top,
+                F2LAlgorithm::from_number(37).unwrap(),
+            )],
+        ] {
+            let constraints = ScrambleConstraints::new(constraints);
+            let scramble = constraints.sourced_scramble(&mut rng).unwrap();
+            let mut cube = Cube3x3x3::new();
+            cube.do_moves(&scramble);
+            assert!(!cube.is_solved());
+            assert!(constraints.is_satisfied(&cube));
+        }
+
+        // A solved cross can't need any moves to solve
+        assert!(ScrambleConstraints::new(&[
+            ScrambleConstraint::SolvedF2LPairs(CubeFace::Top, 0),
+            ScrambleConstraint::MinimumCrossMoves(CubeFace::Top, 1),
+        ])
+        .sourced_random_state(&mut rng)
+        .is_none());
+    }
+
+    #[test]
+    fn optimal_cfop_steps() {
+        let solver = CFOPStepSolver::new(CubeFace::Bottom);
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(&[Move::R2, Move::U, Move::F2]);
+        let cross = solver.optimal_cross(&cube);
+        assert_eq!(cross.len(), 3);
+        let mut solved_cross = cube.clone();
+        solved_cross.do_moves(&cross);
+        assert_eq!(solver.optimal_cross(&solved_cross).len(), 0);
+        assert_eq!(
+            CrossMoveTable::new(CubeFace::Bottom).optimal_cross(&cube),
+            cross
+        );
+
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(&[Move::R, Move::U, Move::Rp, Move::F, Move::Up, Move::Fp]);
+        assert_eq!(solver.solved_slots(&cube).len(), 2);
+        let pairs = solver.optimal_f2l_pairs(&cube, &[0, 1, 2, 3]).unwrap();
+        assert_eq!(pairs.len(), 6);
+        cube.do_moves(&pairs);
+        assert!(cube.is_solved());
+
+        // Half turns reported as quarter turns count as a single move
+        let moves = "F F R U R' U'";
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(moves).unwrap().inverse());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves(moves),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        let efficiency =
+            CFOPStepSolver::new(analysis.cross.color.face()).efficiency(&analysis, &solve);
+        assert_eq!(efficiency.cross.moves, 1);
+        assert_eq!(efficiency.cross.extra_moves(), Some(0));
+    }
+
+    #[test]
+    fn cross_planning() {
+        let solver = CFOPStepSolver::new(CubeFace::Top);
+        for slot in &F2LSlot::all() {
+            assert_eq!(solver.slot(solver.slot_index(*slot)), *slot);
+        }
+
+        // Half turns reported as quarter turns count as a single move
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(&[Move::F, Move::R]);
+        let solution = parse_timed_move_string("R@1000 R@1100 R@1200 F'@2000").unwrap();
+        let efficiency = StepEfficiency {
+            moves: CrossPlanningAnalysis::target_moves(&cube, &solution, None).unwrap(),
+            optimal: Some(solver.optimal_cross(&cube)),
+        };
+        let analysis =
+            CrossPlanningAnalysis::analyze(&cube, &solution, None, efficiency.clone(), None, 3.0)
+                .unwrap();
+        assert_eq!(analysis.efficiency.moves, 2);
+        assert!(analysis.is_optimal());
+        assert_eq!(analysis.planning_time, 1000);
+        assert_eq!(analysis.execution_time, 1000);
+        assert!(!analysis.without_pauses());
+
+        // Moves after the cross is solved are not part of the attempt
+        let solution = parse_timed_move_string("R'@1500 F'@1700 U@1800").unwrap();
+        let analysis =
+            CrossPlanningAnalysis::analyze(&cube, &solution, None, efficiency.clone(), None, 3.0)
+                .unwrap();
+        assert!(analysis.without_pauses());
+        assert_eq!(analysis.execution_time, 200);
+        let analysis =
+            CrossPlanningAnalysis::analyze(&cube, &solution, None, efficiency, Some(50), 3.0)
+                .unwrap();
+        assert!(!analysis.without_pauses());
+
+        // The pair between the white, green and red centers is in front left when
+        // holding the cross on the bottom
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(&[Move::R, Move::U, Move::Rp]);
+        let xcross = solver.optimal_xcross(&cube, F2LSlot::FrontLeft).unwrap();
+        assert_eq!(xcross.len(), 3);
+        let solution = parse_timed_move_string("R@1000 U'@1100 R'@1200").unwrap();
+        assert!(!CrossPlanningAnalysis::target_solved(
+            &cube,
+            Some(F2LSlot::FrontLeft)
+        ));
+        let efficiency = StepEfficiency {
+            moves: CrossPlanningAnalysis::target_moves(&cube, &solution, Some(F2LSlot::FrontLeft))
+                .unwrap(),
+            optimal: Some(xcross.clone()),
+        };
+        let analysis = CrossPlanningAnalysis::analyze(
+            &cube,
+            &solution,
+            Some(F2LSlot::FrontLeft),
+            efficiency,
+            None,
+            DEFAULT_PAUSE_THRESHOLD,
+        )
+        .unwrap();
+        assert_eq!(analysis.efficiency.extra_moves(), Some(0));
+        assert_eq!(analysis.slot, Some(F2LSlot::FrontLeft));
+        cube.do_moves(&xcross);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn svg_rendering() {
+        let options = SvgOptions::default();
+        let mut cube = Cube3x3x3Faces::new();
+        cube.do_move(Move::R);
+        let svg = cube.net_svg(&options);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 54);
+        assert_eq!(svg.matches(options.color(Color::Yellow)).count(), 9);
+        assert_eq!(
+            Cube2x2x2Faces::new()
+                .net_svg(&options)
+                .matches("<rect")
+                .count(),
+            24
+        );
+        assert_eq!(
+            Cube4x4x4Faces::new()
+                .net_svg(&options)
+                .matches("<rect")
+                .count(),
+            96
+        );
+
+        let svg = cube.last_layer_svg(LastLayerSvgStyle::Full, &options);
+        assert_eq!(svg.matches("<rect").count(), 21);
+        assert_eq!(svg.matches(options.color(Color::Green)).count(), 5);
+        let svg = cube.last_layer_svg(LastLayerSvgStyle::Orientation, &options);
+        assert_eq!(svg.matches(options.color(Color::White)).count(), 7);
+        assert_eq!(svg.matches(&options.ignored_color).count(), 14);
+    }
+
     #[test]
     fn solve_2x2x2() {
         let mut rng = SimpleSeededRandomSource::new();
@@ -757,4 +1214,904 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn algorithm_variants() {
+        let known = KnownAlgorithms::pll(PLLAlgorithm::T);
+
+        // Second T perm algorithm performed with yellow on top, with alignment turns
+        // before and after and a cancelled turn in the middle
+        let moves = parse_move_string("D2 R D R' D' R' B R2 D' R' L L' D B' L' D L D").unwrap();
+        assert_eq!(
+            AlgorithmVariant::identify(&moves, &known, &[]),
+            AlgorithmVariant::Known(1)
+        );
+
+        // Same algorithm with a green top
+        let moves = parse_move_string("R F R' F' R' D R2 F' R' F D' L' F L").unwrap();
+        assert_eq!(
+            AlgorithmVariant::identify(&moves, &known, &[]),
+            AlgorithmVariant::Known(1)
+        );
+
+        let moves = parse_move_string("R U R' U' R U R' U'").unwrap();
+        assert_eq!(
+            AlgorithmVariant::identify(&moves, &known, &[]),
+            AlgorithmVariant::Unknown
+        );
+
+        // User defined algorithms are preferred
+        let moves = parse_move_string("R U R' U' R' F R2 U' R' U' R U R' F'").unwrap();
+        assert_eq!(
+            AlgorithmVariant::identify(&moves, &known, &[known[0].clone()]),
+            AlgorithmVariant::Custom(known[0].clone())
+        );
+    }
+
+    #[test]
+    fn lookahead_pauses() {
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string("R U R' U'").unwrap());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: vec![
+                TimedMove::new(Move::U, 0),
+                TimedMove::new(Move::R, 100),
+                TimedMove::new(Move::Up, 1100),
+                TimedMove::new(Move::Rp, 1200),
+            ],
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+
+        let lookahead = analysis.lookahead(&solve.solution, None, DEFAULT_PAUSE_THRESHOLD);
+        assert_eq!(lookahead.pause_gap, 300);
+        assert_eq!(lookahead.pauses.len(), 1);
+        assert_eq!(lookahead.pauses[0].move_index, 2);
+        assert_eq!(lookahead.pause_time(), 1000);
+        assert!((lookahead.score - 200.0 * 100.0 / 1200.0).abs() < 0.01);
+
+        // A faster solver would consider the other gaps pauses too
+        let lookahead = analysis.lookahead(&solve.solution, Some(20), DEFAULT_PAUSE_THRESHOLD);
+        assert_eq!(lookahead.pauses.len(), 3);
+    }
+
+    fn timed_moves(moves: &str) -> Vec<TimedMove> {
+        parse_move_string(moves)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(idx, mv)| TimedMove::new(*mv, (idx as u32 + 1) * 100))
+            .collect()
+    }
+
+    #[test]
+    fn f2l_known_algorithms() {
+        assert_eq!(F2LAlgorithm::all().len(), 41);
+        for case in F2LAlgorithm::all() {
+            let algs = KnownAlgorithms::f2l(case);
+            assert!(!algs.is_empty());
+            for alg in algs {
+                assert!(
+                    Algorithm::F2L(case).validate(&alg).is_ok(),
+                    "F2L case {} algorithm {} not valid",
+                    case,
+                    alg.iter()
+                        .map(|mv| mv.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                );
+            }
+        }
+
+        // Cases are the same in any slot and with any adjustment of the last layer
+        let case = |moves: &str| {
+            let mut cube = Cube3x3x3::new();
+            cube.do_moves(&parse_move_string(moves).unwrap().inverse());
+            F2LAlgorithm::from_cube(&cube.as_faces(), CubeFace::Bottom).map(|f2l| f2l.as_number())
+        };
+        assert_eq!(case("U R U' R'"), Some(1));
+        assert_eq!(case("U R U' R' U2"), Some(1));
+        assert_eq!(case("U F U' F'"), Some(1));
+        assert_eq!(case("U' L' U L"), Some(2));
+        assert_eq!(case("R U R'"), Some(4));
+        assert_eq!(case("R U' R' U R U' R'"), Some(27));
+        assert_eq!(case("R U R' F"), None);
+        assert_eq!(case("R U R' L' U' L"), None);
+    }
+
+    #[test]
+    fn f2l_pair_slots() {
+        let pair_for_solution = |moves: &str| {
+            let mut initial_state = Cube3x3x3::new();
+            initial_state.do_moves(&parse_move_string(moves).unwrap().inverse());
+            let solve = CubeWithSolution {
+                initial_state,
+                solution: timed_moves(moves),
+            };
+            match Analysis::analyze(&solve) {
+                Analysis::CFOP(analysis) => {
+                    assert_eq!(analysis.f2l_pairs.len(), 1);
+                    analysis.f2l_pairs[0].clone()
+                }
+                _ => panic!("Analysis failed"),
+            }
+        };
+
+        // Slots are relative to the cube held with the yellow cross on the bottom
+        // and green in front
+        assert_eq!(pair_for_solution("U R U' R'").slot, F2LSlot::FrontRight);
+        assert_eq!(pair_for_solution("U' L' U L").slot, F2LSlot::FrontLeft);
+        assert_eq!(pair_for_solution("U' R' U R").slot, F2LSlot::BackRight);
+        assert_eq!(pair_for_solution("U L U' L'").slot, F2LSlot::BackLeft);
+
+        let pair = pair_for_solution("U R U' R'");
+        assert!(!pair.multi_slotted);
+        assert!(!pair.keyhole);
+        assert!(pair_for_solution("U2 R U2 R'").multi_slotted);
+        assert!(pair_for_solution("R' U R U'").keyhole);
+    }
+
+    #[test]
+    fn pre_auf_analysis() {
+        let pll_for_solution = |moves: &str| {
+            let mut initial_state = Cube3x3x3::new();
+            initial_state.do_moves(&parse_move_string(moves).unwrap().inverse());
+            let solve = CubeWithSolution {
+                initial_state,
+                solution: timed_moves(moves),
+            };
+            match Analysis::analyze(&solve) {
+                Analysis::CFOP(analysis) => analysis,
+                _ => panic!("Analysis failed"),
+            }
+        };
+
+        let t_perm = "R U R' U' R' F R2 U' R' U' R U R' F'";
+        let analysis = pll_for_solution(&format!("U' {}", t_perm));
+        assert_eq!(analysis.pll.len(), 1);
+        assert_eq!(analysis.pll[0].pre_auf.moves, vec![Move::Up]);
+        assert_eq!(analysis.pll[0].pre_auf.time, 100);
+        assert!(analysis.pll[0].pre_auf.optimal);
+        assert_eq!(analysis.auf_time(), 100);
+        assert_eq!(analysis.bad_auf_count(), 0);
+
+        // Half turns reported as two quarter turns are a single adjustment
+        let analysis = pll_for_solution(&format!("U U {}", t_perm));
+        assert_eq!(analysis.pll[0].pre_auf.moves, vec![Move::U, Move::U]);
+        assert!(analysis.pll[0].pre_auf.optimal);
+        assert_eq!(analysis.bad_auf_count(), 0);
+
+        // Two moves for a single adjustment is not optimal
+        let analysis = pll_for_solution(&format!("U U2 {}", t_perm));
+        assert_eq!(analysis.pll[0].pre_auf.moves.len(), 2);
+        assert!(!analysis.pll[0].pre_auf.optimal);
+        assert_eq!(analysis.bad_auf_count(), 1);
+
+        // H perm is symmetric, so adjusting before it is never needed
+        let analysis = pll_for_solution("U2 R2 U2 R U2 R2 U2 R2 U2 R U2 R2");
+        assert_eq!(analysis.pll[0].performed_algorithm, PLLAlgorithm::H);
+        assert!(!analysis.pll[0].pre_auf.optimal);
+    }
+
+    #[test]
+    fn analysis_serialization() {
+        // Cached analysis results are stored as JSON and must be read back unchanged
+        let moves = "U' R U R' U' R' F R2 U' R' U' R U R' F'";
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(moves).unwrap().inverse());
+        let analysis = Analysis::analyze(&CubeWithSolution {
+            initial_state,
+            solution: timed_moves(moves),
+        });
+        assert!(matches!(analysis, Analysis::CFOP(_)));
+
+        let data = serde_json::to_string(&analysis).unwrap();
+        let stored: Analysis = serde_json::from_str(&data).unwrap();
+        assert_eq!(serde_json::to_string(&stored).unwrap(), data);
+    }
+
+    #[test]
+    fn algorithm_type_and_number() {
+        // Algorithm status actions identify algorithms by type and number
+        let algorithms = OLLAlgorithm::all()
+            .iter()
+            .map(|oll| Algorithm::OLL(*oll))
+            .chain(PLLAlgorithm::all().iter().map(|pll| Algorithm::PLL(*pll)))
+            .chain(COLLAlgorithm::all().into_iter().map(Algorithm::COLL))
+            .chain(CLLAlgorithm::all().into_iter().map(Algorithm::CLL));
+        for algorithm in algorithms {
+            let (alg_type, num) = algorithm.to_type_and_number();
+            assert_eq!(
+                Algorithm::from_type_and_number(alg_type, num),
+                Some(algorithm)
+            );
+        }
+        assert_eq!(Algorithm::from_type_and_number(AlgorithmType::OLL, 0), None);
+        assert_eq!(
+            Algorithm::from_type_and_number(AlgorithmType::OLL, 58),
+            None
+        );
+        assert_eq!(
+            Algorithm::from_type_and_number(AlgorithmType::PLL, 21),
+            None
+        );
+    }
+
+    #[test]
+    fn review_schedule() {
+        assert_eq!(
+            ReviewGrade::from_solve(&Penalty::RecognitionDNF, 800, None),
+            ReviewGrade::Misrecognized
+        );
+        assert_eq!(
+            ReviewGrade::from_solve(&Penalty::None, 800, None),
+            ReviewGrade::Easy
+        );
+        assert_eq!(
+            ReviewGrade::from_solve(&Penalty::None, 800, Some((300, 500))),
+            ReviewGrade::Easy
+        );
+        assert_eq!(
+            ReviewGrade::from_solve(&Penalty::None, 2500, Some((2000, 500))),
+            ReviewGrade::Hard
+        );
+
+        // Successful reviews extend the interval, failures reset it
+        let start = Local.timestamp(1_600_000_000, 0);
+        let t = Algorithm::PLL(PLLAlgorithm::T);
+        let mut schedule = ReviewSchedule::new();
+        schedule.review(t, ReviewGrade::Good, start);
+        assert_eq!(schedule.state(t).unwrap().due, start + Duration::days(1));
+        schedule.review(t, ReviewGrade::Good, start + Duration::days(1));
+        assert_eq!(schedule.state(t).unwrap().due, start + Duration::days(7));
+        schedule.review(t, ReviewGrade::Good, start + Duration::days(7));
+        assert_eq!(schedule.state(t).unwrap().interval, Duration::days(15));
+        schedule.review(t, ReviewGrade::Misexecuted, start + Duration::days(22));
+        let state = schedule.state(t).unwrap();
+        assert_eq!(state.repetitions, 0);
+        assert_eq!(state.failures, 1);
+        assert_eq!(state.due, start + Duration::days(22) + Duration::minutes(5));
+        assert!(state.ease_factor < 2.5);
+
+        // Due cases are chosen first, then new cases, then the next case to be due
+        let now = start + Duration::days(23);
+        let y = Algorithm::PLL(PLLAlgorithm::Y);
+        let aa = Algorithm::PLL(PLLAlgorithm::Aa);
+        schedule.review(y, ReviewGrade::Easy, now);
+        let candidates = [t, y, aa];
+        assert_eq!(schedule.next_case(&candidates, None, now), Some(t));
+        assert_eq!(schedule.next_case(&candidates, Some(t), now), Some(aa));
+        assert_eq!(schedule.next_case(&[t, y], Some(t), now), Some(y));
+        assert_eq!(schedule.next_case(&[t], Some(t), now), Some(t));
+    }
+
+    #[test]
+    fn custom_algorithms() {
+        let moves = parse_extended_move_string("Rw U' r2 M' x2 y' S E2 R").unwrap();
+        assert_eq!(moves.to_string(), "r U' r2 M' x2 y' S E2 R");
+        assert!(parse_extended_move_string("R Q").is_err());
+        assert!(parse_extended_move_string("R++").is_err());
+
+        let t_perm = "R U R' U' R' F R2 U' R' U' R U R' F'";
+        let t = Algorithm::PLL(PLLAlgorithm::T);
+        assert!(t
+            .validate(&parse_extended_move_string(t_perm).unwrap())
+            .is_ok());
+        assert!(t
+            .validate(&parse_extended_move_string(&format!("U2 {} U", t_perm)).unwrap())
+            .is_ok());
+        assert!(Algorithm::PLL(PLLAlgorithm::Jb)
+            .validate(&parse_extended_move_string(t_perm).unwrap())
+            .is_err());
+        assert!(t
+            .validate(&parse_extended_move_string(&format!("{} z", t_perm)).unwrap())
+            .is_err());
+        assert!(t.validate(&[]).is_err());
+
+        // Algorithms must not disturb the first two layers
+        let sune = Algorithm::OLL(OLLAlgorithm::Sune);
+        assert!(sune
+            .validate(&parse_extended_move_string("R U R' U R U2 R'").unwrap())
+            .is_ok());
+        assert!(sune
+            .validate(&parse_extended_move_string("y R U R' U R U2 R' y'").unwrap())
+            .is_ok());
+        assert!(sune
+            .validate(&parse_extended_move_string("R U R' U R U2 R' D").unwrap())
+            .is_err());
+
+        for case in OLLAlgorithm::all() {
+            let algorithm = Algorithm::OLL(*case);
+            for moves in algorithm.known_algorithms() {
+                assert!(algorithm.validate(&moves).is_ok());
+            }
+        }
+        for case in PLLAlgorithm::all() {
+            let algorithm = Algorithm::PLL(*case);
+            for moves in algorithm.known_algorithms() {
+                assert!(algorithm.validate(&moves).is_ok());
+            }
+        }
+        for case in CLLAlgorithm::all() {
+            let algorithm = Algorithm::CLL(case);
+            for moves in algorithm.known_algorithms() {
+                assert!(algorithm.validate(&moves).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn algorithm_transformations() {
+        let moves = |string: &str| parse_extended_move_string(string).unwrap();
+        let sune = moves("R U R' U R U2 R'");
+        assert_eq!(
+            sune.mirror(MirrorAxis::LeftRight).to_string(),
+            "L' U' L U' L' U2 L"
+        );
+        assert_eq!(
+            moves("r M x y E S F")
+                .mirror(MirrorAxis::LeftRight)
+                .to_string(),
+            "l' M x y' E' S' F'"
+        );
+        assert_eq!(
+            moves("F R U R' U' F'")
+                .mirror(MirrorAxis::FrontBack)
+                .to_string(),
+            "B' R' U' R U B"
+        );
+        assert_eq!(sune.rotate(CubeRotation::Y).to_string(), "B U B' U B U2 B'");
+        assert_eq!(
+            moves("M' U x").rotate(CubeRotation::Y).to_string(),
+            "S' U z'"
+        );
+        assert_eq!(
+            moves("U2 R U R' U' U").normalize_auf().to_string(),
+            "R U R'"
+        );
+        assert!(moves("U U'").normalize_auf().is_empty());
+
+        // T perm is symmetric, so its mirror solves the same case from another side
+        let t_perm = moves("R U R' U' R' F R2 U' R' U' R U R' F'");
+        let t = Algorithm::PLL(PLLAlgorithm::T);
+        assert!(t.validate(&t_perm.rotate(CubeRotation::Y2)).is_ok());
+        assert!(t_perm.same_case(&moves("U R U R' U' R' F R2 U' R' U' R U R' F' U2")));
+        assert!(t_perm.same_case(&t_perm.rotate(CubeRotation::Yp)));
+        assert!(t_perm.same_case(&t_perm.mirror(MirrorAxis::LeftRight)));
+        assert!(!t_perm.same_case(&moves("R' U R' U' y R' F' R2 U' R' U R' F R F")));
+        assert!(!sune.same_case(&sune.mirror(MirrorAxis::LeftRight)));
+        assert!(!sune.same_case(&moves("R U2 R' U' R U' R'")));
+        assert!(sune.same_case(&moves("U2 y R U R' U R U2 R' y' U")));
+
+        // Algorithms are only the same if they have the same moves
+        assert!(sune.same_algorithm(&moves("U R U R' U R U2 R' U'")));
+        assert!(sune.same_algorithm(&sune.rotate(CubeRotation::Y)));
+        assert!(sune.same_algorithm(&moves("U y' B U B' U B U2 B' y")));
+        assert!(!sune.same_algorithm(&moves("R U2 R' U' R U' R'")));
+        assert!(!t_perm.same_algorithm(&t_perm.mirror(MirrorAxis::LeftRight)));
+    }
+
+    #[test]
+    fn algorithm_ergonomics() {
+        let moves = |string: &str| parse_extended_move_string(string).unwrap();
+        let sune = AlgorithmErgonomics::new(&moves("R U R' U R U2 R'"));
+        assert_eq!(sune.moves, 7);
+        assert_eq!(sune.rotations, 0);
+        assert_eq!(sune.regrips, 0);
+        assert_eq!(sune.wrist_turns, 4);
+        assert_eq!(sune.non_ru_moves, 0);
+
+        // Turning the right wrist three times in a row requires a regrip
+        assert_eq!(AlgorithmErgonomics::new(&moves("R U R U R")).regrips, 1);
+        assert_eq!(AlgorithmErgonomics::new(&moves("R U R2 U R")).regrips, 0);
+        assert_eq!(AlgorithmErgonomics::new(&moves("R' U R' U R'")).regrips, 2);
+
+        let rotated = AlgorithmErgonomics::new(&moves("y' R U R' U R U2 R' y"));
+        assert_eq!(rotated.moves, 9);
+        assert_eq!(rotated.rotations, 2);
+        assert!(rotated.score > sune.score);
+        let back = AlgorithmErgonomics::new(&moves("B U B' U B U2 B'"));
+        assert_eq!(back.non_ru_moves, 4);
+        assert!(back.score > sune.score);
+
+        let algorithms = vec![
+            moves("y' R U R' U R U2 R' y"),
+            moves("R U R' U R U2 R'"),
+            moves("B U B' U B U2 B'"),
+        ];
+        assert_eq!(
+            AlgorithmErgonomics::rank(&algorithms, &[None, None, None]),
+            vec![1, 0, 2]
+        );
+
+        // Measured times take priority over the estimate
+        assert_eq!(
+            AlgorithmErgonomics::rank(&algorithms, &[Some(800), Some(1000), None]),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            AlgorithmErgonomics::rank(&algorithms, &[None, Some(2000), None]),
+            vec![1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn algorithm_sets() {
+        use crate::algorithms::set::AlgorithmSet;
+
+        let text = "// Shared algorithms
+            OLL
+            Sune [Known]: R U R' U R U2 R'; y' R' U2 R U R' U R
+            #45: F R U R' U' F'
+            OLL 21: R U2 R' U' R U R' U' R U' R'
+
+            PLL:
+            T [learning]\tR U R' U' R' F R2 U' R' U' R U R' F'
+            Ua: M2 U M U2 M' U M2
+            Ua: R U' R U R U R U' R' U' R2
+            Ua: y2 M2 U M' U2 M U M2 U'
+            F2L 1: U R U' R'
+            COLL Sune 1: R U R' U R U2 R'";
+        let set = AlgorithmSet::import(text).unwrap();
+        let cases: Vec<Algorithm> = set.entries.iter().map(|entry| entry.algorithm).collect();
+        assert_eq!(
+            cases,
+            vec![
+                Algorithm::OLL(OLLAlgorithm::Sune),
+                Algorithm::OLL(OLLAlgorithm::from_number(45)),
+                Algorithm::OLL(OLLAlgorithm::H),
+                Algorithm::PLL(PLLAlgorithm::T),
+                Algorithm::PLL(PLLAlgorithm::Ua),
+                Algorithm::F2L(F2LAlgorithm::from_number(1).unwrap()),
+                Algorithm::COLL(
+                    COLLAlgorithm::all()
+                        .into_iter()
+                        .find(|coll| coll.to_string() == "Sune 1")
+                        .unwrap()
+                ),
+            ]
+        );
+        assert_eq!(set.entries[0].status, Some(AlgorithmStatus::Known));
+        assert_eq!(set.entries[0].moves.len(), 2);
+        assert_eq!(set.entries[1].status, None);
+        assert_eq!(set.entries[3].status, Some(AlgorithmStatus::Learning));
+        assert_eq!(set.entries[4].moves.len(), 2);
+
+        // Exported sets can be imported again
+        for format in [AlgorithmSetFormat::Text, AlgorithmSetFormat::JSON].iter() {
+            let exported = set.export(*format).unwrap();
+            let imported = AlgorithmSet::import(&exported).unwrap();
+            assert_eq!(imported.entries.len(), set.entries.len());
+            for (a, b) in imported.entries.iter().zip(set.entries.iter()) {
+                assert_eq!(a.algorithm, b.algorithm);
+                assert_eq!(a.status, b.status);
+                assert_eq!(a.moves, b.moves);
+            }
+        }
+
+        // Algorithms are verified against their case
+        let error = AlgorithmSet::import("PLL\nT: R U R' U'").err().unwrap();
+        assert_eq!(error.to_string(), "Line 2: Algorithm does not solve T");
+        assert!(AlgorithmSet::import("PLL\nT: R U Q").is_err());
+        assert!(AlgorithmSet::import("PLL\nX: R U R'").is_err());
+        assert!(AlgorithmSet::import("T: R U R' U' R' F R2 U' R' U' R U R' F'").is_err());
+        assert!(AlgorithmSet::import("OLL\nSune [Mastered]: R U R' U R U2 R'").is_err());
+    }
+
+    #[test]
+    fn last_layer_recognition() {
+        // Counts the distinct angles found when a case is seen from every side. Turning
+        // the last layer before the setup only changes the colors, not the angle.
+        let angles = |solve_type: SolveType, alg: &str, expected: Algorithm| {
+            let setup = parse_move_string(alg).unwrap().inverse();
+            let turns = [vec![], vec![Move::U], vec![Move::U2], vec![Move::Up]];
+            let mut result = Vec::new();
+            for view in turns.iter() {
+                let mut angle = None;
+                for color in turns.iter() {
+                    let mut scramble = color.clone();
+                    scramble.extend(setup.iter());
+                    scramble.extend(view.iter());
+                    let case = RecognitionCase::from_scramble(solve_type, &scramble).unwrap();
+                    assert_eq!(case.algorithm, expected);
+                    assert!((case.angle as usize) < RecognitionCase::ANGLE_COUNT);
+                    assert!(angle.is_none() || angle == Some(case.angle));
+                    angle = Some(case.angle);
+                }
+                if !result.contains(&angle.unwrap()) {
+                    result.push(angle.unwrap());
+                }
+            }
+            result.len()
+        };
+
+        let t_perm = "R U R' U' R' F R2 U' R' U' R U R' F'";
+        let z_perm = "R' U' R U' R U R U' R' U R U R2 U' R'";
+        let h_perm = "R2 U2 R U2 R2 U2 R2 U2 R U2 R2";
+        let sune = "R U R' U R U2 R'";
+        let oll_21 = "R U2 R' U' R U R' U' R U' R'";
+        assert_eq!(
+            angles(
+                SolveType::PLLRecognition,
+                t_perm,
+                Algorithm::PLL(PLLAlgorithm::T)
+            ),
+            4
+        );
+        assert_eq!(
+            angles(
+                SolveType::PLLRecognition,
+                z_perm,
+                Algorithm::PLL(PLLAlgorithm::Z)
+            ),
+            2
+        );
+        assert_eq!(
+            angles(
+                SolveType::PLLRecognition,
+                h_perm,
+                Algorithm::PLL(PLLAlgorithm::H)
+            ),
+            1
+        );
+        assert_eq!(
+            angles(
+                SolveType::OLLRecognition,
+                sune,
+                Algorithm::OLL(OLLAlgorithm::Sune)
+            ),
+            4
+        );
+        assert_eq!(
+            angles(
+                SolveType::OLLRecognition,
+                oll_21,
+                Algorithm::OLL(OLLAlgorithm::H)
+            ),
+            2
+        );
+
+        // Scrambles for other solve types are not recognition drills
+        let setup = parse_move_string(t_perm).unwrap().inverse();
+        assert!(RecognitionCase::from_scramble(SolveType::PLLTraining, &setup).is_none());
+    }
+
+    #[test]
+    fn lbl_analysis() {
+        // Beginner solve of a single first layer corner, second layer edge, and the
+        // last layer using the two-look orientation and permutation algorithms
+        let moves = parse_move_string(concat!(
+            "F' U' F U R U' R' U' F' U F F R U R' U' F' R U R' U R U2 R' ",
+            "R' F R' B2 R F' R' B2 R2 R U' R U R U R U' R' U' R2"
+        ))
+        .unwrap();
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&moves.inverse());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: moves
+                .iter()
+                .enumerate()
+                .map(|(idx, mv)| TimedMove::new(*mv, (idx as u32 + 1) * 100))
+                .collect(),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::LBL(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.first_layer_corners.len(), 1);
+        assert!(!analysis.first_layer_corners[0].paired);
+        assert_eq!(analysis.second_layer_edges.len(), 1);
+        assert_eq!(analysis.second_layer_edges[0].moves.len(), 8);
+        assert_eq!(analysis.last_layer_cross.len(), 1);
+        assert_eq!(analysis.last_layer_face.len(), 1);
+        assert_eq!(analysis.corner_permutation.len(), 1);
+        assert_eq!(analysis.edge_permutation.len(), 1);
+        assert_eq!(analysis.step_summary().len(), 7);
+
+        // Inserting the corner and edge together is an F2L pair, so the solve is
+        // analyzed as CFOP instead
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string("R U R' U'").unwrap());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves("U R U' R'"),
+        };
+        match Analysis::analyze(&solve) {
+            Analysis::CFOP(_) => (),
+            _ => panic!("Analysis should be CFOP"),
+        }
+
+        // A corner passing through its slot while building pairs is not a layer-by-layer
+        // corner insertion
+        let moves = "U' L' U L F U F' R' U' R L' U' L";
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(moves).unwrap().inverse());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves(moves),
+        };
+        let lbl = LBLPartialAnalysis::analyze(&solve);
+        assert!(lbl.first_layer_corners.iter().any(|corner| !corner.paired));
+        match Analysis::analyze(&solve) {
+            Analysis::CFOP(cfop) => assert_eq!(cfop.f2l_pairs.len(), 2),
+            _ => panic!("Analysis should be CFOP"),
+        }
+    }
+
+    #[test]
+    fn mistake_detection() {
+        // Undo a move, then take out a solved pair for too long before solving
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string("R U R' U'").unwrap());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves("F F' L' U U U U L U R U' R'"),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.mistakes.len(), 2);
+        assert_eq!(analysis.mistakes[0].kind, MistakeKind::UndoneMoves);
+        assert_eq!(analysis.mistakes[0].move_index, 0);
+        assert_eq!(analysis.mistakes[0].move_count, 2);
+        assert_eq!(analysis.mistakes[0].time_lost, 100);
+        assert_eq!(analysis.mistakes[1].kind, MistakeKind::BrokenPair);
+        assert_eq!(analysis.mistakes[1].step, CFOPStep::F2LPair(0));
+        assert_eq!(analysis.mistakes[1].move_index, 2);
+        assert_eq!(analysis.mistakes[1].move_count, 6);
+        assert_eq!(analysis.mistakes[1].time_lost, 600);
+        assert_eq!(analysis.mistake_time(), 700);
+
+        // Performing a U perm in the wrong direction is a failed algorithm
+        let u_perm = "R U' R U R U R U' R' U' R2";
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(u_perm).unwrap());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves(&format!("{} {}", u_perm, u_perm)),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.mistakes.len(), 1);
+        assert_eq!(analysis.mistakes[0].kind, MistakeKind::FailedAlgorithm);
+        assert_eq!(analysis.mistakes[0].step, CFOPStep::PLL);
+        assert_eq!(analysis.mistakes[0].move_count, 11);
+    }
+
+    #[test]
+    fn one_look_last_layer() {
+        assert_eq!(COLLAlgorithm::all().len(), 40);
+        assert_eq!(ZBLLAlgorithm::all().len(), 493);
+        assert_eq!(OLLCPAlgorithm::all().len(), 289);
+
+        // Weights cover all states except oriented corners for COLL and the solved
+        // state for ZBLL
+        let coll_weight: usize = COLLAlgorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        let zbll_weight: usize = ZBLLAlgorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        let ollcp_weight: usize = OLLCPAlgorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        assert_eq!(coll_weight + 6, COLLAlgorithm::TOTAL_PROBABILITY_WEIGHT);
+        assert_eq!(zbll_weight + 1, ZBLLAlgorithm::TOTAL_PROBABILITY_WEIGHT);
+        assert_eq!(ollcp_weight, OLLCPAlgorithm::TOTAL_PROBABILITY_WEIGHT);
+
+        // Cases must be recognized on any last layer face
+        for face in &[
+            CubeFace::Top,
+            CubeFace::Front,
+            CubeFace::Right,
+            CubeFace::Back,
+            CubeFace::Left,
+            CubeFace::Bottom,
+        ] {
+            for case in COLLAlgorithm::all() {
+                assert_eq!(
+                    COLLAlgorithm::from_cube(&case.as_faces(*face), *face),
+                    Some(case)
+                );
+            }
+            for case in ZBLLAlgorithm::all() {
+                assert_eq!(
+                    ZBLLAlgorithm::from_cube(&case.as_faces(*face), *face),
+                    Some(case)
+                );
+            }
+            for case in OLLCPAlgorithm::all() {
+                assert_eq!(
+                    OLLCPAlgorithm::from_cube(&case.as_faces(*face), *face),
+                    Some(case)
+                );
+            }
+        }
+        assert_eq!(ZBLLAlgorithm::all()[492].pll(), Some(PLLAlgorithm::Z));
+
+        // Training states must be in the requested case
+        let mut rng = SimpleSeededRandomSource::new();
+        for case in ZBLLAlgorithm::all().iter().step_by(7) {
+            let cube = Cube3x3x3::sourced_random_last_layer(
+                &mut rng,
+                CubeFace::Top,
+                LastLayerRandomization::RandomZBLL(vec![*case]),
+            );
+            assert_eq!(
+                ZBLLAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top),
+                Some(*case)
+            );
+        }
+
+        // Solving the whole last layer at once from a state with oriented edges is a ZBLL
+        // and skips PLL
+        let sune = "R U R' U R U2 R'";
+        let t_perm = "R U R' U' R' F R2 U' R' U' R U R' F'";
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(&format!("{} {}", sune, t_perm)).unwrap());
+        let zbll = ZBLLAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top).unwrap();
+        assert_eq!(zbll.oll(), Some(OLLAlgorithm::Antisune));
+        let mut solution = parse_move_string(&format!("{} {}", sune, t_perm)).unwrap();
+        solution = solution.inverse();
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: solution
+                .iter()
+                .enumerate()
+                .map(|(idx, mv)| TimedMove::new(*mv, (idx as u32 + 1) * 100))
+                .collect(),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.oll.len(), 1);
+        assert_eq!(
+            analysis.oll[0].subset_algorithm,
+            Some(Algorithm::ZBLL(zbll))
+        );
+        assert_eq!(analysis.pll_skip_case(), Some(Algorithm::ZBLL(zbll)));
+
+        // A known OLL algorithm that happens to skip PLL is not a ZBLL
+        let mut initial_state = Cube3x3x3::new();
+        initial_state.do_moves(&parse_move_string(sune).unwrap().inverse());
+        let solve = CubeWithSolution {
+            initial_state,
+            solution: timed_moves(sune),
+        };
+        let analysis = match Analysis::analyze(&solve) {
+            Analysis::CFOP(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.oll.len(), 1);
+        assert_eq!(analysis.oll[0].subset_algorithm, None);
+        assert_eq!(analysis.pll_skip_case(), None);
+    }
+
+    #[test]
+    fn cube2x2x2_last_layer() {
+        assert_eq!(CLLAlgorithm::all().len(), 42);
+        assert_eq!(EG1Algorithm::all().len(), 43);
+        assert_eq!(EG2Algorithm::all().len(), 43);
+
+        // Weights cover all states except the solved state for CLL
+        let cll_weight: usize = CLLAlgorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        let eg1_weight: usize = EG1Algorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        let eg2_weight: usize = EG2Algorithm::all()
+            .iter()
+            .map(|case| case.probability_weight())
+            .sum();
+        assert_eq!(cll_weight + 1, CLLAlgorithm::TOTAL_PROBABILITY_WEIGHT);
+        assert_eq!(eg1_weight, EG1Algorithm::TOTAL_PROBABILITY_WEIGHT);
+        assert_eq!(eg2_weight, EG2Algorithm::TOTAL_PROBABILITY_WEIGHT);
+
+        // Perform inverse of known algorithms and check that the correct case is present
+        fn case_state(alg: &[ExtendedMove]) -> Cube2x2x2Faces {
+            let mut cube = Cube2x2x2::new();
+            let mut state = ExtendedMoveContext::new(&mut cube);
+            state.do_moves(&alg.to_vec().inverse());
+            cube.as_faces()
+        }
+        for case in CLLAlgorithm::all() {
+            let algs = KnownAlgorithms::cll(case);
+            assert!(!algs.is_empty());
+            for alg in algs {
+                let cube = case_state(&alg);
+                assert_eq!(CLLAlgorithm::from_cube(&cube, CubeFace::Bottom), Some(case));
+            }
+        }
+        for case in EG1Algorithm::all() {
+            let algs = KnownAlgorithms::eg1(case);
+            assert!(!algs.is_empty());
+            for alg in algs {
+                let cube = case_state(&alg);
+                assert_eq!(EG1Algorithm::from_cube(&cube, CubeFace::Bottom), Some(case));
+            }
+        }
+        for case in EG2Algorithm::all() {
+            let algs = KnownAlgorithms::eg2(case);
+            assert!(!algs.is_empty());
+            for alg in algs {
+                let cube = case_state(&alg);
+                assert_eq!(EG2Algorithm::from_cube(&cube, CubeFace::Bottom), Some(case));
+            }
+        }
+        assert_eq!(CLLAlgorithm::all()[34].to_string(), "Sune 1");
+        assert_eq!(CLLAlgorithm::all()[34].oll(), Some(OLLAlgorithm::Sune));
+        assert_eq!(EG2Algorithm::all()[42].to_string(), "Diag/Diag");
+    }
+
+    #[test]
+    fn cube2x2x2_analysis() {
+        fn analyze(solution: &str) -> Analysis {
+            let mut initial_state = Cube2x2x2::new();
+            initial_state.do_moves(&parse_move_string(solution).unwrap().inverse());
+            Analysis::analyze_2x2x2(&Cube2x2x2WithSolution {
+                initial_state,
+                solution: timed_moves(solution),
+            })
+        }
+
+        // First face, then a Sune for the last layer
+        let analysis = match analyze("F R R U R' U R U2 R'") {
+            Analysis::Cube2x2x2(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.method, Cube2x2x2Method::CLL);
+        assert_eq!(analysis.first_face.moves.len(), 2);
+        assert_eq!(analysis.first_face.layer, LayerPermutation::Solved);
+        let cll = analysis.last_layer.as_ref().unwrap();
+        assert_eq!(cll.algorithm, Algorithm::CLL(CLLAlgorithm::all()[34]));
+        assert_eq!(cll.recognition_time, 100);
+        assert_eq!(cll.execution_time, 600);
+        assert_eq!(cll.variant(&[]), AlgorithmVariant::Known(0));
+        assert_eq!(analysis.step_summary().len(), 2);
+
+        // Orientation followed by a diagonal swap on both layers
+        let analysis = match analyze("F R R U R' U R U2 R' R2 F2 R2") {
+            Analysis::Cube2x2x2(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.method, Cube2x2x2Method::Ortega);
+        assert_eq!(analysis.oll.len(), 1);
+        assert_eq!(analysis.oll[0].performed_algorithm, OLLAlgorithm::Sune);
+        assert_eq!(analysis.pbl.len(), 1);
+        assert_eq!(
+            analysis.pbl[0].algorithm,
+            PBLAlgorithm {
+                top: LayerPermutation::DiagonalSwap,
+                bottom: LayerPermutation::DiagonalSwap,
+            }
+        );
+        assert_eq!(analysis.pbl[0].new_state, None);
+        assert_eq!(analysis.step_summary().len(), 3);
+
+        // First face with diagonal corners swapped solved with a single algorithm after
+        // aligning the last layer
+        let analysis = match analyze("U R2 F2 R2") {
+            Analysis::Cube2x2x2(analysis) => analysis,
+            _ => panic!("Analysis failed"),
+        };
+        assert_eq!(analysis.method, Cube2x2x2Method::EG2);
+        assert_eq!(
+            analysis.last_layer.unwrap().algorithm,
+            Algorithm::EG2(EG2Algorithm::all()[42])
+        );
+    }
 }
@@ -1,4 +1,5 @@
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
 /// Source of randomness for generating states and scrambles
 pub trait RandomSource {
@@ -14,6 +15,12 @@ pub struct SimpleSeededRandomSource {
 /// Random source using the `rand` crate
 pub struct StandardRandomSource;
 
+/// Cryptographically secure random source with a specific seed, for scramble sets that
+/// must be reproducible. Sources with the same seed always produce the same sequence.
+pub struct SeededRandomSource {
+    rng: ChaCha20Rng,
+}
+
 impl SimpleSeededRandomSource {
     /// Creates a new random source. This always starts at the same seed, and is intended
     /// for use in repeatable testing. Do not use for generating scrambles for a user.
@@ -30,6 +37,20 @@ impl RandomSource for SimpleSeededRandomSource {
     }
 }
 
+impl SeededRandomSource {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            rng: ChaCha20Rng::seed_from_u64(seed as u64),
+        }
+    }
+}
+
+impl RandomSource for SeededRandomSource {
+    fn next(&mut self, range: u32) -> u32 {
+        self.rng.gen_range(0..range)
+    }
+}
+
 impl RandomSource for StandardRandomSource {
     fn next(&mut self, range: u32) -> u32 {
         thread_rng().gen_range(0..range)
@@ -0,0 +1,185 @@
+use crate::{
+    sourced_scramble_2x2x2, sourced_scramble_3x3x3, sourced_scramble_4x4x4,
+    sourced_scramble_last_layer, sourced_scramble_megaminx, LastLayerRandomization, Move,
+    MoveSequence, OLLAlgorithm, PLLAlgorithm, RandomSource, ScrambleConstraints,
+    SeededRandomSource, SolveType,
+};
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use serde_json::json;
+
+/// Number of solves in a daily challenge (an average of 5)
+pub const DAILY_CHALLENGE_SOLVE_COUNT: usize = 5;
+
+/// A reproducible set of scrambles for a competition. Generating a set with the same
+/// parameters always produces the same scrambles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScrambleSet {
+    pub seed: u32,
+    pub solve_type: SolveType,
+    pub rounds: Vec<ScrambleRound>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScrambleRound {
+    pub groups: Vec<ScrambleGroup>,
+}
+
+/// Scrambles for a single group of a round. Extra scrambles are used when a scramble
+/// must be replaced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScrambleGroup {
+    pub scrambles: Vec<Vec<Move>>,
+    pub extras: Vec<Vec<Move>>,
+}
+
+/// Generates a random scramble for the given solve type with a given random number source
+pub fn sourced_scramble<T: RandomSource>(rng: &mut T, solve_type: SolveType) -> Vec<Move> {
+    match solve_type {
+        SolveType::Standard2x2x2 => sourced_scramble_2x2x2(rng),
+        SolveType::Standard3x3x3
+        | SolveType::OneHanded3x3x3
+        | SolveType::Blind3x3x3
+        | SolveType::DailyChallenge3x3x3
+        | SolveType::CrossTraining
+        | SolveType::XCrossTraining => sourced_scramble_3x3x3(rng),
+        SolveType::Standard4x4x4 | SolveType::Blind4x4x4 => sourced_scramble_4x4x4(rng),
+        SolveType::Megaminx => sourced_scramble_megaminx(rng),
+        SolveType::OLLTraining => {
+            sourced_scramble_last_layer(rng, LastLayerRandomization::RandomStateUnsolved)
+        }
+        SolveType::OLLRecognition => sourced_scramble_last_layer(
+            rng,
+            LastLayerRandomization::WeightedRandomOLL(OLLAlgorithm::all().to_vec()),
+        ),
+        SolveType::PLLRecognition => sourced_scramble_last_layer(
+            rng,
+            LastLayerRandomization::WeightedRandomPLL(PLLAlgorithm::all().to_vec()),
+        ),
+        SolveType::PLLTraining => {
+            sourced_scramble_last_layer(rng, LastLayerRandomization::OrientedRandomStateUnsolved)
+        }
+        SolveType::HardCrossTraining | SolveType::LastSlotTraining | SolveType::F2LTraining => {
+            // Fall back to a random state if the constraints could not be satisfied, as
+            // is done when scrambling in the timer
+            ScrambleConstraints::new(&solve_type.scramble_constraints())
+                .sourced_scramble(rng)
+                .unwrap_or_else(|| sourced_scramble_3x3x3(rng))
+        }
+    }
+}
+
+impl ScrambleSet {
+    /// Number of scrambles in each group for the usual competition format of an event
+    pub fn default_scramble_count(solve_type: SolveType) -> usize {
+        match solve_type {
+            SolveType::Blind3x3x3 | SolveType::Blind4x4x4 => 3,
+            _ => 5,
+        }
+    }
+
+    /// Generates a scramble set. Scrambles are generated in order of round, then group,
+    /// then the scrambles and extras of the group.
+    pub fn generate(
+        seed: u32,
+        solve_type: SolveType,
+        rounds: usize,
+        groups: usize,
+        scrambles: usize,
+        extras: usize,
+    ) -> Self {
+        let mut rng = SeededRandomSource::new(seed);
+        let rounds = (0..rounds)
+            .map(|_| ScrambleRound {
+                groups: (0..groups)
+                    .map(|_| ScrambleGroup {
+                        scrambles: (0..scrambles)
+                            .map(|_| sourced_scramble(&mut rng, solve_type))
+                            .collect(),
+                        extras: (0..extras)
+                            .map(|_| sourced_scramble(&mut rng, solve_type))
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self {
+            seed,
+            solve_type,
+            rounds,
+        }
+    }
+
+    /// Generates the scramble set for the daily challenge on a given date. The seed is
+    /// derived only from the date, so every user gets the same scrambles without any
+    /// coordination through a server.
+    pub fn daily_challenge(date: NaiveDate) -> Self {
+        Self::generate(
+            Self::daily_challenge_seed(date),
+            SolveType::DailyChallenge3x3x3,
+            1,
+            1,
+            DAILY_CHALLENGE_SOLVE_COUNT,
+            0,
+        )
+    }
+
+    pub fn daily_challenge_seed(date: NaiveDate) -> u32 {
+        // Mix the date bits so that consecutive days start from unrelated seeds
+        let mut seed = (date.year() as u32)
+            .wrapping_mul(10000)
+            .wrapping_add(date.month() * 100 + date.day());
+        seed ^= seed >> 16;
+        seed = seed.wrapping_mul(0x45d9f3b);
+        seed ^= seed >> 16;
+        seed = seed.wrapping_mul(0x45d9f3b);
+        seed ^= seed >> 16;
+        seed
+    }
+
+    /// Gets the display name of a group, using letters as in competition scramble sheets
+    pub fn group_name(idx: usize) -> String {
+        let mut name = String::new();
+        let mut idx = idx + 1;
+        while idx > 0 {
+            idx -= 1;
+            name.insert(0, (b'A' + (idx % 26) as u8) as char);
+            idx /= 26;
+        }
+        name
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let mut round_list = Vec::new();
+        for (round_idx, round) in self.rounds.iter().enumerate() {
+            let mut group_list = Vec::new();
+            for (group_idx, group) in round.groups.iter().enumerate() {
+                let scrambles: Vec<String> = group
+                    .scrambles
+                    .iter()
+                    .map(|scramble| scramble.to_string())
+                    .collect();
+                let extras: Vec<String> = group
+                    .extras
+                    .iter()
+                    .map(|scramble| scramble.to_string())
+                    .collect();
+                group_list.push(json!({
+                    "group": Self::group_name(group_idx),
+                    "scrambles": scrambles,
+                    "extras": extras,
+                }));
+            }
+            round_list.push(json!({
+                "round": round_idx + 1,
+                "groups": group_list,
+            }));
+        }
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "seed": self.seed,
+            "event": self.solve_type.to_string(),
+            "rounds": round_list,
+        }))?)
+    }
+}
@@ -4,7 +4,7 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "native-storage")]
-use rocksdb::{DBCompressionType, Options, DB};
+use rocksdb::{DBCompressionType, Direction, IteratorMode, Options, DB};
 #[cfg(feature = "native-storage")]
 use std::path::Path;
 
@@ -17,7 +17,7 @@ use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 #[cfg(feature = "web-storage")]
 use wasm_bindgen_futures::JsFuture;
 #[cfg(feature = "web-storage")]
-use web_sys::{IdbDatabase, IdbTransactionMode};
+use web_sys::{IdbDatabase, IdbKeyRange, IdbTransactionMode};
 
 #[cfg(feature = "native-storage")]
 pub(crate) struct Storage {
@@ -34,9 +34,14 @@ pub(crate) struct DeferredStorage {
     queue: Arc<Mutex<StorageQueue>>,
 }
 
+/// Callback for a deferred read. It is given the stored value, and returns a new value
+/// to store in its place if the value should be replaced.
+type UpdateCallback = Box<dyn FnOnce(Option<Vec<u8>>) -> Option<Vec<u8>> + Send>;
+
 enum StorageQueueItem {
     Put(String, Vec<u8>),
     Delete(String),
+    Update(String, UpdateCallback),
     Flush,
 }
 
@@ -68,6 +73,20 @@ impl Storage {
         Ok(self.db.delete(key)?)
     }
 
+    /// Deletes all keys that start with the given prefix
+    pub async fn delete_prefix(&mut self, prefix: &str) -> Result<()> {
+        let keys: Vec<Box<[u8]>> = self
+            .db
+            .iterator(IteratorMode::From(prefix.as_bytes(), Direction::Forward))
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(prefix.as_bytes()))
+            .collect();
+        for key in keys {
+            self.db.delete(key)?;
+        }
+        Ok(())
+    }
+
     pub async fn flush(&self) {
         let _ = self.db.flush();
     }
@@ -284,6 +303,57 @@ impl Storage {
         Ok(())
     }
 
+    /// Deletes all keys that start with the given prefix
+    pub async fn delete_prefix(&mut self, prefix: &str) -> Result<()> {
+        let transaction = self
+            .db
+            .transaction_with_str_and_mode("storage", IdbTransactionMode::Readwrite)
+            .map_err(|_| anyhow!("Failed to start IndexedDB transaction"))?;
+        let store = transaction
+            .object_store("storage")
+            .map_err(|_| anyhow!("Transaction does not have object store"))?;
+
+        // Keys are strings, so every key with the prefix sorts between the prefix
+        // itself and the prefix followed by the highest character
+        let range = IdbKeyRange::bound(
+            &JsValue::from_str(prefix),
+            &JsValue::from_str(&format!("{}\u{ffff}", prefix)),
+        )
+        .map_err(|_| anyhow!("Failed to create key range"))?;
+        let request = store
+            .delete(&range)
+            .map_err(|_| anyhow!("Failed to request database item delete"))?;
+
+        // Create a `Promise` object for the request so that we can obtain a future
+        let request_copy = request.clone();
+        let mut promise = move |resolve: Function, reject: Function| {
+            let resolve_request = request_copy.clone();
+            let resolve = move || {
+                resolve.call0(&resolve_request).unwrap();
+            };
+            let resolve = Closure::wrap(Box::new(resolve) as Box<dyn FnMut()>);
+
+            let reject_request = request_copy.clone();
+            let reject = move || {
+                reject
+                    .call1(&reject_request, &reject_request.error().unwrap().unwrap())
+                    .unwrap();
+            };
+            let reject = Closure::wrap(Box::new(reject) as Box<dyn FnMut()>);
+
+            request_copy.set_onsuccess(Some(&resolve.into_js_value().dyn_into().unwrap()));
+            request_copy.set_onerror(Some(&reject.into_js_value().dyn_into().unwrap()));
+        };
+        let promise = Promise::new(&mut promise);
+
+        // Wait for the delete to finish
+        JsFuture::from(promise)
+            .await
+            .map_err(|_| anyhow!("Delete items from database failed"))?;
+
+        Ok(())
+    }
+
     pub async fn flush(&self) {}
 }
 
@@ -367,6 +437,20 @@ impl DeferredStorage {
                                 }
                             }
                         }
+                        StorageQueueItem::Update(key, callback) => {
+                            let result = match storage.get(&key).await {
+                                Ok(value) => match callback(value) {
+                                    Some(value) => storage.put(&key, &value).await,
+                                    None => Ok(()),
+                                },
+                                Err(error) => Err(error),
+                            };
+                            if let Err(error) = result {
+                                // Abort all future work on error, as is done for writes
+                                *error_message.lock().unwrap() = Some(error.to_string());
+                                break;
+                            }
+                        }
                         StorageQueueItem::Flush => storage.flush().await,
                     }
                 }
@@ -382,6 +466,19 @@ impl DeferredStorage {
         self.push(StorageQueueItem::Delete(key.to_string()));
     }
 
+    /// Reads a value in the background. The callback is given the stored value once it
+    /// has been read, and can return a new value to store in its place.
+    pub fn update(
+        &self,
+        key: &str,
+        callback: impl FnOnce(Option<Vec<u8>>) -> Option<Vec<u8>> + Send + 'static,
+    ) {
+        self.push(StorageQueueItem::Update(
+            key.to_string(),
+            Box::new(callback),
+        ));
+    }
+
     pub fn flush(&self) {
         self.push(StorageQueueItem::Flush);
     }
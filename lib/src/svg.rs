@@ -0,0 +1,206 @@
+use crate::{Color, Cube2x2x2Faces, Cube3x3x3Faces, Cube4x4x4Faces, CubeFace};
+use std::fmt::Write;
+
+/// Options for rendering cube images as SVG
+#[derive(Clone, Debug)]
+pub struct SvgOptions {
+    /// Size of a single sticker in pixels
+    pub sticker_size: f32,
+    /// Space between faces of a net, or between the last layer and its side stickers
+    pub face_gap: f32,
+    pub stroke_width: f32,
+    pub stroke_color: String,
+    pub background: Option<String>,
+    /// Fill colors for each sticker color, indexed by `Color`
+    pub colors: [String; 6],
+    /// Fill color for stickers that are not relevant to the view, such as the side
+    /// stickers of an orientation only last layer view
+    pub ignored_color: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LastLayerSvgStyle {
+    /// Show all stickers
+    Full,
+    /// Show only the stickers that match the top center color, used for OLL cases
+    Orientation,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            sticker_size: 16.0,
+            face_gap: 4.0,
+            stroke_width: 1.0,
+            stroke_color: "#000000".into(),
+            background: None,
+            colors: [
+                "#ffffff".into(),
+                "#00b000".into(),
+                "#e00000".into(),
+                "#0040ff".into(),
+                "#ff8000".into(),
+                "#ffff00".into(),
+            ],
+            ignored_color: "#606060".into(),
+        }
+    }
+}
+
+impl SvgOptions {
+    pub fn color(&self, color: Color) -> &str {
+        &self.colors[color as u8 as usize]
+    }
+
+    fn sticker(&self, result: &mut String, x: f32, y: f32, width: f32, height: f32, fill: &str) {
+        write!(
+            result,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\" \
+             stroke-width=\"{}\"/>",
+            x, y, width, height, fill, self.stroke_color, self.stroke_width
+        )
+        .unwrap();
+    }
+
+    fn document(&self, width: f32, height: f32, body: &str) -> String {
+        let background = match &self.background {
+            Some(color) => format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", color),
+            None => String::new(),
+        };
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\">{}{}</svg>",
+            width, height, width, height, background, body
+        )
+    }
+}
+
+/// Renders an unfolded net of a cube. The top face is above the front face, with the
+/// left, front, right and back faces in a row and the bottom face below the front face.
+fn net_svg<F: Fn(CubeFace, usize, usize) -> Color>(
+    size: usize,
+    color: F,
+    options: &SvgOptions,
+) -> String {
+    let face_size = options.sticker_size * size as f32 + options.face_gap;
+    let margin = options.stroke_width / 2.0;
+    let mut body = String::new();
+    for (face, face_x, face_y) in &[
+        (CubeFace::Top, 1.0, 0.0),
+        (CubeFace::Left, 0.0, 1.0),
+        (CubeFace::Front, 1.0, 1.0),
+        (CubeFace::Right, 2.0, 1.0),
+        (CubeFace::Back, 3.0, 1.0),
+        (CubeFace::Bottom, 1.0, 2.0),
+    ] {
+        for row in 0..size {
+            for col in 0..size {
+                options.sticker(
+                    &mut body,
+                    margin + face_x * face_size + col as f32 * options.sticker_size,
+                    margin + face_y * face_size + row as f32 * options.sticker_size,
+                    options.sticker_size,
+                    options.sticker_size,
+                    options.color(color(*face, row, col)),
+                );
+            }
+        }
+    }
+    options.document(
+        face_size * 4.0 - options.face_gap + margin * 2.0,
+        face_size * 3.0 - options.face_gap + margin * 2.0,
+        &body,
+    )
+}
+
+impl Cube2x2x2Faces {
+    /// Renders this cube state as an unfolded net in SVG format
+    pub fn net_svg(&self, options: &SvgOptions) -> String {
+        net_svg(2, |face, row, col| self.color(face, row, col), options)
+    }
+}
+
+impl Cube3x3x3Faces {
+    /// Renders this cube state as an unfolded net in SVG format
+    pub fn net_svg(&self, options: &SvgOptions) -> String {
+        net_svg(3, |face, row, col| self.color(face, row, col), options)
+    }
+
+    /// Renders a top down view of the last layer in SVG format, with the top row of
+    /// stickers of each side face placed around the top face. The back face is at the
+    /// top of the image.
+    pub fn last_layer_svg(&self, style: LastLayerSvgStyle, options: &SvgOptions) -> String {
+        let top_color = self.color(CubeFace::Top, 1, 1);
+        let fill = |color: Color| {
+            if style == LastLayerSvgStyle::Orientation && color != top_color {
+                options.ignored_color.as_str()
+            } else {
+                options.color(color)
+            }
+        };
+
+        let sticker = options.sticker_size;
+        let side = sticker / 3.0;
+        let margin = options.stroke_width / 2.0;
+        let top_start = margin + side + options.face_gap;
+        let far_side = top_start + sticker * 3.0 + options.face_gap;
+        let mut body = String::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                options.sticker(
+                    &mut body,
+                    top_start + col as f32 * sticker,
+                    top_start + row as f32 * sticker,
+                    sticker,
+                    sticker,
+                    fill(self.color(CubeFace::Top, row, col)),
+                );
+            }
+        }
+        for i in 0..3 {
+            let offset = top_start + i as f32 * sticker;
+            options.sticker(
+                &mut body,
+                offset,
+                margin,
+                sticker,
+                side,
+                fill(self.color(CubeFace::Back, 0, 2 - i)),
+            );
+            options.sticker(
+                &mut body,
+                offset,
+                far_side,
+                sticker,
+                side,
+                fill(self.color(CubeFace::Front, 0, i)),
+            );
+            options.sticker(
+                &mut body,
+                margin,
+                offset,
+                side,
+                sticker,
+                fill(self.color(CubeFace::Left, 0, i)),
+            );
+            options.sticker(
+                &mut body,
+                far_side,
+                offset,
+                side,
+                sticker,
+                fill(self.color(CubeFace::Right, 0, 2 - i)),
+            );
+        }
+
+        let size = far_side + side + margin;
+        options.document(size, size, &body)
+    }
+}
+
+impl Cube4x4x4Faces {
+    /// Renders this cube state as an unfolded net in SVG format
+    pub fn net_svg(&self, options: &SvgOptions) -> String {
+        net_svg(4, |face, row, col| self.color(face, row, col), options)
+    }
+}
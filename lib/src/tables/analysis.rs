@@ -330,3 +330,1001 @@ pub(crate) const CUBE3_PLL_CASES: [[u16; 4]; 21] = [
     [0x012, 0x35a, 0x468, 0x79b],
     [0x024, 0x135, 0x68a, 0x79b],
 ];
+
+/// 3x3x3 COLL cases. Each case is stored as a key packing the state of the last layer
+/// (7 bits per position, see `LastLayerState::key`), the probability weight of the case,
+/// and the number of the OLL case it belongs to. The key is the smallest of the
+/// keys for all the ways to align the last layer before and after the case. Cases are
+/// sorted by OLL case.
+pub(crate) const CUBE3_COLL_CASES: [(u32, u8, u8); 40] = [
+    (0x10c2470, 2, 21),
+    (0x10c3450, 4, 21),
+    (0x1141470, 4, 21),
+    (0x11c2430, 2, 21),
+    (0x10a2870, 4, 22),
+    (0x10a3850, 4, 22),
+    (0x10c3848, 4, 22),
+    (0x1121870, 4, 22),
+    (0x1143828, 4, 22),
+    (0x11a2830, 4, 22),
+    (0x0082470, 4, 23),
+    (0x0083450, 4, 23),
+    (0x00a3840, 4, 23),
+    (0x0101470, 4, 23),
+    (0x0123820, 4, 23),
+    (0x0182430, 4, 23),
+    (0x0082868, 4, 24),
+    (0x0083848, 4, 24),
+    (0x00c3440, 4, 24),
+    (0x0101868, 4, 24),
+    (0x0143420, 4, 24),
+    (0x0182828, 4, 24),
+    (0x00a2070, 4, 25),
+    (0x00a3050, 4, 25),
+    (0x00c3048, 4, 25),
+    (0x0121070, 4, 25),
+    (0x0123030, 4, 25),
+    (0x01a2030, 4, 25),
+    (0x00c2870, 4, 26),
+    (0x00c3850, 4, 26),
+    (0x0141870, 4, 26),
+    (0x0143830, 4, 26),
+    (0x01c1850, 4, 26),
+    (0x01c2830, 4, 26),
+    (0x00a2468, 4, 27),
+    (0x00a3448, 4, 27),
+    (0x0121468, 4, 27),
+    (0x0123428, 4, 27),
+    (0x01a1448, 4, 27),
+    (0x01a2428, 4, 27),
+];
+
+/// 3x3x3 ZBLL cases. Each case is stored as a key packing the state of the last layer
+/// (7 bits per position, see `LastLayerState::key`), the probability weight of the case,
+/// and the number of the OLL case it belongs to (zero for PLL cases). The key is the
+/// smallest of the keys for all the ways to align the last layer before and after the
+/// case. Cases are sorted by OLL case, with the PLL cases last in PLL order.
+pub(crate) const CUBE3_ZBLL_CASES: [(u32, u8, u8); 493] = [
+    (0x10ca676, 2, 21),
+    (0x10cb754, 4, 21),
+    (0x10d2772, 4, 21),
+    (0x10d3556, 4, 21),
+    (0x10da574, 4, 21),
+    (0x10db652, 4, 21),
+    (0x1149774, 4, 21),
+    (0x114b636, 4, 21),
+    (0x1151576, 4, 21),
+    (0x1159672, 4, 21),
+    (0x115b534, 4, 21),
+    (0x11c9656, 4, 21),
+    (0x11ca734, 4, 21),
+    (0x11d1752, 4, 21),
+    (0x11d2536, 4, 21),
+    (0x11da632, 2, 21),
+    (0x14c2774, 2, 21),
+    (0x14c3656, 4, 21),
+    (0x14d2476, 4, 21),
+    (0x14db454, 4, 21),
+    (0x1541676, 4, 21),
+    (0x1551770, 4, 21),
+    (0x1559474, 4, 21),
+    (0x155b630, 4, 21),
+    (0x15c1754, 4, 21),
+    (0x15d1456, 4, 21),
+    (0x15d2730, 2, 21),
+    (0x15da434, 4, 21),
+    (0x18c2576, 4, 21),
+    (0x18da472, 2, 21),
+    (0x18db550, 4, 21),
+    (0x1941772, 4, 21),
+    (0x1949476, 4, 21),
+    (0x19ca436, 2, 21),
+    (0x19d9452, 4, 21),
+    (0x19da530, 4, 21),
+    (0x1ccb650, 4, 21),
+    (0x1cd2570, 2, 21),
+    (0x1d43632, 4, 21),
+    (0x1dc2534, 2, 21),
+    (0x10aaa76, 4, 22),
+    (0x10abb54, 4, 22),
+    (0x10b2b72, 4, 22),
+    (0x10b3956, 4, 22),
+    (0x10ba974, 4, 22),
+    (0x10bba52, 4, 22),
+    (0x10cbb4c, 4, 22),
+    (0x10d2b6a, 4, 22),
+    (0x10d394e, 4, 22),
+    (0x10da96c, 4, 22),
+    (0x10dba4a, 4, 22),
+    (0x1129b74, 4, 22),
+    (0x112ba36, 4, 22),
+    (0x1131976, 4, 22),
+    (0x1139a72, 4, 22),
+    (0x113b934, 4, 22),
+    (0x1149b6c, 4, 22),
+    (0x114ba2e, 4, 22),
+    (0x1153b2a, 4, 22),
+    (0x1159a6a, 4, 22),
+    (0x115b92c, 4, 22),
+    (0x11a9a56, 4, 22),
+    (0x11aab34, 4, 22),
+    (0x11b1b52, 4, 22),
+    (0x11b2936, 4, 22),
+    (0x11baa32, 4, 22),
+    (0x11c9a4e, 4, 22),
+    (0x11cab2c, 4, 22),
+    (0x11d1b4a, 4, 22),
+    (0x11d292e, 4, 22),
+    (0x14a2b74, 4, 22),
+    (0x14a3a56, 4, 22),
+    (0x14b2876, 4, 22),
+    (0x14b3b50, 4, 22),
+    (0x14baa70, 4, 22),
+    (0x14bb854, 4, 22),
+    (0x14c2b6c, 4, 22),
+    (0x14c3a4e, 4, 22),
+    (0x14d3b48, 4, 22),
+    (0x1521a76, 4, 22),
+    (0x1523b34, 4, 22),
+    (0x1539874, 4, 22),
+    (0x1543b2c, 4, 22),
+    (0x155382e, 4, 22),
+    (0x155986c, 4, 22),
+    (0x155ba28, 4, 22),
+    (0x15a2a36, 4, 22),
+    (0x15b1856, 4, 22),
+    (0x15b2b30, 4, 22),
+    (0x15c2a2e, 4, 22),
+    (0x15d2b28, 4, 22),
+    (0x15d9a48, 4, 22),
+    (0x18a2976, 4, 22),
+    (0x18a3b52, 4, 22),
+    (0x18ba872, 4, 22),
+    (0x18bb950, 4, 22),
+    (0x18c3b4a, 4, 22),
+    (0x18db948, 4, 22),
+    (0x1921b72, 4, 22),
+    (0x1929876, 4, 22),
+    (0x192bb30, 4, 22),
+    (0x194392e, 4, 22),
+    (0x194bb28, 4, 22),
+    (0x19aa836, 4, 22),
+    (0x19d984a, 4, 22),
+    (0x19da928, 4, 22),
+    (0x1ca2a72, 4, 22),
+    (0x1ca3954, 4, 22),
+    (0x1cd384a, 4, 22),
+    (0x1d29a70, 4, 22),
+    (0x1d53928, 4, 22),
+    (0x1db2832, 4, 22),
+    (0x008a676, 4, 23),
+    (0x008b754, 4, 23),
+    (0x0092772, 4, 23),
+    (0x0093556, 4, 23),
+    (0x009a574, 4, 23),
+    (0x009b652, 4, 23),
+    (0x00abb44, 4, 23),
+    (0x00b2b62, 4, 23),
+    (0x00b3946, 4, 23),
+    (0x00ba964, 4, 23),
+    (0x00bba42, 4, 23),
+    (0x0109774, 4, 23),
+    (0x010b636, 4, 23),
+    (0x0111576, 4, 23),
+    (0x0119672, 4, 23),
+    (0x011b534, 4, 23),
+    (0x0129b64, 4, 23),
+    (0x012ba26, 4, 23),
+    (0x0133b22, 4, 23),
+    (0x0139a62, 4, 23),
+    (0x013b924, 4, 23),
+    (0x0189656, 4, 23),
+    (0x018a734, 4, 23),
+    (0x0191752, 4, 23),
+    (0x0192536, 4, 23),
+    (0x019a632, 4, 23),
+    (0x01a9a46, 4, 23),
+    (0x01aab24, 4, 23),
+    (0x01b1b42, 4, 23),
+    (0x01b2926, 4, 23),
+    (0x0482774, 4, 23),
+    (0x0483656, 4, 23),
+    (0x0492476, 4, 23),
+    (0x0493750, 4, 23),
+    (0x049a670, 4, 23),
+    (0x049b454, 4, 23),
+    (0x04a2b64, 4, 23),
+    (0x04a3a46, 4, 23),
+    (0x04b3b40, 4, 23),
+    (0x0501676, 4, 23),
+    (0x0503734, 4, 23),
+    (0x0519474, 4, 23),
+    (0x0523b24, 4, 23),
+    (0x0533826, 4, 23),
+    (0x0539864, 4, 23),
+    (0x053ba20, 4, 23),
+    (0x0582636, 4, 23),
+    (0x0591456, 4, 23),
+    (0x0592730, 4, 23),
+    (0x05a2a26, 4, 23),
+    (0x05b2b20, 4, 23),
+    (0x05b9a40, 4, 23),
+    (0x0882576, 4, 23),
+    (0x0883752, 4, 23),
+    (0x089a472, 4, 23),
+    (0x089b550, 4, 23),
+    (0x08a3b42, 4, 23),
+    (0x08bb940, 4, 23),
+    (0x0901772, 4, 23),
+    (0x0909476, 4, 23),
+    (0x090b730, 4, 23),
+    (0x0923926, 4, 23),
+    (0x092bb20, 4, 23),
+    (0x098a436, 4, 23),
+    (0x09b9842, 4, 23),
+    (0x09ba920, 4, 23),
+    (0x0c82672, 4, 23),
+    (0x0c83554, 4, 23),
+    (0x0cb3842, 4, 23),
+    (0x0d09670, 4, 23),
+    (0x0d33920, 4, 23),
+    (0x0d92432, 4, 23),
+    (0x008aa6e, 4, 24),
+    (0x008bb4c, 4, 24),
+    (0x0092b6a, 4, 24),
+    (0x009394e, 4, 24),
+    (0x009a96c, 4, 24),
+    (0x009ba4a, 4, 24),
+    (0x00cb744, 4, 24),
+    (0x00d2762, 4, 24),
+    (0x00d3546, 4, 24),
+    (0x00da564, 4, 24),
+    (0x00db642, 4, 24),
+    (0x0109b6c, 4, 24),
+    (0x010ba2e, 4, 24),
+    (0x011196e, 4, 24),
+    (0x0119a6a, 4, 24),
+    (0x011b92c, 4, 24),
+    (0x0149764, 4, 24),
+    (0x014b626, 4, 24),
+    (0x0153722, 4, 24),
+    (0x0159662, 4, 24),
+    (0x015b524, 4, 24),
+    (0x0189a4e, 4, 24),
+    (0x018ab2c, 4, 24),
+    (0x0191b4a, 4, 24),
+    (0x019292e, 4, 24),
+    (0x019aa2a, 4, 24),
+    (0x01c9646, 4, 24),
+    (0x01ca724, 4, 24),
+    (0x01d1742, 4, 24),
+    (0x01d2526, 4, 24),
+    (0x0482b6c, 4, 24),
+    (0x0483a4e, 4, 24),
+    (0x049286e, 4, 24),
+    (0x0493b48, 4, 24),
+    (0x049aa68, 4, 24),
+    (0x049b84c, 4, 24),
+    (0x04c2764, 4, 24),
+    (0x04c3646, 4, 24),
+    (0x04d3740, 4, 24),
+    (0x0501a6e, 4, 24),
+    (0x0503b2c, 4, 24),
+    (0x051986c, 4, 24),
+    (0x0543724, 4, 24),
+    (0x0553426, 4, 24),
+    (0x0559464, 4, 24),
+    (0x055b620, 4, 24),
+    (0x0582a2e, 4, 24),
+    (0x059184e, 4, 24),
+    (0x0592b28, 4, 24),
+    (0x05c2626, 4, 24),
+    (0x05d2720, 4, 24),
+    (0x05d9640, 4, 24),
+    (0x088296e, 4, 24),
+    (0x0883b4a, 4, 24),
+    (0x089a86a, 4, 24),
+    (0x089b948, 4, 24),
+    (0x08c3742, 4, 24),
+    (0x08db540, 4, 24),
+    (0x0901b6a, 4, 24),
+    (0x090986e, 4, 24),
+    (0x090bb28, 4, 24),
+    (0x0943526, 4, 24),
+    (0x094b720, 4, 24),
+    (0x098a82e, 4, 24),
+    (0x09d9442, 4, 24),
+    (0x09da520, 4, 24),
+    (0x0c82a6a, 4, 24),
+    (0x0c8394c, 4, 24),
+    (0x0cd3442, 4, 24),
+    (0x0d09a68, 4, 24),
+    (0x0d53520, 4, 24),
+    (0x0d9282a, 4, 24),
+    (0x00aa276, 4, 25),
+    (0x00ab354, 4, 25),
+    (0x00b2372, 4, 25),
+    (0x00b3156, 4, 25),
+    (0x00ba174, 4, 25),
+    (0x00bb252, 4, 25),
+    (0x00cb34c, 4, 25),
+    (0x00d236a, 4, 25),
+    (0x00d314e, 4, 25),
+    (0x00da16c, 4, 25),
+    (0x00db24a, 4, 25),
+    (0x0129374, 4, 25),
+    (0x012b236, 4, 25),
+    (0x0131176, 4, 25),
+    (0x0133332, 4, 25),
+    (0x0139272, 4, 25),
+    (0x013b134, 4, 25),
+    (0x014936c, 4, 25),
+    (0x014b22e, 4, 25),
+    (0x015926a, 4, 25),
+    (0x015b12c, 4, 25),
+    (0x01a9256, 4, 25),
+    (0x01aa334, 4, 25),
+    (0x01b1352, 4, 25),
+    (0x01b2136, 4, 25),
+    (0x01ba232, 4, 25),
+    (0x01c924e, 4, 25),
+    (0x01ca32c, 4, 25),
+    (0x01d134a, 4, 25),
+    (0x01d212e, 4, 25),
+    (0x04a2374, 4, 25),
+    (0x04a3256, 4, 25),
+    (0x04b2076, 4, 25),
+    (0x04bb054, 4, 25),
+    (0x04c324e, 4, 25),
+    (0x04d206e, 4, 25),
+    (0x04db04c, 4, 25),
+    (0x0521276, 4, 25),
+    (0x0531370, 4, 25),
+    (0x0533036, 4, 25),
+    (0x0539074, 4, 25),
+    (0x053b230, 4, 25),
+    (0x0551368, 4, 25),
+    (0x055906c, 4, 25),
+    (0x055b228, 4, 25),
+    (0x05a1354, 4, 25),
+    (0x05b1056, 4, 25),
+    (0x05b2330, 4, 25),
+    (0x05ba034, 4, 25),
+    (0x05c134c, 4, 25),
+    (0x05d104e, 4, 25),
+    (0x05da02c, 4, 25),
+    (0x08a2176, 4, 25),
+    (0x08ba072, 4, 25),
+    (0x08bb150, 4, 25),
+    (0x08c216e, 4, 25),
+    (0x08db148, 4, 25),
+    (0x0921372, 4, 25),
+    (0x0923136, 4, 25),
+    (0x0929076, 4, 25),
+    (0x094906e, 4, 25),
+    (0x09aa036, 4, 25),
+    (0x09b9052, 4, 25),
+    (0x09ba130, 4, 25),
+    (0x09d904a, 4, 25),
+    (0x09da128, 4, 25),
+    (0x0cab250, 4, 25),
+    (0x0cb2170, 4, 25),
+    (0x0ccb248, 4, 25),
+    (0x0d23232, 4, 25),
+    (0x0d31072, 4, 25),
+    (0x0da2134, 4, 25),
+    (0x00caa76, 4, 26),
+    (0x00cbb54, 4, 26),
+    (0x00d2b72, 4, 26),
+    (0x00d3956, 4, 26),
+    (0x00da974, 4, 26),
+    (0x00dba52, 4, 26),
+    (0x0149b74, 4, 26),
+    (0x014ba36, 4, 26),
+    (0x0151976, 4, 26),
+    (0x0153b32, 4, 26),
+    (0x0159a72, 4, 26),
+    (0x015b934, 4, 26),
+    (0x01c9a56, 4, 26),
+    (0x01cab34, 4, 26),
+    (0x01d1b52, 4, 26),
+    (0x01d2936, 4, 26),
+    (0x01d9954, 4, 26),
+    (0x01daa32, 4, 26),
+    (0x04c2b74, 4, 26),
+    (0x04c3a56, 4, 26),
+    (0x04d2876, 4, 26),
+    (0x04d3b50, 4, 26),
+    (0x04daa70, 4, 26),
+    (0x04db854, 4, 26),
+    (0x0541a76, 4, 26),
+    (0x0543b34, 4, 26),
+    (0x0551b70, 4, 26),
+    (0x0553836, 4, 26),
+    (0x0559874, 4, 26),
+    (0x055ba30, 4, 26),
+    (0x05c1b54, 4, 26),
+    (0x05c2a36, 4, 26),
+    (0x05d1856, 4, 26),
+    (0x05d2b30, 4, 26),
+    (0x05d9a50, 4, 26),
+    (0x05da834, 4, 26),
+    (0x08c2976, 4, 26),
+    (0x08c3b52, 4, 26),
+    (0x08cab70, 4, 26),
+    (0x08cb856, 4, 26),
+    (0x08da872, 4, 26),
+    (0x08db950, 4, 26),
+    (0x0941b72, 4, 26),
+    (0x0943936, 4, 26),
+    (0x0949876, 4, 26),
+    (0x094bb30, 4, 26),
+    (0x0959970, 4, 26),
+    (0x095b832, 4, 26),
+    (0x09c1956, 4, 26),
+    (0x09c2b32, 4, 26),
+    (0x09c9b50, 4, 26),
+    (0x09ca836, 4, 26),
+    (0x09d9852, 4, 26),
+    (0x09da930, 4, 26),
+    (0x0cc2a72, 4, 26),
+    (0x0cc3954, 4, 26),
+    (0x0cca874, 4, 26),
+    (0x0ccba50, 4, 26),
+    (0x0cd2970, 4, 26),
+    (0x0cd3852, 4, 26),
+    (0x0d41974, 4, 26),
+    (0x0d43a32, 4, 26),
+    (0x0d49a70, 4, 26),
+    (0x0d4b834, 4, 26),
+    (0x0d51872, 4, 26),
+    (0x0d53930, 4, 26),
+    (0x0dc1a52, 4, 26),
+    (0x0dc2934, 4, 26),
+    (0x0dc9854, 4, 26),
+    (0x0dcaa30, 4, 26),
+    (0x0dd1950, 4, 26),
+    (0x0dd2832, 4, 26),
+    (0x00aa66e, 4, 27),
+    (0x00ab74c, 4, 27),
+    (0x00b276a, 4, 27),
+    (0x00b354e, 4, 27),
+    (0x00ba56c, 4, 27),
+    (0x00bb64a, 4, 27),
+    (0x012976c, 4, 27),
+    (0x012b62e, 4, 27),
+    (0x013156e, 4, 27),
+    (0x013372a, 4, 27),
+    (0x013966a, 4, 27),
+    (0x013b52c, 4, 27),
+    (0x01a964e, 4, 27),
+    (0x01aa72c, 4, 27),
+    (0x01b174a, 4, 27),
+    (0x01b252e, 4, 27),
+    (0x01b954c, 4, 27),
+    (0x01ba62a, 4, 27),
+    (0x04a276c, 4, 27),
+    (0x04a364e, 4, 27),
+    (0x04b246e, 4, 27),
+    (0x04b3748, 4, 27),
+    (0x04ba668, 4, 27),
+    (0x04bb44c, 4, 27),
+    (0x052166e, 4, 27),
+    (0x052372c, 4, 27),
+    (0x0531768, 4, 27),
+    (0x053342e, 4, 27),
+    (0x053946c, 4, 27),
+    (0x053b628, 4, 27),
+    (0x05a174c, 4, 27),
+    (0x05a262e, 4, 27),
+    (0x05b144e, 4, 27),
+    (0x05b2728, 4, 27),
+    (0x05b9648, 4, 27),
+    (0x05ba42c, 4, 27),
+    (0x08a256e, 4, 27),
+    (0x08a374a, 4, 27),
+    (0x08aa768, 4, 27),
+    (0x08ab44e, 4, 27),
+    (0x08ba46a, 4, 27),
+    (0x08bb548, 4, 27),
+    (0x092176a, 4, 27),
+    (0x092352e, 4, 27),
+    (0x092946e, 4, 27),
+    (0x092b728, 4, 27),
+    (0x0939568, 4, 27),
+    (0x093b42a, 4, 27),
+    (0x09a154e, 4, 27),
+    (0x09a272a, 4, 27),
+    (0x09a9748, 4, 27),
+    (0x09aa42e, 4, 27),
+    (0x09b944a, 4, 27),
+    (0x09ba528, 4, 27),
+    (0x0ca266a, 4, 27),
+    (0x0ca354c, 4, 27),
+    (0x0caa46c, 4, 27),
+    (0x0cab648, 4, 27),
+    (0x0cb2568, 4, 27),
+    (0x0cb344a, 4, 27),
+    (0x0d2156c, 4, 27),
+    (0x0d2362a, 4, 27),
+    (0x0d29668, 4, 27),
+    (0x0d2b42c, 4, 27),
+    (0x0d3146a, 4, 27),
+    (0x0d33528, 4, 27),
+    (0x0da164a, 4, 27),
+    (0x0da252c, 4, 27),
+    (0x0da944c, 4, 27),
+    (0x0daa628, 4, 27),
+    (0x0db1548, 4, 27),
+    (0x0db242a, 4, 27),
+    (0x010b226, 4, 0),
+    (0x0189246, 4, 0),
+    (0x009b242, 4, 0),
+    (0x011b124, 4, 0),
+    (0x0191342, 4, 0),
+    (0x0519064, 4, 0),
+    (0x089b140, 4, 0),
+    (0x0093146, 4, 0),
+    (0x008b344, 4, 0),
+    (0x0483246, 4, 0),
+    (0x0109364, 4, 0),
+    (0x0119262, 4, 0),
+    (0x0592320, 2, 0),
+    (0x019a222, 1, 0),
+    (0x098a026, 1, 0),
+    (0x0192126, 4, 0),
+    (0x018a324, 4, 0),
+    (0x089a062, 1, 0),
+    (0x009a164, 4, 0),
+    (0x0092362, 4, 0),
+    (0x0482364, 2, 0),
+];
+
+/// 3x3x3 OLLCP cases. Each case is stored as a key packing the state of the last layer
+/// (7 bits per position, see `LastLayerState::key`), the probability weight of the case,
+/// and the number of the OLL case it belongs to. The key is the smallest of the
+/// keys for all the ways to align the last layer before and after the case. Cases are
+/// sorted by OLL case.
+pub(crate) const CUBE3_OLLCP_CASES: [(u32, u8, u8); 289] = [
+    (0x12c64f1, 2, 1),
+    (0x12c74d1, 4, 1),
+    (0x13454f1, 4, 1),
+    (0x13c64b1, 2, 1),
+    (0x12a68f1, 4, 2),
+    (0x12a78d1, 4, 2),
+    (0x12c78c9, 4, 2),
+    (0x13258f1, 4, 2),
+    (0x13478a9, 4, 2),
+    (0x13a68b1, 4, 2),
+    (0x02a64e9, 4, 3),
+    (0x02a74c9, 4, 3),
+    (0x03254e9, 4, 3),
+    (0x03274a9, 4, 3),
+    (0x03a54c9, 4, 3),
+    (0x03a64a9, 4, 3),
+    (0x02c68f1, 4, 4),
+    (0x02c78d1, 4, 4),
+    (0x03458f1, 4, 4),
+    (0x03478b1, 4, 4),
+    (0x03c58d1, 4, 4),
+    (0x03c68b1, 4, 4),
+    (0x00a64e8, 4, 5),
+    (0x00a74c8, 4, 5),
+    (0x01254e8, 4, 5),
+    (0x01274a8, 4, 5),
+    (0x01a54c8, 4, 5),
+    (0x01a64a8, 4, 5),
+    (0x00c68f0, 4, 6),
+    (0x00c78d0, 4, 6),
+    (0x01458f0, 4, 6),
+    (0x01478b0, 4, 6),
+    (0x01c58d0, 4, 6),
+    (0x01c68b0, 4, 6),
+    (0x00a24e9, 4, 7),
+    (0x00a34c9, 4, 7),
+    (0x01214e9, 4, 7),
+    (0x01234a9, 4, 7),
+    (0x01a14c9, 4, 7),
+    (0x01a24a9, 4, 7),
+    (0x02c6870, 4, 8),
+    (0x02c7850, 4, 8),
+    (0x0345870, 4, 8),
+    (0x0347830, 4, 8),
+    (0x03c5850, 4, 8),
+    (0x03c6830, 4, 8),
+    (0x02c2871, 4, 9),
+    (0x02c3851, 4, 9),
+    (0x0341871, 4, 9),
+    (0x0343831, 4, 9),
+    (0x03c1851, 4, 9),
+    (0x03c2831, 4, 9),
+    (0x02a2469, 4, 10),
+    (0x02a3449, 4, 10),
+    (0x0321469, 4, 10),
+    (0x0323429, 4, 10),
+    (0x03a1449, 4, 10),
+    (0x03a2429, 4, 10),
+    (0x02a6468, 4, 11),
+    (0x02a7448, 4, 11),
+    (0x0325468, 4, 11),
+    (0x0327428, 4, 11),
+    (0x03a5448, 4, 11),
+    (0x03a6428, 4, 11),
+    (0x00c28f1, 4, 12),
+    (0x00c38d1, 4, 12),
+    (0x01418f1, 4, 12),
+    (0x01438b1, 4, 12),
+    (0x01c18d1, 4, 12),
+    (0x01c28b1, 4, 12),
+    (0x00a6469, 4, 13),
+    (0x00a7449, 4, 13),
+    (0x0125469, 4, 13),
+    (0x0127429, 4, 13),
+    (0x01a5449, 4, 13),
+    (0x01a6429, 4, 13),
+    (0x02c28f0, 4, 14),
+    (0x02c38d0, 4, 14),
+    (0x03418f0, 4, 14),
+    (0x03438b0, 4, 14),
+    (0x03c18d0, 4, 14),
+    (0x03c28b0, 4, 14),
+    (0x02a24e8, 4, 15),
+    (0x02a34c8, 4, 15),
+    (0x03214e8, 4, 15),
+    (0x03234a8, 4, 15),
+    (0x03a14c8, 4, 15),
+    (0x03a24a8, 4, 15),
+    (0x00c6871, 4, 16),
+    (0x00c7851, 4, 16),
+    (0x0145871, 4, 16),
+    (0x0147831, 4, 16),
+    (0x01c5851, 4, 16),
+    (0x01c6831, 4, 16),
+    (0x02a60f1, 4, 17),
+    (0x02a70d1, 4, 17),
+    (0x02c70c9, 4, 17),
+    (0x03250f1, 4, 17),
+    (0x03270b1, 4, 17),
+    (0x03a60b1, 4, 17),
+    (0x02864f1, 4, 18),
+    (0x02874d1, 4, 18),
+    (0x02a78c1, 4, 18),
+    (0x03054f1, 4, 18),
+    (0x03278a1, 4, 18),
+    (0x03864b1, 4, 18),
+    (0x02868e9, 4, 19),
+    (0x02878c9, 4, 19),
+    (0x02c74c1, 4, 19),
+    (0x03058e9, 4, 19),
+    (0x03474a1, 4, 19),
+    (0x03868a9, 4, 19),
+    (0x02860e1, 1, 20),
+    (0x02870c1, 4, 20),
+    (0x03860a1, 1, 20),
+    (0x00820e1, 4, 28),
+    (0x00830c1, 4, 28),
+    (0x00870c0, 4, 28),
+    (0x01010e1, 4, 28),
+    (0x01070a0, 4, 28),
+    (0x01820a1, 4, 28),
+    (0x0286868, 4, 29),
+    (0x0287848, 4, 29),
+    (0x02c3441, 4, 29),
+    (0x0305868, 4, 29),
+    (0x0343421, 4, 29),
+    (0x0386828, 4, 29),
+    (0x00c24e1, 4, 30),
+    (0x00c34c1, 4, 30),
+    (0x01414e1, 4, 30),
+    (0x01434a1, 4, 30),
+    (0x01c14c1, 4, 30),
+    (0x01c24a1, 4, 30),
+    (0x00868e8, 4, 31),
+    (0x00878c8, 4, 31),
+    (0x01058e8, 4, 31),
+    (0x01078a8, 4, 31),
+    (0x01858c8, 4, 31),
+    (0x01868a8, 4, 31),
+    (0x00828e9, 4, 32),
+    (0x00838c9, 4, 32),
+    (0x00c74c0, 4, 32),
+    (0x01018e9, 4, 32),
+    (0x01474a0, 4, 32),
+    (0x01828a9, 4, 32),
+    (0x0086869, 4, 33),
+    (0x0087849, 4, 33),
+    (0x0105869, 4, 33),
+    (0x0107829, 4, 33),
+    (0x0185849, 4, 33),
+    (0x0186829, 4, 33),
+    (0x00c6461, 4, 34),
+    (0x00c7441, 4, 34),
+    (0x0145461, 4, 34),
+    (0x0147421, 4, 34),
+    (0x01c5441, 4, 34),
+    (0x01c6421, 4, 34),
+    (0x00c60e8, 4, 35),
+    (0x00c70c8, 4, 35),
+    (0x01450e8, 4, 35),
+    (0x01470a8, 4, 35),
+    (0x01c50c8, 4, 35),
+    (0x01c60a8, 4, 35),
+    (0x00c20e9, 4, 36),
+    (0x00c30c9, 4, 36),
+    (0x01410e9, 4, 36),
+    (0x01430a9, 4, 36),
+    (0x01c10c9, 4, 36),
+    (0x01c20a9, 4, 36),
+    (0x00a60f0, 4, 37),
+    (0x00a70d0, 4, 37),
+    (0x01250f0, 4, 37),
+    (0x01270b0, 4, 37),
+    (0x01a50d0, 4, 37),
+    (0x01a60b0, 4, 37),
+    (0x00a20f1, 4, 38),
+    (0x00a30d1, 4, 38),
+    (0x01210f1, 4, 38),
+    (0x01230b1, 4, 38),
+    (0x01a10d1, 4, 38),
+    (0x01a20b1, 4, 38),
+    (0x00a6071, 4, 39),
+    (0x00a7051, 4, 39),
+    (0x00c7049, 4, 39),
+    (0x0125071, 4, 39),
+    (0x0127031, 4, 39),
+    (0x01a6031, 4, 39),
+    (0x02a20f0, 4, 40),
+    (0x02a30d0, 4, 40),
+    (0x02c30c8, 4, 40),
+    (0x03210f0, 4, 40),
+    (0x03230b0, 4, 40),
+    (0x03a20b0, 4, 40),
+    (0x00a28e1, 4, 41),
+    (0x00a38c1, 4, 41),
+    (0x01218e1, 4, 41),
+    (0x01238a1, 4, 41),
+    (0x01a18c1, 4, 41),
+    (0x01a28a1, 4, 41),
+    (0x0286470, 4, 42),
+    (0x0287450, 4, 42),
+    (0x02a3841, 4, 42),
+    (0x0305470, 4, 42),
+    (0x0323821, 4, 42),
+    (0x0386430, 4, 42),
+    (0x00864f0, 4, 43),
+    (0x00874d0, 4, 43),
+    (0x01054f0, 4, 43),
+    (0x01074b0, 4, 43),
+    (0x01854d0, 4, 43),
+    (0x01864b0, 4, 43),
+    (0x00824f1, 4, 44),
+    (0x00834d1, 4, 44),
+    (0x00a78c0, 4, 44),
+    (0x01014f1, 4, 44),
+    (0x01278a0, 4, 44),
+    (0x01824b1, 4, 44),
+    (0x0086471, 4, 45),
+    (0x0087451, 4, 45),
+    (0x0105471, 4, 45),
+    (0x0107431, 4, 45),
+    (0x0185451, 4, 45),
+    (0x0186431, 4, 45),
+    (0x00a6861, 4, 46),
+    (0x00a7841, 4, 46),
+    (0x0125861, 4, 46),
+    (0x0127821, 4, 46),
+    (0x01a5841, 4, 46),
+    (0x01a6821, 4, 46),
+    (0x10a28f1, 4, 47),
+    (0x10a38d1, 4, 47),
+    (0x10c78c8, 4, 47),
+    (0x11218f1, 4, 47),
+    (0x11478a8, 4, 47),
+    (0x11a28b1, 4, 47),
+    (0x10c28e9, 4, 48),
+    (0x10c38c9, 4, 48),
+    (0x11418e9, 4, 48),
+    (0x11438a9, 4, 48),
+    (0x11c18c9, 4, 48),
+    (0x11c28a9, 4, 48),
+    (0x12a6870, 4, 49),
+    (0x12a7850, 4, 49),
+    (0x12c3849, 4, 49),
+    (0x1325870, 4, 49),
+    (0x1343829, 4, 49),
+    (0x13a6830, 4, 49),
+    (0x10a68f0, 4, 50),
+    (0x10a78d0, 4, 50),
+    (0x11258f0, 4, 50),
+    (0x11278b0, 4, 50),
+    (0x11a58d0, 4, 50),
+    (0x11a68b0, 4, 50),
+    (0x10c6869, 4, 51),
+    (0x10c7849, 4, 51),
+    (0x1145869, 4, 51),
+    (0x1147829, 4, 51),
+    (0x11c5849, 4, 51),
+    (0x11c6829, 4, 51),
+    (0x10a6871, 4, 52),
+    (0x10a7851, 4, 52),
+    (0x1125871, 4, 52),
+    (0x1127831, 4, 52),
+    (0x11a5851, 4, 52),
+    (0x11a6831, 4, 52),
+    (0x10c24f1, 4, 53),
+    (0x10c34d1, 4, 53),
+    (0x11414f1, 4, 53),
+    (0x11434b1, 4, 53),
+    (0x11c14d1, 4, 53),
+    (0x11c24b1, 4, 53),
+    (0x10c64f0, 4, 54),
+    (0x10c74d0, 4, 54),
+    (0x11454f0, 4, 54),
+    (0x11474b0, 4, 54),
+    (0x11c54d0, 4, 54),
+    (0x11c64b0, 4, 54),
+    (0x12c24f0, 2, 55),
+    (0x12c34d0, 4, 55),
+    (0x13414f0, 4, 55),
+    (0x13c24b0, 2, 55),
+    (0x10c6471, 2, 56),
+    (0x10c7451, 4, 56),
+    (0x1145471, 4, 56),
+    (0x11c6431, 2, 56),
+    (0x0086061, 2, 57),
+    (0x0087041, 4, 57),
+    (0x0105061, 4, 57),
+    (0x0186021, 2, 57),
+];
+
+/// 2x2x2 CLL cases. Each case is stored as a key packing the state of both layers (6 bits
+/// per position, see `FirstFaceState::key`), the probability weight of the case, and the
+/// number of the OLL case it belongs to (zero if the last layer is oriented). The key is
+/// the smallest of the keys for all the ways to turn either layer before and after the
+/// case. Cases are sorted by OLL case.
+pub(crate) const CUBE2_CLL_CASES: [(u32, u8, u8); 42] = [
+    (0x1199bb, 2, 21),
+    (0x119dab, 4, 21),
+    (0x1295bb, 4, 21),
+    (0x13999b, 2, 21),
+    (0x115abb, 4, 22),
+    (0x115eab, 4, 22),
+    (0x119ea7, 4, 22),
+    (0x1256bb, 4, 22),
+    (0x129e97, 4, 22),
+    (0x135a9b, 4, 22),
+    (0x011ab7, 4, 23),
+    (0x011ea7, 4, 23),
+    (0x019da3, 4, 23),
+    (0x0216b7, 4, 23),
+    (0x029d93, 4, 23),
+    (0x031a97, 4, 23),
+    (0x0119bb, 4, 24),
+    (0x011dab, 4, 24),
+    (0x015ea3, 4, 24),
+    (0x0215bb, 4, 24),
+    (0x025e93, 4, 24),
+    (0x03199b, 4, 24),
+    (0x0158bb, 4, 25),
+    (0x015cab, 4, 25),
+    (0x019ca7, 4, 25),
+    (0x0254bb, 4, 25),
+    (0x025c9b, 4, 25),
+    (0x03589b, 4, 25),
+    (0x0159b7, 4, 26),
+    (0x015da7, 4, 26),
+    (0x0255b7, 4, 26),
+    (0x025d97, 4, 26),
+    (0x0355a7, 4, 26),
+    (0x035997, 4, 26),
+    (0x019abb, 4, 27),
+    (0x019eab, 4, 27),
+    (0x0296bb, 4, 27),
+    (0x029e9b, 4, 27),
+    (0x0396ab, 4, 27),
+    (0x039a9b, 4, 27),
+    (0x011ca3, 4, 0),
+    (0x031893, 1, 0),
+];
+
+/// 2x2x2 EG-1 cases, stored the same way as `CUBE2_CLL_CASES`
+pub(crate) const CUBE2_EG1_CASES: [(u32, u8, u8); 43] = [
+    (0x1199fa, 2, 21),
+    (0x119dea, 4, 21),
+    (0x1295fa, 4, 21),
+    (0x1399da, 2, 21),
+    (0x115afa, 4, 22),
+    (0x115eea, 4, 22),
+    (0x119ee6, 4, 22),
+    (0x1256fa, 4, 22),
+    (0x129ed6, 4, 22),
+    (0x135ada, 4, 22),
+    (0x011af6, 4, 23),
+    (0x011ee6, 4, 23),
+    (0x019de2, 4, 23),
+    (0x0216f6, 4, 23),
+    (0x029dd2, 4, 23),
+    (0x031ad6, 4, 23),
+    (0x0119fa, 4, 24),
+    (0x011dea, 4, 24),
+    (0x015ee2, 4, 24),
+    (0x0215fa, 4, 24),
+    (0x025ed2, 4, 24),
+    (0x0319da, 4, 24),
+    (0x0158fa, 4, 25),
+    (0x015cea, 4, 25),
+    (0x019ce6, 4, 25),
+    (0x0254fa, 4, 25),
+    (0x025cda, 4, 25),
+    (0x0358da, 4, 25),
+    (0x0159f6, 4, 26),
+    (0x015de6, 4, 26),
+    (0x0255f6, 4, 26),
+    (0x025dd6, 4, 26),
+    (0x0355e6, 4, 26),
+    (0x0359d6, 4, 26),
+    (0x019afa, 4, 27),
+    (0x019eea, 4, 27),
+    (0x0296fa, 4, 27),
+    (0x029eda, 4, 27),
+    (0x0396ea, 4, 27),
+    (0x039ada, 4, 27),
+    (0x0118f2, 1, 0),
+    (0x011ce2, 4, 0),
+    (0x0318d2, 1, 0),
+];
+
+/// 2x2x2 EG-2 cases, stored the same way as `CUBE2_CLL_CASES`
+pub(crate) const CUBE2_EG2_CASES: [(u32, u8, u8); 43] = [
+    (0x11b9b9, 2, 21),
+    (0x11bda9, 4, 21),
+    (0x12b5b9, 4, 21),
+    (0x13b999, 2, 21),
+    (0x117ab9, 4, 22),
+    (0x117ea9, 4, 22),
+    (0x11bea5, 4, 22),
+    (0x1276b9, 4, 22),
+    (0x12be95, 4, 22),
+    (0x137a99, 4, 22),
+    (0x013ab5, 4, 23),
+    (0x013ea5, 4, 23),
+    (0x01bda1, 4, 23),
+    (0x0236b5, 4, 23),
+    (0x02bd91, 4, 23),
+    (0x033a95, 4, 23),
+    (0x0139b9, 4, 24),
+    (0x013da9, 4, 24),
+    (0x017ea1, 4, 24),
+    (0x0235b9, 4, 24),
+    (0x027e91, 4, 24),
+    (0x033999, 4, 24),
+    (0x0178b9, 4, 25),
+    (0x017ca9, 4, 25),
+    (0x01bca5, 4, 25),
+    (0x0274b9, 4, 25),
+    (0x027c99, 4, 25),
+    (0x037899, 4, 25),
+    (0x0179b5, 4, 26),
+    (0x017da5, 4, 26),
+    (0x0275b5, 4, 26),
+    (0x027d95, 4, 26),
+    (0x0375a5, 4, 26),
+    (0x037995, 4, 26),
+    (0x01bab9, 4, 27),
+    (0x01bea9, 4, 27),
+    (0x02b6b9, 4, 27),
+    (0x02be99, 4, 27),
+    (0x03b6a9, 4, 27),
+    (0x03ba99, 4, 27),
+    (0x0138b1, 1, 0),
+    (0x013ca1, 4, 0),
+    (0x033891, 1, 0),
+];
+
+/// 3x3x3 F2L cases for the last pair, in the standard order. Each case is stored as a
+/// key packing the state of the pair (see `F2LState::key`). The key is the smallest of
+/// the keys for all the ways to align the last layer before the case.
+pub(crate) const CUBE3_F2L_CASES: [u8; 41] = [
+    17, 20, 12, 25, 15, 22, 13, 24, 14, 23, 16, 21, 10, 27, 11, 26, 7, 0, 5, 2, 3, 4, 1, 6, 121,
+    120, 131, 140, 141, 130, 9, 8, 18, 28, 19, 29, 129, 138, 148, 139, 149,
+];
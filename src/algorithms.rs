@@ -1,34 +1,97 @@
+mod f2l;
 mod list;
+mod mastery;
+mod recognition;
 mod report;
 
 use crate::font::FontSize;
 use crate::style::{content_visuals, side_visuals};
 use crate::theme::Theme;
-use crate::widgets::CustomWidgets;
+use crate::widgets::{algorithm_name, CustomWidgets};
 use egui::{
     epaint::{Mesh, TextureId, Vertex, WHITE_UV},
     Align, CentralPanel, Color32, CtxRef, Direction, Layout, Rect, Rgba, Shape, SidePanel, Stroke,
     TopBottomPanel, Ui, Vec2,
 };
-use list::AlgorithmList;
+use f2l::{F2LReport, F2LStats};
+use list::{AlgorithmEditor, AlgorithmList};
+use mastery::MasteryReport;
+use recognition::{RecognitionReport, RecognitionStats};
 use report::TPSReport;
 use std::collections::HashMap;
 use tpscube_core::{
-    Algorithm, AlgorithmType, Analysis, Color, Cube, Cube3x3x3, Cube3x3x3Faces, CubeFace,
-    CubeRotation, CubeWithSolution, ExtendedMove, ExtendedMoveContext, ExtendedMoveSequence,
-    History, InitialCubeState, OLLAlgorithm, PLLAlgorithm,
+    Algorithm, AlgorithmType, AlgorithmVariant, Analysis, CLLAlgorithm, COLLAlgorithm, Color, Cube,
+    Cube3x3x3, Cube3x3x3Faces, CubeFace, CubeRotation, EG1Algorithm, EG2Algorithm, ExtendedMove,
+    ExtendedMoveContext, ExtendedMoveSequence, F2LAlgorithm, History, InitialCubeState,
+    OLLAlgorithm, OLLCPAlgorithm, PLLAlgorithm, ReviewSchedule, SolveType, ZBLLAlgorithm,
 };
 
+/// Number of times an algorithm must be performed before its execution time is used
+/// for ranking algorithms
+const MEASURED_EXECUTION_COUNT: usize = 5;
+
 pub struct AlgorithmsWidget {
     cached_update_id: Option<u64>,
     algorithm_stats: AlgorithmStats,
+    f2l_stats: F2LStats,
+    recognition_stats: RecognitionStats,
+    review_schedule: ReviewSchedule,
     mode: AlgorithmMode,
     sort: Sort,
+    algorithm_editor: AlgorithmEditor,
 }
 
 struct AlgorithmStats {
     oll: HashMap<OLLAlgorithm, AlgorithmCounts>,
     pll: HashMap<PLLAlgorithm, AlgorithmCounts>,
+    coll: HashMap<COLLAlgorithm, AlgorithmCounts>,
+    zbll: HashMap<ZBLLAlgorithm, AlgorithmCounts>,
+    ollcp: HashMap<OLLCPAlgorithm, AlgorithmCounts>,
+    cll: HashMap<CLLAlgorithm, AlgorithmCounts>,
+    eg1: HashMap<EG1Algorithm, AlgorithmCounts>,
+    eg2: HashMap<EG2Algorithm, AlgorithmCounts>,
+    f2l_training: HashMap<F2LAlgorithm, TrainingTimes>,
+}
+
+/// Times of the completed F2L training solves for a case
+#[derive(Default)]
+struct TrainingTimes {
+    count: usize,
+    total_time: u64,
+    best_time: Option<u32>,
+}
+
+impl TrainingTimes {
+    fn add(&mut self, time: u32) {
+        self.count += 1;
+        self.total_time += time as u64;
+        self.best_time = Some(self.best_time.map_or(time, |best| best.min(time)));
+    }
+
+    fn average_time(&self) -> Option<u32> {
+        if self.count == 0 {
+            None
+        } else {
+            Some((self.total_time / self.count as u64) as u32)
+        }
+    }
+}
+
+impl AlgorithmStats {
+    /// Gets the counts for a case as performed in solves
+    fn counts(&self, algorithm: Algorithm) -> Option<&AlgorithmCounts> {
+        match algorithm {
+            Algorithm::OLL(oll) => self.oll.get(&oll),
+            Algorithm::PLL(pll) => self.pll.get(&pll),
+            Algorithm::COLL(coll) => self.coll.get(&coll),
+            Algorithm::ZBLL(zbll) => self.zbll.get(&zbll),
+            Algorithm::OLLCP(ollcp) => self.ollcp.get(&ollcp),
+            Algorithm::CLL(cll) => self.cll.get(&cll),
+            Algorithm::EG1(eg1) => self.eg1.get(&eg1),
+            Algorithm::EG2(eg2) => self.eg2.get(&eg2),
+            Algorithm::F2L(_) => None,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -37,12 +100,59 @@ struct AlgorithmCounts {
     total_moves: usize,
     total_recognition_time: u64,
     total_execution_time: u64,
+    /// Number of times the last layer adjustment before the algorithm used more
+    /// moves than needed
+    bad_auf_count: usize,
+    /// Counts for each algorithm variant used to solve the case
+    variants: Vec<(AlgorithmVariant, AlgorithmCounts)>,
+}
+
+impl AlgorithmCounts {
+    fn add(
+        &mut self,
+        variant: AlgorithmVariant,
+        moves: usize,
+        recognition_time: u32,
+        execution_time: u32,
+    ) {
+        self.perform_count += 1;
+        self.total_moves += moves;
+        self.total_recognition_time += recognition_time as u64;
+        self.total_execution_time += execution_time as u64;
+
+        let idx = match self.variants.iter().position(|(v, _)| *v == variant) {
+            Some(idx) => idx,
+            None => {
+                self.variants.push((variant, AlgorithmCounts::default()));
+                self.variants.len() - 1
+            }
+        };
+        let counts = &mut self.variants[idx].1;
+        counts.perform_count += 1;
+        counts.total_moves += moves;
+        counts.total_recognition_time += recognition_time as u64;
+        counts.total_execution_time += execution_time as u64;
+    }
+
+    /// Gets the average execution time of an algorithm variant in milliseconds, if it
+    /// has been performed enough times for the average to be meaningful
+    fn execution_time(&self, variant: &AlgorithmVariant) -> Option<u32> {
+        self.variants
+            .iter()
+            .find(|(v, _)| v == variant)
+            .map(|(_, counts)| counts)
+            .filter(|counts| counts.perform_count >= MEASURED_EXECUTION_COUNT)
+            .map(|counts| (counts.total_execution_time / counts.perform_count as u64) as u32)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum AlgorithmMode {
     Algorithms(AlgorithmType),
     TPSReport(AlgorithmType),
+    F2LReport,
+    Mastery(AlgorithmType),
+    Recognition(AlgorithmType),
 }
 
 struct Sort {
@@ -72,39 +182,139 @@ impl AlgorithmsWidget {
             algorithm_stats: AlgorithmStats {
                 oll: HashMap::new(),
                 pll: HashMap::new(),
+                coll: HashMap::new(),
+                zbll: HashMap::new(),
+                ollcp: HashMap::new(),
+                cll: HashMap::new(),
+                eg1: HashMap::new(),
+                eg2: HashMap::new(),
+                f2l_training: HashMap::new(),
             },
+            f2l_stats: F2LStats::default(),
+            recognition_stats: RecognitionStats::default(),
+            review_schedule: ReviewSchedule::new(),
             mode: AlgorithmMode::Algorithms(AlgorithmType::OLL),
             sort: Sort {
                 column: SortColumn::TPS,
                 order: SortOrder::Descending,
             },
+            algorithm_editor: AlgorithmEditor::default(),
         }
     }
 
     fn analyze(&mut self, history: &History) {
         self.algorithm_stats.oll.clear();
         self.algorithm_stats.pll.clear();
+        self.algorithm_stats.coll.clear();
+        self.algorithm_stats.zbll.clear();
+        self.algorithm_stats.ollcp.clear();
+        self.algorithm_stats.cll.clear();
+        self.algorithm_stats.eg1.clear();
+        self.algorithm_stats.eg2.clear();
+        self.algorithm_stats.f2l_training.clear();
+        self.f2l_stats = F2LStats::default();
+        self.recognition_stats = RecognitionStats::default();
+        self.review_schedule = ReviewSchedule::new();
 
         for solve in history.iter() {
-            if let Some(moves) = &solve.moves {
-                let mut unsolved_state = Cube3x3x3::new();
-                unsolved_state.do_moves(&solve.scramble);
-                let analysis = Analysis::analyze(&CubeWithSolution {
-                    initial_state: unsolved_state.clone(),
-                    solution: moves.clone(),
-                });
+            let analysis = history.analysis(solve);
+            self.review_schedule.add_solve(solve, analysis.as_ref());
+            self.recognition_stats.add(solve);
+
+            // F2L training solves end when the pair is inserted, so the solve time is
+            // the time for the case
+            if solve.solve_type == SolveType::F2LTraining {
+                if let Some(time) = solve.final_time() {
+                    let mut cube = Cube3x3x3::new();
+                    cube.do_moves(&solve.scramble);
+                    if let Some(f2l) = F2LAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top) {
+                        self.algorithm_stats
+                            .f2l_training
+                            .entry(f2l)
+                            .or_insert(TrainingTimes::default())
+                            .add(time);
+                    }
+                }
+            }
+
+            if let Some(analysis) = analysis {
+                if let Analysis::Cube2x2x2(analysis) = &analysis {
+                    // Only the one algorithm last layer methods are tracked, as Ortega
+                    // steps would skew the times for the 3x3x3 OLL cases
+                    if let Some(last_layer) = &analysis.last_layer {
+                        let entry = match last_layer.algorithm {
+                            Algorithm::CLL(cll) => Some(
+                                self.algorithm_stats
+                                    .cll
+                                    .entry(cll)
+                                    .or_insert(AlgorithmCounts::default()),
+                            ),
+                            Algorithm::EG1(eg1) => Some(
+                                self.algorithm_stats
+                                    .eg1
+                                    .entry(eg1)
+                                    .or_insert(AlgorithmCounts::default()),
+                            ),
+                            Algorithm::EG2(eg2) => Some(
+                                self.algorithm_stats
+                                    .eg2
+                                    .entry(eg2)
+                                    .or_insert(AlgorithmCounts::default()),
+                            ),
+                            _ => None,
+                        };
+                        if let Some(entry) = entry {
+                            entry.add(
+                                last_layer.variant(history.custom_algorithms(last_layer.algorithm)),
+                                last_layer.moves.len(),
+                                last_layer.recognition_time,
+                                last_layer.execution_time,
+                            );
+                        }
+                    }
+                }
 
                 if let Analysis::CFOP(cfop) = analysis {
+                    self.f2l_stats.add(&cfop.f2l_pairs);
+
                     for oll in cfop.oll {
-                        let oll_entry = self
-                            .algorithm_stats
-                            .oll
-                            .entry(oll.performed_algorithm)
-                            .or_insert(AlgorithmCounts::default());
-                        oll_entry.perform_count += 1;
-                        oll_entry.total_moves += oll.moves.len();
-                        oll_entry.total_recognition_time += oll.recognition_time as u64;
-                        oll_entry.total_execution_time += oll.execution_time as u64;
+                        // Subset algorithms are counted only for their subset case, as they
+                        // would skew the times for the OLL case
+                        let oll_entry = match oll.subset_algorithm {
+                            Some(Algorithm::COLL(coll)) => self
+                                .algorithm_stats
+                                .coll
+                                .entry(coll)
+                                .or_insert(AlgorithmCounts::default()),
+                            Some(Algorithm::ZBLL(zbll)) => self
+                                .algorithm_stats
+                                .zbll
+                                .entry(zbll)
+                                .or_insert(AlgorithmCounts::default()),
+                            Some(Algorithm::OLLCP(ollcp)) => self
+                                .algorithm_stats
+                                .ollcp
+                                .entry(ollcp)
+                                .or_insert(AlgorithmCounts::default()),
+                            _ => self
+                                .algorithm_stats
+                                .oll
+                                .entry(oll.performed_algorithm)
+                                .or_insert(AlgorithmCounts::default()),
+                        };
+                        oll_entry.add(
+                            oll.variant(
+                                history.custom_algorithms(Algorithm::OLL(oll.performed_algorithm)),
+                            ),
+                            oll.moves.len(),
+                            oll.recognition_time,
+                            oll.execution_time,
+                        );
+                        if let Some(auf) = &oll.pre_auf {
+                            if !auf.optimal {
+                                oll_entry.bad_auf_count += 1;
+                            }
+                        }
                     }
 
                     for pll in cfop.pll {
@@ -113,10 +323,17 @@ impl AlgorithmsWidget {
                             .pll
                             .entry(pll.performed_algorithm)
                             .or_insert(AlgorithmCounts::default());
-                        pll_entry.perform_count += 1;
-                        pll_entry.total_moves += pll.moves.len();
-                        pll_entry.total_recognition_time += pll.recognition_time as u64;
-                        pll_entry.total_execution_time += pll.execution_time as u64;
+                        pll_entry.add(
+                            pll.variant(
+                                history.custom_algorithms(Algorithm::PLL(pll.performed_algorithm)),
+                            ),
+                            pll.moves.len(),
+                            pll.recognition_time,
+                            pll.execution_time,
+                        );
+                        if !pll.pre_auf.optimal {
+                            pll_entry.bad_auf_count += 1;
+                        }
                     }
                 }
             }
@@ -143,6 +360,76 @@ impl AlgorithmsWidget {
         {
             self.mode = AlgorithmMode::Algorithms(AlgorithmType::PLL);
         }
+
+        if ui
+            .mode_label(
+                "COLL",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::COLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::COLL);
+        }
+
+        if ui
+            .mode_label(
+                "ZBLL",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::ZBLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::ZBLL);
+        }
+
+        if ui
+            .mode_label(
+                "OLLCP",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::OLLCP),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::OLLCP);
+        }
+
+        if ui
+            .mode_label(
+                "CLL",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::CLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::CLL);
+        }
+
+        if ui
+            .mode_label(
+                "EG-1",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::EG1),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::EG1);
+        }
+
+        if ui
+            .mode_label(
+                "EG-2",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::EG2),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::EG2);
+        }
+
+        if ui
+            .mode_label(
+                "F2L",
+                self.mode == AlgorithmMode::Algorithms(AlgorithmType::F2L),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Algorithms(AlgorithmType::F2L);
+        }
     }
 
     fn report_options(&mut self, ui: &mut Ui) {
@@ -165,6 +452,115 @@ impl AlgorithmsWidget {
         {
             self.mode = AlgorithmMode::TPSReport(AlgorithmType::PLL);
         }
+
+        if ui
+            .mode_label(
+                "COLL",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::COLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::COLL);
+        }
+
+        if ui
+            .mode_label(
+                "ZBLL",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::ZBLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::ZBLL);
+        }
+
+        if ui
+            .mode_label(
+                "OLLCP",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::OLLCP),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::OLLCP);
+        }
+
+        if ui
+            .mode_label(
+                "CLL",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::CLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::CLL);
+        }
+
+        if ui
+            .mode_label(
+                "EG-1",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::EG1),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::EG1);
+        }
+
+        if ui
+            .mode_label(
+                "EG-2",
+                self.mode == AlgorithmMode::TPSReport(AlgorithmType::EG2),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::TPSReport(AlgorithmType::EG2);
+        }
+
+        if ui
+            .mode_label("F2L Slots", self.mode == AlgorithmMode::F2LReport)
+            .clicked()
+        {
+            self.mode = AlgorithmMode::F2LReport;
+        }
+    }
+
+    fn training_options(&mut self, ui: &mut Ui) {
+        if ui
+            .mode_label(
+                "OLL",
+                self.mode == AlgorithmMode::Mastery(AlgorithmType::OLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Mastery(AlgorithmType::OLL);
+        }
+
+        if ui
+            .mode_label(
+                "PLL",
+                self.mode == AlgorithmMode::Mastery(AlgorithmType::PLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Mastery(AlgorithmType::PLL);
+        }
+
+        if ui
+            .mode_label(
+                "OLL Recognition",
+                self.mode == AlgorithmMode::Recognition(AlgorithmType::OLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Recognition(AlgorithmType::OLL);
+        }
+
+        if ui
+            .mode_label(
+                "PLL Recognition",
+                self.mode == AlgorithmMode::Recognition(AlgorithmType::PLL),
+            )
+            .clicked()
+        {
+            self.mode = AlgorithmMode::Recognition(AlgorithmType::PLL);
+        }
     }
 
     fn landscape_sidebar(&mut self, ctxt: &CtxRef) {
@@ -179,6 +575,10 @@ impl AlgorithmsWidget {
                     ui.add_space(8.0);
                     ui.section("TPS Reports");
                     self.report_options(ui);
+
+                    ui.add_space(8.0);
+                    ui.section("Training Progress");
+                    self.training_options(ui);
                 });
             });
     }
@@ -216,6 +616,9 @@ impl AlgorithmsWidget {
                                     ui.section("TPS Reports");
                                     self.report_options(ui);
                                     ui.add_space(4.0);
+                                    ui.section("Training Progress");
+                                    self.training_options(ui);
+                                    ui.add_space(4.0);
                                 });
                             },
                         );
@@ -244,13 +647,30 @@ impl AlgorithmsWidget {
         ctxt.set_visuals(content_visuals());
         CentralPanel::default().show(ctxt, |ui| match self.mode {
             AlgorithmMode::Algorithms(alg_type) => {
-                let list = AlgorithmList::new(alg_type);
-                list.update(ui);
+                let list = AlgorithmList::new(alg_type, history, &self.algorithm_stats);
+                list.update(
+                    ui,
+                    history,
+                    &self.algorithm_stats,
+                    &mut self.algorithm_editor,
+                );
             }
             AlgorithmMode::TPSReport(alg_type) => {
                 let report = TPSReport::new(&self.algorithm_stats, alg_type, &mut self.sort);
                 report.update(ui);
             }
+            AlgorithmMode::F2LReport => {
+                let report = F2LReport::new(&self.f2l_stats);
+                report.update(ui);
+            }
+            AlgorithmMode::Mastery(alg_type) => {
+                let report = MasteryReport::new(&self.review_schedule, alg_type);
+                report.update(ui);
+            }
+            AlgorithmMode::Recognition(alg_type) => {
+                let report = RecognitionReport::new(&self.recognition_stats, alg_type);
+                report.update(ui);
+            }
         });
     }
 }
@@ -276,12 +696,31 @@ impl AlgorithmRender for Algorithm {
         rect: Option<Rect>,
     ) {
         let yellow_only = matches!(self, Algorithm::OLL(_));
+        let corners_only = matches!(
+            self,
+            Algorithm::CLL(_) | Algorithm::EG1(_) | Algorithm::EG2(_)
+        );
 
-        // Generate cube state from inverted algorithm
-        let mut cube = Cube3x3x3Faces::new();
-        let mut move_ctxt = ExtendedMoveContext::new(&mut cube);
-        move_ctxt.do_move(ExtendedMove::Rotation(CubeRotation::X2));
-        move_ctxt.do_moves(&moves.inverse());
+        // Generate cube state from inverted algorithm. Subset cases don't have known
+        // algorithms, so they are generated from the case itself.
+        let cube = match self {
+            Algorithm::COLL(coll) => coll.as_faces(CubeFace::Bottom),
+            Algorithm::ZBLL(zbll) => zbll.as_faces(CubeFace::Bottom),
+            Algorithm::OLLCP(ollcp) => ollcp.as_faces(CubeFace::Bottom),
+            Algorithm::OLL(_)
+            | Algorithm::PLL(_)
+            | Algorithm::CLL(_)
+            | Algorithm::EG1(_)
+            | Algorithm::EG2(_)
+            | Algorithm::F2L(_) => {
+                // 2x2x2 algorithms are shown on a 3x3x3 with the edges hidden
+                let mut cube = Cube3x3x3Faces::new();
+                let mut move_ctxt = ExtendedMoveContext::new(&mut cube);
+                move_ctxt.do_move(ExtendedMove::Rotation(CubeRotation::X2));
+                move_ctxt.do_moves(&moves.inverse());
+                cube
+            }
+        };
 
         // Compute sizes of each element (cube representation and name below)
         let piece_size = cube_size / 4.0;
@@ -300,6 +739,13 @@ impl AlgorithmRender for Algorithm {
                     match self {
                         Algorithm::OLL(_) => "Antisune (#26)".into(),
                         Algorithm::PLL(_) => "Aa".into(),
+                        Algorithm::COLL(_) => "COLL Antisune 6".into(),
+                        Algorithm::ZBLL(_) => "ZBLL Antisune 72".into(),
+                        Algorithm::OLLCP(_) => "OLLCP #57d".into(),
+                        Algorithm::CLL(_) => "CLL Antisune 6".into(),
+                        Algorithm::EG1(_) => "EG-1 Antisune 6".into(),
+                        Algorithm::EG2(_) => "EG-2 Antisune 6".into(),
+                        Algorithm::F2L(_) => "F2L 41".into(),
                     },
                 )
                 .size
@@ -336,7 +782,14 @@ impl AlgorithmRender for Algorithm {
 
         let get_color = |face, row, col| -> Color32 {
             let color = cube.color(face, row, col);
-            if !yellow_only || color == Color::Yellow {
+            let edge = if face == CubeFace::Bottom {
+                (row == 1) != (col == 1)
+            } else {
+                col == 1
+            };
+            if corners_only && edge {
+                Theme::Light.into()
+            } else if !yellow_only || color == Color::Yellow {
                 let rgba = &FACE_COLORS[color as u8 as usize];
                 Rgba::from_rgb(rgba[0], rgba[1], rgba[2]).into()
             } else {
@@ -498,16 +951,9 @@ impl AlgorithmRender for Algorithm {
 
         if show_name {
             // Render name
-            let name = ui.fonts().layout_single_line(
-                FontSize::Normal.into(),
-                match self {
-                    Algorithm::OLL(oll) => match oll {
-                        OLLAlgorithm::OLL(_) => oll.to_string(),
-                        _ => format!("{} (#{})", oll.to_string(), oll.as_number()),
-                    },
-                    _ => self.to_string(),
-                },
-            );
+            let name = ui
+                .fonts()
+                .layout_single_line(FontSize::Normal.into(), algorithm_name(*self));
             ui.painter().galley(
                 rect.center_bottom() - Vec2::new(name.size.x / 2.0, name.size.y),
                 name,
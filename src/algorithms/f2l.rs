@@ -0,0 +1,163 @@
+use egui::Ui;
+use std::collections::HashMap;
+use tpscube_core::{F2LPairAnalysis, F2LSlot};
+
+/// Statistics for F2L pair insertions, broken down by the slot the pair was
+/// inserted into
+#[derive(Default)]
+pub(super) struct F2LStats {
+    slots: HashMap<F2LSlot, SlotCounts>,
+    first_slot: HashMap<F2LSlot, usize>,
+    last_slot: HashMap<F2LSlot, usize>,
+    solve_count: usize,
+    /// Total times of each back slot insertion, used to find how often back
+    /// slots are slower than front slots
+    back_slot_times: Vec<u32>,
+}
+
+#[derive(Default)]
+struct SlotCounts {
+    count: usize,
+    total_moves: usize,
+    total_recognition_time: u64,
+    total_execution_time: u64,
+    multi_slotted: usize,
+    keyhole: usize,
+}
+
+pub(super) struct F2LReport<'a> {
+    stats: &'a F2LStats,
+}
+
+impl F2LStats {
+    pub fn add(&mut self, pairs: &[F2LPairAnalysis]) {
+        if pairs.is_empty() {
+            return;
+        }
+
+        self.solve_count += 1;
+        *self.first_slot.entry(pairs[0].slot).or_insert(0) += 1;
+        *self
+            .last_slot
+            .entry(pairs[pairs.len() - 1].slot)
+            .or_insert(0) += 1;
+
+        for pair in pairs {
+            let counts = self.slots.entry(pair.slot).or_insert(SlotCounts::default());
+            counts.count += 1;
+            counts.total_moves += pair.moves.len();
+            counts.total_recognition_time += pair.recognition_time as u64;
+            counts.total_execution_time += pair.execution_time as u64;
+            if pair.multi_slotted {
+                counts.multi_slotted += 1;
+            }
+            if pair.keyhole {
+                counts.keyhole += 1;
+            }
+            if pair.slot.is_back() {
+                self.back_slot_times
+                    .push(pair.recognition_time + pair.execution_time);
+            }
+        }
+    }
+
+    fn most_common(slots: &HashMap<F2LSlot, usize>) -> Option<(F2LSlot, usize)> {
+        // Iterate in slot order so that ties are resolved consistently
+        F2LSlot::all()
+            .iter()
+            .filter_map(|slot| slots.get(slot).map(|count| (*slot, *count)))
+            .fold(None, |best, (slot, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((slot, count)),
+            })
+    }
+
+    /// Gets the average total time of front slot insertions, in milliseconds
+    fn front_slot_average(&self) -> Option<f32> {
+        let (count, time) = F2LSlot::all()
+            .iter()
+            .filter(|slot| !slot.is_back())
+            .filter_map(|slot| self.slots.get(slot))
+            .fold((0, 0), |(count, time), counts| {
+                (
+                    count + counts.count,
+                    time + counts.total_recognition_time + counts.total_execution_time,
+                )
+            });
+        if count == 0 {
+            None
+        } else {
+            Some(time as f32 / count as f32)
+        }
+    }
+}
+
+impl<'a> F2LReport<'a> {
+    pub fn new(stats: &'a F2LStats) -> Self {
+        Self { stats }
+    }
+
+    pub fn update(&self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            if self.stats.solve_count == 0 {
+                ui.label("No analyzed CFOP solves");
+                return;
+            }
+
+            for slot in F2LSlot::all() {
+                if let Some(counts) = self.stats.slots.get(&slot) {
+                    let recognition_time =
+                        counts.total_recognition_time as f32 / 1000.0 / counts.count as f32;
+                    let execution_time =
+                        counts.total_execution_time as f32 / 1000.0 / counts.count as f32;
+                    ui.label(format!(
+                        "{}: count {} recog {:.2} exec {:.2} total {:.2} moves {:.1} multislot {} keyhole {}",
+                        slot.to_str(),
+                        counts.count,
+                        recognition_time,
+                        execution_time,
+                        recognition_time + execution_time,
+                        counts.total_moves as f32 / counts.count as f32,
+                        counts.multi_slotted,
+                        counts.keyhole
+                    ));
+                }
+            }
+
+            ui.add_space(8.0);
+            if let Some((slot, count)) = F2LStats::most_common(&self.stats.first_slot) {
+                ui.label(format!(
+                    "Most common first slot: {} ({:.0}%)",
+                    slot.to_str(),
+                    count as f32 * 100.0 / self.stats.solve_count as f32
+                ));
+            }
+            if let Some((slot, count)) = F2LStats::most_common(&self.stats.last_slot) {
+                ui.label(format!(
+                    "Most common last slot: {} ({:.0}%)",
+                    slot.to_str(),
+                    count as f32 * 100.0 / self.stats.solve_count as f32
+                ));
+            }
+
+            // Show how often back slots take longer than an average front slot
+            if let Some(front_average) = self.stats.front_slot_average() {
+                let back_times = &self.stats.back_slot_times;
+                if !back_times.is_empty() {
+                    let slower = back_times
+                        .iter()
+                        .filter(|time| **time as f32 > front_average)
+                        .count();
+                    let back_average =
+                        back_times.iter().map(|time| *time as f32).sum::<f32>()
+                            / back_times.len() as f32;
+                    ui.label(format!(
+                        "Back slots slower than average front slot: {:.0}% ({:+.2} average)",
+                        slower as f32 * 100.0 / back_times.len() as f32,
+                        (back_average - front_average) / 1000.0
+                    ));
+                }
+            }
+        });
+    }
+}
@@ -1,38 +1,163 @@
-use super::{Algorithm, AlgorithmRender, AlgorithmType};
+use super::{Algorithm, AlgorithmRender, AlgorithmStats, AlgorithmType};
 use crate::font::{FontSize, LabelFontSize};
 use crate::theme::Theme;
-use egui::{Label, ScrollArea, Stroke, Ui};
-use tpscube_core::{ExtendedMove, KnownAlgorithms, OLLAlgorithm, PLLAlgorithm};
+use crate::widgets::solve_time_string;
+use egui::{Key, Label, ScrollArea, SelectableLabel, Sense, Stroke, TextEdit, Ui};
+use tpscube_core::{
+    parse_extended_move_string, AlgorithmErgonomics, AlgorithmStatus, AlgorithmVariant,
+    CLLAlgorithm, COLLAlgorithm, EG1Algorithm, EG2Algorithm, ExtendedMove, ExtendedMoveSequence,
+    F2LAlgorithm, History, MirrorAxis, OLLAlgorithm, OLLCPAlgorithm, PLLAlgorithm, ZBLLAlgorithm,
+};
 
 pub(super) struct AlgorithmList {
     rows: Vec<AlgorithmRow>,
 }
 
+/// State of the algorithm editor, which can be open for one case at a time
+#[derive(Default)]
+pub(super) struct AlgorithmEditor {
+    algorithm: Option<Algorithm>,
+    moves: String,
+    error: Option<String>,
+}
+
 struct AlgorithmRow {
     algorithm: Algorithm,
     moves: Vec<ExtendedMove>,
+    description: String,
+    mirror: Option<String>,
+    times: Option<String>,
+}
+
+impl AlgorithmRow {
+    fn known(algorithm: Algorithm, moves: Vec<ExtendedMove>) -> Self {
+        let description = moves
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let mirror = Some(format!(
+            "Mirror: {}",
+            moves.mirror(MirrorAxis::LeftRight).to_string()
+        ));
+        Self {
+            algorithm,
+            moves,
+            description,
+            mirror,
+            times: None,
+        }
+    }
+
+    /// Creates a row for a case that has no known algorithms, showing how likely
+    /// the case is instead
+    fn subset(algorithm: Algorithm, weight: usize, total: usize) -> Self {
+        Self {
+            algorithm,
+            moves: Vec::new(),
+            description: format!("Probability {}/{}", weight, total),
+            mirror: None,
+            times: None,
+        }
+    }
 }
 
 impl AlgorithmList {
-    pub fn new(alg_type: AlgorithmType) -> Self {
+    /// Creates a row showing the preferred algorithm for a case, or `None` if there are
+    /// no algorithms for the case
+    fn preferred(algorithm: Algorithm, history: &History) -> Option<AlgorithmRow> {
+        history
+            .algorithms(algorithm)
+            .into_iter()
+            .next()
+            .map(|moves| AlgorithmRow::known(algorithm, moves))
+    }
+
+    pub fn new(alg_type: AlgorithmType, history: &History, stats: &AlgorithmStats) -> Self {
         let mut rows = Vec::new();
         match alg_type {
             AlgorithmType::OLL => {
                 for alg in OLLAlgorithm::all() {
-                    let moves = KnownAlgorithms::oll(*alg)[0].clone();
-                    rows.push(AlgorithmRow {
-                        algorithm: Algorithm::OLL(*alg),
-                        moves,
-                    });
+                    rows.extend(Self::preferred(Algorithm::OLL(*alg), history));
                 }
             }
             AlgorithmType::PLL => {
                 for alg in PLLAlgorithm::all() {
-                    let moves = KnownAlgorithms::pll(*alg)[0].clone();
-                    rows.push(AlgorithmRow {
-                        algorithm: Algorithm::PLL(*alg),
-                        moves,
-                    });
+                    rows.extend(Self::preferred(Algorithm::PLL(*alg), history));
+                }
+            }
+            AlgorithmType::COLL => {
+                for alg in COLLAlgorithm::all() {
+                    rows.push(
+                        Self::preferred(Algorithm::COLL(alg), history).unwrap_or_else(|| {
+                            AlgorithmRow::subset(
+                                Algorithm::COLL(alg),
+                                alg.probability_weight(),
+                                COLLAlgorithm::TOTAL_PROBABILITY_WEIGHT,
+                            )
+                        }),
+                    );
+                }
+            }
+            AlgorithmType::ZBLL => {
+                for alg in ZBLLAlgorithm::all() {
+                    rows.push(
+                        Self::preferred(Algorithm::ZBLL(alg), history).unwrap_or_else(|| {
+                            AlgorithmRow::subset(
+                                Algorithm::ZBLL(alg),
+                                alg.probability_weight(),
+                                ZBLLAlgorithm::TOTAL_PROBABILITY_WEIGHT,
+                            )
+                        }),
+                    );
+                }
+            }
+            AlgorithmType::OLLCP => {
+                for alg in OLLCPAlgorithm::all() {
+                    rows.push(
+                        Self::preferred(Algorithm::OLLCP(alg), history).unwrap_or_else(|| {
+                            AlgorithmRow::subset(
+                                Algorithm::OLLCP(alg),
+                                alg.probability_weight(),
+                                OLLCPAlgorithm::TOTAL_PROBABILITY_WEIGHT,
+                            )
+                        }),
+                    );
+                }
+            }
+            AlgorithmType::CLL => {
+                for alg in CLLAlgorithm::all() {
+                    rows.extend(Self::preferred(Algorithm::CLL(alg), history));
+                }
+            }
+            AlgorithmType::EG1 => {
+                for alg in EG1Algorithm::all() {
+                    rows.extend(Self::preferred(Algorithm::EG1(alg), history));
+                }
+            }
+            AlgorithmType::EG2 => {
+                for alg in EG2Algorithm::all() {
+                    rows.extend(Self::preferred(Algorithm::EG2(alg), history));
+                }
+            }
+            AlgorithmType::F2L => {
+                for alg in F2LAlgorithm::all() {
+                    // Show the times from F2L training along with the algorithm
+                    let times = match stats.f2l_training.get(&alg) {
+                        Some(times) => format!(
+                            "Best {}, average {} ({} solves)",
+                            solve_time_string(times.best_time.unwrap_or(0)),
+                            solve_time_string(times.average_time().unwrap_or(0)),
+                            times.count
+                        ),
+                        None => "Not trained".into(),
+                    };
+                    rows.extend(Self::preferred(Algorithm::F2L(alg), history).map(|row| {
+                        AlgorithmRow {
+                            times: Some(times),
+                            ..row
+                        }
+                    }));
                 }
             }
         }
@@ -40,7 +165,13 @@ impl AlgorithmList {
         Self { rows }
     }
 
-    pub fn update(&self, ui: &mut Ui) {
+    pub fn update(
+        &self,
+        ui: &mut Ui,
+        history: &mut History,
+        stats: &AlgorithmStats,
+        editor: &mut AlgorithmEditor,
+    ) {
         ui.visuals_mut().widgets.inactive.bg_fill = Theme::BackgroundHighlight.into();
         ui.visuals_mut().widgets.hovered.bg_fill = Theme::Disabled.into();
         ui.visuals_mut().widgets.active.bg_fill = Theme::Disabled.into();
@@ -66,17 +197,21 @@ impl AlgorithmList {
                                 ui.separator();
                             });
 
-                            ui.add(
-                                Label::new(
-                                    row.moves
-                                        .iter()
-                                        .map(|x| x.to_string())
-                                        .collect::<Vec<String>>()
-                                        .join(" "),
-                                )
-                                .font_size(FontSize::Section)
-                                .wrap(true),
-                            );
+                            ui.vertical(|ui| {
+                                ui.add(
+                                    Label::new(&row.description)
+                                        .font_size(FontSize::Section)
+                                        .wrap(true),
+                                );
+                                if let Some(mirror) = &row.mirror {
+                                    ui.add(Label::new(mirror).text_color(Theme::Disabled));
+                                }
+                                if let Some(times) = &row.times {
+                                    ui.add(Label::new(times).text_color(Theme::Disabled));
+                                }
+                                Self::status_selection(ui, row.algorithm, history);
+                                Self::algorithm_editor(ui, row.algorithm, history, stats, editor);
+                            });
                         });
 
                         ui.scope(|ui| {
@@ -90,4 +225,160 @@ impl AlgorithmList {
                 })
             });
     }
+
+    /// Shows the learning status of an algorithm and allows it to be changed. The
+    /// status is used to choose cases for last layer training.
+    fn status_selection(ui: &mut Ui, algorithm: Algorithm, history: &mut History) {
+        let current = history.algorithm_status(algorithm);
+        ui.horizontal(|ui| {
+            for status in AlgorithmStatus::all() {
+                if ui
+                    .add(
+                        SelectableLabel::new(current == *status, status.to_str())
+                            .text_style(FontSize::Normal.into()),
+                    )
+                    .clicked()
+                {
+                    history.set_algorithm_status(algorithm, *status);
+                    let _ = history.local_commit();
+                }
+            }
+        });
+    }
+
+    /// Shows the algorithms for a case when the editor is open for it. Algorithms can be
+    /// added, removed or chosen as the preferred algorithm for the case. Each algorithm
+    /// is shown with its ergonomics ranking among the algorithms for the case.
+    fn algorithm_editor(
+        ui: &mut Ui,
+        algorithm: Algorithm,
+        history: &mut History,
+        stats: &AlgorithmStats,
+        editor: &mut AlgorithmEditor,
+    ) {
+        let open = editor.algorithm == Some(algorithm);
+        if ui
+            .add(
+                Label::new(if open {
+                    "Algorithms ⏶"
+                } else {
+                    "Algorithms ⏷"
+                })
+                .text_color(Theme::Disabled)
+                .sense(Sense::click()),
+            )
+            .clicked()
+        {
+            *editor = AlgorithmEditor::default();
+            if !open {
+                editor.algorithm = Some(algorithm);
+            }
+        }
+        if !open {
+            return;
+        }
+
+        // The first algorithm is the preferred one
+        let preferred = history.preferred_algorithm(algorithm).is_some();
+        let custom = history.custom_algorithms(algorithm).to_vec();
+        let algorithms = history.algorithms(algorithm);
+
+        // Use the execution times of algorithms performed in solves for the ranking,
+        // identified the same way as the variants in the solve analysis
+        let known = algorithm.known_algorithms();
+        let execution_times: Vec<Option<u32>> = algorithms
+            .iter()
+            .map(|moves| {
+                let variant = if custom.contains(moves) {
+                    AlgorithmVariant::Custom(moves.clone())
+                } else {
+                    AlgorithmVariant::Known(known.iter().position(|alg| alg == moves)?)
+                };
+                stats.counts(algorithm)?.execution_time(&variant)
+            })
+            .collect();
+        let ranking = AlgorithmErgonomics::rank(&algorithms, &execution_times);
+
+        for (idx, moves) in algorithms.iter().enumerate() {
+            let rank = ranking
+                .iter()
+                .position(|ranked| *ranked == idx)
+                .unwrap_or(idx);
+            let details = match execution_times[idx] {
+                Some(time) => format!("#{}, {} measured", rank + 1, solve_time_string(time)),
+                None => format!(
+                    "#{}, score {:.1}",
+                    rank + 1,
+                    AlgorithmErgonomics::new(moves).score
+                ),
+            };
+            ui.horizontal(|ui| {
+                if ui
+                    .add(
+                        SelectableLabel::new(idx == 0, moves.to_string())
+                            .text_style(FontSize::Normal.into()),
+                    )
+                    .clicked()
+                    && idx != 0
+                {
+                    let _ = history.set_preferred_algorithm(algorithm, Some(moves.clone()));
+                    let _ = history.local_commit();
+                }
+                ui.add(Label::new(&details).text_color(Theme::Disabled));
+
+                if custom.contains(moves)
+                    && ui
+                        .add(
+                            Label::new("Remove")
+                                .text_color(Theme::Red)
+                                .sense(Sense::click()),
+                        )
+                        .clicked()
+                {
+                    history.remove_algorithm(algorithm, moves);
+                    let _ = history.local_commit();
+                }
+            });
+        }
+
+        if preferred
+            && ui
+                .add(
+                    Label::new("Reset to default")
+                        .text_color(Theme::Disabled)
+                        .sense(Sense::click()),
+                )
+                .clicked()
+        {
+            let _ = history.set_preferred_algorithm(algorithm, None);
+            let _ = history.local_commit();
+        }
+
+        let mut add = false;
+        ui.horizontal(|ui| {
+            let response = ui.add(TextEdit::singleline(&mut editor.moves));
+            if response.lost_focus() && ui.input().key_pressed(Key::Enter) {
+                add = true;
+            }
+            if ui.button("Add").clicked() {
+                add = true;
+            }
+        });
+        if let Some(error) = &editor.error {
+            ui.add(Label::new(error).text_color(Theme::Red));
+        }
+
+        if add {
+            let result = parse_extended_move_string(&editor.moves)
+                .and_then(|moves| history.add_algorithm(algorithm, moves));
+            match result {
+                Ok(_) => {
+                    let _ = history.local_commit();
+                    editor.moves.clear();
+                    editor.error = None;
+                }
+                Err(error) => editor.error = Some(error.to_string()),
+            }
+        }
+    }
 }
@@ -0,0 +1,145 @@
+use super::{Algorithm, AlgorithmRender, AlgorithmType};
+use crate::font::{FontSize, LabelFontSize};
+use crate::theme::Theme;
+use chrono::{DateTime, Local};
+use egui::{Label, ScrollArea, Stroke, Ui};
+use tpscube_core::{
+    ExtendedMove, KnownAlgorithms, OLLAlgorithm, PLLAlgorithm, ReviewSchedule, ReviewState,
+};
+
+/// Shows the spaced repetition progress of each case trained in last layer training
+pub(super) struct MasteryReport<'a> {
+    schedule: &'a ReviewSchedule,
+    rows: Vec<(Algorithm, Vec<ExtendedMove>)>,
+}
+
+impl<'a> MasteryReport<'a> {
+    pub fn new(schedule: &'a ReviewSchedule, alg_type: AlgorithmType) -> Self {
+        let rows = match alg_type {
+            AlgorithmType::OLL => OLLAlgorithm::all()
+                .iter()
+                .map(|alg| (Algorithm::OLL(*alg), KnownAlgorithms::oll(*alg)[0].clone()))
+                .collect(),
+            AlgorithmType::PLL => PLLAlgorithm::all()
+                .iter()
+                .map(|alg| (Algorithm::PLL(*alg), KnownAlgorithms::pll(*alg)[0].clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self { schedule, rows }
+    }
+
+    fn due_string(state: &ReviewState, now: DateTime<Local>) -> String {
+        if state.is_due(now) {
+            return "due now".into();
+        }
+        let remaining = state.due - now;
+        if remaining.num_days() > 0 {
+            format!("due in {} days", remaining.num_days() + 1)
+        } else if remaining.num_hours() > 0 {
+            format!("due in {} hours", remaining.num_hours() + 1)
+        } else {
+            format!("due in {} min", remaining.num_minutes() + 1)
+        }
+    }
+
+    pub fn update(&self, ui: &mut Ui) {
+        let now = Local::now();
+        let states: Vec<Option<&ReviewState>> = self
+            .rows
+            .iter()
+            .map(|(algorithm, _)| self.schedule.state(*algorithm))
+            .collect();
+        let mastered = states
+            .iter()
+            .filter(|state| matches!(state, Some(state) if state.mastery() >= 1.0))
+            .count();
+        let due = states
+            .iter()
+            .filter(|state| matches!(state, Some(state) if state.is_due(now)))
+            .count();
+        let new = states.iter().filter(|state| state.is_none()).count();
+
+        ui.add(
+            Label::new(format!(
+                "{} of {} mastered, {} due, {} not yet trained",
+                mastered,
+                self.rows.len(),
+                due,
+                new
+            ))
+            .font_size(FontSize::Section),
+        );
+        ui.add_space(8.0);
+
+        ScrollArea::auto_sized()
+            .id_source("mastery_report")
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    for ((algorithm, moves), state) in self.rows.iter().zip(states.iter()) {
+                        ui.horizontal(|ui| {
+                            algorithm.draw(
+                                ui,
+                                moves,
+                                ui.fonts().row_height(FontSize::Normal.into()) * 4.0,
+                                true,
+                                None,
+                            );
+
+                            ui.scope(|ui| {
+                                ui.style_mut().visuals.widgets.noninteractive.bg_stroke = Stroke {
+                                    width: 1.0,
+                                    color: Theme::Light.into(),
+                                };
+                                ui.separator();
+                            });
+
+                            ui.vertical(|ui| match state {
+                                Some(state) => {
+                                    let mastery = state.mastery();
+                                    ui.add(
+                                        Label::new(format!("Mastery {:.0}%", mastery * 100.0))
+                                            .font_size(FontSize::Section)
+                                            .text_color(if mastery >= 1.0 {
+                                                Theme::Green
+                                            } else if state.repetitions == 0 {
+                                                Theme::Red
+                                            } else {
+                                                Theme::Orange
+                                            }),
+                                    );
+                                    ui.add(
+                                        Label::new(format!(
+                                            "{} reviews, {:.0}% success, last {}, ease {:.2}, {}",
+                                            state.reviews,
+                                            state.success_rate() * 100.0,
+                                            state.last_grade.to_str().to_lowercase(),
+                                            state.ease_factor,
+                                            Self::due_string(state, now)
+                                        ))
+                                        .text_color(Theme::Disabled)
+                                        .wrap(true),
+                                    );
+                                }
+                                None => {
+                                    ui.add(
+                                        Label::new("Not yet trained")
+                                            .font_size(FontSize::Section)
+                                            .text_color(Theme::Disabled),
+                                    );
+                                }
+                            });
+                        });
+
+                        ui.scope(|ui| {
+                            ui.style_mut().visuals.widgets.noninteractive.bg_stroke = Stroke {
+                                width: 1.0,
+                                color: Theme::DarkBlue.into(),
+                            };
+                            ui.separator();
+                        });
+                    }
+                })
+            });
+    }
+}
@@ -0,0 +1,131 @@
+use crate::font::{FontSize, LabelFontSize};
+use crate::widgets::{algorithm_name, solve_time_string};
+use egui::{Label, ScrollArea, Ui};
+use std::collections::HashMap;
+use tpscube_core::{
+    Algorithm, AlgorithmType, OLLAlgorithm, PLLAlgorithm, RecognitionCase, Solve, SolveType,
+};
+
+/// Results of the last layer recognition drills, broken down by case and by the
+/// angle the case was seen from
+#[derive(Default)]
+pub(super) struct RecognitionStats {
+    oll: RecognitionTypeStats,
+    pll: RecognitionTypeStats,
+}
+
+#[derive(Default)]
+struct RecognitionTypeStats {
+    cases: HashMap<Algorithm, RecognitionCounts>,
+    angles: [RecognitionCounts; RecognitionCase::ANGLE_COUNT],
+}
+
+#[derive(Default)]
+struct RecognitionCounts {
+    count: usize,
+    correct: usize,
+    total_correct_time: u64,
+}
+
+pub(super) struct RecognitionReport<'a> {
+    stats: &'a RecognitionTypeStats,
+    algorithms: Vec<Algorithm>,
+}
+
+impl RecognitionStats {
+    pub fn add(&mut self, solve: &Solve) {
+        let stats = match solve.solve_type {
+            SolveType::OLLRecognition => &mut self.oll,
+            SolveType::PLLRecognition => &mut self.pll,
+            _ => return,
+        };
+        if let Some(case) = RecognitionCase::from_scramble(solve.solve_type, &solve.scramble) {
+            // Wrong answers are stored as a recognition DNF, so the final time is only
+            // present for correct answers
+            let time = solve.final_time();
+            stats
+                .cases
+                .entry(case.algorithm)
+                .or_insert(RecognitionCounts::default())
+                .add(time);
+            stats.angles[case.angle as usize].add(time);
+        }
+    }
+}
+
+impl RecognitionCounts {
+    fn add(&mut self, time: Option<u32>) {
+        self.count += 1;
+        if let Some(time) = time {
+            self.correct += 1;
+            self.total_correct_time += time as u64;
+        }
+    }
+
+    fn summary(&self) -> String {
+        let accuracy = self.correct as f32 * 100.0 / self.count as f32;
+        if self.correct == 0 {
+            format!("count {} accuracy {:.0}%", self.count, accuracy)
+        } else {
+            format!(
+                "count {} accuracy {:.0}% average {}",
+                self.count,
+                accuracy,
+                solve_time_string((self.total_correct_time / self.correct as u64) as u32)
+            )
+        }
+    }
+}
+
+impl<'a> RecognitionReport<'a> {
+    pub fn new(stats: &'a RecognitionStats, alg_type: AlgorithmType) -> Self {
+        match alg_type {
+            AlgorithmType::PLL => Self {
+                stats: &stats.pll,
+                algorithms: PLLAlgorithm::all()
+                    .iter()
+                    .map(|alg| Algorithm::PLL(*alg))
+                    .collect(),
+            },
+            _ => Self {
+                stats: &stats.oll,
+                algorithms: OLLAlgorithm::all()
+                    .iter()
+                    .map(|alg| Algorithm::OLL(*alg))
+                    .collect(),
+            },
+        }
+    }
+
+    pub fn update(&self, ui: &mut Ui) {
+        if self.stats.cases.is_empty() {
+            ui.label("No recognition drills completed");
+            return;
+        }
+
+        ScrollArea::auto_sized()
+            .id_source("recognition_report")
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    ui.add(Label::new("Viewing Angle").font_size(FontSize::Section));
+                    for (angle, counts) in self.stats.angles.iter().enumerate() {
+                        if counts.count != 0 {
+                            ui.label(format!("{}: {}", angle, counts.summary()));
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                    ui.add(Label::new("Case").font_size(FontSize::Section));
+                    for algorithm in &self.algorithms {
+                        if let Some(counts) = self.stats.cases.get(algorithm) {
+                            ui.label(format!(
+                                "{}: {}",
+                                algorithm_name(*algorithm),
+                                counts.summary()
+                            ));
+                        }
+                    }
+                });
+            });
+    }
+}
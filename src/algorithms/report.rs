@@ -2,6 +2,7 @@ use super::{
     Algorithm, AlgorithmCounts, AlgorithmStats, AlgorithmType, Sort, SortColumn, SortOrder,
 };
 use egui::Ui;
+use tpscube_core::KnownAlgorithms;
 
 const REQUIRED_COUNT: usize = 10;
 
@@ -12,6 +13,18 @@ pub(super) struct TPSReport<'a> {
 
 struct AlgorithmRow {
     algorithm: Algorithm,
+    times: AlgorithmTimes,
+    bad_auf_count: usize,
+    variants: Vec<VariantRow>,
+}
+
+/// Breakdown of the times for a single algorithm variant used to solve a case
+struct VariantRow {
+    name: String,
+    times: AlgorithmTimes,
+}
+
+struct AlgorithmTimes {
     count: usize,
     moves: f32,
     recognition_time: f32,
@@ -39,10 +52,55 @@ impl<'a> TPSReport<'a> {
                     }
                 }
             }
+            AlgorithmType::COLL => {
+                for (alg, counts) in stats.coll.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::COLL(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            AlgorithmType::ZBLL => {
+                for (alg, counts) in stats.zbll.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::ZBLL(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            AlgorithmType::OLLCP => {
+                for (alg, counts) in stats.ollcp.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::OLLCP(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            AlgorithmType::CLL => {
+                for (alg, counts) in stats.cll.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::CLL(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            AlgorithmType::EG1 => {
+                for (alg, counts) in stats.eg1.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::EG1(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            AlgorithmType::EG2 => {
+                for (alg, counts) in stats.eg2.iter() {
+                    if let Some(row) = AlgorithmRow::from_counts(Algorithm::EG2(*alg), counts) {
+                        rows.push(row);
+                    }
+                }
+            }
+            // F2L cases are only timed in F2L training, which has no move data to report
+            AlgorithmType::F2L => (),
         }
 
         // Sort algorithms by the desired sort order
         rows.sort_by(|a, b| {
+            let (a, b) = (&a.times, &b.times);
             let result = match sort.column {
                 SortColumn::Count => a.count.cmp(&b.count),
                 SortColumn::RecognitionTime => {
@@ -71,17 +129,29 @@ impl<'a> TPSReport<'a> {
     pub fn update(&self, ui: &mut Ui) {
         ui.vertical(|ui| {
             for row in &self.rows {
-                ui.label(format!(
-                    "{}: count {} recog {:.2} exec {:.2} total {:.2} moves {:.1} tps {:.2} etps {:.2}",
-                    row.algorithm.to_string(),
-                    row.count,
-                    row.recognition_time,
-                    row.execution_time,
-                    row.recognition_time + row.execution_time,
-                    row.moves,
-                    row.tps,
-                    row.execution_tps
-                ));
+                if row.bad_auf_count > 0 {
+                    ui.label(format!(
+                        "{}: {} bad AUF {}",
+                        row.algorithm.to_string(),
+                        row.times.to_string(),
+                        row.bad_auf_count
+                    ));
+                } else {
+                    ui.label(format!(
+                        "{}: {}",
+                        row.algorithm.to_string(),
+                        row.times.to_string()
+                    ));
+                }
+
+                // Show which algorithms were used for the case
+                for variant in &row.variants {
+                    ui.label(format!(
+                        "    {}: {}",
+                        variant.name,
+                        variant.times.to_string()
+                    ));
+                }
             }
         });
     }
@@ -93,6 +163,42 @@ impl AlgorithmRow {
             // Not enough data to be meaningful for this algorithm
             return None;
         }
+
+        let known = match algorithm {
+            Algorithm::OLL(oll) => KnownAlgorithms::oll(oll),
+            Algorithm::PLL(pll) => KnownAlgorithms::pll(pll),
+            Algorithm::CLL(cll) => KnownAlgorithms::cll(cll),
+            Algorithm::EG1(eg1) => KnownAlgorithms::eg1(eg1),
+            Algorithm::EG2(eg2) => KnownAlgorithms::eg2(eg2),
+            Algorithm::F2L(f2l) => KnownAlgorithms::f2l(f2l),
+            Algorithm::COLL(_) | Algorithm::ZBLL(_) | Algorithm::OLLCP(_) => Vec::new(),
+        };
+
+        // Variants are only identified for cases with known algorithms
+        let mut variants: Vec<VariantRow> = counts
+            .variants
+            .iter()
+            .filter(|_| !known.is_empty())
+            .filter_map(|(variant, counts)| {
+                Some(VariantRow {
+                    name: variant.name(&known),
+                    times: AlgorithmTimes::from_counts(counts)?,
+                })
+            })
+            .collect();
+        variants.sort_by(|a, b| b.times.count.cmp(&a.times.count));
+
+        Some(Self {
+            algorithm,
+            times: AlgorithmTimes::from_counts(counts)?,
+            bad_auf_count: counts.bad_auf_count,
+            variants,
+        })
+    }
+}
+
+impl AlgorithmTimes {
+    fn from_counts(counts: &AlgorithmCounts) -> Option<Self> {
         if counts.total_execution_time == 0 {
             // Avoid NaN in any computations, don't include these algorithms as the data
             // won't make sense anyway.
@@ -107,7 +213,6 @@ impl AlgorithmRow {
             counts.total_execution_time as f32 / 1000.0 / counts.perform_count as f32;
         let total_time = recognition_time + execution_time;
         Some(Self {
-            algorithm,
             count: counts.perform_count,
             moves,
             recognition_time,
@@ -117,3 +222,18 @@ impl AlgorithmRow {
         })
     }
 }
+
+impl ToString for AlgorithmTimes {
+    fn to_string(&self) -> String {
+        format!(
+            "count {} recog {:.2} exec {:.2} total {:.2} moves {:.1} tps {:.2} etps {:.2}",
+            self.count,
+            self.recognition_time,
+            self.execution_time,
+            self.recognition_time + self.execution_time,
+            self.moves,
+            self.tps,
+            self.execution_tps
+        )
+    }
+}
@@ -497,10 +497,16 @@ impl App for Application {
 
             match details {
                 Some(SolveDetails::IndividualSolve(solve)) => {
-                    self.solve_details = Some(SolveDetailsWindow::new(solve));
+                    self.solve_details = Some(SolveDetailsWindow::new(
+                        solve,
+                        self.history.as_ref().unwrap(),
+                    ));
                 }
                 Some(SolveDetails::AverageOfSolves(solves)) => {
-                    self.average_details = Some(AverageDetailsWindow::new(solves));
+                    self.average_details = Some(AverageDetailsWindow::new(
+                        solves,
+                        self.history.as_ref().unwrap(),
+                    ));
                 }
                 None => (),
             }
@@ -524,6 +530,7 @@ impl App for Application {
                 let mut open = true;
                 solve_details.update(
                     ctxt,
+                    self.history.as_ref().unwrap(),
                     framerate,
                     &mut self.solve_details_cube_rect,
                     &mut open,
@@ -541,7 +548,10 @@ impl App for Application {
 
                 match details {
                     Some(SolveDetails::IndividualSolve(solve)) => {
-                        self.solve_details = Some(SolveDetailsWindow::new(solve));
+                        self.solve_details = Some(SolveDetailsWindow::new(
+                            solve,
+                            self.history.as_ref().unwrap(),
+                        ));
                     }
                     _ => (),
                 }
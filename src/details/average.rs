@@ -7,8 +7,7 @@ use crate::widgets::{date_string, solve_time_string};
 use egui::{CtxRef, CursorIcon, Label, Pos2, Rect, Sense, Vec2, Window};
 use std::cmp::Ordering;
 use tpscube_core::{
-    Analysis, AnalysisStepSummary, AnalysisSummary, Cube, Cube3x3x3, CubeWithSolution,
-    InitialCubeState, ListAverage, Solve,
+    AnalysisStepSummary, AnalysisSummary, History, ListAverage, Solve, SolveAnalysis,
 };
 
 const TARGET_MAX_WIDTH: f32 = 300.0;
@@ -26,22 +25,15 @@ struct SolveWithAnalysis {
 }
 
 impl AverageDetailsWindow {
-    pub fn new(solves: Vec<Solve>) -> Self {
+    pub fn new(solves: Vec<Solve>, history: &History) -> Self {
         let average = solves.as_slice().average();
 
         // Analyze solves
         let mut solves_with_analysis = Vec::new();
         for solve in solves {
-            let mut unsolved_state = Cube3x3x3::new();
-            unsolved_state.do_moves(&solve.scramble);
-            let analysis = if let Some(solution) = &solve.moves {
-                Analysis::analyze(&CubeWithSolution {
-                    initial_state: unsolved_state.clone(),
-                    solution: solution.clone(),
-                })
-            } else {
-                Analysis::default()
-            };
+            // Analyze directly if the cached result is not available yet, as the
+            // window is not rebuilt when it becomes available
+            let analysis = history.analysis(&solve).unwrap_or_else(|| solve.analyze());
             let summary = analysis.detailed_step_summary();
             solves_with_analysis.push(SolveWithAnalysis {
                 solve,
@@ -1,15 +1,17 @@
 use crate::theme::Theme;
 use crate::widgets::{color_for_recognition_step_index, color_for_step_index};
 use egui::{CtxRef, Pos2, Rect, Response, Stroke, Ui, Vec2};
-use tpscube_core::{AnalysisStepSummary, Solve};
+use tpscube_core::{AnalysisStepSummary, Pause, Solve};
 
 const SOLVE_BAR_HEIGHT: f32 = 4.0;
 const SOLVE_STEP_SEPARATOR_HEIGHT: f32 = 10.0;
 const CURSOR_HEIGHT: f32 = 16.0;
+const PAUSE_HEIGHT: f32 = 3.0;
 
 pub struct SolveBar<'a> {
     solve: &'a Solve,
     summary: &'a [AnalysisStepSummary],
+    pauses: &'a [Pause],
     max_time: u32,
     cursor: Option<f32>,
 }
@@ -24,11 +26,18 @@ impl<'a> SolveBar<'a> {
         Self {
             solve,
             summary,
+            pauses: &[],
             max_time,
             cursor,
         }
     }
 
+    /// Highlights pauses between moves below the bar
+    pub fn pauses(mut self, pauses: &'a [Pause]) -> Self {
+        self.pauses = pauses;
+        self
+    }
+
     fn draw_time_only(&self, ui: &mut Ui, rect: Rect) {
         let total = self.max_time as f32 / 1000.0;
         if let Some(time) = self.solve.final_time() {
@@ -140,6 +149,24 @@ impl<'a> SolveBar<'a> {
             },
         ));
 
+        // Highlight pauses below the bar
+        for pause in self.pauses {
+            ui.painter().rect_filled(
+                Rect::from_min_size(
+                    Pos2::new(
+                        rect.width() * pause.start_time as f32 / 1000.0 / total + rect.left(),
+                        rect.center().y + SOLVE_BAR_HEIGHT / 2.0 + 1.0,
+                    ),
+                    Vec2::new(
+                        rect.width() * pause.duration as f32 / 1000.0 / total,
+                        PAUSE_HEIGHT,
+                    ),
+                ),
+                0.0,
+                Theme::Red,
+            );
+        }
+
         // Draw solve stage separators
         for line in separators {
             ui.painter().line_segment(line.0, line.1);
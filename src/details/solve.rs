@@ -3,6 +3,7 @@ use crate::details::bar::SolveBar;
 use crate::font::FontSize;
 use crate::framerate::Framerate;
 use crate::gl::GlContext;
+use crate::settings::Settings;
 use crate::style::dialog_visuals;
 use crate::theme::Theme;
 use crate::widgets::{
@@ -16,8 +17,9 @@ use egui::{
 };
 use instant::Instant;
 use tpscube_core::{
-    Analysis, AnalysisStepSummary, AnalysisSummary, Cube, Cube2x2x2, Cube3x3x3, Cube4x4x4,
-    CubeWithSolution, InitialCubeState, Penalty, Solve, SolveType,
+    median_move_gap, Analysis, AnalysisStepSummary, AnalysisSummary, Color, Cube, Cube2x2x2,
+    Cube2x2x2WithSolution, Cube3x3x3, Cube4x4x4, CubeWithSolution, History, InitialCubeState,
+    LookaheadAnalysis, Penalty, Solve, SolveType,
 };
 
 const TARGET_MIN_WIDTH: f32 = 280.0;
@@ -29,6 +31,12 @@ pub struct SolveDetailsWindow {
     unsolved_state: Box<dyn Cube>,
     analysis: Analysis,
     summary: Vec<AnalysisStepSummary>,
+    /// Optimal move count for each step in the summary, if known. Optimal solutions
+    /// are found in the background, so this is `None` until they are available.
+    optimal_moves: Option<Vec<Option<usize>>>,
+    /// Optimal cross move count for each color, once available
+    optimal_crosses: Option<Vec<(Color, usize)>>,
+    lookahead: Option<LookaheadAnalysis>,
     renderer: CubeRenderer,
     replay_time: f32,
     replay_move_idx: usize,
@@ -44,18 +52,31 @@ enum SolveDetailsMode {
 }
 
 impl SolveDetailsWindow {
-    pub fn new(solve: Solve) -> Self {
+    pub fn new(solve: Solve, history: &History) -> Self {
         match solve.solve_type {
             SolveType::Standard2x2x2 => {
-                let mut unsolved_state = Box::new(Cube2x2x2::new());
+                let mut unsolved_state = Cube2x2x2::new();
                 unsolved_state.do_moves(&solve.scramble);
-                let renderer = CubeRenderer::new(unsolved_state.dyn_clone());
+                let renderer = CubeRenderer::new(Box::new(unsolved_state.clone()));
+
+                let analysis = if let Some(solution) = &solve.moves {
+                    Analysis::analyze_2x2x2(&Cube2x2x2WithSolution {
+                        initial_state: unsolved_state.clone(),
+                        solution: solution.clone(),
+                    })
+                } else {
+                    Analysis::default()
+                };
+                let summary = analysis.detailed_step_summary();
 
                 Self {
                     solve,
-                    unsolved_state,
-                    analysis: Analysis::default(),
-                    summary: Vec::new(),
+                    unsolved_state: Box::new(unsolved_state),
+                    analysis,
+                    summary,
+                    optimal_moves: None,
+                    optimal_crosses: None,
+                    lookahead: None,
                     renderer,
                     replay_time: 0.0,
                     replay_move_idx: 0,
@@ -68,26 +89,53 @@ impl SolveDetailsWindow {
             | SolveType::OneHanded3x3x3
             | SolveType::Blind3x3x3
             | SolveType::OLLTraining
-            | SolveType::PLLTraining => {
+            | SolveType::PLLTraining
+            | SolveType::DailyChallenge3x3x3
+            | SolveType::HardCrossTraining
+            | SolveType::LastSlotTraining
+            | SolveType::F2LTraining
+            | SolveType::CrossTraining
+            | SolveType::XCrossTraining
+            | SolveType::OLLRecognition
+            | SolveType::PLLRecognition => {
                 let mut unsolved_state = Cube3x3x3::new();
                 unsolved_state.do_moves(&solve.scramble);
                 let renderer = CubeRenderer::new(Box::new(unsolved_state.clone()));
 
-                let analysis = if let Some(solution) = &solve.moves {
-                    Analysis::analyze(&CubeWithSolution {
-                        initial_state: unsolved_state.clone(),
-                        solution: solution.clone(),
-                    })
+                let solution = solve.moves.as_ref().map(|solution| CubeWithSolution {
+                    initial_state: unsolved_state.clone(),
+                    solution: solution.clone(),
+                });
+                let analysis = if let Some(solution) = &solution {
+                    Analysis::analyze(solution)
                 } else {
                     Analysis::default()
                 };
                 let summary = analysis.detailed_step_summary();
+                // Find pauses relative to the usual gap between moves for this solve type
+                let lookahead = match (&analysis, &solution) {
+                    (Analysis::CFOP(cfop), Some(solution)) => Some(
+                        cfop.lookahead(
+                            &solution.solution,
+                            median_move_gap(
+                                history
+                                    .iter()
+                                    .filter(|other| other.solve_type == solve.solve_type),
+                            ),
+                            Settings::pause_threshold(history),
+                        ),
+                    ),
+                    _ => None,
+                };
 
                 Self {
                     solve,
                     unsolved_state: Box::new(unsolved_state),
                     analysis,
                     summary,
+                    optimal_moves: None,
+                    optimal_crosses: None,
+                    lookahead,
                     renderer,
                     replay_time: 0.0,
                     replay_move_idx: 0,
@@ -106,6 +154,9 @@ impl SolveDetailsWindow {
                     unsolved_state: Box::new(unsolved_state),
                     analysis: Analysis::default(),
                     summary: Vec::new(),
+                    optimal_moves: None,
+                    optimal_crosses: None,
+                    lookahead: None,
                     renderer,
                     replay_time: 0.0,
                     replay_move_idx: 0,
@@ -119,6 +170,9 @@ impl SolveDetailsWindow {
                 unsolved_state: Box::new(Cube3x3x3::new()),
                 analysis: Analysis::default(),
                 summary: Vec::new(),
+                optimal_moves: None,
+                optimal_crosses: None,
+                lookahead: None,
                 renderer: CubeRenderer::new(Box::new(Cube3x3x3::new())),
                 replay_time: 0.0,
                 replay_move_idx: 0,
@@ -376,12 +430,15 @@ impl SolveDetailsWindow {
     fn solve_bar(&mut self, ctxt: &CtxRef, ui: &mut Ui) {
         let (id, rect) = ui.allocate_space(Vec2::new(ui.available_width(), 16.0));
         let response = ui.interact(rect, id, Sense::click_and_drag());
-        let bar = SolveBar::new(
+        let mut bar = SolveBar::new(
             &self.solve,
             &self.summary,
             self.solve.moves.as_ref().unwrap().last().unwrap().time(),
             Some(self.replay_time),
         );
+        if let Some(lookahead) = &self.lookahead {
+            bar = bar.pauses(&lookahead.pauses);
+        }
         if let Some(navigate_time) = bar.interactive(ctxt, ui, rect, response) {
             self.go_to_time(navigate_time);
         }
@@ -492,9 +549,19 @@ impl SolveDetailsWindow {
             let execution_header = ui
                 .fonts()
                 .layout_single_line(FontSize::Small.into(), "Execute".into());
-            let move_count_header = ui
-                .fonts()
-                .layout_single_line(FontSize::Small.into(), "Moves".into());
+            let move_count_header = ui.fonts().layout_single_line(
+                FontSize::Small.into(),
+                if self
+                    .optimal_moves
+                    .iter()
+                    .flatten()
+                    .any(|moves| moves.is_some())
+                {
+                    "Moves / Opt".into()
+                } else {
+                    "Moves".into()
+                },
+            );
             let tps_header = ui
                 .fonts()
                 .layout_single_line(FontSize::Small.into(), "eTPS / TPS".into());
@@ -519,7 +586,7 @@ impl SolveDetailsWindow {
             let mut total_recognition_time = 0;
             let mut total_execution_time = 0;
             let mut total_move_count = 0;
-            for step in &self.summary {
+            for (i, step) in self.summary.iter().enumerate() {
                 // Lay out name and algorithm column
                 let step_name = ui
                     .fonts()
@@ -563,10 +630,17 @@ impl SolveDetailsWindow {
                 execution_width = execution_width.max(execution.size.x);
                 executions.push(execution);
 
-                // Lay out move count column
-                let move_count = ui
-                    .fonts()
-                    .layout_single_line(FontSize::Normal.into(), format!("{}", step.move_count));
+                // Lay out move count column, comparing against optimal when known
+                let move_count = ui.fonts().layout_single_line(
+                    FontSize::Normal.into(),
+                    if let Some(Some(optimal)) =
+                        self.optimal_moves.as_ref().and_then(|moves| moves.get(i))
+                    {
+                        format!("{} / {}", step.move_count, optimal)
+                    } else {
+                        format!("{}", step.move_count)
+                    },
+                );
                 move_count_width = move_count_width.max(move_count.size.x);
                 move_counts.push(move_count);
 
@@ -891,17 +965,132 @@ impl SolveDetailsWindow {
                 Theme::Content.into(),
             );
 
+            // Show lookahead summary
+            if let Some(lookahead) = &self.lookahead {
+                ui.add_space(4.0);
+                ui.add(
+                    Label::new(format!(
+                        "Lookahead:  {:.0}%  ({} pauses, {})",
+                        lookahead.score,
+                        lookahead.pauses.len(),
+                        solve_time_string(lookahead.pause_time())
+                    ))
+                    .text_style(FontSize::Small.into())
+                    .text_color(Theme::Disabled),
+                );
+            }
+
+            // Show mistakes and the time they cost
+            if let Analysis::CFOP(cfop) = &self.analysis {
+                if !cfop.mistakes.is_empty() {
+                    ui.add_space(4.0);
+                    ui.add(
+                        Label::new(format!(
+                            "Mistakes:  {}  ({} lost)",
+                            cfop.mistakes.len(),
+                            solve_time_string(cfop.mistake_time())
+                        ))
+                        .text_style(FontSize::Small.into())
+                        .text_color(Theme::Orange),
+                    );
+                    for mistake in &cfop.mistakes {
+                        ui.add(
+                            Label::new(format!(
+                                "    {} during {}, {} moves, {} lost",
+                                mistake.kind.to_str(),
+                                mistake.step,
+                                mistake.move_count,
+                                solve_time_string(mistake.time_lost)
+                            ))
+                            .text_style(FontSize::Small.into())
+                            .text_color(Theme::Disabled),
+                        );
+                    }
+                }
+            }
+
+            // Show time spent adjusting the last layer and how many adjustments were
+            // longer than needed
+            if let Analysis::CFOP(cfop) = &self.analysis {
+                let bad_auf_count = cfop.bad_auf_count();
+                ui.add_space(4.0);
+                ui.add(
+                    Label::new(format!(
+                        "AUF:  {}  ({} suboptimal)",
+                        solve_time_string(cfop.auf_time()),
+                        bad_auf_count
+                    ))
+                    .text_style(FontSize::Small.into())
+                    .text_color(if bad_auf_count > 0 {
+                        Theme::Orange
+                    } else {
+                        Theme::Disabled
+                    }),
+                );
+            }
+
+            // Show optimal cross length for every color to help with color neutrality
+            if let Some(optimal_crosses) = &self.optimal_crosses {
+                ui.add_space(4.0);
+                let crosses: Vec<String> = optimal_crosses
+                    .iter()
+                    .map(|(color, moves)| format!("{} {}", color.to_str(), moves))
+                    .collect();
+                ui.add(
+                    Label::new(format!("Optimal cross:  {}", crosses.join("  ")))
+                        .text_style(FontSize::Small.into())
+                        .text_color(Theme::Disabled),
+                );
+            }
+
             ui.add_space(8.0);
         });
     }
 
+    /// Fills in optimal move counts once they have been found in the background
+    fn update_optimal_moves(&mut self, ctxt: &CtxRef, history: &History) {
+        if self.solve.moves.is_none() || !matches!(self.analysis, Analysis::CFOP(_)) {
+            return;
+        }
+
+        if self.optimal_moves.is_none() {
+            if let Some(efficiency) = history.efficiency(&self.solve) {
+                let mut pairs = efficiency.f2l_pairs.iter();
+                self.optimal_moves = Some(
+                    self.summary
+                        .iter()
+                        .map(|step| match step.major_step_index {
+                            0 => efficiency.cross.optimal.as_ref().map(|moves| moves.len()),
+                            1 => pairs
+                                .next()
+                                .and_then(|pair| pair.optimal.as_ref())
+                                .map(|moves| moves.len()),
+                            _ => None,
+                        })
+                        .collect(),
+                );
+            }
+        }
+        if self.optimal_crosses.is_none() {
+            self.optimal_crosses = history.optimal_crosses(&self.solve);
+        }
+
+        // Keep checking for results until they are available
+        if self.optimal_moves.is_none() || self.optimal_crosses.is_none() {
+            ctxt.request_repaint();
+        }
+    }
+
     pub fn update(
         &mut self,
         ctxt: &CtxRef,
+        history: &History,
         framerate: &mut Framerate,
         cube_rect: &mut Option<Rect>,
         open: &mut bool,
     ) {
+        self.update_optimal_moves(ctxt, history);
+
         let full_rect = ctxt.available_rect();
 
         // Compute layout parameters
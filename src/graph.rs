@@ -3,6 +3,7 @@ mod plot;
 
 use crate::algorithms::AlgorithmRender;
 use crate::font::FontSize;
+use crate::settings::Settings;
 use crate::style::{content_visuals, side_visuals};
 use crate::theme::Theme;
 use crate::widgets::CustomWidgets;
@@ -106,6 +107,36 @@ impl GraphWidget {
                 self.plot = None;
                 let _ = self.save_settings(history);
             }
+
+            if ui
+                .mode_label(
+                    "Moves over optimal",
+                    self.statistic == Statistic::MovesOverOptimal,
+                )
+                .clicked()
+            {
+                self.statistic = Statistic::MovesOverOptimal;
+                self.plot = None;
+                let _ = self.save_settings(history);
+            }
+
+            if ui
+                .mode_label("Pause time", self.statistic == Statistic::PauseTime)
+                .clicked()
+            {
+                self.statistic = Statistic::PauseTime;
+                self.plot = None;
+                let _ = self.save_settings(history);
+            }
+
+            if ui
+                .mode_label("AUF time", self.statistic == Statistic::AUFTime)
+                .clicked()
+            {
+                self.statistic = Statistic::AUFTime;
+                self.plot = None;
+                let _ = self.save_settings(history);
+            }
         }
 
         if ui
@@ -117,7 +148,7 @@ impl GraphWidget {
             let _ = self.save_settings(history);
         }
 
-        if self.solve_type.is_last_layer_training() {
+        if self.solve_type.is_last_layer_training() || self.solve_type.is_recognition_training() {
             if ui
                 .mode_label(
                     "Recognition Accuracy",
@@ -129,7 +160,9 @@ impl GraphWidget {
                 self.plot = None;
                 let _ = self.save_settings(history);
             }
+        }
 
+        if self.solve_type.is_last_layer_training() {
             if ui
                 .mode_label(
                     "Execution Accuracy",
@@ -268,11 +301,11 @@ impl GraphWidget {
 
         // Get list of all algorithms
         let algs: Vec<Algorithm> = match solve_type {
-            SolveType::OLLTraining => OLLAlgorithm::all()
+            SolveType::OLLTraining | SolveType::OLLRecognition => OLLAlgorithm::all()
                 .iter()
                 .map(|alg| Algorithm::OLL(*alg))
                 .collect(),
-            SolveType::PLLTraining => PLLAlgorithm::all()
+            SolveType::PLLTraining | SolveType::PLLRecognition => PLLAlgorithm::all()
                 .iter()
                 .map(|alg| Algorithm::PLL(*alg))
                 .collect(),
@@ -294,6 +327,11 @@ impl GraphWidget {
                 let moves = match alg {
                     Algorithm::OLL(oll) => KnownAlgorithms::oll(oll)[0].clone(),
                     Algorithm::PLL(pll) => KnownAlgorithms::pll(pll)[0].clone(),
+                    Algorithm::CLL(cll) => KnownAlgorithms::cll(cll)[0].clone(),
+                    Algorithm::EG1(eg1) => KnownAlgorithms::eg1(eg1)[0].clone(),
+                    Algorithm::EG2(eg2) => KnownAlgorithms::eg2(eg2)[0].clone(),
+                    Algorithm::F2L(f2l) => KnownAlgorithms::f2l(f2l)[0].clone(),
+                    Algorithm::COLL(_) | Algorithm::ZBLL(_) | Algorithm::OLLCP(_) => Vec::new(),
                 };
                 alg.draw(
                     ui,
@@ -358,6 +396,16 @@ impl GraphWidget {
                                     self.statistic = Statistic::TotalTime;
                                 }
                                 self.phase = Phase::EntireSolve;
+                            } else if solve_type.is_recognition_training() {
+                                if !matches!(
+                                    self.statistic,
+                                    Statistic::TotalTime
+                                        | Statistic::SuccessRate
+                                        | Statistic::RecognitionAccuracy
+                                ) {
+                                    self.statistic = Statistic::TotalTime;
+                                }
+                                self.phase = Phase::EntireSolve;
                             } else {
                                 if !matches!(
                                     self.statistic,
@@ -373,7 +421,9 @@ impl GraphWidget {
                             ui.section("Average");
                             self.average_options(ui, history, false);
 
-                            if solve_type.is_last_layer_training() {
+                            if solve_type.is_last_layer_training()
+                                || solve_type.is_recognition_training()
+                            {
                                 ui.add_space(8.0);
                                 ui.section("Algorithm");
                                 self.algorithm_options(ui, history, solve_type, false);
@@ -445,6 +495,16 @@ impl GraphWidget {
                                 self.statistic = Statistic::TotalTime;
                             }
                             self.phase = Phase::EntireSolve;
+                        } else if solve_type.is_recognition_training() {
+                            if !matches!(
+                                self.statistic,
+                                Statistic::TotalTime
+                                    | Statistic::SuccessRate
+                                    | Statistic::RecognitionAccuracy
+                            ) {
+                                self.statistic = Statistic::TotalTime;
+                            }
+                            self.phase = Phase::EntireSolve;
                         } else {
                             if !matches!(
                                 self.statistic,
@@ -467,7 +527,9 @@ impl GraphWidget {
                             },
                         );
 
-                        if solve_type.is_last_layer_training() {
+                        if solve_type.is_last_layer_training()
+                            || solve_type.is_recognition_training()
+                        {
                             // Show separator between sections
                             ui.scope(|ui| {
                                 ui.style_mut().visuals.widgets.noninteractive.bg_stroke = Stroke {
@@ -520,6 +582,9 @@ impl GraphWidget {
             Some("success") => Statistic::SuccessRate,
             Some("recognition_accuracy") => Statistic::RecognitionAccuracy,
             Some("execution_accuracy") => Statistic::ExecutionAccuracy,
+            Some("moves_over_optimal") => Statistic::MovesOverOptimal,
+            Some("pause_time") => Statistic::PauseTime,
+            Some("auf_time") => Statistic::AUFTime,
             Some(_) | None => Statistic::TotalTime,
         };
         self.phase = match history
@@ -551,6 +616,9 @@ impl GraphWidget {
                 Statistic::SuccessRate => "success",
                 Statistic::RecognitionAccuracy => "recognition_accuracy",
                 Statistic::ExecutionAccuracy => "execution_accuracy",
+                Statistic::MovesOverOptimal => "moves_over_optimal",
+                Statistic::PauseTime => "pause_time",
+                Statistic::AUFTime => "auf_time",
             },
         )?;
         history.set_string_setting(
@@ -619,6 +687,7 @@ impl GraphWidget {
                         .phase(self.phase)
                         .average_size(self.average_size)
                         .algorithm(self.algorithm)
+                        .pause_threshold(Settings::pause_threshold(history))
                         .build(history, solve_type),
                 );
                 self.update_id = Some(history.update_id());
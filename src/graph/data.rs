@@ -1,8 +1,9 @@
 use crate::graph::plot::{Plot, SinglePlot, YAxis};
 use crate::theme::Theme;
 use tpscube_core::{
-    Algorithm, Analysis, Cube, Cube3x3x3, CubeFace, CubeWithSolution, History, InitialCubeState,
-    ListAverage, OLLAlgorithm, PLLAlgorithm, Penalty, Solve, SolveType,
+    median_move_gap, Algorithm, Analysis, CFOPStep, COLLAlgorithm, Cube, Cube3x3x3, CubeFace,
+    History, InitialCubeState, ListAverage, OLLAlgorithm, OLLCPAlgorithm, PLLAlgorithm, Penalty,
+    Solve, SolveType, ZBLLAlgorithm, DEFAULT_PAUSE_THRESHOLD,
 };
 
 pub struct GraphData {
@@ -10,6 +11,16 @@ pub struct GraphData {
     phase: Phase,
     average_size: usize,
     algorithm: Option<Algorithm>,
+    pause_threshold: f32,
+}
+
+/// State shared between the data points of a plot
+struct PlotContext<'a> {
+    /// Solve history, used to look up cached analysis results
+    history: &'a History,
+    /// Median gap between moves over all solves in the plot, used to find pauses
+    median_move_gap: Option<u32>,
+    pause_threshold: f32,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -23,6 +34,9 @@ pub enum Statistic {
     SuccessRate,
     RecognitionAccuracy,
     ExecutionAccuracy,
+    MovesOverOptimal,
+    PauseTime,
+    AUFTime,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -42,10 +56,12 @@ pub enum CFOPPhase {
 impl Statistic {
     fn y_axis(&self) -> YAxis {
         match self {
-            Statistic::TotalTime | Statistic::RecognitionTime | Statistic::ExecutionTime => {
-                YAxis::Time
-            }
-            Statistic::MoveCount => YAxis::MoveCount,
+            Statistic::TotalTime
+            | Statistic::RecognitionTime
+            | Statistic::ExecutionTime
+            | Statistic::PauseTime
+            | Statistic::AUFTime => YAxis::Time,
+            Statistic::MoveCount | Statistic::MovesOverOptimal => YAxis::MoveCount,
             Statistic::TurnsPerSecond | Statistic::ExecutionTurnsPerSecond => YAxis::TurnsPerSecond,
             Statistic::SuccessRate
             | Statistic::RecognitionAccuracy
@@ -61,6 +77,7 @@ impl GraphData {
             phase: Phase::EntireSolve,
             average_size: 5,
             algorithm: None,
+            pause_threshold: DEFAULT_PAUSE_THRESHOLD,
         }
     }
 
@@ -84,17 +101,13 @@ impl GraphData {
         self
     }
 
-    fn analyze(solve: &Solve) -> Option<Analysis> {
-        if let Some(solution) = &solve.moves {
-            let mut initial_state = Cube3x3x3::new();
-            initial_state.do_moves(&solve.scramble);
-            Some(Analysis::analyze(&CubeWithSolution {
-                initial_state,
-                solution: solution.clone(),
-            }))
-        } else {
-            None
-        }
+    pub fn pause_threshold(mut self, threshold: f32) -> Self {
+        self.pause_threshold = threshold;
+        self
+    }
+
+    fn analyze(solve: &Solve, context: &PlotContext) -> Option<Analysis> {
+        context.history.analysis(solve)
     }
 
     fn data_point(
@@ -102,6 +115,7 @@ impl GraphData {
         statistic: Statistic,
         phase: Phase,
         algorithm: Option<Algorithm>,
+        context: &PlotContext<'_>,
     ) -> Option<u32> {
         // If a specific algorithm is specified, match against state
         match algorithm {
@@ -119,14 +133,40 @@ impl GraphData {
                     return None;
                 }
             }
+            Some(Algorithm::COLL(coll)) => {
+                let mut initial_state = Cube3x3x3::new();
+                initial_state.do_moves(&solve.scramble);
+                if COLLAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top) != Some(coll)
+                {
+                    return None;
+                }
+            }
+            Some(Algorithm::ZBLL(zbll)) => {
+                let mut initial_state = Cube3x3x3::new();
+                initial_state.do_moves(&solve.scramble);
+                if ZBLLAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top) != Some(zbll)
+                {
+                    return None;
+                }
+            }
+            Some(Algorithm::OLLCP(ollcp)) => {
+                let mut initial_state = Cube3x3x3::new();
+                initial_state.do_moves(&solve.scramble);
+                if OLLCPAlgorithm::from_cube(&initial_state.as_faces(), CubeFace::Top)
+                    != Some(ollcp)
+                {
+                    return None;
+                }
+            }
             _ => (),
         }
 
         match statistic {
             Statistic::TurnsPerSecond => {
                 // Calculate TPS generically by fetching time and move stats
-                let time = Self::data_point(solve, Statistic::TotalTime, phase, algorithm);
-                let moves = Self::data_point(solve, Statistic::MoveCount, phase, algorithm);
+                let time = Self::data_point(solve, Statistic::TotalTime, phase, algorithm, context);
+                let moves =
+                    Self::data_point(solve, Statistic::MoveCount, phase, algorithm, context);
                 if let Some(time) = time {
                     if let Some(moves) = moves {
                         if moves > 0 && time > 0 {
@@ -143,8 +183,10 @@ impl GraphData {
             }
             Statistic::ExecutionTurnsPerSecond => {
                 // Calculate TPS generically by fetching time and move stats
-                let time = Self::data_point(solve, Statistic::ExecutionTime, phase, algorithm);
-                let moves = Self::data_point(solve, Statistic::MoveCount, phase, algorithm);
+                let time =
+                    Self::data_point(solve, Statistic::ExecutionTime, phase, algorithm, context);
+                let moves =
+                    Self::data_point(solve, Statistic::MoveCount, phase, algorithm, context);
                 if let Some(time) = time {
                     if let Some(moves) = moves {
                         if moves > 0 && time > 0 {
@@ -174,11 +216,14 @@ impl GraphData {
                 Penalty::RecognitionDNF => None,
                 _ => Some(1),
             },
+            Statistic::MovesOverOptimal => Self::moves_over_optimal(solve, phase, context),
+            Statistic::PauseTime => Self::pause_time(solve, phase, context),
+            Statistic::AUFTime => Self::auf_time(solve, phase, context),
             _ => match phase {
                 Phase::EntireSolve => match statistic {
                     Statistic::TotalTime => solve.final_time(),
                     Statistic::RecognitionTime => {
-                        if let Some(analysis) = Self::analyze(solve) {
+                        if let Some(analysis) = Self::analyze(solve, context) {
                             if let Analysis::CFOP(cfop) = analysis {
                                 // Total up recognition times from all phases
                                 Some(
@@ -202,7 +247,7 @@ impl GraphData {
                         }
                     }
                     Statistic::ExecutionTime => {
-                        if let Some(analysis) = Self::analyze(solve) {
+                        if let Some(analysis) = Self::analyze(solve, context) {
                             if let Analysis::CFOP(cfop) = analysis {
                                 // Total up execution times from all phases
                                 Some(
@@ -239,12 +284,15 @@ impl GraphData {
                     | Statistic::ExecutionTurnsPerSecond
                     | Statistic::SuccessRate
                     | Statistic::RecognitionAccuracy
-                    | Statistic::ExecutionAccuracy => {
+                    | Statistic::ExecutionAccuracy
+                    | Statistic::MovesOverOptimal
+                    | Statistic::PauseTime
+                    | Statistic::AUFTime => {
                         unreachable!()
                     }
                 },
                 Phase::CFOP(phase) => {
-                    if let Some(analysis) = Self::analyze(solve) {
+                    if let Some(analysis) = Self::analyze(solve, context) {
                         if let Analysis::CFOP(cfop) = analysis {
                             match phase {
                                 CFOPPhase::Cross => match statistic {
@@ -258,7 +306,10 @@ impl GraphData {
                                     | Statistic::ExecutionTurnsPerSecond
                                     | Statistic::SuccessRate
                                     | Statistic::RecognitionAccuracy
-                                    | Statistic::ExecutionAccuracy => {
+                                    | Statistic::ExecutionAccuracy
+                                    | Statistic::MovesOverOptimal
+                                    | Statistic::PauseTime
+                                    | Statistic::AUFTime => {
                                         unreachable!()
                                     }
                                 },
@@ -277,7 +328,10 @@ impl GraphData {
                                         | Statistic::ExecutionTurnsPerSecond
                                         | Statistic::SuccessRate
                                         | Statistic::RecognitionAccuracy
-                                        | Statistic::ExecutionAccuracy => unreachable!(),
+                                        | Statistic::ExecutionAccuracy
+                                        | Statistic::MovesOverOptimal
+                                        | Statistic::PauseTime
+                                        | Statistic::AUFTime => unreachable!(),
                                     },
                                 )),
                                 CFOPPhase::OLL => {
@@ -300,7 +354,10 @@ impl GraphData {
                                             | Statistic::ExecutionTurnsPerSecond
                                             | Statistic::SuccessRate
                                             | Statistic::RecognitionAccuracy
-                                            | Statistic::ExecutionAccuracy => unreachable!(),
+                                            | Statistic::ExecutionAccuracy
+                                            | Statistic::MovesOverOptimal
+                                            | Statistic::PauseTime
+                                            | Statistic::AUFTime => unreachable!(),
                                         }))
                                     }
                                 }
@@ -334,7 +391,10 @@ impl GraphData {
                                             | Statistic::ExecutionTurnsPerSecond
                                             | Statistic::SuccessRate
                                             | Statistic::RecognitionAccuracy
-                                            | Statistic::ExecutionAccuracy => unreachable!(),
+                                            | Statistic::ExecutionAccuracy
+                                            | Statistic::MovesOverOptimal
+                                            | Statistic::PauseTime
+                                            | Statistic::AUFTime => unreachable!(),
                                         })
                                     }
                                 }
@@ -350,6 +410,79 @@ impl GraphData {
         }
     }
 
+    /// Number of moves performed beyond optimal solutions of the cross and F2L steps.
+    /// Optimal solutions are found in the background, so solves are left out until
+    /// their results are available.
+    fn moves_over_optimal(solve: &Solve, phase: Phase, context: &PlotContext<'_>) -> Option<u32> {
+        let extra_moves = match phase {
+            // Searching for optimal F2L pairs is slow, so only the cross is solved
+            // when looking at the cross alone
+            Phase::CFOP(CFOPPhase::Cross) => context.history.cross_efficiency(solve)?.extra_moves(),
+            // Later steps are algorithms and are not compared against optimal
+            Phase::EntireSolve => {
+                let efficiency = context.history.efficiency(solve)?;
+                efficiency
+                    .cross
+                    .extra_moves()
+                    .zip(efficiency.f2l_extra_moves())
+                    .map(|(cross, f2l)| cross + f2l)
+            }
+            Phase::CFOP(CFOPPhase::F2L) => context.history.efficiency(solve)?.f2l_extra_moves(),
+            Phase::CFOP(CFOPPhase::OLL) | Phase::CFOP(CFOPPhase::PLL) => None,
+        };
+        extra_moves.map(|moves| moves as u32 * 1000)
+    }
+
+    fn pause_time(solve: &Solve, phase: Phase, context: &PlotContext<'_>) -> Option<u32> {
+        if let Some(solution) = &solve.moves {
+            if let Some(Analysis::CFOP(cfop)) = Self::analyze(solve, context) {
+                let lookahead =
+                    cfop.lookahead(solution, context.median_move_gap, context.pause_threshold);
+                Some(match phase {
+                    Phase::EntireSolve => lookahead.pause_time(),
+                    Phase::CFOP(CFOPPhase::Cross) => {
+                        lookahead.step_pause_time(|step| step == CFOPStep::Cross)
+                    }
+                    Phase::CFOP(CFOPPhase::F2L) => {
+                        lookahead.step_pause_time(|step| matches!(step, CFOPStep::F2LPair(_)))
+                    }
+                    Phase::CFOP(CFOPPhase::OLL) => {
+                        lookahead.step_pause_time(|step| step == CFOPStep::OLL)
+                    }
+                    Phase::CFOP(CFOPPhase::PLL) => {
+                        lookahead.step_pause_time(|step| step == CFOPStep::PLL)
+                    }
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Total time spent adjusting the last layer
+    fn auf_time(solve: &Solve, phase: Phase, context: &PlotContext<'_>) -> Option<u32> {
+        if let Some(Analysis::CFOP(cfop)) = Self::analyze(solve, context) {
+            match phase {
+                Phase::EntireSolve => Some(cfop.auf_time()),
+                Phase::CFOP(CFOPPhase::Cross) | Phase::CFOP(CFOPPhase::F2L) => None,
+                Phase::CFOP(CFOPPhase::OLL) => Some(
+                    cfop.oll
+                        .iter()
+                        .filter_map(|oll| oll.pre_auf.as_ref())
+                        .map(|auf| auf.time)
+                        .sum(),
+                ),
+                Phase::CFOP(CFOPPhase::PLL) => Some(
+                    cfop.pll.iter().map(|pll| pll.pre_auf.time).sum::<u32>() + cfop.alignment.time,
+                ),
+            }
+        } else {
+            None
+        }
+    }
+
     pub fn build(self, history: &History, solve_type: SolveType) -> Plot {
         let title = format!(
             "{} for {}",
@@ -363,6 +496,9 @@ impl GraphData {
                 Statistic::SuccessRate => "Success Rate",
                 Statistic::RecognitionAccuracy => "Recognition Accuracy",
                 Statistic::ExecutionAccuracy => "Execution Accuracy",
+                Statistic::MovesOverOptimal => "Moves over Optimal",
+                Statistic::PauseTime => "Pause Time",
+                Statistic::AUFTime => "AUF Time",
             },
             match self.phase {
                 Phase::EntireSolve => "Entire Solve",
@@ -385,6 +521,15 @@ impl GraphData {
             },
         );
 
+        let context = PlotContext {
+            history,
+            median_move_gap: median_move_gap(
+                history
+                    .iter()
+                    .filter(|solve| solve.solve_type == solve_type),
+            ),
+            pause_threshold: self.pause_threshold,
+        };
         let mut window = Vec::new();
         for solve in history.iter() {
             if solve.solve_type != solve_type {
@@ -393,7 +538,8 @@ impl GraphData {
             }
 
             let data_point =
-                match Self::data_point(solve, self.statistic, self.phase, self.algorithm) {
+                match Self::data_point(solve, self.statistic, self.phase, self.algorithm, &context)
+                {
                     Some(value) => Some(value),
                     None => continue,
                 };
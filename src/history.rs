@@ -8,9 +8,12 @@ use egui::{
     containers::ScrollArea, popup_below_widget, Align2, CentralPanel, CtxRef, CursorIcon, Pos2,
     Rect, SelectableLabel, Sense, Stroke, Ui, Vec2,
 };
+use std::cell::RefCell;
+use std::collections::HashMap;
 use tpscube_core::{
-    Algorithm, Average, BestSolve, Cube, Cube3x3x3, CubeFace, History, InitialCubeState,
-    KnownAlgorithms, ListAverage, OLLAlgorithm, PLLAlgorithm, Penalty, Solve, SolveList, SolveType,
+    Algorithm, Analysis, Average, BestSolve, Cube, Cube3x3x3, CubeFace, History, InitialCubeState,
+    KnownAlgorithms, ListAverage, OLLAlgorithm, PLLAlgorithm, Penalty, Solve, SolveAnalysis,
+    SolveList, SolveType,
 };
 
 const REGION_PADDING: f32 = 16.0;
@@ -62,6 +65,9 @@ struct SessionRegion {
     best_ao50: Option<Average>,
     best_ao100: Option<Average>,
     average: Option<u32>,
+    /// Number of mistakes and time lost for each solve by ID, filled in as solves
+    /// are drawn
+    mistakes: RefCell<HashMap<String, Option<(usize, u32)>>>,
 }
 
 struct HistoryRegionLayout {
@@ -729,6 +735,25 @@ impl HistoryRegion for AllTimeBestRegion {
 }
 
 impl SessionRegion {
+    /// Gets the number of mistakes made in a solve and the time lost to them. Solves
+    /// are analyzed the first time they are drawn, as analyzing the entire history
+    /// up front would take too long.
+    fn mistakes(&self, solve: &Solve) -> Option<(usize, u32)> {
+        if !solve.solve_type.is_3x3x3() || solve.moves.is_none() {
+            return None;
+        }
+        *self
+            .mistakes
+            .borrow_mut()
+            .entry(solve.id.clone())
+            .or_insert_with(|| match solve.analyze() {
+                Analysis::CFOP(cfop) if !cfop.mistakes.is_empty() => {
+                    Some((cfop.mistakes.len(), cfop.mistake_time()))
+                }
+                _ => None,
+            })
+    }
+
     fn paint_standard_solve(
         &self,
         ui: &mut Ui,
@@ -836,14 +861,33 @@ impl SessionRegion {
                     ui.fonts().row_height(FontSize::Small.into()),
                 ),
             );
+            // Flag solves with mistakes so that they can be reviewed
+            let mistakes = self.mistakes(solve);
+            let hover_text = match mistakes {
+                Some((count, time_lost)) => format!(
+                    "Analysis available for this solve\n{} mistake{}, {} lost",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    solve_time_string(time_lost)
+                ),
+                None => "Analysis available for this solve".into(),
+            };
             ui.allocate_rect(icon_rect, Sense::hover())
-                .on_hover_text("Analysis available for this solve");
+                .on_hover_text(hover_text);
             ui.painter().text(
                 icon_rect.left_bottom(),
                 Align2::LEFT_BOTTOM,
-                "   📊",
+                if mistakes.is_some() {
+                    "   ⚠"
+                } else {
+                    "   📊"
+                },
                 FontSize::Small.into(),
-                Theme::Light.into(),
+                if mistakes.is_some() {
+                    Theme::Orange.into()
+                } else {
+                    Theme::Light.into()
+                },
             );
         }
 
@@ -964,6 +1008,11 @@ impl SessionRegion {
         let moves = match algorithm {
             Algorithm::OLL(oll) => KnownAlgorithms::oll(oll)[0].clone(),
             Algorithm::PLL(pll) => KnownAlgorithms::pll(pll)[0].clone(),
+            Algorithm::CLL(cll) => KnownAlgorithms::cll(cll)[0].clone(),
+            Algorithm::EG1(eg1) => KnownAlgorithms::eg1(eg1)[0].clone(),
+            Algorithm::EG2(eg2) => KnownAlgorithms::eg2(eg2)[0].clone(),
+            Algorithm::F2L(f2l) => KnownAlgorithms::f2l(f2l)[0].clone(),
+            Algorithm::COLL(_) | Algorithm::ZBLL(_) | Algorithm::OLLCP(_) => Vec::new(),
         };
 
         let cube_size = ui.fonts().row_height(FontSize::Normal.into()) * 1.85;
@@ -1193,7 +1242,7 @@ impl SessionRegion {
 impl HistoryRegion for SessionRegion {
     fn height(&self, ui: &Ui, layout_metrics: &SolveLayoutMetrics) -> f32 {
         // Layout best solve and average region to determine line wrapping
-        if self.solve_type.is_last_layer_training() {
+        if self.solve_type.is_last_layer_training() || self.solve_type.is_recognition_training() {
             ui.fonts().row_height(FontSize::Normal.into()) * (self.rows as f32) * 2.1
                 + ui.fonts().row_height(FontSize::Section.into())
                 + SESSION_REGION_BORDER
@@ -1422,7 +1471,7 @@ impl HistoryRegion for SessionRegion {
 
             for row in 0..self.rows {
                 match self.solve_type {
-                    SolveType::OLLTraining => {
+                    SolveType::OLLTraining | SolveType::OLLRecognition => {
                         let mut cube = Cube3x3x3::new();
                         cube.do_moves(&self.solves[i].scramble);
                         let algorithm = OLLAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top);
@@ -1452,7 +1501,7 @@ impl HistoryRegion for SessionRegion {
                             )
                         }
                     }
-                    SolveType::PLLTraining => {
+                    SolveType::PLLTraining | SolveType::PLLRecognition => {
                         let mut cube = Cube3x3x3::new();
                         cube.do_moves(&self.solves[i].scramble);
                         let algorithm = PLLAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top);
@@ -1513,7 +1562,9 @@ impl HistoryRegion for SessionRegion {
                         x,
                         y + self.rows as f32
                             * row_height
-                            * if self.solve_type.is_last_layer_training() {
+                            * if self.solve_type.is_last_layer_training()
+                                || self.solve_type.is_recognition_training()
+                            {
                                 2.1
                             } else {
                                 1.0
@@ -1527,7 +1578,8 @@ impl HistoryRegion for SessionRegion {
             );
         }
 
-        if !self.solve_type.is_last_layer_training() {
+        if !(self.solve_type.is_last_layer_training() || self.solve_type.is_recognition_training())
+        {
             y += self.rows as f32 * row_height;
 
             // Draw separator between solves and best times
@@ -1913,6 +1965,7 @@ impl HistoryWidget {
                 best_ao50,
                 best_ao100,
                 average,
+                mistakes: RefCell::new(HashMap::new()),
             });
         }
 
@@ -1935,7 +1988,7 @@ impl HistoryWidget {
             regions.push(Box::new(NoSolvesRegion));
             self.all_time_best_region = None;
         } else {
-            if solve_type.is_last_layer_training() {
+            if solve_type.is_last_layer_training() || solve_type.is_recognition_training() {
                 self.all_time_best_region = None;
             } else {
                 let running_best_ao50 = all_solves.as_slice().best_average(50);
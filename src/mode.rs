@@ -47,9 +47,34 @@ impl SolveTypeSelectWindow {
                     self.option(ui, selected, SolveType::Blind3x3x3, "3x3x3 Blindfolded");
                     self.option(ui, selected, SolveType::Blind4x4x4, "4x4x4 Blindfolded");
 
+                    ui.section("Challenges");
+                    self.option(
+                        ui,
+                        selected,
+                        SolveType::DailyChallenge3x3x3,
+                        "3x3x3 Daily Challenge",
+                    );
+
                     ui.section("Training");
                     self.option(ui, selected, SolveType::OLLTraining, "OLL Training");
                     self.option(ui, selected, SolveType::PLLTraining, "PLL Training");
+                    self.option(ui, selected, SolveType::F2LTraining, "F2L Training");
+                    self.option(
+                        ui,
+                        selected,
+                        SolveType::HardCrossTraining,
+                        "Hard Cross Training",
+                    );
+                    self.option(
+                        ui,
+                        selected,
+                        SolveType::LastSlotTraining,
+                        "Last Slot Training",
+                    );
+                    self.option(ui, selected, SolveType::CrossTraining, "Cross Training");
+                    self.option(ui, selected, SolveType::XCrossTraining, "XCross Training");
+                    self.option(ui, selected, SolveType::OLLRecognition, "OLL Recognition");
+                    self.option(ui, selected, SolveType::PLLRecognition, "PLL Recognition");
                 });
             });
     }
@@ -7,7 +7,7 @@ use egui::{
     containers::ScrollArea, popup_below_widget, widgets::Label, CentralPanel, CtxRef,
     SelectableLabel, Sense, Stroke,
 };
-use tpscube_core::{History, SyncRequest};
+use tpscube_core::{AlgorithmSetFormat, History, SyncRequest, DEFAULT_PAUSE_THRESHOLD};
 
 pub struct Settings {
     sync_key_visible: bool,
@@ -16,6 +16,8 @@ pub struct Settings {
     organize_result: Option<String>,
     import_result: Option<Result<String>>,
     export_result: Option<Result<()>>,
+    algorithm_import_result: Option<Result<String>>,
+    algorithm_export_result: Option<Result<()>>,
 }
 
 impl Settings {
@@ -27,6 +29,8 @@ impl Settings {
             organize_result: None,
             import_result: None,
             export_result: None,
+            algorithm_import_result: None,
+            algorithm_export_result: None,
         }
     }
 
@@ -38,6 +42,14 @@ impl Settings {
         history.setting_as_i64("auto_session_time").unwrap_or(3600)
     }
 
+    /// Gap between moves that is considered a pause, as a multiple of the median gap
+    pub fn pause_threshold(history: &History) -> f32 {
+        history
+            .setting_as_i64("pause_threshold")
+            .map(|threshold| threshold as f32 / 10.0)
+            .unwrap_or(DEFAULT_PAUSE_THRESHOLD)
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn import_solves_from_path(path: &str, history: &mut History) -> Result<String> {
         let contents = String::from_utf8(std::fs::read(path)?)?;
@@ -73,6 +85,48 @@ impl Settings {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import_algorithms_from_path(path: &str, history: &mut History) -> Result<String> {
+        let contents = String::from_utf8(std::fs::read(path)?)?;
+        history.import_algorithms(contents)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import_algorithms(&mut self, history: &mut History) {
+        if let Some(path) = tinyfiledialogs::open_file_dialog(
+            "Import Algorithms",
+            ".",
+            Some((&["*.txt", "*.json"], "Algorithm sets")),
+        ) {
+            self.algorithm_import_result = Some(Self::import_algorithms_from_path(&path, history));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_algorithms_to_path(path: &str, history: &mut History) -> Result<()> {
+        // Use the JSON format if the file has a JSON extension, otherwise use the text
+        // format which is easier to edit and share
+        let format = if path.to_lowercase().ends_with(".json") {
+            AlgorithmSetFormat::JSON
+        } else {
+            AlgorithmSetFormat::Text
+        };
+        let contents = history.export_algorithms(format)?;
+        Ok(std::fs::write(path, contents.as_bytes())?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_algorithms(&mut self, history: &mut History) {
+        if let Some(path) = tinyfiledialogs::save_file_dialog_with_filter(
+            "Export Algorithms",
+            "algorithms.txt",
+            &["*.txt", "*.json"],
+            "Algorithm sets",
+        ) {
+            self.algorithm_export_result = Some(Self::export_algorithms_to_path(&path, history));
+        }
+    }
+
     pub fn update(&mut self, ctxt: &CtxRef, _frame: &mut epi::Frame<'_>, history: &mut History) {
         ctxt.set_visuals(settings_visuals());
         CentralPanel::default().show(ctxt, |ui| {
@@ -195,6 +249,46 @@ impl Settings {
                         );
                     }
 
+                    ui.add_space(16.0);
+                    ui.section("Analysis");
+
+                    let pause_threshold = Self::pause_threshold(history);
+                    let popup_id = ui.make_persistent_id("pause-threshold");
+                    let response = ui.add(
+                        Label::new(format!("⏸  Pause Threshold: {}× median ⏷", pause_threshold))
+                            .text_style(FontSize::Section.into())
+                            .sense(Sense::click()),
+                    );
+                    if response.clicked() {
+                        ui.memory().toggle_popup(popup_id);
+                    }
+                    popup_below_widget(ui, popup_id, &response, |ui| {
+                        ui.set_min_width(180.0);
+                        for threshold in &[20, 25, 30, 40, 50] {
+                            let item_threshold = *threshold as f32 / 10.0;
+                            if ui
+                                .add(
+                                    SelectableLabel::new(
+                                        pause_threshold == item_threshold,
+                                        format!("{}× median", item_threshold),
+                                    )
+                                    .text_style(FontSize::Normal.into()),
+                                )
+                                .clicked()
+                            {
+                                let _ = history.set_i64_setting("pause_threshold", *threshold);
+                            }
+                        }
+                    });
+                    ui.add(
+                        Label::new(
+                            "Gaps between moves at least this many times longer than your \
+                                median gap between moves are counted as pauses in the \
+                                lookahead analysis.",
+                        )
+                        .wrap(true),
+                    );
+
                     ui.add_space(16.0);
                     ui.section("Cloud Sync");
 
@@ -378,7 +472,79 @@ impl Settings {
                                 }
                             }
                         }
-                        ui.label("Export all solve information to a file for backup.")
+                        ui.label("Export all solve information to a file for backup.");
+
+                        ui.add_space(8.0);
+
+                        // Import algorithms option
+                        if ui
+                            .add(
+                                Label::new("🗁  Import algorithms")
+                                    .text_style(FontSize::Section.into())
+                                    .sense(Sense::click()),
+                            )
+                            .clicked()
+                        {
+                            self.import_algorithms(history);
+                        }
+                        if let Some(result) = &self.algorithm_import_result {
+                            match result {
+                                Ok(message) => {
+                                    ui.add(
+                                        Label::new(format!("Import complete.\n{}", message))
+                                            .text_color(Theme::Green),
+                                    );
+                                }
+                                Err(error) => {
+                                    ui.add(
+                                        Label::new(format!("Error: {}", error))
+                                            .wrap(true)
+                                            .text_color(Theme::Red),
+                                    );
+                                }
+                            }
+                        }
+                        ui.add(
+                            Label::new(
+                                "Import an algorithm set, with one case per line in the form \
+                                \"case: alg; alg\". The first algorithm for each case becomes \
+                                the preferred algorithm. Nothing is imported if any algorithm \
+                                does not solve its case.",
+                            )
+                            .wrap(true),
+                        );
+
+                        ui.add_space(8.0);
+
+                        // Export algorithms option
+                        if ui
+                            .add(
+                                Label::new("🗐  Export algorithms")
+                                    .text_style(FontSize::Section.into())
+                                    .sense(Sense::click()),
+                            )
+                            .clicked()
+                        {
+                            self.export_algorithms(history);
+                        }
+                        if let Some(result) = &self.algorithm_export_result {
+                            match result {
+                                Ok(()) => {
+                                    ui.add(Label::new("Export complete.").text_color(Theme::Green));
+                                }
+                                Err(error) => {
+                                    ui.add(
+                                        Label::new(format!("Error: {}", error))
+                                            .wrap(true)
+                                            .text_color(Theme::Red),
+                                    );
+                                }
+                            }
+                        }
+                        ui.label(
+                            "Export algorithms and learning status to share with others. \
+                            Use a .json file name to export in JSON format.",
+                        )
                     }
                 });
             });
@@ -15,11 +15,12 @@ use egui::{Align, CentralPanel, CtxRef, Event, Key, Layout, Rect, Response, Sens
 use instant::Instant;
 use scramble::TimerCube;
 use session::TimerSession;
-use solve::{bluetooth_timer_ui, timer_ui};
-use state::{LastSolve, TimerState};
+use solve::{bluetooth_timer_ui, recognition_choice_for_key, recognition_ui, timer_ui};
+use state::{LastSolve, RecognitionAnswer, TimerState};
 use tpscube_core::{
-    Analysis, Cube, Cube3x3x3, CubeWithSolution, History, InitialCubeState, PartialAnalysis,
-    Penalty, Solve, SolveType, TimedMove,
+    Algorithm, Analysis, CrossPlanningAnalysis, Cube, Cube3x3x3, CubeFace, CubeWithSolution,
+    F2LSlot, History, InitialCubeState, PartialAnalysis, Penalty, RecognitionCase, Solve,
+    SolveType, TimedMove,
 };
 
 pub struct TimerWidget {
@@ -62,6 +63,7 @@ impl TimerWidget {
             penalty: Penalty::None,
             device: None,
             moves: None,
+            target_slot: self.xcross_slot(solve_type),
         });
         let _ = history.local_commit();
         self.state = TimerState::SolveComplete(
@@ -70,6 +72,8 @@ impl TimerWidget {
                 id,
                 solve_type,
                 analysis: None,
+                cross_planning: None,
+                recognition: None,
                 scramble: self.cube.scramble().to_vec(),
                 penalty: Penalty::None,
             },
@@ -96,7 +100,25 @@ impl TimerWidget {
             time += mv.time();
             final_moves.push(TimedMove::new(mv.move_(), time));
         }
-        let (moves, analysis) = if cube.is_solved() {
+        let id = Solve::new_id();
+        let (moves, analysis) = if solve_type.is_f2l_training() {
+            // F2L training solves end with the last layer unsolved, so there is
+            // nothing to analyze
+            if cube.first_two_layers_solved(CubeFace::Top) {
+                (Some(final_moves), None)
+            } else {
+                (None, None)
+            }
+        } else if solve_type.is_cross_training() {
+            // Cross training solves end once the cross is solved, and are compared
+            // against the optimal cross in the background instead of being analyzed as
+            // a full solve
+            if CrossPlanningAnalysis::target_solved(&cube, self.xcross_slot(solve_type)) {
+                (Some(final_moves), None)
+            } else {
+                (None, None)
+            }
+        } else if cube.is_solved() {
             let analysis = Analysis::analyze(&CubeWithSolution {
                 initial_state,
                 solution: final_moves.clone(),
@@ -106,7 +128,6 @@ impl TimerWidget {
             (None, None)
         };
 
-        let id = Solve::new_id();
         history.new_solve(Solve {
             id: id.clone(),
             solve_type,
@@ -117,6 +138,7 @@ impl TimerWidget {
             penalty: Penalty::None,
             device: name,
             moves,
+            target_slot: self.xcross_slot(solve_type),
         });
         let _ = history.local_commit();
         self.state = TimerState::SolveComplete(
@@ -125,6 +147,8 @@ impl TimerWidget {
                 id,
                 solve_type,
                 analysis,
+                cross_planning: None,
+                recognition: None,
                 scramble: self.cube.scramble().to_vec(),
                 penalty: Penalty::None,
             },
@@ -132,8 +156,70 @@ impl TimerWidget {
         self.cube.new_scramble();
     }
 
+    /// Completes a recognition drill with the case given as the answer. Wrong answers
+    /// are stored with a recognition DNF so that recognition accuracy can be graphed.
+    fn finish_recognition(
+        &mut self,
+        time: u32,
+        answer: Algorithm,
+        history: &mut History,
+        solve_type: SolveType,
+    ) {
+        let case = match RecognitionCase::from_scramble(solve_type, self.cube.scramble()) {
+            Some(case) => case,
+            None => {
+                self.state = TimerState::Inactive(0, None);
+                self.cube.new_scramble();
+                return;
+            }
+        };
+        let recognition = RecognitionAnswer { case, answer };
+        let penalty = if recognition.is_correct() {
+            Penalty::None
+        } else {
+            Penalty::RecognitionDNF
+        };
+
+        let id = Solve::new_id();
+        history.new_solve(Solve {
+            id: id.clone(),
+            solve_type,
+            session: history.current_session().into(),
+            scramble: self.cube.scramble().to_vec(),
+            created: Local::now(),
+            time,
+            penalty,
+            device: None,
+            moves: None,
+            target_slot: None,
+        });
+        let _ = history.local_commit();
+        self.state = TimerState::SolveComplete(
+            time,
+            LastSolve {
+                id,
+                solve_type,
+                analysis: None,
+                cross_planning: None,
+                recognition: Some(recognition),
+                scramble: self.cube.scramble().to_vec(),
+                penalty,
+            },
+        );
+        self.cube.new_scramble();
+    }
+
+    /// Target pair to solve along with the cross, if training XCross
+    fn xcross_slot(&self, solve_type: SolveType) -> Option<F2LSlot> {
+        if solve_type == SolveType::XCrossTraining {
+            Some(self.cube.xcross_slot())
+        } else {
+            None
+        }
+    }
+
     fn abort_solve(&mut self, time: u32, history: &mut History, solve_type: SolveType) {
-        let (penalty, min_time) = if solve_type.is_last_layer_training() {
+        let (penalty, min_time) = if solve_type.is_algorithm_training() {
             (Penalty::ExecutionDNF, 500)
         } else {
             (Penalty::DNF, 2000)
@@ -153,6 +239,7 @@ impl TimerWidget {
                 penalty,
                 device: None,
                 moves: None,
+                target_slot: self.xcross_slot(solve_type),
             });
             let _ = history.local_commit();
             self.state = TimerState::SolveComplete(
@@ -161,6 +248,8 @@ impl TimerWidget {
                     id,
                     solve_type,
                     analysis: None,
+                    cross_planning: None,
+                    recognition: None,
                     scramble: self.cube.scramble().to_vec(),
                     penalty,
                 },
@@ -245,9 +334,10 @@ impl TimerWidget {
                         .cube
                         .update_bluetooth_scramble_and_check_finish(&bluetooth_events)
                     {
-                        if solve_type == SolveType::Blind3x3x3 {
+                        if solve_type == SolveType::Blind3x3x3 || solve_type.is_cross_training() {
                             // When solving in blind mode, start timer immediately when the
                             // scramble is complete so that memorization time is accounted for.
+                            // Cross training times inspection in the same way.
                             self.state = TimerState::BluetoothSolving(
                                 Instant::now(),
                                 Vec::new(),
@@ -258,7 +348,9 @@ impl TimerWidget {
                                 TimerState::BluetoothPreparing(Instant::now(), time, last_solve);
                         }
                     }
-                } else if accept_keyboard {
+                } else if accept_keyboard && !solve_type.is_recognition_training() {
+                    // Recognition drills use the number keys for answers, so times can't
+                    // be entered manually
                     for event in &ctxt.input().events {
                         if let Event::Text(text) = event {
                             if text.len() == 1 {
@@ -335,6 +427,33 @@ impl TimerWidget {
                     self.state = TimerState::Inactive(0, None);
                 }
             }
+            TimerState::Solving(start) if solve_type.is_recognition_training() => {
+                // Recognition drills are answered with the keys mapped to the cases, so
+                // other keys and touches don't stop the timer. Escape skips the case.
+                if ctxt.input().keys_down.contains(&Key::Escape) {
+                    self.state = TimerState::Inactive(0, None);
+                    self.cube.new_scramble();
+                    ctxt.request_repaint();
+                } else {
+                    let choices = self.cube.recognition_choices();
+                    let answer = ctxt.input().events.iter().find_map(|event| match event {
+                        Event::Text(text) if text.chars().count() == 1 => {
+                            recognition_choice_for_key(text.chars().next().unwrap())
+                                .and_then(|idx| choices.get(idx).cloned())
+                        }
+                        _ => None,
+                    });
+                    if let Some(answer) = answer {
+                        self.finish_recognition(
+                            (Instant::now() - start).as_millis() as u32,
+                            answer,
+                            history,
+                            solve_type,
+                        );
+                        ctxt.request_repaint();
+                    }
+                }
+            }
             TimerState::Solving(start) => {
                 if ctxt.input().keys_down.contains(&Key::Escape) {
                     self.abort_solve(
@@ -377,20 +496,30 @@ impl TimerWidget {
                 } else if bluetooth_moves.len() != 0 {
                     let mut moves = moves.clone();
                     moves.extend(bluetooth_moves);
-                    if self.cube.is_solved() {
+
+                    let mut cube = Cube3x3x3::new();
+                    cube.do_moves(self.cube.scramble());
+                    let initial_state = cube.clone();
+                    let mut final_moves = Vec::new();
+                    let mut time = 0;
+                    for mv in &moves {
+                        cube.do_move(mv.move_());
+                        time += mv.time();
+                        final_moves.push(TimedMove::new(mv.move_(), time));
+                    }
+
+                    // F2L training is complete once the last pair is inserted, and cross
+                    // training once the cross and any target pair are solved
+                    let slot = self.xcross_slot(solve_type);
+                    if self.cube.is_solved()
+                        || (solve_type.is_f2l_training()
+                            && cube.first_two_layers_solved(CubeFace::Top))
+                        || (solve_type.is_cross_training()
+                            && CrossPlanningAnalysis::target_solved(&cube, slot))
+                    {
                         self.finish_bluetooth_solve(history, moves, bluetooth_name, solve_type);
                         ctxt.request_repaint();
                     } else {
-                        let mut cube = Cube3x3x3::new();
-                        cube.do_moves(self.cube.scramble());
-                        let initial_state = cube.clone();
-                        let mut final_moves = Vec::new();
-                        let mut time = 0;
-                        for mv in &moves {
-                            cube.do_move(mv.move_());
-                            time += mv.time();
-                            final_moves.push(TimedMove::new(mv.move_(), time));
-                        }
                         let analysis = PartialAnalysis::analyze(&CubeWithSolution {
                             initial_state,
                             solution: final_moves.clone(),
@@ -454,7 +583,12 @@ impl TimerWidget {
         self.session.check_solve_type(history, solve_type);
 
         if let Some(last_solve_time) = self.session.last_solve_time() {
-            if Settings::auto_sessions_enabled(history) {
+            if solve_type.is_daily_challenge() {
+                // Each daily challenge has its own session
+                if last_solve_time.naive_local().date() != Local::now().naive_local().date() {
+                    self.session.new_session(history);
+                }
+            } else if Settings::auto_sessions_enabled(history) {
                 let since = Local::now() - last_solve_time;
                 if since.num_seconds() > Settings::auto_session_time(history) {
                     self.session.new_session(history);
@@ -483,13 +617,25 @@ impl TimerWidget {
 
         if bluetooth_state.is_some() {
             // If Bluetooth cube is connected, force 3x3x3 solve type
-            if !solve_type.is_3x3x3() && !solve_type.is_last_layer_training() {
+            if !solve_type.is_3x3x3()
+                && !solve_type.is_algorithm_training()
+                && !solve_type.is_cross_training()
+                && !solve_type.is_recognition_training()
+            {
                 *solve_type = SolveType::Standard3x3x3;
             }
         }
 
         self.cube.check_solve_type(*solve_type, history);
+        self.cube.update_last_layer_training(history);
         self.check_for_expired_session(history, *solve_type);
+        self.cube
+            .update_daily_challenge(self.session.daily_challenge_completed(history));
+
+        // Cross planning results are found in the background, so show the result of the
+        // last solve once it is available
+        self.state
+            .update_cross_planning(self.session.cross_planning());
 
         ctxt.set_visuals(side_visuals());
         let aspect = ctxt.available_rect().width() / ctxt.available_rect().height();
@@ -512,8 +658,9 @@ impl TimerWidget {
                     let mut center = rect.center();
                     let is_solving = self.state.is_solving();
 
-                    if !is_solving {
-                        // Draw new scramble button at top
+                    if !is_solving && !solve_type.is_daily_challenge() {
+                        // Draw new scramble button at top. Daily challenge scrambles
+                        // are fixed, so they can't be skipped.
                         self.cube.new_scramble_button(
                             if let TimerState::Inactive(_, _) = &self.state {
                                 true
@@ -525,7 +672,7 @@ impl TimerWidget {
                             &mut center,
                         );
 
-                        if solve_type.is_last_layer_training() {
+                        if solve_type.is_algorithm_training() {
                             self.cube.training_penalty_buttons(
                                 ui,
                                 history,
@@ -536,6 +683,29 @@ impl TimerWidget {
                         }
                     }
 
+                    // Recognition drills show the case only while the timer is running. The
+                    // choices are checked for clicks before the timer area, which would
+                    // otherwise take the click.
+                    let recognition_start = match self.state {
+                        TimerState::Solving(start) if solve_type.is_recognition_training() => {
+                            Some(start)
+                        }
+                        _ => None,
+                    };
+                    let recognition_answer = if recognition_start.is_some() {
+                        recognition_ui(
+                            ui,
+                            &rect,
+                            &center,
+                            &self.cube,
+                            &self.cube.recognition_choices(),
+                            cube_rect,
+                            framerate,
+                        )
+                    } else {
+                        None
+                    };
+
                     // The entire timer area is interactable, touch events should start/stop the
                     // timer anywhere in the timer area.
                     let interact = self.check_for_interaction_and_state_transition(
@@ -545,11 +715,31 @@ impl TimerWidget {
                         history,
                         bluetooth_events,
                         bluetooth_name,
-                        accept_keyboard,
+                        accept_keyboard
+                            && !self.cube.is_cube_state_entry_open()
+                            && !self.cube.is_daily_challenge_complete(),
                         *solve_type,
                     );
 
-                    if is_solving {
+                    if let Some(start) = recognition_start {
+                        // The case may have already been answered with a key
+                        if let (Some(answer), TimerState::Solving(_)) =
+                            (recognition_answer, &self.state)
+                        {
+                            self.finish_recognition(
+                                (Instant::now() - start).as_millis() as u32,
+                                answer,
+                                history,
+                                *solve_type,
+                            );
+                            ctxt.request_repaint();
+                        }
+
+                        // Don't show the next case until the timer is started again
+                        if !matches!(self.state, TimerState::Solving(_)) {
+                            *cube_rect = None;
+                        }
+                    } else if is_solving {
                         if self.cube.is_bluetooth_active() {
                             bluetooth_timer_ui(
                                 ui,
@@ -575,12 +765,25 @@ impl TimerWidget {
                         );
                     }
 
-                    self.cube
-                        .rotate_cube_with_input(ctxt, ui, cube_rect, interact);
+                    // Recognition drills only show two sides of the last layer, so the cube
+                    // can't be rotated to see the others
+                    if !solve_type.is_recognition_training() {
+                        self.cube
+                            .rotate_cube_with_input(ctxt, ui, cube_rect, interact);
+                    }
                 });
             });
         });
 
+        // Cube state entry is only available between solves
+        let close_cube_state_entry = if let TimerState::Inactive(_, _) = &self.state {
+            false
+        } else {
+            true
+        };
+        self.cube
+            .cube_state_entry_window(ctxt, close_cube_state_entry);
+
         // Run at 10 FPS when solving (to update counting timer), or only when
         // updates occur otherwise
         match self.state {
@@ -2,17 +2,24 @@ use crate::cube::CubeRenderer;
 use crate::font::FontSize;
 use crate::framerate::Framerate;
 use crate::gl::GlContext;
+use crate::style::dialog_visuals;
 use crate::theme::Theme;
 use crate::timer::analysis::TimerPostAnalysis;
 use crate::timer::state::TimerState;
 use crate::timer::BluetoothEvent;
-use crate::widgets::fit_scramble;
-use anyhow::Result;
-use egui::{CtxRef, Pos2, Rect, Response, Sense, Ui, Vec2};
+use crate::widgets::{
+    color_for_cross_planning, cross_planning_string, fit_scramble, recognition_answer_string,
+};
+use anyhow::{anyhow, Result};
+use chrono::{Local, NaiveDate};
+use egui::{widgets::Label, CtxRef, Key, Pos2, Rect, Response, Sense, TextEdit, Ui, Vec2, Window};
 use tpscube_core::{
-    scramble_2x2x2, scramble_3x3x3, scramble_4x4x4, scramble_last_layer, scramble_megaminx, Cube,
-    Cube2x2x2, Cube3x3x3, Cube4x4x4, History, InitialCubeState, LastLayerRandomization, Move,
-    MoveSequence, Penalty, SolveType,
+    constrained_scramble_3x3x3, scramble_2x2x2, scramble_3x3x3, scramble_4x4x4,
+    scramble_last_layer, scramble_megaminx, Algorithm, AlgorithmStatus, Cube, Cube2x2x2,
+    Cube2x2x2Faces, Cube3x3x3, Cube3x3x3Faces, Cube4x4x4, Cube4x4x4Faces, CubeFace, F2LAlgorithm,
+    F2LSlot, History, InitialCubeState, LastLayerRandomization, Move, MoveSequence, OLLAlgorithm,
+    PLLAlgorithm, Penalty, RandomSource, ReviewSchedule, ScrambleConstraint, ScrambleConstraints,
+    ScrambleSet, SolveType, StandardRandomSource, DAILY_CHALLENGE_SOLVE_COUNT,
 };
 
 const TARGET_SCRAMBLE_FRACTION: f32 = 0.2;
@@ -20,6 +27,7 @@ const TARGET_ANALYSIS_SCRAMBLE_FRACTION: f32 = 0.15;
 const TARGET_TIMER_FRACTION: f32 = 0.2;
 
 const NEW_SCRAMBLE_PADDING: f32 = 4.0;
+const NEW_SCRAMBLE_SPACING: f32 = 32.0;
 
 const TRAINING_PENALTY_SPACING: f32 = 32.0;
 const TRAINING_PENALTY_PADDING: f32 = 16.0;
@@ -43,6 +51,37 @@ pub struct TimerCube {
     scramble_fix_moves: Vec<Move>,
     solve_type: SolveType,
     last_layer_training: LastLayerTrainingSettings,
+    last_layer_state: LastLayerTrainingState,
+    xcross_slot: F2LSlot,
+    /// Constraint checkers for the training modes with fixed scramble constraints. These
+    /// are built on first use, as some of them need move tables that are slow to build.
+    scramble_constraints: Vec<(SolveType, ScrambleConstraints)>,
+    cube_state_entry: Option<CubeStateEntry>,
+    daily_challenge: Option<DailyChallenge>,
+}
+
+/// Learning status of each OLL, PLL and F2L case, in the order given by `all()`, along
+/// with the spaced repetition schedule. This is refreshed from history whenever it
+/// changes.
+#[derive(Default)]
+struct LastLayerTrainingState {
+    update_id: Option<u64>,
+    oll: Vec<AlgorithmStatus>,
+    pll: Vec<AlgorithmStatus>,
+    f2l: Vec<AlgorithmStatus>,
+    schedule: ReviewSchedule,
+}
+
+/// Scrambles for today's daily challenge along with the number of solves already done
+struct DailyChallenge {
+    date: NaiveDate,
+    scrambles: Vec<Vec<Move>>,
+    completed: usize,
+}
+
+struct CubeStateEntry {
+    facelets: String,
+    error: Option<String>,
 }
 
 enum ScrambleMoveResult {
@@ -57,6 +96,7 @@ pub struct LastLayerTrainingSettings {
     pub algorithms: LastLayerAlgorithmSelection,
     pub realistic_weights: bool,
     pub learning_multiplier: usize,
+    pub spaced_repetition: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -79,6 +119,126 @@ impl LastLayerAlgorithmSelection {
     }
 }
 
+impl LastLayerTrainingSettings {
+    /// Number of times a case with the given status is included in training
+    fn case_count(&self, status: AlgorithmStatus) -> usize {
+        match (self.algorithms, status) {
+            (LastLayerAlgorithmSelection::Known, AlgorithmStatus::Known)
+            | (LastLayerAlgorithmSelection::KnownAndLearning, AlgorithmStatus::Known)
+            | (LastLayerAlgorithmSelection::All, AlgorithmStatus::Known)
+            | (LastLayerAlgorithmSelection::All, AlgorithmStatus::Unknown) => 1,
+            (LastLayerAlgorithmSelection::Known, AlgorithmStatus::Learning) => 0,
+            (_, AlgorithmStatus::Learning) => self.learning_multiplier,
+            (_, AlgorithmStatus::Unknown) => 0,
+        }
+    }
+
+    /// Gets the cases selected for spaced repetition training. Every case is used
+    /// if none are selected.
+    fn selected_cases<T: Copy>(&self, all: &[T], status: &[AlgorithmStatus]) -> Vec<T> {
+        let cases: Vec<T> = all
+            .iter()
+            .zip(status.iter())
+            .filter(|(_, status)| self.case_count(**status) > 0)
+            .map(|(case, _)| *case)
+            .collect();
+        if cases.is_empty() {
+            all.to_vec()
+        } else {
+            cases
+        }
+    }
+
+    /// Gets the list of cases to choose from for training. Cases being learned are
+    /// repeated according to the learning multiplier. Returns `None` if every case
+    /// is included once and realistic weights are used, as a random last layer state
+    /// already gives the correct distribution.
+    fn cases<T: Copy>(&self, all: &[T], status: &[AlgorithmStatus]) -> Option<Vec<T>> {
+        let mut cases = Vec::new();
+        for (case, status) in all.iter().zip(status.iter()) {
+            for _ in 0..self.case_count(*status) {
+                cases.push(*case);
+            }
+        }
+
+        if self.algorithms == LastLayerAlgorithmSelection::All
+            && self.realistic_weights
+            && cases.len() == all.len()
+        {
+            None
+        } else {
+            Some(cases)
+        }
+    }
+
+    fn oll_randomization(&self, status: &[AlgorithmStatus]) -> LastLayerRandomization {
+        match self.cases(OLLAlgorithm::all(), status) {
+            Some(cases) if !cases.is_empty() => {
+                if self.realistic_weights {
+                    LastLayerRandomization::WeightedRandomOLL(cases)
+                } else {
+                    LastLayerRandomization::RandomOLL(cases)
+                }
+            }
+            // No cases selected, fall back to a random last layer
+            _ => LastLayerRandomization::RandomStateUnsolved,
+        }
+    }
+
+    fn pll_randomization(&self, status: &[AlgorithmStatus]) -> LastLayerRandomization {
+        match self.cases(PLLAlgorithm::all(), status) {
+            Some(cases) if !cases.is_empty() => {
+                if self.realistic_weights {
+                    LastLayerRandomization::WeightedRandomPLL(cases)
+                } else {
+                    LastLayerRandomization::RandomPLL(cases)
+                }
+            }
+            // No cases selected, fall back to a random permutation
+            _ => LastLayerRandomization::OrientedRandomStateUnsolved,
+        }
+    }
+
+    /// Recognition drills must always show a case, so every case is used when none
+    /// are selected instead of falling back to a random last layer
+    fn oll_recognition_randomization(&self, status: &[AlgorithmStatus]) -> LastLayerRandomization {
+        let cases = match self.cases(OLLAlgorithm::all(), status) {
+            Some(cases) if !cases.is_empty() => cases,
+            _ => OLLAlgorithm::all().to_vec(),
+        };
+        if self.realistic_weights {
+            LastLayerRandomization::WeightedRandomOLL(cases)
+        } else {
+            LastLayerRandomization::RandomOLL(cases)
+        }
+    }
+
+    fn pll_recognition_randomization(&self, status: &[AlgorithmStatus]) -> LastLayerRandomization {
+        let cases = match self.cases(PLLAlgorithm::all(), status) {
+            Some(cases) if !cases.is_empty() => cases,
+            _ => PLLAlgorithm::all().to_vec(),
+        };
+        if self.realistic_weights {
+            LastLayerRandomization::WeightedRandomPLL(cases)
+        } else {
+            LastLayerRandomization::RandomPLL(cases)
+        }
+    }
+
+    fn f2l_constraints(&self, status: &[AlgorithmStatus]) -> Vec<ScrambleConstraint> {
+        match self.cases(&F2LAlgorithm::all(), status) {
+            Some(cases) if !cases.is_empty() => {
+                // Cases are chosen with equal weight here, as only a random state with
+                // three pairs solved gives the realistic distribution
+                let case = cases[StandardRandomSource.next(cases.len() as u32) as usize];
+                vec![ScrambleConstraint::F2LCase(CubeFace::Top, case)]
+            }
+            // No cases selected, fall back to a random last pair
+            _ => SolveType::F2LTraining.scramble_constraints(),
+        }
+    }
+}
+
 impl ToString for LastLayerAlgorithmSelection {
     fn to_string(&self) -> String {
         match self {
@@ -113,7 +273,13 @@ impl TimerCube {
                 algorithms: LastLayerAlgorithmSelection::All,
                 realistic_weights: true,
                 learning_multiplier: 1,
+                spaced_repetition: false,
             },
+            last_layer_state: LastLayerTrainingState::default(),
+            xcross_slot: F2LSlot::FrontRight,
+            scramble_constraints: Vec::new(),
+            cube_state_entry: None,
+            daily_challenge: None,
         }
     }
 
@@ -125,24 +291,131 @@ impl TimerCube {
         self.bluetooth_active
     }
 
-    fn generate_scramble(&self) -> Vec<Move> {
+    fn constrained_scramble(&mut self) -> Vec<Move> {
+        let solve_type = self.solve_type;
+        let idx = match self
+            .scramble_constraints
+            .iter()
+            .position(|(constraint_type, _)| *constraint_type == solve_type)
+        {
+            Some(idx) => idx,
+            None => {
+                self.scramble_constraints.push((
+                    solve_type,
+                    ScrambleConstraints::new(&solve_type.scramble_constraints()),
+                ));
+                self.scramble_constraints.len() - 1
+            }
+        };
+        self.scramble_constraints[idx]
+            .1
+            .sourced_scramble(&mut StandardRandomSource)
+            .unwrap_or_else(scramble_3x3x3)
+    }
+
+    fn generate_scramble(&mut self) -> Vec<Move> {
         match self.solve_type {
             SolveType::Standard2x2x2 => scramble_2x2x2(),
-            SolveType::Standard3x3x3 | SolveType::OneHanded3x3x3 | SolveType::Blind3x3x3 => {
-                scramble_3x3x3()
-            }
+            SolveType::Standard3x3x3
+            | SolveType::OneHanded3x3x3
+            | SolveType::Blind3x3x3
+            | SolveType::CrossTraining
+            | SolveType::XCrossTraining => scramble_3x3x3(),
             SolveType::Standard4x4x4 | SolveType::Blind4x4x4 => scramble_4x4x4(),
             SolveType::Megaminx => scramble_megaminx(),
-            SolveType::OLLTraining => {
-                scramble_last_layer(LastLayerRandomization::RandomStateUnsolved)
+            SolveType::OLLTraining | SolveType::PLLTraining
+                if self.last_layer_training.spaced_repetition =>
+            {
+                self.spaced_repetition_scramble()
+            }
+            SolveType::OLLTraining => scramble_last_layer(
+                self.last_layer_training
+                    .oll_randomization(&self.last_layer_state.oll),
+            ),
+            SolveType::PLLTraining => scramble_last_layer(
+                self.last_layer_training
+                    .pll_randomization(&self.last_layer_state.pll),
+            ),
+            SolveType::OLLRecognition => scramble_last_layer(
+                self.last_layer_training
+                    .oll_recognition_randomization(&self.last_layer_state.oll),
+            ),
+            SolveType::PLLRecognition => scramble_last_layer(
+                self.last_layer_training
+                    .pll_recognition_randomization(&self.last_layer_state.pll),
+            ),
+            SolveType::DailyChallenge3x3x3 => {
+                // Daily challenge scrambles are fixed for the day. Once all solves are
+                // complete there is no scramble to show.
+                match &self.daily_challenge {
+                    Some(daily) => daily
+                        .scrambles
+                        .get(daily.completed)
+                        .cloned()
+                        .unwrap_or_default(),
+                    None => Vec::new(),
+                }
             }
-            SolveType::PLLTraining => {
-                scramble_last_layer(LastLayerRandomization::OrientedRandomStateUnsolved)
+            SolveType::HardCrossTraining | SolveType::LastSlotTraining => {
+                self.constrained_scramble()
             }
+            // The case changes with every scramble, but F2L case constraints do not
+            // need any move tables so they are cheap to build
+            SolveType::F2LTraining => constrained_scramble_3x3x3(
+                &self
+                    .last_layer_training
+                    .f2l_constraints(&self.last_layer_state.f2l),
+            )
+            .unwrap_or_else(scramble_3x3x3),
         }
     }
 
+    /// Chooses the next case to train using the spaced repetition schedule. The case
+    /// that was just trained is not repeated immediately.
+    fn spaced_repetition_scramble(&self) -> Vec<Move> {
+        let state = &self.last_layer_state;
+        let candidates: Vec<Algorithm> = match self.solve_type {
+            SolveType::OLLTraining => self
+                .last_layer_training
+                .selected_cases(OLLAlgorithm::all(), &state.oll)
+                .into_iter()
+                .map(Algorithm::OLL)
+                .collect(),
+            SolveType::PLLTraining => self
+                .last_layer_training
+                .selected_cases(PLLAlgorithm::all(), &state.pll)
+                .into_iter()
+                .map(Algorithm::PLL)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let current = ReviewSchedule::training_case(self.solve_type, &self.current_scramble);
+
+        match state.schedule.next_case(&candidates, current, Local::now()) {
+            Some(Algorithm::OLL(oll)) => {
+                scramble_last_layer(LastLayerRandomization::RandomOLL(vec![oll]))
+            }
+            Some(Algorithm::PLL(pll)) => {
+                scramble_last_layer(LastLayerRandomization::RandomPLL(vec![pll]))
+            }
+            _ => scramble_last_layer(LastLayerRandomization::RandomStateUnsolved),
+        }
+    }
+
+    /// Returns true if scrambles depend on the solves performed so far, so they
+    /// can't be generated ahead of time
+    fn scramble_depends_on_progress(&self) -> bool {
+        self.solve_type.is_daily_challenge()
+            || (self.solve_type.is_last_layer_training()
+                && self.last_layer_training.spaced_repetition)
+    }
+
     pub fn new_scramble(&mut self) {
+        if self.scramble_depends_on_progress() {
+            // Scrambles for the daily challenge and spaced repetition training depend
+            // on progress, they can't be generated ahead of time
+            self.next_scramble = None;
+        }
         if let Some(scramble) = &self.next_scramble {
             self.current_scramble = scramble.clone();
         } else {
@@ -161,6 +434,154 @@ impl TimerCube {
         }
     }
 
+    /// Sets the scramble to one that arrives at the cube state given by a facelet
+    /// string. The solver is used to find a solution to the state, and the inverse of
+    /// the solution is used as the scramble.
+    pub fn set_cube_state(&mut self, facelets: &str) -> Result<()> {
+        let solution = match self.solve_type {
+            SolveType::Standard2x2x2 => {
+                let cube = Cube2x2x2Faces::from_facelet_string(facelets)?;
+                cube.validate()?;
+                cube.solve()
+            }
+            SolveType::Standard3x3x3 | SolveType::OneHanded3x3x3 | SolveType::Blind3x3x3 => {
+                let cube = Cube3x3x3Faces::from_facelet_string(facelets)?;
+                cube.validate()?;
+                cube.solve()
+            }
+            SolveType::Standard4x4x4 | SolveType::Blind4x4x4 => {
+                let cube = Cube4x4x4Faces::from_facelet_string(facelets)?;
+                cube.validate()?;
+                cube.solve()
+            }
+            _ => return Err(anyhow!("Cube state cannot be set for this puzzle")),
+        }
+        .ok_or_else(|| anyhow!("Cube state could not be solved"))?;
+
+        self.current_scramble = solution.inverse();
+        self.current_scramble_displayed = false;
+        self.displayed_scramble = self.current_scramble.clone();
+        self.renderer.reset_cube_state();
+        self.renderer.do_moves(&self.current_scramble);
+        self.renderer.reset_angle();
+        Ok(())
+    }
+
+    /// Updates the daily challenge with the number of solves completed today. When the
+    /// date changes the scrambles for the new day are generated.
+    pub fn update_daily_challenge(&mut self, completed: usize) {
+        if !self.solve_type.is_daily_challenge() {
+            self.daily_challenge = None;
+            return;
+        }
+
+        let today = Local::now().naive_local().date();
+        match &mut self.daily_challenge {
+            Some(daily) if daily.date == today => {
+                if daily.completed == completed {
+                    return;
+                }
+                daily.completed = completed;
+            }
+            _ => {
+                let set = ScrambleSet::daily_challenge(today);
+                self.daily_challenge = Some(DailyChallenge {
+                    date: today,
+                    scrambles: set.rounds[0].groups[0].scrambles.clone(),
+                    completed,
+                });
+            }
+        }
+        self.new_scramble();
+    }
+
+    pub fn is_daily_challenge_complete(&self) -> bool {
+        match &self.daily_challenge {
+            Some(daily) => {
+                self.solve_type.is_daily_challenge()
+                    && daily.completed >= DAILY_CHALLENGE_SOLVE_COUNT
+            }
+            None => false,
+        }
+    }
+
+    fn cube_state_entry_available(&self) -> bool {
+        !self.bluetooth_active
+            && match self.solve_type {
+                SolveType::Standard2x2x2
+                | SolveType::Standard3x3x3
+                | SolveType::OneHanded3x3x3
+                | SolveType::Blind3x3x3
+                | SolveType::Standard4x4x4
+                | SolveType::Blind4x4x4 => true,
+                SolveType::Megaminx
+                | SolveType::OLLTraining
+                | SolveType::PLLTraining
+                | SolveType::DailyChallenge3x3x3
+                | SolveType::HardCrossTraining
+                | SolveType::LastSlotTraining
+                | SolveType::F2LTraining
+                | SolveType::CrossTraining
+                | SolveType::XCrossTraining
+                | SolveType::OLLRecognition
+                | SolveType::PLLRecognition => false,
+            }
+    }
+
+    pub fn is_cube_state_entry_open(&self) -> bool {
+        self.cube_state_entry.is_some()
+    }
+
+    pub fn cube_state_entry_window(&mut self, ctxt: &CtxRef, close: bool) {
+        if !self.cube_state_entry_available() || close {
+            self.cube_state_entry = None;
+            return;
+        }
+
+        let mut open = true;
+        let mut apply = false;
+        if let Some(entry) = &mut self.cube_state_entry {
+            ctxt.set_visuals(dialog_visuals());
+            Window::new("Set Cube State")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctxt, |ui| {
+                    ui.vertical(|ui| {
+                        ui.label(
+                            "Enter a facelet string in URFDLB order, using the face letters \
+                            U, R, F, D, L and B for each sticker.",
+                        );
+                        ui.add_space(8.0);
+                        let response = ui.add(TextEdit::singleline(&mut entry.facelets));
+                        if response.lost_focus() && ui.input().key_pressed(Key::Enter) {
+                            apply = true;
+                        }
+                        if let Some(error) = &entry.error {
+                            ui.add(Label::new(error).text_color(Theme::Red));
+                        }
+                        ui.add_space(8.0);
+                        if ui.button("Set state").clicked() {
+                            apply = true;
+                        }
+                    });
+                });
+        }
+
+        if apply {
+            let facelets = self.cube_state_entry.as_ref().unwrap().facelets.clone();
+            match self.set_cube_state(&facelets) {
+                Ok(_) => self.cube_state_entry = None,
+                Err(error) => {
+                    self.cube_state_entry.as_mut().unwrap().error = Some(error.to_string())
+                }
+            }
+        }
+        if !open {
+            self.cube_state_entry = None;
+        }
+    }
+
     pub fn display_scramble_from_current_state(&mut self) {
         if self.bluetooth_active {
             let state = self.renderer.cube_state();
@@ -313,7 +734,10 @@ impl TimerCube {
     pub fn check_for_new_scramble(&mut self) {
         // Generate a scramble when the current one is onscreen. The slight delay will
         // not be noticed as much when performing a new scramble.
-        if self.current_scramble_displayed && self.next_scramble.is_none() {
+        if self.current_scramble_displayed
+            && self.next_scramble.is_none()
+            && !self.scramble_depends_on_progress()
+        {
             self.next_scramble = Some(self.generate_scramble());
         }
     }
@@ -344,7 +768,7 @@ impl TimerCube {
         &mut self,
         bluetooth_events: &[BluetoothEvent],
     ) -> bool {
-        if self.bluetooth_active {
+        if self.bluetooth_active && !self.is_daily_challenge_complete() {
             self.apply_bluetooth_moves_for_scramble(bluetooth_events);
             if let Some(move_index) = self.scramble_move_index {
                 if move_index >= self.displayed_scramble.len() && self.scramble_fix_moves.len() == 0
@@ -371,10 +795,25 @@ impl TimerCube {
         let scramble_galley = ui
             .fonts()
             .layout_single_line(FontSize::Small.into(), "↺  New scramble".into());
-        let new_scramble_rect = Rect::from_center_size(
+        let set_state_galley = if self.cube_state_entry_available() {
+            Some(
+                ui.fonts()
+                    .layout_single_line(FontSize::Small.into(), "✎  Set cube state".into()),
+            )
+        } else {
+            None
+        };
+
+        // Center the buttons as a group
+        let total_width = if let Some(set_state_galley) = &set_state_galley {
+            scramble_galley.size.x + NEW_SCRAMBLE_SPACING + set_state_galley.size.x
+        } else {
+            scramble_galley.size.x
+        };
+        let new_scramble_rect = Rect::from_min_size(
             Pos2::new(
-                rect.center().x,
-                rect.top() + NEW_SCRAMBLE_PADDING + scramble_galley.size.y / 2.0,
+                rect.center().x - total_width / 2.0,
+                rect.top() + NEW_SCRAMBLE_PADDING,
             ),
             scramble_galley.size,
         );
@@ -398,6 +837,37 @@ impl TimerCube {
             self.new_scramble();
         }
 
+        if let Some(set_state_galley) = set_state_galley {
+            let set_state_rect = Rect::from_min_size(
+                Pos2::new(
+                    new_scramble_rect.right() + NEW_SCRAMBLE_SPACING,
+                    rect.top() + NEW_SCRAMBLE_PADDING,
+                ),
+                set_state_galley.size,
+            );
+            let interact = ui.allocate_rect(set_state_rect, Sense::click());
+            ui.painter().galley(
+                set_state_rect.left_top(),
+                set_state_galley,
+                if active {
+                    if interact.hovered() {
+                        Theme::Red.into()
+                    } else {
+                        Theme::Disabled.into()
+                    }
+                } else {
+                    Theme::Light.into()
+                },
+            );
+
+            if interact.clicked() && active {
+                self.cube_state_entry = Some(CubeStateEntry {
+                    facelets: String::new(),
+                    error: None,
+                });
+            }
+        }
+
         // Adjust remaining rectangle to remove new scramble button area
         let top_left = Pos2::new(
             rect.left(),
@@ -420,7 +890,7 @@ impl TimerCube {
     ) {
         if let TimerState::Inactive(_, Some(last_solve)) = state {
             if last_solve.solve_type != self.solve_type
-                || !last_solve.solve_type.is_last_layer_training()
+                || !last_solve.solve_type.is_algorithm_training()
             {
                 return;
             }
@@ -599,7 +1069,13 @@ impl TimerCube {
             FontSize::Scramble
         };
 
-        let (fix, scramble) = if self.bluetooth_active && self.scramble_fix_moves.len() > 0 {
+        // Recognition drills keep the case hidden until the timer starts, so there is no
+        // scramble to show
+        let daily_complete = self.is_daily_challenge_complete();
+        let recognition = self.solve_type.is_recognition_training();
+        let (fix, scramble) = if daily_complete || recognition {
+            (false, vec![vec![]])
+        } else if self.bluetooth_active && self.scramble_fix_moves.len() > 0 {
             (
                 true,
                 vec![
@@ -643,8 +1119,10 @@ impl TimerCube {
             16.0
         };
 
-        let show_cube =
-            !self.solve_type.is_last_layer_training() && self.solve_type != SolveType::Megaminx;
+        let show_cube = !self.solve_type.is_last_layer_training()
+            && self.solve_type != SolveType::Megaminx
+            && !daily_complete
+            && !recognition;
         let cube_height = rect.height()
             - (scramble_padding + scramble_height + timer_height + timer_padding - timer_overlap);
 
@@ -661,7 +1139,21 @@ impl TimerCube {
         for (line_idx, line) in scramble.iter().enumerate() {
             // Layout individual moves in the scramble
             let mut tokens = Vec::new();
-            if fix && line_idx == 0 {
+            if daily_complete {
+                tokens.push(ui.fonts().layout_single_line(
+                    scramble_font.into(),
+                    "Daily challenge complete, come back tomorrow".into(),
+                ));
+            } else if recognition {
+                tokens.push(ui.fonts().layout_single_line(
+                    scramble_font.into(),
+                    if crate::is_mobile() == Some(true) {
+                        "Touch and hold to show the next case".into()
+                    } else {
+                        "Hold space to show the next case".into()
+                    },
+                ));
+            } else if fix && line_idx == 0 {
                 tokens.push(ui.fonts().layout_single_line(
                     scramble_font.into(),
                     "Scramble incorrect, fix with".into(),
@@ -689,7 +1181,11 @@ impl TimerCube {
                 ui.painter().galley(
                     Pos2::new(x, y),
                     token,
-                    if !fix
+                    if daily_complete {
+                        Theme::Green.into()
+                    } else if recognition {
+                        Theme::Disabled.into()
+                    } else if !fix
                         && (self.scramble_move_index.is_none()
                             || Some(move_idx) == self.scramble_move_index)
                     {
@@ -786,6 +1282,38 @@ impl TimerCube {
                 move_count_galley,
                 Theme::Disabled.into(),
             );
+        } else if let Some(cross_planning) = state.cross_planning() {
+            let result_galley = ui.fonts().layout_single_line(
+                FontSize::Normal.into(),
+                cross_planning_string(cross_planning),
+            );
+            let result_width = result_galley.size.x;
+            ui.painter().galley(
+                Pos2::new(
+                    timer_x + timer_width / 2.0 - result_width / 2.0,
+                    timer_y + min_timer_height + 16.0,
+                ),
+                result_galley,
+                color_for_cross_planning(cross_planning),
+            );
+        } else if let Some(recognition) = state.recognition() {
+            let result_galley = ui.fonts().layout_single_line(
+                FontSize::Normal.into(),
+                recognition_answer_string(recognition.case.algorithm, recognition.answer),
+            );
+            let result_width = result_galley.size.x;
+            ui.painter().galley(
+                Pos2::new(
+                    timer_x + timer_width / 2.0 - result_width / 2.0,
+                    timer_y + min_timer_height + 16.0,
+                ),
+                result_galley,
+                if recognition.is_correct() {
+                    Theme::Green.into()
+                } else {
+                    Theme::Red.into()
+                },
+            );
         }
 
         // Render analysis if present
@@ -845,7 +1373,7 @@ impl TimerCube {
 
         self.solve_type = solve_type;
 
-        if solve_type.is_last_layer_training() {
+        if solve_type.is_algorithm_training() || solve_type.is_recognition_training() {
             self.last_layer_training.algorithms = LastLayerAlgorithmSelection::from_str(
                 &history
                     .setting_as_string("last_layer_training_algorithms")
@@ -859,6 +1387,17 @@ impl TimerCube {
                 .setting_as_i64("last_layer_training_learning_multiplier")
                 .unwrap_or(1)
                 .clamp(1, 32) as usize;
+            self.last_layer_training.spaced_repetition = history
+                .setting_as_bool("last_layer_training_spaced_repetition")
+                .unwrap_or(false);
+            self.update_last_layer_training(history);
+        }
+
+        if solve_type == SolveType::XCrossTraining {
+            self.xcross_slot = history
+                .setting_as_string("xcross_training_slot")
+                .and_then(|slot| F2LSlot::from_str(&slot))
+                .unwrap_or(F2LSlot::FrontRight);
         }
 
         self.renderer = match solve_type {
@@ -867,7 +1406,15 @@ impl TimerCube {
             | SolveType::OneHanded3x3x3
             | SolveType::Blind3x3x3
             | SolveType::OLLTraining
-            | SolveType::PLLTraining => CubeRenderer::new(Box::new(Cube3x3x3::new())),
+            | SolveType::PLLTraining
+            | SolveType::DailyChallenge3x3x3
+            | SolveType::HardCrossTraining
+            | SolveType::LastSlotTraining
+            | SolveType::F2LTraining
+            | SolveType::CrossTraining
+            | SolveType::XCrossTraining
+            | SolveType::OLLRecognition
+            | SolveType::PLLRecognition => CubeRenderer::new(Box::new(Cube3x3x3::new())),
             SolveType::Standard4x4x4 | SolveType::Blind4x4x4 => {
                 CubeRenderer::new(Box::new(Cube4x4x4::new()))
             }
@@ -882,6 +1429,74 @@ impl TimerCube {
         self.solve_type
     }
 
+    /// Reads the learning status of the last layer and F2L algorithms and the spaced
+    /// repetition schedule when history changes, so that training scrambles use the
+    /// cases currently selected.
+    pub fn update_last_layer_training(&mut self, history: &History) {
+        if !(self.solve_type.is_algorithm_training() || self.solve_type.is_recognition_training())
+            || self.last_layer_state.update_id == Some(history.update_id())
+        {
+            return;
+        }
+
+        if self.solve_type.is_last_layer_training() && self.last_layer_training.spaced_repetition {
+            let solve_type = self.solve_type;
+            self.last_layer_state.schedule = ReviewSchedule::from_solves(
+                history
+                    .iter()
+                    .filter(|solve| solve.solve_type == solve_type),
+                |solve| history.analysis(solve),
+            );
+        }
+
+        let oll: Vec<AlgorithmStatus> = OLLAlgorithm::all()
+            .iter()
+            .map(|oll| history.algorithm_status(Algorithm::OLL(*oll)))
+            .collect();
+        let pll: Vec<AlgorithmStatus> = PLLAlgorithm::all()
+            .iter()
+            .map(|pll| history.algorithm_status(Algorithm::PLL(*pll)))
+            .collect();
+        let f2l: Vec<AlgorithmStatus> = F2LAlgorithm::all()
+            .iter()
+            .map(|f2l| history.algorithm_status(Algorithm::F2L(*f2l)))
+            .collect();
+
+        self.last_layer_state.update_id = Some(history.update_id());
+        if oll != self.last_layer_state.oll
+            || pll != self.last_layer_state.pll
+            || f2l != self.last_layer_state.f2l
+        {
+            self.last_layer_state.oll = oll;
+            self.last_layer_state.pll = pll;
+            self.last_layer_state.f2l = f2l;
+
+            // The pregenerated scramble may be for a case that is no longer selected
+            self.next_scramble = None;
+        }
+    }
+
+    /// Cases that can be given as the answer in a recognition drill. These are the
+    /// cases selected for training, or every case if none are selected.
+    pub fn recognition_choices(&self) -> Vec<Algorithm> {
+        let state = &self.last_layer_state;
+        match self.solve_type {
+            SolveType::OLLRecognition => self
+                .last_layer_training
+                .selected_cases(OLLAlgorithm::all(), &state.oll)
+                .into_iter()
+                .map(Algorithm::OLL)
+                .collect(),
+            SolveType::PLLRecognition => self
+                .last_layer_training
+                .selected_cases(PLLAlgorithm::all(), &state.pll)
+                .into_iter()
+                .map(Algorithm::PLL)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn last_layer_training_algorithms(&self) -> LastLayerAlgorithmSelection {
         self.last_layer_training.algorithms
     }
@@ -894,6 +1509,10 @@ impl TimerCube {
         self.last_layer_training.learning_multiplier
     }
 
+    pub fn last_layer_training_spaced_repetition(&self) -> bool {
+        self.last_layer_training.spaced_repetition
+    }
+
     pub fn set_last_layer_training_algorithms(
         &mut self,
         algorithms: LastLayerAlgorithmSelection,
@@ -946,4 +1565,39 @@ impl TimerCube {
         self.next_scramble = None;
         self.new_scramble();
     }
+
+    pub fn set_last_layer_training_spaced_repetition(
+        &mut self,
+        spaced_repetition: bool,
+        history: &mut History,
+    ) {
+        if self.last_layer_training.spaced_repetition == spaced_repetition {
+            return;
+        }
+
+        self.last_layer_training.spaced_repetition = spaced_repetition;
+        let _ =
+            history.set_bool_setting("last_layer_training_spaced_repetition", spaced_repetition);
+
+        // Build the review schedule now that it is needed
+        self.last_layer_state.update_id = None;
+        self.update_last_layer_training(history);
+
+        self.next_scramble = None;
+        self.new_scramble();
+    }
+
+    /// Target pair to solve along with the cross in XCross training
+    pub fn xcross_slot(&self) -> F2LSlot {
+        self.xcross_slot
+    }
+
+    pub fn set_xcross_slot(&mut self, slot: F2LSlot, history: &mut History) {
+        if self.xcross_slot == slot {
+            return;
+        }
+
+        self.xcross_slot = slot;
+        let _ = history.set_string_setting("xcross_training_slot", slot.to_str());
+    }
 }
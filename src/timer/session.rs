@@ -1,18 +1,25 @@
 use crate::algorithms::AlgorithmRender;
 use crate::app::SolveDetails;
 use crate::font::{FontSize, LabelFontSize};
+use crate::settings::Settings;
 use crate::theme::Theme;
 use crate::timer::scramble::TimerCube;
 use crate::timer::state::TimerState;
-use crate::widgets::{solve_time_string, CustomWidgets};
-use chrono::{DateTime, Local};
+use crate::widgets::{
+    color_for_cross_planning, cross_planning_string, short_day_string, solve_time_string,
+    CustomWidgets,
+};
+use chrono::{DateTime, Local, NaiveDate};
 use egui::{
     popup_below_widget, Align, Align2, CtxRef, CursorIcon, Label, Layout, ScrollArea,
     SelectableLabel, Sense, SidePanel, Stroke, TopBottomPanel, Ui, Vec2,
 };
+use std::collections::HashMap;
 use tpscube_core::{
-    Algorithm, Average, BestSolve, Cube, Cube3x3x3, CubeFace, History, InitialCubeState,
-    KnownAlgorithms, ListAverage, OLLAlgorithm, PLLAlgorithm, Penalty, Solve, SolveList, SolveType,
+    median_move_gap, Algorithm, Average, BestSolve, CrossPlanningAnalysis, Cube, Cube3x3x3,
+    CubeFace, F2LAlgorithm, F2LSlot, History, InitialCubeState, KnownAlgorithms, ListAverage,
+    OLLAlgorithm, PLLAlgorithm, Penalty, Session, Solve, SolveList, SolveType,
+    DAILY_CHALLENGE_SOLVE_COUNT,
 };
 
 use super::scramble::LastLayerAlgorithmSelection;
@@ -26,12 +33,110 @@ pub struct TimerSession {
     best_solve: Option<BestSolve>,
     best_ao5: Option<Average>,
     best_ao12: Option<Average>,
+    best_daily_challenge: Option<Average>,
+    daily_challenge_results: Vec<(DateTime<Local>, Average)>,
+    daily_challenge_completed: usize,
+    cross_planning: CrossPlanningResults,
+}
+
+/// Cross planning analysis of each solve, by solve ID. Optimal solutions are found
+/// in the background, so solves are kept pending until their comparison is available.
+#[derive(Default)]
+pub struct CrossPlanningResults {
+    results: HashMap<String, Option<CrossPlanningAnalysis>>,
+    /// Solves waiting on a comparison against an optimal solution, with the median gap
+    /// and pause threshold to analyze them with
+    pending: Vec<(Solve, Option<u32>, f32)>,
 }
 
 enum SessionTime {
     BestSolve(BestSolve),
     AverageOfN(Average),
     SessionAverage(u32),
+    Progress(usize, usize),
+}
+
+impl CrossPlanningResults {
+    pub fn get(&self, id: &str) -> Option<&CrossPlanningAnalysis> {
+        self.results.get(id).and_then(|result| result.as_ref())
+    }
+
+    /// Analyzes a cross training solve if it hasn't been already. If the comparison
+    /// against an optimal solution isn't available yet, the solve is kept pending.
+    fn analyze(
+        &mut self,
+        history: &History,
+        solve: &Solve,
+        median_gap: Option<u32>,
+        threshold: f32,
+    ) {
+        if self.results.contains_key(&solve.id) {
+            return;
+        }
+
+        let moves = match &solve.moves {
+            // Solves from before the target slot was saved with XCross solves can't be
+            // compared against an optimal XCross
+            Some(moves)
+                if solve.solve_type != SolveType::XCrossTraining || solve.target_slot.is_some() =>
+            {
+                moves
+            }
+            _ => {
+                self.results.insert(solve.id.clone(), None);
+                return;
+            }
+        };
+
+        match history.cross_planning_efficiency(solve) {
+            Some(efficiency) => {
+                let mut initial_state = Cube3x3x3::new();
+                initial_state.do_moves(&solve.scramble);
+                let result = CrossPlanningAnalysis::analyze(
+                    &initial_state,
+                    moves,
+                    solve.target_slot,
+                    efficiency,
+                    median_gap,
+                    threshold,
+                );
+                self.results.insert(solve.id.clone(), result);
+            }
+            None => {
+                if !self
+                    .pending
+                    .iter()
+                    .any(|(pending, _, _)| pending.id == solve.id)
+                {
+                    self.pending.push((solve.clone(), median_gap, threshold));
+                }
+            }
+        }
+    }
+
+    /// Analyzes pending solves whose comparison against an optimal solution has since
+    /// become available
+    fn update_pending(&mut self, history: &History) {
+        for (solve, median_gap, threshold) in std::mem::take(&mut self.pending) {
+            self.analyze(history, &solve, median_gap, threshold);
+        }
+    }
+
+    /// Counts the solves that were executed optimally and without pauses
+    fn counts(&self, solves: &[Solve]) -> (usize, usize, usize) {
+        let results: Vec<&CrossPlanningAnalysis> = solves
+            .iter()
+            .filter_map(|solve| self.get(&solve.id))
+            .collect();
+        (
+            results.iter().filter(|result| result.is_optimal()).count(),
+            results
+                .iter()
+                .filter(|result| result.without_pauses())
+                .count(),
+            results.len(),
+        )
+    }
 }
 
 impl TimerSession {
@@ -45,10 +150,26 @@ impl TimerSession {
             best_solve: None,
             best_ao5: None,
             best_ao12: None,
+            best_daily_challenge: None,
+            daily_challenge_results: Vec::new(),
+            daily_challenge_completed: 0,
+            cross_planning: CrossPlanningResults::default(),
         }
     }
 
     pub fn check_solve_type(&mut self, history: &mut History, solve_type: SolveType) {
+        // Each day's daily challenge is kept in a single session, so continue today's
+        // session instead of starting the challenge over
+        if solve_type.is_daily_challenge() {
+            if let Some(session) = Self::todays_daily_challenge_session(history) {
+                if session != history.current_session() {
+                    history.set_current_session(session);
+                    self.update(history);
+                }
+                return;
+            }
+        }
+
         // If solve type no longer matches session's, create a new session to hold the
         // new type of solves
         if let Some(session) = history.sessions().get(history.current_session()) {
@@ -75,7 +196,66 @@ impl TimerSession {
             best_solve,
             best_ao5,
             best_ao12,
+            best_daily_challenge: None,
+            daily_challenge_results: Vec::new(),
+            daily_challenge_completed: 0,
+            cross_planning: CrossPlanningResults::default(),
+        }
+    }
+
+    /// Finds the session holding today's daily challenge solves, preferring the current
+    /// session if there is more than one
+    fn todays_daily_challenge_session(history: &History) -> Option<String> {
+        let today = Local::now().naive_local().date();
+        let is_today = |session: &&Session| {
+            session.solve_type().is_daily_challenge()
+                && session
+                    .last_solve_time()
+                    .map(|time| time.naive_local().date())
+                    == Some(today)
+        };
+        history
+            .sessions()
+            .get(history.current_session())
+            .filter(is_today)
+            .or_else(|| history.sessions().values().find(is_today))
+            .map(|session| session.id().to_string())
+    }
+
+    /// Finds the result of every completed daily challenge, most recent first, along with
+    /// the number of daily challenge solves completed today. Only the first solves of
+    /// each day count towards that day's result, no matter which session they are in.
+    fn daily_challenge_results(history: &History) -> (Vec<(DateTime<Local>, Average)>, usize) {
+        let mut days: HashMap<NaiveDate, Vec<Solve>> = HashMap::new();
+        for solve in history.iter() {
+            if solve.solve_type.is_daily_challenge() {
+                days.entry(solve.created.naive_local().date())
+                    .or_default()
+                    .push(solve.clone());
+            }
         }
+
+        let today = Local::now().naive_local().date();
+        let mut completed = 0;
+        let mut results = Vec::new();
+        for (date, mut solves) in days {
+            solves.sort_by(|a, b| a.created.cmp(&b.created));
+            solves.truncate(DAILY_CHALLENGE_SOLVE_COUNT);
+            if date == today {
+                completed = solves.len();
+            }
+            if let Some(average) = solves.as_slice().best_average(DAILY_CHALLENGE_SOLVE_COUNT) {
+                results.push((solves.last().unwrap().created, average));
+            }
+        }
+        results.sort_by(|a, b| b.0.cmp(&a.0));
+        (results, completed)
+    }
+
+    /// Gets the number of daily challenge solves completed today
+    pub fn daily_challenge_completed(&mut self, history: &History) -> usize {
+        self.update(history);
+        self.daily_challenge_completed
     }
 
     fn update(&mut self, history: &History) {
@@ -84,15 +264,44 @@ impl TimerSession {
             if let Some(update_id) = self.update_id {
                 if update_id == session.update_id() {
                     // Already cached and up to date
+                    self.cross_planning.update_pending(history);
                     return;
                 }
             }
 
             // Cache solve information
+            let cross_planning = std::mem::take(&mut self.cross_planning);
             *self = Self::from_solves(Some(session.update_id()), session.to_vec(history));
+            self.cross_planning = cross_planning;
+            if session.solve_type().is_daily_challenge() {
+                let (results, completed) = Self::daily_challenge_results(history);
+                self.daily_challenge_results = results;
+                self.daily_challenge_completed = completed;
+                self.best_daily_challenge = self
+                    .daily_challenge_results
+                    .iter()
+                    .map(|(_, average)| average)
+                    .min_by_key(|average| average.time)
+                    .cloned();
+            }
+            if session.solve_type().is_cross_training() {
+                let solve_type = session.solve_type();
+                let median_gap = median_move_gap(
+                    history
+                        .iter()
+                        .filter(|solve| solve.solve_type == solve_type),
+                );
+                let threshold = Settings::pause_threshold(history);
+                for solve in &self.solves {
+                    self.cross_planning
+                        .analyze(history, solve, median_gap, threshold);
+                }
+            }
         } else {
             // New session, invalidate cache
+            let cross_planning = std::mem::take(&mut self.cross_planning);
             *self = Self::from_solves(None, Vec::new());
+            self.cross_planning = cross_planning;
         }
     }
 
@@ -144,6 +353,9 @@ impl TimerSession {
                         SessionTime::SessionAverage(time) => {
                             ui.label(solve_time_string(time));
                         }
+                        SessionTime::Progress(done, total) => {
+                            ui.label(format!("{} / {}", done, total));
+                        }
                         SessionTime::BestSolve(solve) => {
                             if ui
                                 .add(
@@ -391,6 +603,11 @@ impl TimerSession {
         let moves = match algorithm {
             Algorithm::OLL(oll) => KnownAlgorithms::oll(oll)[0].clone(),
             Algorithm::PLL(pll) => KnownAlgorithms::pll(pll)[0].clone(),
+            Algorithm::CLL(cll) => KnownAlgorithms::cll(cll)[0].clone(),
+            Algorithm::EG1(eg1) => KnownAlgorithms::eg1(eg1)[0].clone(),
+            Algorithm::EG2(eg2) => KnownAlgorithms::eg2(eg2)[0].clone(),
+            Algorithm::F2L(f2l) => KnownAlgorithms::f2l(f2l)[0].clone(),
+            Algorithm::COLL(_) | Algorithm::ZBLL(_) | Algorithm::OLLCP(_) => Vec::new(),
         };
 
         ui.horizontal(|ui| {
@@ -471,6 +688,29 @@ impl TimerSession {
         });
     }
 
+    /// Shows the accuracy of the answers in a recognition drill session, along with
+    /// the average time of the correct answers
+    fn recognition_summary(&self, ui: &mut Ui) {
+        if self.solves.is_empty() {
+            return;
+        }
+
+        let correct: Vec<u32> = self
+            .solves
+            .iter()
+            .filter_map(|solve| solve.final_time())
+            .collect();
+        ui.label(format!(
+            "Accuracy: {:.0}%",
+            correct.len() as f32 * 100.0 / self.solves.len() as f32
+        ));
+        if !correct.is_empty() {
+            let average =
+                correct.iter().map(|time| *time as u64).sum::<u64>() / correct.len() as u64;
+            ui.label(format!("Average: {}", solve_time_string(average as u32)));
+        }
+    }
+
     fn add_oll_training_solve(
         ui: &mut Ui,
         idx: usize,
@@ -505,12 +745,50 @@ impl TimerSession {
         }
     }
 
+    fn add_f2l_training_solve(
+        ui: &mut Ui,
+        idx: usize,
+        solve: &Solve,
+        history: &mut History,
+        details: &mut Option<SolveDetails>,
+    ) {
+        let mut cube = Cube3x3x3::new();
+        cube.do_moves(&solve.scramble);
+        let algorithm = F2LAlgorithm::from_cube(&cube.as_faces(), CubeFace::Top);
+        if let Some(algorithm) = algorithm {
+            Self::add_training_solve(ui, idx, solve, history, details, Algorithm::F2L(algorithm))
+        } else {
+            Self::add_standard_solve(ui, idx, solve, history, details)
+        }
+    }
+
+    fn add_cross_training_solve(
+        ui: &mut Ui,
+        idx: usize,
+        solve: &Solve,
+        history: &mut History,
+        details: &mut Option<SolveDetails>,
+        cross_planning: Option<&CrossPlanningAnalysis>,
+    ) {
+        Self::add_standard_solve(ui, idx, solve, history, details);
+        if let Some(cross_planning) = cross_planning {
+            ui.with_layout(Layout::right_to_left(), |ui| {
+                ui.add(
+                    Label::new(cross_planning_string(cross_planning))
+                        .small()
+                        .text_color(color_for_cross_planning(cross_planning)),
+                );
+            });
+        }
+    }
+
     fn add_solve(
         ui: &mut Ui,
         idx: usize,
         solve: &Solve,
         history: &mut History,
         details: &mut Option<SolveDetails>,
+        cross_planning: Option<&CrossPlanningAnalysis>,
     ) {
         // Change window theme so that popup menu stands out
         let old_visuals = ui.ctx().style().visuals.clone();
@@ -519,12 +797,18 @@ impl TimerSession {
         ui.style_mut().spacing.item_spacing.x = 0.0;
 
         match solve.solve_type {
-            SolveType::OLLTraining => {
+            SolveType::OLLTraining | SolveType::OLLRecognition => {
                 Self::add_oll_training_solve(ui, idx, solve, history, details)
             }
-            SolveType::PLLTraining => {
+            SolveType::PLLTraining | SolveType::PLLRecognition => {
                 Self::add_pll_training_solve(ui, idx, solve, history, details)
             }
+            SolveType::F2LTraining => {
+                Self::add_f2l_training_solve(ui, idx, solve, history, details)
+            }
+            SolveType::CrossTraining | SolveType::XCrossTraining => {
+                Self::add_cross_training_solve(ui, idx, solve, history, details, cross_planning)
+            }
             _ => Self::add_standard_solve(ui, idx, solve, history, details),
         };
 
@@ -661,6 +945,45 @@ impl TimerSession {
         });
     }
 
+    fn last_layer_training_mode_menu(ui: &mut Ui, history: &mut History, cube: &mut TimerCube) {
+        let popup_id = ui.make_persistent_id("last-layer-training-mode");
+        let response = ui.add(
+            Label::new(match cube.last_layer_training_spaced_repetition() {
+                false => "Random ⏷",
+                true => "Spaced repetition ⏷",
+            })
+            .sense(Sense::click()),
+        );
+        if response.clicked() {
+            ui.memory().toggle_popup(popup_id);
+        }
+        popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_width(150.0);
+            if ui
+                .add(
+                    SelectableLabel::new(!cube.last_layer_training_spaced_repetition(), "Random")
+                        .text_style(FontSize::Normal.into()),
+                )
+                .clicked()
+            {
+                cube.set_last_layer_training_spaced_repetition(false, history);
+            }
+
+            if ui
+                .add(
+                    SelectableLabel::new(
+                        cube.last_layer_training_spaced_repetition(),
+                        "Spaced repetition",
+                    )
+                    .text_style(FontSize::Normal.into()),
+                )
+                .clicked()
+            {
+                cube.set_last_layer_training_spaced_repetition(true, history);
+            }
+        });
+    }
+
     fn last_layer_training_learning_multiplier_menu(
         ui: &mut Ui,
         history: &mut History,
@@ -721,6 +1044,127 @@ impl TimerSession {
         });
     }
 
+    fn daily_challenge_times(&self, ui: &mut Ui, small: bool, details: &mut Option<SolveDetails>) {
+        Self::session_time(
+            ui,
+            "Progress",
+            small,
+            Some(SessionTime::Progress(
+                self.daily_challenge_completed,
+                DAILY_CHALLENGE_SOLVE_COUNT,
+            )),
+            details,
+        );
+        Self::session_time(
+            ui,
+            "Today",
+            small,
+            self.daily_challenge_results
+                .first()
+                .filter(|(time, _)| time.naive_local().date() == Local::now().naive_local().date())
+                .map(|(_, avg)| SessionTime::AverageOfN(avg.clone())),
+            details,
+        );
+        Self::session_time(
+            ui,
+            "Best day",
+            small,
+            self.best_daily_challenge
+                .clone()
+                .map(|avg| SessionTime::AverageOfN(avg)),
+            details,
+        );
+    }
+
+    /// Shows the result of each day's challenge, most recent first
+    fn daily_challenge_history(&self, ui: &mut Ui, details: &mut Option<SolveDetails>) {
+        for (time, average) in &self.daily_challenge_results {
+            Self::session_time(
+                ui,
+                &short_day_string(time),
+                false,
+                Some(SessionTime::AverageOfN(average.clone())),
+                details,
+            );
+        }
+    }
+
+    fn xcross_slot_name(slot: F2LSlot) -> String {
+        let faces = slot.side_faces(CubeFace::Top);
+        format!(
+            "{} ({}/{})",
+            slot.to_str(),
+            faces[0].color().to_str(),
+            faces[1].color().to_str()
+        )
+    }
+
+    fn xcross_slot_menu(ui: &mut Ui, history: &mut History, cube: &mut TimerCube) {
+        let popup_id = ui.make_persistent_id("xcross-training-slot");
+        let response = ui.add(
+            Label::new(format!("{} ⏷", Self::xcross_slot_name(cube.xcross_slot())))
+                .sense(Sense::click()),
+        );
+        if response.clicked() {
+            ui.memory().toggle_popup(popup_id);
+        }
+        popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_width(150.0);
+            for slot in &F2LSlot::all() {
+                if ui
+                    .add(
+                        SelectableLabel::new(
+                            cube.xcross_slot() == *slot,
+                            Self::xcross_slot_name(*slot),
+                        )
+                        .text_style(FontSize::Normal.into()),
+                    )
+                    .clicked()
+                {
+                    cube.set_xcross_slot(*slot, history);
+                }
+            }
+        });
+    }
+
+    /// Shows the target pair for XCross training along with how many solves in the
+    /// session were optimal and executed without pauses
+    fn cross_training_summary(
+        &self,
+        ui: &mut Ui,
+        small: bool,
+        history: &mut History,
+        cube: &mut TimerCube,
+        details: &mut Option<SolveDetails>,
+    ) {
+        if cube.solve_type() == SolveType::XCrossTraining {
+            ui.horizontal(|ui| {
+                if small {
+                    ui.add(Label::new("Target pair:").small());
+                } else {
+                    ui.label("Target pair:");
+                }
+                Self::xcross_slot_menu(ui, history, cube);
+            });
+        }
+
+        let (optimal, without_pauses, total) = self.cross_planning.counts(&self.solves);
+        Self::session_time(
+            ui,
+            "Optimal",
+            small,
+            Some(SessionTime::Progress(optimal, total)),
+            details,
+        );
+        Self::session_time(
+            ui,
+            "No pauses",
+            small,
+            Some(SessionTime::Progress(without_pauses, total)),
+            details,
+        );
+    }
+
     fn new_session_button(ui: &mut Ui, history: &mut History) {
         ui.horizontal(|ui| {
             ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke {
@@ -755,23 +1199,42 @@ impl TimerSession {
             .show(ctxt, |ui| {
                 self.update(history);
 
-                if cube.solve_type().is_last_layer_training() {
+                if cube.solve_type().is_algorithm_training()
+                    || cube.solve_type().is_recognition_training()
+                {
                     ui.section("Settings");
 
                     ui.vertical(|ui| {
                         ui.label("Algorithms: ");
                         Self::last_layer_training_algorithm_menu(ui, history, cube);
 
-                        ui.horizontal(|ui| {
-                            ui.label("Weighting:");
-                            Self::last_layer_training_weight_menu(ui, history, cube);
-                        });
+                        // Spaced repetition is only available for last layer cases
+                        let last_layer = cube.solve_type().is_last_layer_training();
+                        if last_layer {
+                            ui.horizontal(|ui| {
+                                ui.label("Mode:");
+                                Self::last_layer_training_mode_menu(ui, history, cube);
+                            });
+                        }
 
-                        if matches!(
-                            cube.last_layer_training_algorithms(),
-                            LastLayerAlgorithmSelection::KnownAndLearning
-                                | LastLayerAlgorithmSelection::All
-                        ) {
+                        // Spaced repetition chooses cases from the schedule, so the
+                        // weighting settings only apply to random training
+                        let spaced_repetition =
+                            last_layer && cube.last_layer_training_spaced_repetition();
+                        if !spaced_repetition {
+                            ui.horizontal(|ui| {
+                                ui.label("Weighting:");
+                                Self::last_layer_training_weight_menu(ui, history, cube);
+                            });
+                        }
+
+                        if !spaced_repetition
+                            && matches!(
+                                cube.last_layer_training_algorithms(),
+                                LastLayerAlgorithmSelection::KnownAndLearning
+                                    | LastLayerAlgorithmSelection::All
+                            )
+                        {
                             ui.horizontal(|ui| {
                                 ui.label("Learning Weight:");
                                 Self::last_layer_training_learning_multiplier_menu(
@@ -780,9 +1243,31 @@ impl TimerSession {
                             });
                         }
 
+                        if cube.solve_type().is_recognition_training() {
+                            ui.add_space(8.0);
+                            self.recognition_summary(ui);
+                        }
+
                         ui.add_space(8.0);
                         Self::new_session_button(ui, history);
                     });
+                } else if cube.solve_type().is_daily_challenge() {
+                    // Daily challenge sessions are created automatically for each day, so
+                    // there is no new session button.
+                    ui.section("Daily Challenge");
+                    ui.vertical(|ui| {
+                        self.daily_challenge_times(ui, false, details);
+                    });
+
+                    if !self.daily_challenge_results.is_empty() {
+                        ui.add_space(8.0);
+                        ui.section("Past Days");
+                        ScrollArea::from_max_height(150.0)
+                            .id_source("daily_challenge_history")
+                            .show(ui, |ui| {
+                                self.daily_challenge_history(ui, details);
+                            });
+                    }
                 } else {
                     ui.section("Session");
 
@@ -840,6 +1325,11 @@ impl TimerSession {
                             details,
                         );
 
+                        if cube.solve_type().is_cross_training() {
+                            ui.add_space(8.0);
+                            self.cross_training_summary(ui, false, history, cube, details);
+                        }
+
                         ui.add_space(8.0);
                         Self::new_session_button(ui, history);
                     });
@@ -856,7 +1346,14 @@ impl TimerSession {
                     .show(ui, |ui| {
                         let mut has_solves = false;
                         for (idx, solve) in self.solves.iter().enumerate().rev() {
-                            Self::add_solve(ui, idx, solve, history, details);
+                            Self::add_solve(
+                                ui,
+                                idx,
+                                solve,
+                                history,
+                                details,
+                                self.cross_planning.get(&solve.id),
+                            );
                             has_solves = true;
                         }
                         if !has_solves {
@@ -879,7 +1376,9 @@ impl TimerSession {
         TopBottomPanel::top("top_timer").show(ctxt, |ui| {
             self.update(history);
 
-            if cube.solve_type().is_last_layer_training() {
+            if cube.solve_type().is_algorithm_training()
+                || cube.solve_type().is_recognition_training()
+            {
                 // Settings header with embedded new session button.
                 ui.horizontal(|ui| {
                     ui.add(
@@ -915,16 +1414,30 @@ impl TimerSession {
                                     Self::last_layer_training_algorithm_menu(ui, history, cube);
                                 });
 
-                                ui.horizontal(|ui| {
-                                    ui.label("Weighting:");
-                                    Self::last_layer_training_weight_menu(ui, history, cube);
-                                });
+                                let last_layer = cube.solve_type().is_last_layer_training();
+                                if last_layer {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Mode:");
+                                        Self::last_layer_training_mode_menu(ui, history, cube);
+                                    });
+                                }
 
-                                if matches!(
-                                    cube.last_layer_training_algorithms(),
-                                    LastLayerAlgorithmSelection::KnownAndLearning
-                                        | LastLayerAlgorithmSelection::All
-                                ) {
+                                let spaced_repetition =
+                                    last_layer && cube.last_layer_training_spaced_repetition();
+                                if !spaced_repetition {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Weighting:");
+                                        Self::last_layer_training_weight_menu(ui, history, cube);
+                                    });
+                                }
+
+                                if !spaced_repetition
+                                    && matches!(
+                                        cube.last_layer_training_algorithms(),
+                                        LastLayerAlgorithmSelection::KnownAndLearning
+                                            | LastLayerAlgorithmSelection::All
+                                    )
+                                {
                                     ui.horizontal(|ui| {
                                         ui.label("Learning Weight:");
                                         Self::last_layer_training_learning_multiplier_menu(
@@ -952,7 +1465,7 @@ impl TimerSession {
                                 if let TimerState::Inactive(_, Some(last_solve)) = state {
                                     if last_solve.solve_type == cube.solve_type() {
                                         let algorithm = match last_solve.solve_type {
-                                            SolveType::OLLTraining => {
+                                            SolveType::OLLTraining | SolveType::OLLRecognition => {
                                                 let mut cube = Cube3x3x3::new();
                                                 cube.do_moves(&last_solve.scramble);
                                                 let algorithm = OLLAlgorithm::from_cube(
@@ -961,7 +1474,7 @@ impl TimerSession {
                                                 );
                                                 algorithm.map(|alg| Algorithm::OLL(alg))
                                             }
-                                            SolveType::PLLTraining => {
+                                            SolveType::PLLTraining | SolveType::PLLRecognition => {
                                                 let mut cube = Cube3x3x3::new();
                                                 cube.do_moves(&last_solve.scramble);
                                                 let algorithm = PLLAlgorithm::from_cube(
@@ -970,6 +1483,15 @@ impl TimerSession {
                                                 );
                                                 algorithm.map(|alg| Algorithm::PLL(alg))
                                             }
+                                            SolveType::F2LTraining => {
+                                                let mut cube = Cube3x3x3::new();
+                                                cube.do_moves(&last_solve.scramble);
+                                                let algorithm = F2LAlgorithm::from_cube(
+                                                    &cube.as_faces(),
+                                                    CubeFace::Top,
+                                                );
+                                                algorithm.map(|alg| Algorithm::F2L(alg))
+                                            }
                                             _ => None,
                                         };
 
@@ -997,6 +1519,21 @@ impl TimerSession {
                                                 Algorithm::PLL(pll) => {
                                                     KnownAlgorithms::pll(pll)[0].clone()
                                                 }
+                                                Algorithm::CLL(cll) => {
+                                                    KnownAlgorithms::cll(cll)[0].clone()
+                                                }
+                                                Algorithm::EG1(eg1) => {
+                                                    KnownAlgorithms::eg1(eg1)[0].clone()
+                                                }
+                                                Algorithm::EG2(eg2) => {
+                                                    KnownAlgorithms::eg2(eg2)[0].clone()
+                                                }
+                                                Algorithm::F2L(f2l) => {
+                                                    KnownAlgorithms::f2l(f2l)[0].clone()
+                                                }
+                                                Algorithm::COLL(_)
+                                                | Algorithm::ZBLL(_)
+                                                | Algorithm::OLLCP(_) => Vec::new(),
                                             };
 
                                             algorithm.draw(
@@ -1014,6 +1551,14 @@ impl TimerSession {
                         },
                     );
                 });
+            } else if cube.solve_type().is_daily_challenge() {
+                ui.add(
+                    Label::new("Daily Challenge")
+                        .font_size(FontSize::Section)
+                        .text_color(Theme::Blue),
+                );
+                ui.section_separator();
+                self.daily_challenge_times(ui, true, details);
             } else {
                 // Session header with embedded new session button.
                 ui.horizontal(|ui| {
@@ -1130,12 +1675,20 @@ impl TimerSession {
                         );
                     }
                 });
+
+                if cube.solve_type().is_cross_training() {
+                    self.cross_training_summary(ui, true, history, cube, details);
+                }
             }
 
             ui.add_space(4.0);
         });
     }
 
+    pub fn cross_planning(&self) -> &CrossPlanningResults {
+        &self.cross_planning
+    }
+
     pub fn last_solve_time(&self) -> Option<DateTime<Local>> {
         if let Some(solve) = self.solves.last() {
             Some(solve.created)
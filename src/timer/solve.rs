@@ -1,11 +1,22 @@
 use crate::font::FontSize;
 use crate::framerate::Framerate;
+use crate::theme::Theme;
 use crate::timer::scramble::TimerCube;
 use crate::timer::state::TimerState;
-use egui::{Pos2, Rect, Ui, Vec2};
+use crate::widgets::algorithm_name;
+use egui::{Pos2, Rect, Sense, Ui, Vec2};
+use tpscube_core::Algorithm;
 
 const TARGET_CUBE_FRACTION: f32 = 0.75;
 
+/// Keys that answer a recognition drill, in the order of the answer choices. There are
+/// more OLL cases than keys, so the remaining cases can only be answered by clicking.
+const RECOGNITION_KEYS: &str = "1234567890QWERTYUIOPASDFGHJKLZXCVBNM";
+
+const RECOGNITION_CHOICE_PADDING: f32 = 24.0;
+const RECOGNITION_CHOICE_SPACING: f32 = 8.0;
+const RECOGNITION_CUBE_PADDING: f32 = 32.0;
+
 pub fn timer_ui(ui: &mut Ui, center: &Pos2, state: &TimerState) {
     // Render timer only in center of screen
     let timer_height = ui.fonts().row_height(FontSize::Timer.into());
@@ -63,3 +74,92 @@ pub fn bluetooth_timer_ui(
         state.current_time_color(),
     );
 }
+
+/// Gets the index of the answer choice mapped to a key in a recognition drill
+pub fn recognition_choice_for_key(key: char) -> Option<usize> {
+    RECOGNITION_KEYS.find(key.to_ascii_uppercase())
+}
+
+/// Shows the case for a recognition drill with the cases to choose from below it.
+/// Returns the case that was clicked, if any.
+pub fn recognition_ui(
+    ui: &mut Ui,
+    rect: &Rect,
+    center: &Pos2,
+    cube: &TimerCube,
+    choices: &[Algorithm],
+    cube_rect: &mut Option<Rect>,
+    framerate: &mut Framerate,
+) -> Option<Algorithm> {
+    // Lay out the choices in a grid, labeled with the keys mapped to them
+    let galleys: Vec<_> = choices
+        .iter()
+        .enumerate()
+        .map(|(idx, choice)| {
+            let name = algorithm_name(*choice);
+            ui.fonts().layout_single_line(
+                FontSize::Normal.into(),
+                match RECOGNITION_KEYS.chars().nth(idx) {
+                    Some(key) => format!("{}  {}", key, name),
+                    None => name,
+                },
+            )
+        })
+        .collect();
+    let choice_width = galleys
+        .iter()
+        .fold(0.0, |width: f32, galley| width.max(galley.size.x))
+        + RECOGNITION_CHOICE_PADDING;
+    let choice_height = ui.fonts().row_height(FontSize::Normal.into()) + RECOGNITION_CHOICE_SPACING;
+    let columns = ((rect.width() / choice_width) as usize).clamp(1, galleys.len().max(1));
+    let rows = (galleys.len() + columns - 1) / columns;
+    let choices_height = rows as f32 * choice_height;
+
+    // Allocate space for the cube rendering. This is 3D so it will be rendered
+    // with OpenGL after egui is done painting.
+    let cube_height = ((rect.height() - choices_height - RECOGNITION_CUBE_PADDING)
+        * TARGET_CUBE_FRACTION)
+        .min(rect.width());
+    let total_height = cube_height + RECOGNITION_CUBE_PADDING + choices_height;
+    let y = center.y - total_height / 2.0;
+    let computed_cube_rect = Rect::from_min_size(
+        Pos2::new(center.x - cube_height / 2.0, y),
+        Vec2::new(cube_height, cube_height),
+    );
+    if computed_cube_rect.width() > 0.0 && computed_cube_rect.height() > 0.0 {
+        *cube_rect = Some(computed_cube_rect);
+        if cube.animating() {
+            framerate.request_max();
+        }
+    } else {
+        *cube_rect = None;
+    }
+
+    // Draw choices and check for clicks
+    let left = center.x - columns as f32 * choice_width / 2.0;
+    let top = y + cube_height + RECOGNITION_CUBE_PADDING;
+    let mut answer = None;
+    for (idx, galley) in galleys.into_iter().enumerate() {
+        let choice_rect = Rect::from_min_size(
+            Pos2::new(
+                left + (idx % columns) as f32 * choice_width,
+                top + (idx / columns) as f32 * choice_height,
+            ),
+            Vec2::new(choice_width, choice_height),
+        );
+        let interact = ui.allocate_rect(choice_rect, Sense::click());
+        ui.painter().galley(
+            choice_rect.center() - galley.size / 2.0,
+            galley,
+            if interact.hovered() {
+                Theme::Blue.into()
+            } else {
+                Theme::Content.into()
+            },
+        );
+        if interact.clicked() {
+            answer = Some(choices[idx]);
+        }
+    }
+    answer
+}
@@ -1,9 +1,11 @@
 use crate::theme::Theme;
+use crate::timer::session::CrossPlanningResults;
 use crate::widgets::{solve_time_short_string, solve_time_string, solve_time_string_ms};
 use egui::{Color32, Key};
 use instant::Instant;
 use tpscube_core::{
-    Analysis, AnalysisSummary, Move, PartialAnalysis, Penalty, SolveType, TimedMove,
+    Algorithm, Analysis, AnalysisSummary, CrossPlanningAnalysis, Move, PartialAnalysis, Penalty,
+    RecognitionCase, SolveType, TimedMove,
 };
 
 #[derive(Clone)]
@@ -28,10 +30,25 @@ pub struct LastSolve {
     pub id: String,
     pub solve_type: SolveType,
     pub analysis: Option<Analysis>,
+    pub cross_planning: Option<CrossPlanningAnalysis>,
+    pub recognition: Option<RecognitionAnswer>,
     pub scramble: Vec<Move>,
     pub penalty: Penalty,
 }
 
+/// Case shown in a recognition drill along with the case given as the answer
+#[derive(Clone)]
+pub struct RecognitionAnswer {
+    pub case: RecognitionCase,
+    pub answer: Algorithm,
+}
+
+impl RecognitionAnswer {
+    pub fn is_correct(&self) -> bool {
+        self.case.algorithm == self.answer
+    }
+}
+
 impl TimerState {
     pub fn is_solving(&self) -> bool {
         match self {
@@ -163,6 +180,41 @@ impl TimerState {
         }
     }
 
+    pub fn cross_planning(&self) -> Option<&CrossPlanningAnalysis> {
+        match self {
+            TimerState::Inactive(_, Some(last_solve))
+            | TimerState::Preparing(_, _, Some(last_solve))
+            | TimerState::BluetoothPreparing(_, _, Some(last_solve))
+            | TimerState::SolveComplete(_, last_solve) => last_solve.cross_planning.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Fills in the cross planning analysis of the last solve once it is available
+    pub fn update_cross_planning(&mut self, results: &CrossPlanningResults) {
+        match self {
+            TimerState::Inactive(_, Some(last_solve))
+            | TimerState::Preparing(_, _, Some(last_solve))
+            | TimerState::BluetoothPreparing(_, _, Some(last_solve))
+            | TimerState::SolveComplete(_, last_solve) => {
+                if last_solve.cross_planning.is_none() {
+                    last_solve.cross_planning = results.get(&last_solve.id).cloned();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn recognition(&self) -> Option<&RecognitionAnswer> {
+        match self {
+            TimerState::Inactive(_, Some(last_solve))
+            | TimerState::Preparing(_, _, Some(last_solve))
+            | TimerState::BluetoothPreparing(_, _, Some(last_solve))
+            | TimerState::SolveComplete(_, last_solve) => last_solve.recognition.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn update_for_numerical_input(&mut self, digit: u32) {
         match self {
             TimerState::Inactive(_, _) => {
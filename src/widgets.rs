@@ -2,7 +2,7 @@ use crate::font::{FontSize, LabelFontSize};
 use crate::theme::Theme;
 use chrono::{DateTime, Local};
 use egui::{widgets::Label, Color32, Pos2, Response, Sense, Stroke, Ui, Vec2};
-use tpscube_core::Move;
+use tpscube_core::{Algorithm, CrossPlanningAnalysis, Move, OLLAlgorithm};
 
 const MIN_SCRAMBLE_LINES: usize = 2;
 const MAX_SCRAMBLE_LINES: usize = 7;
@@ -154,6 +154,57 @@ pub fn color_for_recognition_step_index(idx: usize) -> Color32 {
     Color32::from_rgb(color.r(), color.g(), color.b())
 }
 
+/// Summarizes a cross planning attempt as the moves used compared to an optimal
+/// solution, and whether it was executed without pausing
+pub fn cross_planning_string(analysis: &CrossPlanningAnalysis) -> String {
+    let moves = match &analysis.efficiency.optimal {
+        Some(optimal) => format!(
+            "{} moves, {} optimal",
+            analysis.efficiency.moves,
+            optimal.len()
+        ),
+        None => format!("{} moves", analysis.efficiency.moves),
+    };
+    if analysis.without_pauses() {
+        format!("{}, no pauses", moves)
+    } else {
+        format!("{}, paused", moves)
+    }
+}
+
+pub fn color_for_cross_planning(analysis: &CrossPlanningAnalysis) -> Color32 {
+    if analysis.is_optimal() && analysis.without_pauses() {
+        Theme::Green.into()
+    } else {
+        Theme::Disabled.into()
+    }
+}
+
+/// Gets the name of a case as shown to the user. Named OLL cases include their number.
+pub fn algorithm_name(algorithm: Algorithm) -> String {
+    match algorithm {
+        Algorithm::OLL(oll) => match oll {
+            OLLAlgorithm::OLL(_) => oll.to_string(),
+            _ => format!("{} (#{})", oll.to_string(), oll.as_number()),
+        },
+        _ => algorithm.to_string(),
+    }
+}
+
+/// Summarizes an answer in a recognition drill, including the correct case if the
+/// answer was wrong
+pub fn recognition_answer_string(case: Algorithm, answer: Algorithm) -> String {
+    if case == answer {
+        algorithm_name(case)
+    } else {
+        format!(
+            "{}, answered {}",
+            algorithm_name(case),
+            algorithm_name(answer)
+        )
+    }
+}
+
 impl CustomWidgets for Ui {
     fn header_label(&mut self, icon: &str, text: &str, landscape: bool, active: bool) -> Response {
         if landscape {
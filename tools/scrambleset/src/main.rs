@@ -0,0 +1,282 @@
+use anyhow::{anyhow, Result};
+use clap::{App, Arg};
+use std::fmt::Write;
+use std::str::FromStr;
+use tpscube_core::{
+    Cube, Cube2x2x2Faces, Cube3x3x3Faces, Cube4x4x4Faces, InitialCubeState, LastLayerSvgStyle,
+    Move, MoveSequence, ScrambleSet, SolveType, SvgOptions,
+};
+
+const SHEET_WIDTH: f32 = 800.0;
+const SHEET_PADDING: f32 = 24.0;
+const HEADER_HEIGHT: f32 = 40.0;
+const LINE_HEIGHT: f32 = 18.0;
+const ROW_PADDING: f32 = 12.0;
+const IMAGE_WIDTH: f32 = 160.0;
+const MOVES_PER_LINE: usize = 11;
+
+/// Scrambles of a single group as they are laid out on a sheet
+struct SheetGroup<'a> {
+    title: String,
+    scrambles: Vec<(String, &'a [Move])>,
+}
+
+/// Renders the scrambled state in SVG format. Cubes are shown as an unfolded net, and
+/// last layer training scrambles are shown from the top. Returns `None` for puzzles
+/// that do not have an image.
+fn scramble_image(solve_type: SolveType, scramble: &[Move]) -> Option<String> {
+    let net_options = |size: usize| SvgOptions {
+        sticker_size: (IMAGE_WIDTH - 8.0) / (4 * size) as f32,
+        face_gap: 2.0,
+        ..SvgOptions::default()
+    };
+    let last_layer_options = SvgOptions {
+        sticker_size: 30.0,
+        face_gap: 2.0,
+        ..SvgOptions::default()
+    };
+
+    match solve_type {
+        SolveType::Standard2x2x2 => {
+            let mut cube = Cube2x2x2Faces::new();
+            cube.do_moves(scramble);
+            Some(cube.net_svg(&net_options(2)))
+        }
+        SolveType::Standard3x3x3
+        | SolveType::OneHanded3x3x3
+        | SolveType::Blind3x3x3
+        | SolveType::DailyChallenge3x3x3
+        | SolveType::HardCrossTraining
+        | SolveType::LastSlotTraining
+        | SolveType::F2LTraining
+        | SolveType::CrossTraining
+        | SolveType::XCrossTraining => {
+            let mut cube = Cube3x3x3Faces::new();
+            cube.do_moves(scramble);
+            Some(cube.net_svg(&net_options(3)))
+        }
+        SolveType::OLLTraining
+        | SolveType::PLLTraining
+        | SolveType::OLLRecognition
+        | SolveType::PLLRecognition => {
+            let mut cube = Cube3x3x3Faces::new();
+            cube.do_moves(scramble);
+            Some(cube.last_layer_svg(
+                if matches!(
+                    solve_type,
+                    SolveType::OLLTraining | SolveType::OLLRecognition
+                ) {
+                    LastLayerSvgStyle::Orientation
+                } else {
+                    LastLayerSvgStyle::Full
+                },
+                &last_layer_options,
+            ))
+        }
+        SolveType::Standard4x4x4 | SolveType::Blind4x4x4 => {
+            let mut cube = Cube4x4x4Faces::new();
+            cube.do_moves(scramble);
+            Some(cube.net_svg(&net_options(4)))
+        }
+        SolveType::Megaminx => None,
+    }
+}
+
+fn image_height(solve_type: SolveType) -> f32 {
+    if solve_type == SolveType::Megaminx {
+        0.0
+    } else {
+        IMAGE_WIDTH * 3.0 / 4.0
+    }
+}
+
+fn scramble_lines(scramble: &[Move]) -> Vec<String> {
+    scramble
+        .chunks(MOVES_PER_LINE)
+        .map(|line| line.to_vec().to_string())
+        .collect()
+}
+
+/// Gets the labeled scrambles of each group in the set, with a title for each group
+fn groups(set: &ScrambleSet) -> Vec<SheetGroup<'_>> {
+    let mut result = Vec::new();
+    for (round_idx, round) in set.rounds.iter().enumerate() {
+        for (group_idx, group) in round.groups.iter().enumerate() {
+            let title = format!(
+                "{} Round {} Group {}",
+                set.solve_type.to_string(),
+                round_idx + 1,
+                ScrambleSet::group_name(group_idx)
+            );
+            let mut scrambles: Vec<(String, &[Move])> = Vec::new();
+            for (idx, scramble) in group.scrambles.iter().enumerate() {
+                scrambles.push((format!("{}", idx + 1), scramble));
+            }
+            for (idx, scramble) in group.extras.iter().enumerate() {
+                scrambles.push((format!("E{}", idx + 1), scramble));
+            }
+            result.push(SheetGroup { title, scrambles });
+        }
+    }
+    result
+}
+
+fn svg_sheet(set: &ScrambleSet) -> String {
+    let mut body = String::new();
+    let mut y = SHEET_PADDING;
+    for group in groups(set) {
+        write!(
+            body,
+            "<text x=\"{}\" y=\"{}\" font-size=\"20\" font-weight=\"bold\">{}</text>",
+            SHEET_PADDING,
+            y + 24.0,
+            group.title
+        )
+        .unwrap();
+        y += HEADER_HEIGHT;
+
+        for (label, scramble) in group.scrambles {
+            let lines = scramble_lines(scramble);
+            let height =
+                (lines.len() as f32 * LINE_HEIGHT).max(image_height(set.solve_type)) + ROW_PADDING;
+            write!(
+                body,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#808080\"/>",
+                SHEET_PADDING,
+                y,
+                SHEET_WIDTH - SHEET_PADDING,
+                y
+            )
+            .unwrap();
+            write!(
+                body,
+                "<text x=\"{}\" y=\"{}\" font-size=\"14\" font-weight=\"bold\">{}</text>",
+                SHEET_PADDING,
+                y + ROW_PADDING / 2.0 + LINE_HEIGHT,
+                label
+            )
+            .unwrap();
+            for (idx, line) in lines.iter().enumerate() {
+                write!(
+                    body,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"14\" font-family=\"monospace\">{}</text>",
+                    SHEET_PADDING + 40.0,
+                    y + ROW_PADDING / 2.0 + LINE_HEIGHT * (idx + 1) as f32,
+                    line
+                )
+                .unwrap();
+            }
+            if let Some(image) = scramble_image(set.solve_type, scramble) {
+                write!(
+                    body,
+                    "<g transform=\"translate({} {})\">{}</g>",
+                    SHEET_WIDTH - SHEET_PADDING - IMAGE_WIDTH,
+                    y + ROW_PADDING / 2.0,
+                    image
+                )
+                .unwrap();
+            }
+            y += height;
+        }
+        y += SHEET_PADDING;
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\"><rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>{}</svg>\n",
+        SHEET_WIDTH, y, SHEET_WIDTH, y, body
+    )
+}
+
+fn html_sheet(set: &ScrambleSet) -> String {
+    let mut body = String::new();
+    for group in groups(set) {
+        write!(body, "<h2>{}</h2><table>", group.title).unwrap();
+        for (label, scramble) in group.scrambles {
+            write!(
+                body,
+                "<tr><th>{}</th><td class=\"scramble\">{}</td><td>",
+                label,
+                scramble_lines(scramble).join("<br>")
+            )
+            .unwrap();
+            if let Some(image) = scramble_image(set.solve_type, scramble) {
+                body.push_str(&image);
+            }
+            body.push_str("</td></tr>");
+        }
+        body.push_str("</table>");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{} Scrambles</title>\
+         <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; \
+         width: 100%; page-break-after: always; }} th, td {{ border: 1px solid #808080; \
+         padding: 6px; }} td.scramble {{ font-family: monospace; font-size: 14px; \
+         width: 100%; }}</style></head><body><p>Seed {}</p>{}</body></html>\n",
+        set.solve_type.to_string(),
+        set.seed,
+        body
+    )
+}
+
+fn count_arg(value: Option<&str>, default: usize) -> Result<usize> {
+    match value {
+        Some(value) => Ok(usize::from_str(value)?),
+        None => Ok(default),
+    }
+}
+
+fn main() -> Result<()> {
+    let matches = App::new("TPS Cube Scramble Set Generator")
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(Arg::with_name("event").long("event").takes_value(true))
+        .arg(Arg::with_name("rounds").long("rounds").takes_value(true))
+        .arg(Arg::with_name("groups").long("groups").takes_value(true))
+        .arg(
+            Arg::with_name("scrambles")
+                .long("scrambles")
+                .takes_value(true),
+        )
+        .arg(Arg::with_name("extras").long("extras").takes_value(true))
+        .arg(Arg::with_name("json").long("json").takes_value(true))
+        .arg(Arg::with_name("svg").long("svg").takes_value(true))
+        .arg(Arg::with_name("html").long("html").takes_value(true))
+        .get_matches();
+
+    let seed = u32::from_str(matches.value_of("seed").unwrap())?;
+    let event = matches.value_of("event").unwrap_or("3x3x3");
+    let solve_type =
+        SolveType::from_str(event).ok_or_else(|| anyhow!("Invalid event '{}'", event))?;
+    let rounds = count_arg(matches.value_of("rounds"), 1)?;
+    let groups = count_arg(matches.value_of("groups"), 1)?;
+    let scrambles = count_arg(
+        matches.value_of("scrambles"),
+        ScrambleSet::default_scramble_count(solve_type),
+    )?;
+    let extras = count_arg(matches.value_of("extras"), 2)?;
+
+    let set = ScrambleSet::generate(seed, solve_type, rounds, groups, scrambles, extras);
+
+    let json = matches.value_of("json");
+    let svg = matches.value_of("svg");
+    let html = matches.value_of("html");
+    if let Some(path) = json {
+        std::fs::write(path, set.to_json()?)?;
+    }
+    if let Some(path) = svg {
+        std::fs::write(path, svg_sheet(&set))?;
+    }
+    if let Some(path) = html {
+        std::fs::write(path, html_sheet(&set))?;
+    }
+    if json.is_none() && svg.is_none() && html.is_none() {
+        println!("{}", set.to_json()?);
+    }
+    Ok(())
+}